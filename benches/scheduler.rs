@@ -0,0 +1,69 @@
+// Engine scheduling against the null backend: what one click costs on top of
+// the wait, and how closely clicks land on their target rate. Run with
+// `cargo bench`; the throughput line of each rate is the achieved CPS
+use bclicker::{
+    backend::{ClickBackend, NullBackend},
+    scheduler::{ClickScheduler, IntervalTracker, RateMeter},
+};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use enigo::MouseButton;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+// Spans the slow end, the usual range and the rates only the batched
+// SendInput backend keeps up with
+const RATES: [u64; 5] = [100, 1000, 5000, 20_000, 50_000];
+
+// The engine's bookkeeping for one click with no wait in front of it:
+// scheduling, the click itself, the rate meter and the accuracy tracker
+fn tick_overhead(c: &mut Criterion) {
+    let mut backend = NullBackend;
+    let mut scheduler = ClickScheduler::new();
+    let mut meter = RateMeter::new();
+    let mut tracker = IntervalTracker::new();
+
+    c.bench_function("tick_overhead", |b| {
+        b.iter(|| {
+            let deadline = scheduler.next_deadline();
+            if scheduler.poll(Duration::ZERO) {
+                backend.click(black_box(MouseButton::Left));
+                backend.flush();
+                meter.record_click();
+                tracker.record(deadline, Instant::now());
+            }
+            black_box(meter.sample(50_000.0))
+        })
+    });
+}
+
+// One click per iteration at each target rate, wait included, so the time
+// per iteration is the achieved interval
+fn click_interval(c: &mut Criterion) {
+    let mut group = c.benchmark_group("click_interval");
+    group.throughput(Throughput::Elements(1));
+    // 10 ms per iteration at 100 CPS; fewer samples keep it within the time
+    group.sample_size(20);
+    group.measurement_time(Duration::from_secs(3));
+    for cps in RATES {
+        let interval = Duration::from_micros(1_000_000 / cps);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(cps),
+            &interval,
+            |b, &interval| {
+                let mut backend = NullBackend;
+                let mut scheduler = ClickScheduler::new();
+                b.iter(|| {
+                    while !scheduler.poll(interval) {}
+                    backend.click(MouseButton::Left);
+                    backend.flush();
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tick_overhead, click_interval);
+criterion_main!(benches);
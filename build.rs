@@ -0,0 +1,31 @@
+// Generates the gRPC services and client from proto/bclicker.proto when the
+// `grpc` feature is on. The file is parsed in Rust rather than by protoc,
+// so building needs nothing installed
+fn main() {
+    println!("cargo:rerun-if-changed=proto/bclicker.proto");
+    #[cfg(feature = "grpc")]
+    grpc::generate();
+}
+
+#[cfg(feature = "grpc")]
+mod grpc {
+    use prost::Message as _;
+    use protobuf::Message as _;
+
+    pub fn generate() {
+        let parsed = protobuf_parse::Parser::new()
+            .pure()
+            .include("proto")
+            .input("proto/bclicker.proto")
+            .file_descriptor_set()
+            .expect("proto/bclicker.proto doesn't parse");
+        let bytes = parsed
+            .write_to_bytes()
+            .expect("descriptor set doesn't encode");
+        let descriptors = prost_types::FileDescriptorSet::decode(bytes.as_slice())
+            .expect("descriptor set doesn't decode");
+        tonic_prost_build::configure()
+            .compile_fds(descriptors)
+            .expect("gRPC code generation failed");
+    }
+}
@@ -0,0 +1,480 @@
+# BClicker user interface strings. Other languages fall back to these for
+# any message they leave out.
+
+## Main screen actions: descriptions for help and the command palette
+
+action-button = Switch Left/Right click, Scroll Up/Down, Type Text, Alternate, Hold Key, Hover modes
+action-scroll_amount = Set scroll amount (wheel notches per event)
+action-repeat_text = Set repeat text for the Type Text mode
+action-start_delay = Cycle start countdown (off/3s/5s/10s)
+action-idle_trigger = Cycle idle trigger: auto-start after 1/2/5/10m away
+action-overlay = Toggle always-on-top activity overlay
+action-position = Set fixed click position (x,y, empty = cursor)
+action-return_to_origin = Toggle cursor return-to-origin after fixed clicks
+action-region = Capture the click region: toggle hotkey or Enter at two corners (Delete clears)
+action-modifier = Hold Shift, Ctrl or Alt during each click (e.g. shift-clicking items)
+action-backend = Cycle click backend (enigo/sendinput/null)
+action-custom_cps = Edit custom CPS (0.01-50000, e.g. 2.5; >1000 asks to confirm)
+action-presets = Edit the CPS preset list: add, delete, reorder
+action-range = Random CPS range (e.g. 20-40, empty disables)
+action-slow_mode = Slow mode: one click every 90s, 20m, 1h... (empty disables)
+action-anti_afk = Anti-AFK: random wiggle, key tap or click every 3m-7m... (empty disables)
+action-duty_cycle = Duty cycle of the profile: click/pause like 30s/10s (empty disables)
+action-pattern = Click pattern of the profile, e.g. 20x@30cps; wait 2s; 5x@10cps(right)
+action-key_rotation = Keys pressed in turn by the Type Text mode, e.g. 1@1.5s, 2@800ms, 3@800ms
+action-hold_key = Key the Hold Key mode keeps down while active, e.g. w to walk
+action-queue_job = Queue a one-off job such as 50x@20cps(right) at 800,600
+action-second_channel = Second button clicking at its own rate, e.g. right@5
+action-std_dev = Set gaussian std dev (% of interval)
+action-timing = Cycle timing model (fixed/uniform/gaussian)
+action-pause = Pause or resume the run, keeping its place
+action-hotkey = Set global hotkey
+action-hotkey_mode = Switch global hotkey between toggle and hold-to-click
+action-mouse_trigger = Cycle mouse side-button trigger (Mouse4/5, toggle/hold)
+action-cps_up_hotkey = Set global hotkey that raises the CPS by cps_step
+action-cps_down_hotkey = Set global hotkey that lowers the CPS by cps_step
+action-pause_hotkey = Set global hotkey that pauses and resumes clicking
+action-audio = Toggle sound effects
+action-audio_device = Pick the audio output device for sound effects
+action-theme = Cycle theme (professional/high contrast/deuteranopia)
+action-language = Switch the interface language
+action-hide = Hide/Show interface (toggle)
+action-reset_stats = Reset session statistics
+action-stats = Statistics: peak CPS, longest run, clicks/minute
+action-benchmark = Benchmark max sustainable CPS
+action-test_fire = Test-fire one click with the current settings
+action-dry_run = Toggle dry run: full timing and stats, no real clicks
+action-sequence = Open click sequence editor
+action-scheduler = Open the scheduler that starts profiles on cron expressions
+action-save_slot = Store the profile and CPS on a number key (1-9 apply it)
+action-export_config = Export settings and profiles to a shareable file
+action-import_config = Import settings from a file, previewing changes
+action-help = Toggle this help screen
+action-quit = Quit and save
+action-down = Select next CPS preset or pattern
+action-up = Select previous CPS preset or pattern
+action-toggle_clicking = Start/stop clicking
+
+## Instruction bar words
+
+key-button = Button
+key-scroll_amount = Scroll
+key-repeat_text = Text
+key-start_delay = Delay
+key-idle_trigger = Idle
+key-overlay = Overlay
+key-position = Position
+key-return_to_origin = Return
+key-region = Region
+key-modifier = Modifier
+key-backend = Backend
+key-custom_cps = Custom
+key-presets = Presets
+key-range = Range
+key-slow_mode = Slow
+key-anti_afk = AFK
+key-duty_cycle = Duty
+key-pattern = Pattern
+key-second_channel = 2nd
+key-key_rotation = Rotation
+key-hold_key = Hold
+key-queue_job = Job
+key-timing = Timing
+key-pause = Pause
+key-hotkey = Hotkey
+key-hotkey_mode = Hold
+key-mouse_trigger = Mouse
+key-audio = Audio
+key-audio_device = Output
+key-theme = Theme
+key-language = Lang
+key-hide = Hide
+key-reset_stats = Reset
+key-stats = Stats
+key-benchmark = Bench
+key-test_fire = Test
+key-dry_run = DryRun
+key-sequence = Sequence
+key-scheduler = Schedule
+key-save_slot = Slot
+key-help = Help
+key-quit = Quit
+bar-navigation = 🎮 ↑↓=Select │ Ctrl+P=Palette
+
+## Main screen
+
+app-title = BClicker Professional v2.0
+status-starting = ⏳ STARTING IN { $secs }
+status-active = { $mark } ACTIVE
+status-paused-run = ⏸ PAUSED
+status-idle = { $mark } IDLE
+status-dry-run = 🧪 DRY RUN
+status-hotkey = Hotkey:
+status-unavailable = ⚠️ Unavailable
+status-not-set = Not Set
+status-button = Button:
+status-job = 📋 Job { $done }/{ $total } (+{ $queued } queued)
+status-second-channel = + { $button } { $cps } CPS
+status-next-click = ⏳ Next click in { $time }
+status-clicking = ▶ Clicking { $time }
+status-paused = ⏸ Paused { $time }
+status-duty = Duty { $duty }
+status-blocked = ⛔ Blocked window focused, not clicking
+status-outside-region = 🔲 Cursor outside the click region, waiting
+status-capped = 🐌 Capped at { $cps } CPS for this window
+status-unachievable = ⚠️ Unachievable
+status-engine-restarted = 💥 Engine crashed ({ $error }), restarted
+status-engine-stopped = 💥 Engine crashed ({ $error }), restart BClicker
+status-injection-blocked = 🚫 Input not arriving, run stopped
+status-scheduled = ⏰ Scheduled { $time }
+
+speed-title = ⚡ Click Speed Configuration
+choice-preset = { $cps } CPS
+choice-custom = { $cps } CPS (Custom)
+choice-slow = Every { $interval } (Slow)
+choice-anti-afk = Anti-AFK ({ $window })
+
+input-cps = 📝 Custom CPS Input [Type 0.01-50000, Enter to save]
+input-high-cps = ⚠️  High CPS Confirmation
+input-range = 🎲 Random CPS Range [min-max, empty to disable]
+input-interval = 🐢 Slow Mode Interval [90s, 20m, 1h30m, empty to disable]
+input-anti-afk = 💤 Anti-AFK Window [min-max like 3m-7m, empty to disable]
+input-duty = 🔁 Duty Cycle [on/off like 30s/10s, empty to disable]
+input-pattern = 🧩 Click Pattern [20x@30cps; wait 2s; 5x@10cps(right)]
+input-rotation = ⌨️ Key Rotation [1@1.5s, 2@800ms, 3@800ms, 4@2s]
+input-hold-key = 🚶 Key to Hold [w, space, shift...]
+input-job = 📋 Queue Click Job [50x@20cps(right) at 800,600]
+input-second-channel = ➕ Second Channel [right@5]
+input-std-dev = 📐 Gaussian Std Dev [% of interval, 1-100]
+input-scroll = 🖱️  Scroll Amount [wheel notches per event, 1-50]
+input-position = 📍 Fixed Click Position [x,y, empty = cursor]
+input-region = 🔲 Click Region
+input-region-first = Move the cursor to the first corner_
+input-region-second = First corner { $point }, now the opposite one_
+input-text = ⌨️  Repeat Text [typed each cycle, Tab toggles Enter]
+input-export = 📤 Export Settings [file to write]
+input-import = 📥 Import Settings [file to read]
+input-idle = 📝 Custom CPS Input [Press { $key } to edit]
+input-with-enter = [+ Enter]
+input-confirm-high-cps = { $cps } CPS exceeds the { $limit } CPS safety limit and may not be achievable. I understand (y/N)
+
+stats-title = 📈 Statistics
+stats-session = 📊 Session: { $clicks } clicks in { $mins }m { $secs }s (avg { $cps } CPS)
+stats-total = 🎯 Total: { $clicks } clicks │ Sessions: { $sessions } │ Audio: { $audio } │ Backend: { $backend }
+stats-audio-on = 🔊 On
+stats-audio-off = 🔇 Off
+benchmark-running = 🏁 Benchmark: running against null backend...
+benchmark-result = 🏁 Benchmark: { $summary }
+benchmark-hint = 🏁 Benchmark: press { $key } to measure this machine
+
+hint-preparing = 🕐 Preparing to capture hotkey combination...
+hint-capture-toggle = ⌨️  Press key combination (Ctrl+Shift+B, F1-F24, arrows, numpad, etc.) │ Esc=Cancel
+hint-capture-faster = ⌨️  Press the faster-CPS combination (e.g. Ctrl+Shift+Up) │ Esc=Cancel
+hint-capture-slower = ⌨️  Press the slower-CPS combination (e.g. Ctrl+Shift+Down) │ Esc=Cancel
+hint-capture-pause = ⌨️  Press the pause/resume combination (e.g. Ctrl+Shift+P) │ Esc=Cancel
+hint-cps = ✏️  Enter CPS value (0.01-50000, e.g. 0.5 = every 2s) │ Enter=Save │ Esc=Cancel
+hint-high-cps = ⚠️  Y=I understand, apply │ Any other key=Cancel
+hint-range = 🎲 Enter range like 20-40 or 0.5-2.5 (0.01-1000) │ Enter=Save │ Esc=Cancel
+hint-interval = 🐢 Enter an interval from 1s to 24h, like 20m │ Enter=Save │ Esc=Cancel
+hint-anti-afk = 💤 Enter a random window like 3m-7m (1s-24h) │ Enter=Save │ Esc=Cancel
+hint-duty = 🔁 Enter click time/pause time like 30s/10s or 2m/1m │ Enter=Save │ Esc=Cancel
+hint-rotation = ⌨️ KEY@INTERVAL with 500ms/2s/1m, split by , │ Empty=Off │ Enter=Save │ Esc=Cancel
+hint-hold-key = 🚶 A character or space/shift/ctrl/alt/up/down/left/right, held until clicking stops │ Enter=Save │ Esc=Cancel
+hint-job = 📋 COUNTx@RATEcps with optional (right)/(middle) and at X,Y; jobs run in order ahead of clicking │ Empty=Clear queue │ Enter=Queue │ Esc=Cancel
+hint-second-channel = ➕ BUTTON@CPS with left or right, clicking alongside the main action │ Empty=Off │ Enter=Save │ Esc=Cancel
+hint-pattern = 🧩 COUNTx@RATEcps(left|right) and wait 500ms/2s/1m, split by ; │ Empty=Off │ Enter=Save │ Esc=Cancel
+hint-std-dev = 📐 Enter std dev percent (1-100) │ Enter=Save │ Esc=Cancel
+hint-scroll = 🖱️  Enter wheel notches per event (1-50) │ Enter=Save │ Esc=Cancel
+hint-position = 📍 Enter screen coordinates like 800,600 │ Enter=Save │ Esc=Cancel
+hint-region = 🔲 Hotkey or Enter=Mark corner │ Delete=Clear region │ Esc=Cancel
+hint-text = ⌨️  Type the text to repeat │ Tab=Toggle Enter │ Enter=Save & use │ Esc=Cancel
+hint-export = 📤 Enter a file path (statistics are left out) │ Enter=Export │ Esc=Cancel
+hint-import = 📥 Enter the file to import │ Enter=Preview changes │ Esc=Cancel
+hint-slot = 🔢 Press 1-9 to store the current profile and CPS there │ Any other key=Cancel
+pro-features = 🏆 Pro Features:
+pro-features-list = Global hotkeys • System tray • Audio feedback • Statistics • Auto-save
+controls-info-title = 🎛️  Controls & Information
+controls-title = 🎛️  Controls
+keys-save-cancel = Enter=Save │ Esc=Cancel
+
+## Help screen
+
+help-title = BClicker Professional - Help System
+help-heading = Help & Documentation
+help-main-controls = 🎯 MAIN CONTROLS:
+help-nav-keys = ↑/↓ or { $down }/{ $up }
+help-navigate = Navigate CPS presets & click patterns
+help-confirm = Confirm selection
+help-cancel = Cancel operation
+help-palette = Command palette: fuzzy search every action
+help-primary = ⚡ PRIMARY FUNCTIONS:
+help-advanced = 🔧 ADVANCED FEATURES:
+help-hotkey = 🎮 GLOBAL HOTKEY:
+help-hotkey-body =
+    Your hotkey works system-wide to start/stop clicking
+    Default: Ctrl+Shift+B
+    Works even when interface is hidden
+    Optional rate hotkeys step the CPS up/down by cps_step
+help-tray = 📊 SYSTEM TRAY:
+help-tray-body =
+    • Right-click tray icon for menu
+    • Icon flashes when clicker is active
+    • Notifications keep you informed
+    • bclicker --tray-only starts with the tray icon alone
+help-audio = 🔊 AUDIO FEEDBACK:
+help-audio-body =
+    • Start/stop sound effects
+    • Toggle with '{ $key }' key
+    • Pick the output device with '{ $device_key }'
+    • Professional audio cues
+help-config = 💾 CONFIGURATION:
+help-config-body =
+    • Auto-saves to bclicker_config.toml (or .json/.yaml)
+    • Statistics tracked across sessions
+    • All preferences persist
+help-stats = 📈 STATISTICS TRACKING:
+help-stats-body =
+    • Real-time session tracking
+    • Total clicks across sessions
+    • Average CPS calculation
+help-notice = ⚠️  IMPORTANT NOTICE:
+help-notice-body =
+    Using auto-clickers in competitive games
+    may violate terms of service and result
+    in account bans. Use responsibly!
+help-close = Use ↑/↓ to scroll • Press { $key } or Esc to close help
+
+## Other screens
+
+import-title = 📥 Import Preview: { $count } changes
+import-keys = Y=Apply import │ Any other key=Cancel
+
+conflict-title = ⚠️  Global Hotkey Conflict
+conflict-reason = could not be registered: { $reason }.
+conflict-fallback = Clicking can still be toggled from the command palette and tray.
+conflict-keys = Enter=Capture another combo │ Any other key=Keep it
+
+palette-title = 🔍 Command Palette
+palette-keys = ↑↓=Select │ Enter=Run │ Esc=Close
+
+stats-screen-total = 🎯 Total: { $clicks } clicks │ Sessions: { $sessions }
+stats-screen-session = 📊 Session: { $clicks } clicks in { $mins }m { $secs }s
+stats-screen-peak = 🚀 Peak achieved CPS: { $cps }
+stats-screen-longest = ⏱️  Longest continuous run: { $mins }m { $secs }s
+stats-screen-empty = No actions recorded yet
+stats-screen-modes = 🖱️  Actions per Mode
+stats-screen-histogram = 📊 Clicks per Minute of Clicking
+stats-screen-verify = ✅ Unverified clicks: { $failures } │ Retries: { $retries }
+stats-screen-keys = { $back }/Esc=Back │ { $reset } on the main screen resets statistics
+timing-title = ⏱️  Timing accuracy
+timing-empty = Start clicking to measure how closely clicks follow the chosen rate
+timing-errors = Interval error over { $samples } clicks at { $target } ms: mean { $mean } ms │ p95 { $p95 } ms │ max { $max } ms
+timing-steady = ✅ This machine sustains the chosen rate steadily
+timing-unsteady = ⚠️ Clicks drift: p95 error is { $percent }% of the interval, try a lower CPS
+runs-title = 🗄️  Run log
+runs-empty = No runs recorded yet: runs are logged when clicking stops
+runs-unavailable = ⚠️ Run log unavailable: { $error }
+runs-summary = 🏃 Runs: { $runs } │ Clicks: { $clicks } │ Time clicking: { $hours }h { $mins }m
+runs-best = 🚀 Best run average: { $average } CPS │ Best peak: { $peak } CPS
+runs-days = 📅 Recent days: { $days }
+runs-profiles = 🧩 Profiles: { $profiles }
+runs-profile = { $name } { $runs } runs/{ $clicks } clicks
+
+sequence-title = 🧩 Click Sequence Editor
+sequence-enabled = 🟢 ENABLED
+sequence-disabled = ⚪ DISABLED
+sequence-summary = │ Profile: { $profile } │ { $steps } steps │ Cycle time: { $secs }s
+sequence-steps = Steps
+sequence-empty = No steps yet - press A to add one
+sequence-field-position = Position / drag start x,y (empty = cursor)
+sequence-field-target = Drag end x,y (offset when no start)
+sequence-field-duration = Drag move time (ms)
+sequence-field-delay = Delay after step (ms)
+sequence-field-key = Key (char or Enter/Tab/Space/Esc/...) or text, {"{"}name{"}"} = variable
+sequence-keys = ↑↓=Select │ A=Add │ C=Duplicate │ D=Delete │ Shift+J/K=Reorder │ X=Enable
+sequence-keys-edit = T=Type │ B=Button │ P=Position │ G=Drag end │ U=Drag time │ W=Delay │ E=Key │ Esc=Back
+
+presets-title = 📋 CPS Presets
+presets-in-use = ● in use
+presets-new = New preset
+presets-edit = Preset
+presets-keys = ↑↓=Select │ A=Add │ E/Enter=Edit │ D=Delete │ Shift+J/K=Reorder
+presets-saved = Saved to cps_presets in the config │ Esc=Back
+
+scheduler-title = ⏰ Scheduler
+scheduler-empty = No schedules yet - press A to add one for the active profile
+scheduler-new = New schedule for { $profile } (CRON for DURATION, e.g. 0 */2 * * * for 30m)
+scheduler-keys = ↑↓=Select │ A=Add │ Space/Enter=Enable/Disable │ D=Delete │ Esc=Back
+scheduler-saved = Saved to schedules in each profile of the config │ Times are local
+scheduler-next = next { $time }
+scheduler-never = never
+scheduler-off = off
+
+audio-title = 🔊 Audio Output
+audio-default = System default
+audio-keys = ↑↓=Select │ Enter=Use and play a test beep │ Esc=Back
+audio-saved = Saved to audio_device in the config
+
+tray-show = Show Interface
+tray-toggle = Toggle Clicker
+tray-exit = Exit
+
+## Startup splash
+
+splash-loading = Loading
+splash-warning = WARNING: Using auto-clicker in games may result in account bans
+splash-complete = Loading Complete! Press 'h' to hide, '?' for help
+
+## Notifications: titles, then messages
+
+notify-started = Started successfully! Use global hotkey to toggle.
+notify-clicking-started = Clicking started
+notify-clicking-stopped = Clicking stopped
+notify-countdown = Clicking starts in { $secs }s
+notify-stop-condition = Stop condition met
+
+title-presets = CPS Presets
+notify-preset-range = Presets take { $min }-{ $max } CPS
+title-audio = Audio
+notify-audio-output = Output: { $device }
+notify-sound-on = Sound effects enabled
+notify-sound-off = Sound effects disabled
+title-cps-updated = CPS Updated
+notify-custom-cps = Custom CPS set to: { $cps }
+notify-cps = { $cps } CPS
+title-quick-slot = Quick Slot
+notify-slot-stored = { $slot } stored on { $key }
+notify-slot-empty = Slot { $key } is empty; { $save } stores the current profile and CPS
+title-idle = Idle Trigger
+notify-idle-started = You've been away, clicking started
+notify-idle-stopped = Welcome back, clicking stopped
+notify-idle-off = Idle trigger off
+notify-idle-after = Clicking starts after { $mins }m without input
+title-exported = Settings Exported
+notify-exported = Settings and profiles written to { $path }
+title-export-failed = Export Failed
+title-import = Import
+notify-import-same = { $path } matches the current settings
+title-import-failed = Import Failed
+title-imported = Settings Imported
+notify-imported = { $count } settings changed
+title-hotkey-updated = Hotkey Updated
+notify-new-hotkey = New hotkey: { $combo }
+notify-step-hotkey = { $combo } steps the CPS { $step }
+notify-pause-hotkey = { $combo } pauses and resumes clicking
+title-pause = Pause
+notify-paused = Paused, press again to resume where it left off
+notify-resumed = Resumed
+notify-pause-stopped = Not clicking, nothing to pause
+title-test-fire = Test Fire
+notify-test-fire-busy = Stop clicking before test-firing
+notify-test-fire = { $button } via { $backend }
+title-hotkey-mode = Hotkey Mode
+notify-no-hotkey = No hotkey set
+notify-hotkey-toggle = { $combo } starts/stops clicking
+notify-hotkey-hold = Clicking while { $combo } is held
+title-mouse-trigger = Mouse Trigger
+notify-mouse-off = Side buttons no longer start clicking
+notify-mouse-unavailable = { $trigger } saved, but unavailable: { $error }
+notify-hidden = Hidden to system tray
+notify-shown = Interface shown
+title-overlay = Overlay
+notify-overlay-shown = Activity indicator shown
+notify-overlay-hidden = Activity indicator hidden
+title-start-delay = Start Delay
+notify-no-delay = Clicking starts immediately
+notify-delay = { $secs }s countdown before clicking starts
+title-return = Return to Origin
+notify-return-on = Cursor returns to its prior position after fixed-point clicks
+notify-return-off = Cursor stays at the click target
+title-modifier = Click Modifier
+notify-modifier = { $key } held during each click
+notify-modifier-off = Clicks without a modifier
+title-backend = Click Backend
+notify-backend = Injecting input via { $backend }
+title-timing = Timing Model
+notify-timing = Click intervals: { $model }
+notify-gaussian = Gaussian intervals, std dev { $percent }%
+title-theme = Theme
+notify-theme = Theme: { $theme }
+title-language = Language
+notify-language = Language: { $language }
+title-dry-run = Dry Run
+notify-dry-run-on = Clicks are simulated: timing, stats and sounds run, no input is sent
+notify-dry-run-off = Clicking for real via { $backend }
+title-statistics = Statistics
+notify-stats-reset = Statistics reset
+title-range = CPS Range
+notify-range-off = Random range disabled
+notify-range = Random range set to { $range } CPS
+title-slow = Slow Mode
+notify-slow-off = Slow mode off
+notify-slow = One click every { $interval }
+title-anti-afk = Anti-AFK
+notify-anti-afk-off = Anti-AFK off
+notify-anti-afk = Small random action every { $window }
+title-duty = Duty Cycle
+notify-duty = Click { $on }, pause { $off }
+notify-duty-off = Clicking without pauses
+title-rotation = Key Rotation
+notify-rotation-off = Key rotation off, typing the repeat text
+notify-rotation = { $keys } keys per { $secs }s cycle
+title-hold-key = Hold Key
+notify-hold-key = { $key } is held down while active
+error-hold-key = Unknown key '{ $key }'
+title-job = Click Jobs
+notify-job = Queued { $job } ({ $queued } in queue)
+notify-jobs-cleared = Cleared { $count } queued jobs
+title-second-channel = Second Channel
+notify-second-channel-off = Second channel off
+notify-second-channel = { $button } at { $cps } CPS alongside the main action
+title-pattern = Click Pattern
+notify-pattern-off = Pattern off
+notify-pattern = { $clicks } clicks per { $secs }s cycle
+title-position = Click Position
+notify-position = Clicking at fixed point { $point }
+notify-position-cursor = Clicking at the cursor
+title-region = Click Region
+notify-region = Clicking only inside { $region }
+notify-region-unused = Region { $region } saved, but unused while the profile moves the cursor itself
+notify-region-cleared = Clicking anywhere again
+notify-region-unreadable = The cursor position can't be read here
+title-verify = Click Verification
+notify-verify-failed = The click at { $point } had no effect after { $retries } retries
+title-text = Type Text
+notify-text = Typing "{ $text }" each cycle
+title-scroll = Scroll Mode
+notify-scroll = { $amount } wheel notches per event
+title-unachievable = Rate Unachievable
+notify-unachievable = { $rate } cannot be sustained on this system
+title-milestone = Milestone Reached
+notify-milestone = 🎉 { $clicks } total clicks!
+title-faster-unavailable = Faster CPS Hotkey Unavailable
+title-slower-unavailable = Slower CPS Hotkey Unavailable
+title-pause-unavailable = Pause Hotkey Unavailable
+title-sequence = Sequence
+title-scheduler = Scheduler
+notify-schedule-started = { $profile } started by { $cron } for { $duration }
+notify-schedule-stopped = Scheduled run of { $profile } finished
+title-profile-rules = Profile Rules
+notify-rule-switched = Switched to { $profile } for { $window }
+notify-rule-restored = Back to { $profile }
+title-blocked = Blocked Window
+notify-blocked = Not clicking into { $window }; clicking carries on once it loses focus
+title-engine = Click Engine
+notify-engine-restarted = Crashed: { $error }. Clicking stopped and the engine was restarted
+notify-engine-stopped = Crashed again: { $error }. The engine will stay stopped until BClicker restarts
+title-injection-blocked = Input Blocked
+notify-injection-blocked = Clicks are not reaching the screen, maybe blocked by anti-cheat or a window running as administrator. Clicking stopped
+title-benchmark = Benchmark Complete
+
+## Phone remote page
+web-start = Start
+web-stop = Stop
+web-paused = Paused
+web-offline = Can't reach BClicker
+web-cps = CPS
+web-session-clicks = clicks this session
+web-total-clicks = All-time clicks
+web-token-needed = Open the link from `bclicker token` to connect
+web-read-only = Read only: this token can't start or stop clicking
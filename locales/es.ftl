@@ -0,0 +1,480 @@
+# Textos de la interfaz de BClicker en español. Los mensajes que falten aquí
+# se muestran en inglés. Las teclas (Y, Enter, Esc...) no se traducen.
+
+## Acciones de la pantalla principal: descripciones para la ayuda y la paleta
+
+action-button = Cambiar entre clic izquierdo/derecho, desplazamiento arriba/abajo, escribir texto, alternar botones, mantener tecla y mover sin clic
+action-scroll_amount = Cantidad de desplazamiento (muescas de rueda por evento)
+action-repeat_text = Texto que se repite en el modo escribir texto
+action-start_delay = Cambiar la cuenta atrás inicial (no/3s/5s/10s)
+action-idle_trigger = Cambiar el inicio por inactividad: empezar tras 1/2/5/10m sin uso
+action-overlay = Mostrar/ocultar el indicador de actividad siempre visible
+action-position = Fijar la posición del clic (x,y; vacío = cursor)
+action-return_to_origin = Devolver el cursor a su sitio tras los clics en un punto fijo
+action-region = Capturar la zona de clic: atajo global o Enter en dos esquinas (Supr la borra)
+action-modifier = Mantener Shift, Ctrl o Alt durante cada clic (p. ej. Shift+clic en objetos)
+action-backend = Cambiar el método de clic (enigo/sendinput/null)
+action-custom_cps = Editar los CPS personalizados (0.01-50000, p. ej. 2.5; >1000 pide confirmación)
+action-presets = Editar la lista de CPS predefinidos: añadir, borrar, reordenar
+action-range = Rango aleatorio de CPS (p. ej. 20-40; vacío lo desactiva)
+action-slow_mode = Modo lento: un clic cada 90s, 20m, 1h... (vacío lo desactiva)
+action-anti_afk = Anti-AFK: movimiento, tecla o clic aleatorio cada 3m-7m... (vacío lo desactiva)
+action-duty_cycle = Ciclo de trabajo del perfil: clic/pausa como 30s/10s (vacío lo desactiva)
+action-pattern = Patrón de clics del perfil, p. ej. 20x@30cps; wait 2s; 5x@10cps(right)
+action-key_rotation = Teclas pulsadas por turnos en el modo Escribir texto, p. ej. 1@1.5s, 2@800ms, 3@800ms
+action-hold_key = Tecla que el modo Mantener tecla deja pulsada, p. ej. w para caminar
+action-queue_job = Encolar una tarea puntual como 50x@20cps(right) at 800,600
+action-second_channel = Segundo botón con su propia frecuencia, p. ej. right@5
+action-std_dev = Desviación típica gaussiana (% del intervalo)
+action-timing = Cambiar el modelo de tiempos (fijo/uniforme/gaussiano)
+action-pause = Pausar o reanudar la ejecución sin perder su posición
+action-hotkey = Definir la tecla rápida global
+action-hotkey_mode = Alternar la tecla rápida entre activar/desactivar y mantener pulsada
+action-mouse_trigger = Cambiar el botón lateral del ratón (Mouse4/5, alternar/mantener)
+action-cps_up_hotkey = Definir la tecla rápida global que sube los CPS en cps_step
+action-cps_down_hotkey = Definir la tecla rápida global que baja los CPS en cps_step
+action-pause_hotkey = Definir la tecla rápida global que pausa y reanuda los clics
+action-audio = Activar/desactivar los efectos de sonido
+action-audio_device = Elegir el dispositivo de salida de audio de los sonidos
+action-theme = Cambiar el tema (profesional/alto contraste/deuteranopía)
+action-language = Cambiar el idioma de la interfaz
+action-hide = Ocultar/mostrar la interfaz
+action-reset_stats = Reiniciar las estadísticas de la sesión
+action-stats = Estadísticas: CPS máximos, racha más larga, clics por minuto
+action-benchmark = Medir los CPS máximos sostenibles
+action-test_fire = Hacer un clic de prueba con la configuración actual
+action-dry_run = Simulación: tiempos y estadísticas completos, sin clics reales
+action-sequence = Abrir el editor de secuencias de clics
+action-scheduler = Abrir el programador que inicia perfiles con expresiones cron
+action-save_slot = Guardar el perfil y los CPS en una tecla numérica (1-9 los aplica)
+action-export_config = Exportar ajustes y perfiles a un archivo para compartir
+action-import_config = Importar ajustes desde un archivo, viendo antes los cambios
+action-help = Mostrar/ocultar esta ayuda
+action-quit = Guardar y salir
+action-down = Seleccionar el siguiente CPS o patrón
+action-up = Seleccionar el CPS o patrón anterior
+action-toggle_clicking = Iniciar/detener los clics
+
+## Palabras de la barra de instrucciones
+
+key-button = Botón
+key-scroll_amount = Despl.
+key-repeat_text = Texto
+key-start_delay = Espera
+key-idle_trigger = Inactivo
+key-overlay = Indicador
+key-position = Posición
+key-return_to_origin = Volver
+key-region = Zona
+key-modifier = Modificador
+key-backend = Método
+key-custom_cps = Personal.
+key-presets = Predef.
+key-range = Rango
+key-slow_mode = Lento
+key-anti_afk = AFK
+key-duty_cycle = Ciclo
+key-pattern = Patrón
+key-second_channel = Canal 2
+key-key_rotation = Rotación
+key-hold_key = Mantener
+key-queue_job = Tarea
+key-timing = Tiempos
+key-pause = Pausa
+key-hotkey = Tecla
+key-hotkey_mode = Mantener
+key-mouse_trigger = Ratón
+key-audio = Sonido
+key-audio_device = Salida
+key-theme = Tema
+key-language = Idioma
+key-hide = Ocultar
+key-reset_stats = Reiniciar
+key-stats = Estad.
+key-benchmark = Medir
+key-test_fire = Prueba
+key-dry_run = Simular
+key-sequence = Secuencia
+key-scheduler = Programar
+key-save_slot = Ranura
+key-help = Ayuda
+key-quit = Salir
+bar-navigation = 🎮 ↑↓=Elegir │ Ctrl+P=Paleta
+
+## Pantalla principal
+
+app-title = BClicker Professional v2.0
+status-starting = ⏳ EMPIEZA EN { $secs }
+status-active = { $mark } ACTIVO
+status-paused-run = ⏸ EN PAUSA
+status-idle = { $mark } EN ESPERA
+status-dry-run = 🧪 SIMULACIÓN
+status-hotkey = Tecla:
+status-unavailable = ⚠️ No disponible
+status-not-set = Sin definir
+status-button = Botón:
+status-job = 📋 Tarea { $done }/{ $total } (+{ $queued } en cola)
+status-second-channel = + { $button } { $cps } CPS
+status-next-click = ⏳ Próximo clic en { $time }
+status-clicking = ▶ Haciendo clic { $time }
+status-paused = ⏸ En pausa { $time }
+status-duty = Ciclo { $duty }
+status-blocked = ⛔ Ventana bloqueada en primer plano, sin clics
+status-outside-region = 🔲 Cursor fuera de la zona de clic, esperando
+status-capped = 🐌 Limitado a { $cps } CPS en esta ventana
+status-unachievable = ⚠️ Inalcanzable
+status-engine-restarted = 💥 El motor falló ({ $error }), reiniciado
+status-engine-stopped = 💥 El motor falló ({ $error }), reinicia BClicker
+status-injection-blocked = 🚫 La entrada no llega, ejecución detenida
+status-scheduled = ⏰ Programado { $time }
+
+speed-title = ⚡ Configuración de velocidad
+choice-preset = { $cps } CPS
+choice-custom = { $cps } CPS (personalizado)
+choice-slow = Cada { $interval } (lento)
+choice-anti-afk = Anti-AFK ({ $window })
+
+input-cps = 📝 CPS personalizados [0.01-50000, Enter para guardar]
+input-high-cps = ⚠️  Confirmar CPS altos
+input-range = 🎲 Rango aleatorio de CPS [mín-máx, vacío lo desactiva]
+input-interval = 🐢 Intervalo del modo lento [90s, 20m, 1h30m, vacío lo desactiva]
+input-anti-afk = 💤 Ventana anti-AFK [mín-máx como 3m-7m, vacío lo desactiva]
+input-duty = 🔁 Ciclo de trabajo [clic/pausa como 30s/10s, vacío lo desactiva]
+input-pattern = 🧩 Patrón de clics [20x@30cps; wait 2s; 5x@10cps(right)]
+input-rotation = ⌨️ Rotación de teclas [1@1.5s, 2@800ms, 3@800ms, 4@2s]
+input-hold-key = 🚶 Tecla a mantener [w, space, shift...]
+input-job = 📋 Encolar tarea de clics [50x@20cps(right) at 800,600]
+input-second-channel = ➕ Segundo canal [right@5]
+input-std-dev = 📐 Desviación típica [% del intervalo, 1-100]
+input-scroll = 🖱️  Desplazamiento [muescas de rueda por evento, 1-50]
+input-position = 📍 Posición fija [x,y, vacío = cursor]
+input-region = 🔲 Zona de clic
+input-region-first = Lleva el cursor a la primera esquina_
+input-region-second = Primera esquina { $point }, ahora la opuesta_
+input-text = ⌨️  Texto a repetir [se escribe en cada ciclo, Tab alterna Enter]
+input-export = 📤 Exportar ajustes [archivo de destino]
+input-import = 📥 Importar ajustes [archivo de origen]
+input-idle = 📝 CPS personalizados [Pulsa { $key } para editar]
+input-with-enter = [+ Enter]
+input-confirm-high-cps = { $cps } CPS supera el límite de seguridad de { $limit } CPS y puede no alcanzarse. Lo entiendo (y/N)
+
+stats-title = 📈 Estadísticas
+stats-session = 📊 Sesión: { $clicks } clics en { $mins }m { $secs }s (media { $cps } CPS)
+stats-total = 🎯 Total: { $clicks } clics │ Sesiones: { $sessions } │ Sonido: { $audio } │ Método: { $backend }
+stats-audio-on = 🔊 Sí
+stats-audio-off = 🔇 No
+benchmark-running = 🏁 Medición: en curso con el método null...
+benchmark-result = 🏁 Medición: { $summary }
+benchmark-hint = 🏁 Medición: pulsa { $key } para medir este equipo
+
+hint-preparing = 🕐 Preparando la captura de la combinación...
+hint-capture-toggle = ⌨️  Pulsa la combinación (Ctrl+Shift+B, F1-F24, flechas, teclado numérico...) │ Esc=Cancelar
+hint-capture-faster = ⌨️  Pulsa la combinación para subir los CPS (p. ej. Ctrl+Shift+Up) │ Esc=Cancelar
+hint-capture-slower = ⌨️  Pulsa la combinación para bajar los CPS (p. ej. Ctrl+Shift+Down) │ Esc=Cancelar
+hint-capture-pause = ⌨️  Pulsa la combinación para pausar/reanudar (p. ej. Ctrl+Shift+P) │ Esc=Cancelar
+hint-cps = ✏️  Escribe los CPS (0.01-50000, p. ej. 0.5 = cada 2s) │ Enter=Guardar │ Esc=Cancelar
+hint-high-cps = ⚠️  Y=Lo entiendo, aplicar │ Otra tecla=Cancelar
+hint-range = 🎲 Escribe un rango como 20-40 o 0.5-2.5 (0.01-1000) │ Enter=Guardar │ Esc=Cancelar
+hint-interval = 🐢 Escribe un intervalo de 1s a 24h, como 20m │ Enter=Guardar │ Esc=Cancelar
+hint-anti-afk = 💤 Escribe una ventana aleatoria como 3m-7m (1s-24h) │ Enter=Guardar │ Esc=Cancelar
+hint-duty = 🔁 Escribe tiempo de clic/pausa como 30s/10s o 2m/1m │ Enter=Guardar │ Esc=Cancelar
+hint-rotation = ⌨️ TECLA@INTERVALO con 500ms/2s/1m, separadas por , │ Vacío=No │ Enter=Guardar │ Esc=Cancelar
+hint-hold-key = 🚶 Un carácter o space/shift/ctrl/alt/up/down/left/right, pulsada hasta detener │ Enter=Guardar │ Esc=Cancelar
+hint-job = 📋 COUNTx@RATEcps con (right)/(middle) y at X,Y opcionales; se ejecutan en orden antes de los clics │ Vacío=Vaciar cola │ Enter=Encolar │ Esc=Cancelar
+hint-second-channel = ➕ BOTÓN@CPS con left o right, junto a la acción principal │ Vacío=No │ Enter=Guardar │ Esc=Cancelar
+hint-pattern = 🧩 COUNTx@RATEcps(left|right) y wait 500ms/2s/1m, separados por ; │ Vacío=No │ Enter=Guardar │ Esc=Cancelar
+hint-std-dev = 📐 Escribe el porcentaje de desviación (1-100) │ Enter=Guardar │ Esc=Cancelar
+hint-scroll = 🖱️  Escribe las muescas por evento (1-50) │ Enter=Guardar │ Esc=Cancelar
+hint-position = 📍 Escribe coordenadas como 800,600 │ Enter=Guardar │ Esc=Cancelar
+hint-region = 🔲 Atajo o Enter=Marcar esquina │ Supr=Borrar zona │ Esc=Cancelar
+hint-text = ⌨️  Escribe el texto a repetir │ Tab=Alternar Enter │ Enter=Guardar y usar │ Esc=Cancelar
+hint-export = 📤 Escribe la ruta del archivo (sin estadísticas) │ Enter=Exportar │ Esc=Cancelar
+hint-import = 📥 Escribe el archivo a importar │ Enter=Ver cambios │ Esc=Cancelar
+hint-slot = 🔢 Pulsa 1-9 para guardar ahí el perfil y los CPS actuales │ Otra tecla=Cancelar
+pro-features = 🏆 Funciones Pro:
+pro-features-list = Teclas globales • Bandeja del sistema • Sonidos • Estadísticas • Guardado automático
+controls-info-title = 🎛️  Controles e información
+controls-title = 🎛️  Controles
+keys-save-cancel = Enter=Guardar │ Esc=Cancelar
+
+## Pantalla de ayuda
+
+help-title = BClicker Professional - Ayuda
+help-heading = Ayuda y documentación
+help-main-controls = 🎯 CONTROLES PRINCIPALES:
+help-nav-keys = ↑/↓ o { $down }/{ $up }
+help-navigate = Recorrer los CPS predefinidos y los patrones
+help-confirm = Confirmar la selección
+help-cancel = Cancelar la operación
+help-palette = Paleta de comandos: búsqueda aproximada de todas las acciones
+help-primary = ⚡ FUNCIONES PRINCIPALES:
+help-advanced = 🔧 FUNCIONES AVANZADAS:
+help-hotkey = 🎮 TECLA RÁPIDA GLOBAL:
+help-hotkey-body =
+    La tecla funciona en todo el sistema para iniciar/detener los clics
+    Por defecto: Ctrl+Shift+B
+    Funciona aunque la interfaz esté oculta
+    Las teclas de velocidad opcionales suben/bajan los CPS en cps_step
+help-tray = 📊 BANDEJA DEL SISTEMA:
+help-tray-body =
+    • Clic derecho en el icono para ver el menú
+    • El icono parpadea mientras hace clic
+    • Las notificaciones te mantienen informado
+    • bclicker --tray-only arranca solo con el icono de la bandeja
+help-audio = 🔊 SONIDOS:
+help-audio-body =
+    • Sonidos al iniciar y detener
+    • Se activan con la tecla '{ $key }'
+    • Elige el dispositivo de salida con '{ $device_key }'
+    • Avisos sonoros profesionales
+help-config = 💾 CONFIGURACIÓN:
+help-config-body =
+    • Se guarda sola en bclicker_config.toml (o .json/.yaml)
+    • Estadísticas acumuladas entre sesiones
+    • Todas las preferencias se conservan
+help-stats = 📈 ESTADÍSTICAS:
+help-stats-body =
+    • Seguimiento de la sesión en tiempo real
+    • Clics totales de todas las sesiones
+    • Cálculo de CPS medios
+help-notice = ⚠️  AVISO IMPORTANTE:
+help-notice-body =
+    Usar autoclickers en juegos competitivos
+    puede infringir sus condiciones de uso y
+    acabar en un baneo. ¡Úsalo con responsabilidad!
+help-close = Usa ↑/↓ para desplazarte • Pulsa { $key } o Esc para cerrar la ayuda
+
+## Otras pantallas
+
+import-title = 📥 Vista previa de la importación: { $count } cambios
+import-keys = Y=Importar │ Otra tecla=Cancelar
+
+conflict-title = ⚠️  Conflicto de tecla rápida global
+conflict-reason = no se pudo registrar: { $reason }.
+conflict-fallback = Los clics se pueden seguir alternando desde la paleta de comandos y la bandeja.
+conflict-keys = Enter=Capturar otra combinación │ Otra tecla=Mantenerla
+
+palette-title = 🔍 Paleta de comandos
+palette-keys = ↑↓=Elegir │ Enter=Ejecutar │ Esc=Cerrar
+
+stats-screen-total = 🎯 Total: { $clicks } clics │ Sesiones: { $sessions }
+stats-screen-session = 📊 Sesión: { $clicks } clics en { $mins }m { $secs }s
+stats-screen-peak = 🚀 CPS máximos alcanzados: { $cps }
+stats-screen-longest = ⏱️  Racha continua más larga: { $mins }m { $secs }s
+stats-screen-empty = Todavía no hay acciones registradas
+stats-screen-modes = 🖱️  Acciones por modo
+stats-screen-histogram = 📊 Clics por minuto de actividad
+stats-screen-verify = ✅ Clics sin verificar: { $failures } │ Reintentos: { $retries }
+stats-screen-keys = { $back }/Esc=Volver │ { $reset } en la pantalla principal reinicia las estadísticas
+timing-title = ⏱️  Precisión de los tiempos
+timing-empty = Empieza a hacer clic para medir cuánto se ajustan los clics a la velocidad elegida
+timing-errors = Error del intervalo en { $samples } clics a { $target } ms: media { $mean } ms │ p95 { $p95 } ms │ máx. { $max } ms
+timing-steady = ✅ Este equipo mantiene la velocidad elegida de forma estable
+timing-unsteady = ⚠️ Los clics se desvían: el error p95 es el { $percent }% del intervalo, prueba con menos CPS
+runs-title = 🗄️  Registro de ejecuciones
+runs-empty = Aún no hay ejecuciones: se registran al detener los clics
+runs-unavailable = ⚠️ Registro de ejecuciones no disponible: { $error }
+runs-summary = 🏃 Ejecuciones: { $runs } │ Clics: { $clicks } │ Tiempo haciendo clic: { $hours }h { $mins }m
+runs-best = 🚀 Mejor media de una ejecución: { $average } CPS │ Mejor pico: { $peak } CPS
+runs-days = 📅 Últimos días: { $days }
+runs-profiles = 🧩 Perfiles: { $profiles }
+runs-profile = { $name } { $runs } ejecuciones/{ $clicks } clics
+
+sequence-title = 🧩 Editor de secuencias
+sequence-enabled = 🟢 ACTIVADA
+sequence-disabled = ⚪ DESACTIVADA
+sequence-summary = │ Perfil: { $profile } │ { $steps } pasos │ Duración del ciclo: { $secs }s
+sequence-steps = Pasos
+sequence-empty = Aún no hay pasos: pulsa A para añadir uno
+sequence-field-position = Posición / inicio del arrastre x,y (vacío = cursor)
+sequence-field-target = Fin del arrastre x,y (desplazamiento si no hay inicio)
+sequence-field-duration = Duración del arrastre (ms)
+sequence-field-delay = Espera tras el paso (ms)
+sequence-field-key = Tecla (carácter o Enter/Tab/Space/Esc/...) o texto, {"{"}nombre{"}"} = variable
+sequence-keys = ↑↓=Elegir │ A=Añadir │ C=Duplicar │ D=Borrar │ Shift+J/K=Reordenar │ X=Activar
+sequence-keys-edit = T=Tipo │ B=Botón │ P=Posición │ G=Fin arrastre │ U=Duración │ W=Espera │ E=Tecla │ Esc=Volver
+
+presets-title = 📋 CPS predefinidos
+presets-in-use = ● en uso
+presets-new = Nuevo valor
+presets-edit = Valor
+presets-keys = ↑↓=Elegir │ A=Añadir │ E/Enter=Editar │ D=Borrar │ Shift+J/K=Reordenar
+presets-saved = Se guarda en cps_presets de la configuración │ Esc=Volver
+
+scheduler-title = ⏰ Programador
+scheduler-empty = Aún no hay programaciones: pulsa A para añadir una al perfil activo
+scheduler-new = Nueva programación para { $profile } (CRON for DURACIÓN, p. ej. 0 */2 * * * for 30m)
+scheduler-keys = ↑↓=Elegir │ A=Añadir │ Espacio/Enter=Activar/Desactivar │ D=Borrar │ Esc=Volver
+scheduler-saved = Se guarda en schedules de cada perfil de la configuración │ Horas locales
+scheduler-next = próxima { $time }
+scheduler-never = nunca
+scheduler-off = desactivada
+
+audio-title = 🔊 Salida de audio
+audio-default = Predeterminado del sistema
+audio-keys = ↑↓=Elegir │ Enter=Usar y reproducir un pitido │ Esc=Volver
+audio-saved = Se guarda en audio_device de la configuración
+
+tray-show = Mostrar interfaz
+tray-toggle = Alternar clics
+tray-exit = Salir
+
+## Pantalla de inicio
+
+splash-loading = Cargando
+splash-warning = AVISO: usar autoclickers en juegos puede acabar en un baneo de la cuenta
+splash-complete = ¡Carga completa! Pulsa 'h' para ocultar, '?' para la ayuda
+
+## Notificaciones: títulos y mensajes
+
+notify-started = ¡Iniciado! Usa la tecla rápida global para alternar.
+notify-clicking-started = Clics iniciados
+notify-clicking-stopped = Clics detenidos
+notify-countdown = Los clics empiezan en { $secs }s
+notify-stop-condition = Se cumplió la condición de parada
+
+title-presets = CPS predefinidos
+notify-preset-range = Los valores admiten { $min }-{ $max } CPS
+title-audio = Sonido
+notify-audio-output = Salida: { $device }
+notify-sound-on = Efectos de sonido activados
+notify-sound-off = Efectos de sonido desactivados
+title-cps-updated = CPS actualizados
+notify-custom-cps = CPS personalizados: { $cps }
+notify-cps = { $cps } CPS
+title-quick-slot = Ranura rápida
+notify-slot-stored = { $slot } guardado en { $key }
+notify-slot-empty = La ranura { $key } está vacía; { $save } guarda el perfil y los CPS actuales
+title-idle = Inicio por inactividad
+notify-idle-started = Estabas ausente: clics iniciados
+notify-idle-stopped = Bienvenido de nuevo: clics detenidos
+notify-idle-off = Inicio por inactividad desactivado
+notify-idle-after = Los clics empiezan tras { $mins }m sin actividad
+title-exported = Ajustes exportados
+notify-exported = Ajustes y perfiles guardados en { $path }
+title-export-failed = Error al exportar
+title-import = Importar
+notify-import-same = { $path } coincide con los ajustes actuales
+title-import-failed = Error al importar
+title-imported = Ajustes importados
+notify-imported = { $count } ajustes cambiados
+title-hotkey-updated = Tecla rápida actualizada
+notify-new-hotkey = Nueva tecla rápida: { $combo }
+notify-step-hotkey = { $combo } cambia los CPS en { $step }
+notify-pause-hotkey = { $combo } pausa y reanuda los clics
+title-pause = Pausa
+notify-paused = En pausa, pulsa de nuevo para seguir donde se quedó
+notify-resumed = Reanudado
+notify-pause-stopped = No se está haciendo clic, no hay nada que pausar
+title-test-fire = Clic de prueba
+notify-test-fire-busy = Detén los clics antes de probar
+notify-test-fire = { $button } mediante { $backend }
+title-hotkey-mode = Modo de la tecla rápida
+notify-no-hotkey = No hay tecla rápida definida
+notify-hotkey-toggle = { $combo } inicia/detiene los clics
+notify-hotkey-hold = Clics mientras se mantiene { $combo }
+title-mouse-trigger = Botón del ratón
+notify-mouse-off = Los botones laterales ya no inician los clics
+notify-mouse-unavailable = { $trigger } guardado, pero no disponible: { $error }
+notify-hidden = Oculto en la bandeja del sistema
+notify-shown = Interfaz visible
+title-overlay = Indicador
+notify-overlay-shown = Indicador de actividad visible
+notify-overlay-hidden = Indicador de actividad oculto
+title-start-delay = Cuenta atrás
+notify-no-delay = Los clics empiezan de inmediato
+notify-delay = { $secs }s de cuenta atrás antes de empezar
+title-return = Volver al origen
+notify-return-on = El cursor vuelve a su sitio tras los clics en un punto fijo
+notify-return-off = El cursor se queda en el punto del clic
+title-modifier = Modificador de clic
+notify-modifier = { $key } pulsado durante cada clic
+notify-modifier-off = Clics sin modificador
+title-backend = Método de clic
+notify-backend = Enviando la entrada mediante { $backend }
+title-timing = Modelo de tiempos
+notify-timing = Intervalos: { $model }
+notify-gaussian = Intervalos gaussianos, desviación { $percent }%
+title-theme = Tema
+notify-theme = Tema: { $theme }
+title-language = Idioma
+notify-language = Idioma: { $language }
+title-dry-run = Simulación
+notify-dry-run-on = Clics simulados: tiempos, estadísticas y sonidos funcionan, no se envía nada
+notify-dry-run-off = Clics reales mediante { $backend }
+title-statistics = Estadísticas
+notify-stats-reset = Estadísticas reiniciadas
+title-range = Rango de CPS
+notify-range-off = Rango aleatorio desactivado
+notify-range = Rango aleatorio: { $range } CPS
+title-slow = Modo lento
+notify-slow-off = Modo lento desactivado
+notify-slow = Un clic cada { $interval }
+title-anti-afk = Anti-AFK
+notify-anti-afk-off = Anti-AFK desactivado
+notify-anti-afk = Pequeña acción aleatoria cada { $window }
+title-duty = Ciclo de trabajo
+notify-duty = Clic { $on }, pausa { $off }
+notify-duty-off = Clics sin pausas
+title-rotation = Rotación de teclas
+notify-rotation-off = Rotación desactivada, se escribe el texto repetido
+notify-rotation = { $keys } teclas por ciclo de { $secs }s
+title-hold-key = Mantener tecla
+notify-hold-key = { $key } se mantiene pulsada mientras está activo
+error-hold-key = Tecla desconocida '{ $key }'
+title-job = Tareas de clics
+notify-job = { $job } encolada ({ $queued } en cola)
+notify-jobs-cleared = { $count } tareas eliminadas de la cola
+title-second-channel = Segundo canal
+notify-second-channel-off = Segundo canal desactivado
+notify-second-channel = { $button } a { $cps } CPS junto a la acción principal
+title-pattern = Patrón de clics
+notify-pattern-off = Patrón desactivado
+notify-pattern = { $clicks } clics por ciclo de { $secs }s
+title-position = Posición del clic
+notify-position = Clic en el punto fijo { $point }
+notify-position-cursor = Clic en el cursor
+title-region = Zona de clic
+notify-region = Clics solo dentro de { $region }
+notify-region-unused = Zona { $region } guardada, pero sin uso mientras el perfil mueve el cursor
+notify-region-cleared = Clics en cualquier sitio de nuevo
+notify-region-unreadable = Aquí no se puede leer la posición del cursor
+title-verify = Verificación de clics
+notify-verify-failed = El clic en { $point } no tuvo efecto tras { $retries } reintentos
+title-text = Escribir texto
+notify-text = Escribiendo "{ $text }" en cada ciclo
+title-scroll = Modo desplazamiento
+notify-scroll = { $amount } muescas de rueda por evento
+title-unachievable = Velocidad inalcanzable
+notify-unachievable = { $rate } no se puede mantener en este sistema
+title-milestone = ¡Hito alcanzado!
+notify-milestone = 🎉 ¡{ $clicks } clics en total!
+title-faster-unavailable = Tecla para subir CPS no disponible
+title-slower-unavailable = Tecla para bajar CPS no disponible
+title-pause-unavailable = Tecla de pausa no disponible
+title-sequence = Secuencia
+title-scheduler = Programador
+notify-schedule-started = { $profile } iniciado por { $cron } durante { $duration }
+notify-schedule-stopped = Terminó la ejecución programada de { $profile }
+title-profile-rules = Reglas de perfil
+notify-rule-switched = Cambiado a { $profile } para { $window }
+notify-rule-restored = De vuelta a { $profile }
+title-blocked = Ventana bloqueada
+notify-blocked = Sin clics en { $window }; se reanudan cuando pierda el foco
+title-engine = Motor de clics
+notify-engine-restarted = Falló: { $error }. Se detuvieron los clics y se reinició el motor
+notify-engine-stopped = Falló de nuevo: { $error }. El motor seguirá detenido hasta reiniciar BClicker
+title-injection-blocked = Entrada bloqueada
+notify-injection-blocked = Los clics no llegan a la pantalla, quizá bloqueados por un anticheat o una ventana abierta como administrador. Clics detenidos
+title-benchmark = Medición completada
+
+## Página de control remoto para el móvil
+web-start = Iniciar
+web-stop = Detener
+web-paused = En pausa
+web-offline = No se puede conectar con BClicker
+web-cps = CPS
+web-session-clicks = clics en esta sesión
+web-total-clicks = Clics totales
+web-token-needed = Abre el enlace de `bclicker token` para conectar
+web-read-only = Solo lectura: este token no puede iniciar ni detener los clics
@@ -0,0 +1,846 @@
+use crate::{
+    condition::{Compare, Condition},
+    sequence::{Point, SequenceStep, StepKind},
+};
+
+// Unrolled loops stop here rather than filling the config with steps
+const MAX_STEPS: usize = 5000;
+
+// AutoHotkey waits this long after each mouse action and key by default
+// (SetMouseDelay/SetKeyDelay), so steps without a Sleep after them get it
+const DEFAULT_DELAY_MS: u64 = 10;
+
+// Commands that set up the script rather than act, skipped without a word
+const SETUP: &[&str] = &[
+    "sendmode",
+    "setworkingdir",
+    "setbatchlines",
+    "setkeydelay",
+    "setmousedelay",
+    "setdefaultmousespeed",
+    "settitlematchmode",
+    "setcontroldelay",
+    "setwindelay",
+    "process",
+    "persistent",
+    "global",
+    "static",
+    "local",
+    "return",
+    "exit",
+    "exitapp",
+    "pause",
+    "suspend",
+    "reload",
+];
+
+// A script read as a sequence, with what couldn't be carried over
+pub struct AhkImport {
+    pub steps: Vec<SequenceStep>,
+    pub warnings: Vec<String>,
+}
+
+// A script line without its comment, numbered for warnings
+struct Line {
+    number: usize,
+    text: String,
+}
+
+struct Importer {
+    lines: Vec<Line>,
+    pos: usize,
+    steps: Vec<SequenceStep>,
+    warnings: Vec<String>,
+    screen_coords: bool,
+    positioned: bool,
+}
+
+// Reads the click, sleep and send commands of a simple AutoHotkey v1 or v2
+// clicking macro. Loops with a count are unrolled; an endless Loop or a
+// `while toggle` loop becomes the sequence's own repeat. Only the first
+// hotkey's actions are taken, since a sequence is one routine
+pub fn import(script: &str) -> Result<AhkImport, String> {
+    let mut importer = Importer {
+        lines: split_lines(script),
+        pos: 0,
+        steps: Vec::new(),
+        warnings: Vec::new(),
+        screen_coords: false,
+        positioned: false,
+    };
+    importer.block(false)?;
+
+    if importer.steps.is_empty() {
+        return Err("no clicks, moves, sleeps or keys found to import".to_string());
+    }
+    if importer.positioned && !importer.screen_coords {
+        importer.warnings.push(
+            "coordinates are taken as screen positions; AutoHotkey reads them relative \
+             to the active window unless the script sets CoordMode Mouse, Screen"
+                .to_string(),
+        );
+    }
+    for step in &mut importer.steps {
+        if step.delay_ms == 0 {
+            step.delay_ms = DEFAULT_DELAY_MS;
+        }
+    }
+    Ok(AhkImport {
+        steps: importer.steps,
+        warnings: importer.warnings,
+    })
+}
+
+// Drops comments and blank lines, and puts braces on lines of their own so
+// `Loop 5 {` and `}` read the same in every brace style
+fn split_lines(script: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut in_comment = false;
+    for (i, raw) in script.lines().enumerate() {
+        let trimmed = raw.trim();
+        if in_comment {
+            in_comment = !trimmed.starts_with("*/") && !trimmed.ends_with("*/");
+            continue;
+        }
+        if trimmed.starts_with("/*") {
+            in_comment = true;
+            continue;
+        }
+        let mut text = strip_comment(trimmed).trim().to_string();
+        let mut push = |text: &str| {
+            if !text.is_empty() {
+                lines.push(Line {
+                    number: i + 1,
+                    text: text.to_string(),
+                });
+            }
+        };
+        while let Some(rest) = text.strip_prefix('}') {
+            push("}");
+            text = rest.trim().to_string();
+        }
+        match text.strip_suffix('{') {
+            Some(head) if !head.ends_with('`') => {
+                push(head.trim());
+                push("{");
+            }
+            _ => push(&text),
+        }
+    }
+    lines
+}
+
+// A ";" starts a comment at the beginning of a line or after whitespace,
+// unless escaped with a backtick
+fn strip_comment(line: &str) -> &str {
+    if line.starts_with(';') {
+        return "";
+    }
+    let bytes = line.as_bytes();
+    for i in 1..bytes.len() {
+        if bytes[i] == b';' && bytes[i - 1].is_ascii_whitespace() {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+// The command word and its arguments: "Click, 10, 20", "Click 10, 20" and
+// "Click(10, 20)" all give ("click", "10, 20")
+fn command(text: &str) -> (String, &str) {
+    let end = text
+        .find(|c: char| c == ',' || c == '(' || c.is_whitespace())
+        .unwrap_or(text.len());
+    let name = text[..end].to_ascii_lowercase();
+    let mut args = text[end..].trim_start();
+    args = args.strip_prefix(',').unwrap_or(args).trim();
+    if let Some(inner) = args.strip_prefix('(').and_then(|a| a.strip_suffix(')')) {
+        args = inner.trim();
+    }
+    (name, args)
+}
+
+// Comma-separated arguments without v2's quotes
+fn arguments(args: &str) -> Vec<String> {
+    if args.is_empty() {
+        return Vec::new();
+    }
+    args.split(',')
+        .map(|a| unquote(a.trim()).to_string())
+        .collect()
+}
+
+fn unquote(text: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|q| text.strip_prefix(*q).and_then(|t| t.strip_suffix(*q)))
+        .unwrap_or(text)
+}
+
+// "Left", "L", "Right", "R"; None for anything the sequence can't press
+fn button(word: &str) -> Option<Option<usize>> {
+    match word.to_ascii_lowercase().as_str() {
+        "" | "left" | "l" => Some(Some(0)),
+        "right" | "r" => Some(Some(1)),
+        "middle" | "m" | "x1" | "x2" | "wheelup" | "wu" | "wheeldown" | "wd" | "wheelleft"
+        | "wl" | "wheelright" | "wr" => Some(None),
+        _ => None,
+    }
+}
+
+// `Loop`, `Loop, 5`, `Loop 5` and `Loop(5)`; None for an endless loop
+fn loop_count(args: &str) -> Result<Option<usize>, String> {
+    let args = arguments(args);
+    match args.first().map(String::as_str) {
+        None | Some("") => Ok(None),
+        Some(n) => n
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("loop count '{}' isn't a number", n)),
+    }
+}
+
+fn too_many() -> String {
+    format!("the script unrolls to more than {} steps", MAX_STEPS)
+}
+
+// AHK's {Name} keys the Key step knows
+fn key_name(name: &str) -> Option<&'static str> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => "enter",
+        "tab" => "tab",
+        "space" => "space",
+        "esc" | "escape" => "esc",
+        "backspace" | "bs" => "backspace",
+        "up" => "up",
+        "down" => "down",
+        "left" => "left",
+        "right" => "right",
+        _ => return None,
+    })
+}
+
+impl Importer {
+    fn warn(&mut self, line: usize, message: impl std::fmt::Display) {
+        self.warnings.push(format!("line {}: {}", line, message));
+    }
+
+    fn push(&mut self, step: SequenceStep) -> Result<(), String> {
+        if self.steps.len() >= MAX_STEPS {
+            return Err(too_many());
+        }
+        self.positioned |= step.position.is_some();
+        self.steps.push(step);
+        Ok(())
+    }
+
+    fn step(kind: StepKind) -> SequenceStep {
+        SequenceStep {
+            kind,
+            delay_ms: 0,
+            ..SequenceStep::default()
+        }
+    }
+
+    // Lines up to the closing brace of a block, or the end of the script at
+    // the top level; false once the rest of the script is to be skipped
+    fn block(&mut self, nested: bool) -> Result<bool, String> {
+        while self.pos < self.lines.len() {
+            let number = self.lines[self.pos].number;
+            let text = self.lines[self.pos].text.clone();
+            self.pos += 1;
+            if text == "}" {
+                if nested {
+                    return Ok(true);
+                }
+                self.warn(number, "unmatched }");
+                continue;
+            }
+            if !self.line(number, &text)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    // The body after a loop header, a braced block or else the next line,
+    // run `times` times. It's read once and its steps copied, so warnings
+    // aren't repeated and huge counts fail fast
+    fn body(&mut self, times: usize) -> Result<bool, String> {
+        let braced = self
+            .lines
+            .get(self.pos)
+            .is_some_and(|line| line.text == "{");
+        let (first, warned) = (self.steps.len(), self.warnings.len());
+        let go_on = if braced {
+            self.pos += 1;
+            self.block(true)?
+        } else if let Some(line) = self.lines.get(self.pos) {
+            let (number, text) = (line.number, line.text.clone());
+            self.pos += 1;
+            self.line(number, &text)?
+        } else {
+            true
+        };
+        if times == 0 {
+            self.steps.truncate(first);
+            self.warnings.truncate(warned);
+            return Ok(go_on);
+        }
+        let once = self.steps.len() - first;
+        if once * times > MAX_STEPS {
+            return Err(too_many());
+        }
+        for _ in 1..times {
+            self.steps.extend_from_within(first..first + once);
+        }
+        Ok(go_on)
+    }
+
+    // Imports one line; false once the rest of the script is to be skipped
+    fn line(&mut self, number: usize, text: &str) -> Result<bool, String> {
+        // Hotkey labels such as "F6::" or "^j::Click"; hotstrings aren't
+        // clicking macros
+        let hotkey = text.split_once("::").filter(|(label, _)| {
+            !label.is_empty() && !label.contains(char::is_whitespace) && !text.starts_with(':')
+        });
+        if let Some((label, rest)) = hotkey {
+            if !self.steps.is_empty() {
+                self.warn(
+                    number,
+                    format!(
+                        "only the first hotkey is imported, skipped {}:: onwards",
+                        label
+                    ),
+                );
+                return Ok(false);
+            }
+            let rest = rest.trim();
+            if !rest.is_empty() {
+                return self.line(number, rest);
+            }
+            // v2 puts a hotkey's body in braces
+            if self
+                .lines
+                .get(self.pos)
+                .is_some_and(|line| line.text == "{")
+            {
+                self.pos += 1;
+                return self.block(true);
+            }
+            return Ok(true);
+        }
+        if text.starts_with('#') || text.contains(":=") || text == "{" {
+            return Ok(true);
+        }
+
+        let (name, args) = command(text);
+        match name.as_str() {
+            "click" => self.click(number, args)?,
+            "mouseclick" => self.mouse_click(number, args)?,
+            "mousemove" => self.mouse_move(number, args)?,
+            "mouseclickdrag" => self.drag(number, args)?,
+            "sleep" => self.sleep(number, args),
+            "send" | "sendinput" | "sendevent" | "sendplay" => self.send(number, args, false)?,
+            "sendraw" | "sendtext" => self.send(number, args, true)?,
+            "loop" => {
+                let times = loop_count(args).unwrap_or_else(|e| {
+                    self.warn(number, e);
+                    None
+                });
+                // An endless loop is what the sequence does anyway
+                return self.body(times.unwrap_or(1));
+            }
+            "while" => {
+                self.warn(
+                    number,
+                    format!(
+                        "'while {}' became the sequence's own repeat; stop it with the hotkey",
+                        args
+                    ),
+                );
+                return self.body(1);
+            }
+            "coordmode" => {
+                let args = arguments(args);
+                if args
+                    .first()
+                    .is_some_and(|a| a.eq_ignore_ascii_case("mouse"))
+                {
+                    self.screen_coords = args
+                        .get(1)
+                        .is_some_and(|a| a.eq_ignore_ascii_case("screen"));
+                }
+            }
+            name if SETUP.contains(&name) => {}
+            _ => self.warn(number, format!("'{}' isn't supported, skipped", text)),
+        }
+        Ok(true)
+    }
+
+    // Click [X, Y] [Button] [Count]; words and numbers in any order, split
+    // by commas or spaces
+    fn click(&mut self, number: usize, args: &str) -> Result<(), String> {
+        let mut numbers = Vec::new();
+        let mut pressed = Some(0);
+        for word in unquote(args)
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|w| !w.is_empty())
+        {
+            let word = unquote(word);
+            if let Ok(n) = word.parse::<i32>() {
+                numbers.push(n);
+                continue;
+            }
+            match (button(word), word.to_ascii_lowercase().as_str()) {
+                (Some(b), _) => pressed = b,
+                (None, "down" | "d" | "up" | "u" | "rel" | "relative") => {
+                    self.warn(number, format!("Click {} isn't supported, skipped", word));
+                    return Ok(());
+                }
+                _ => {
+                    self.warn(number, format!("unknown Click option '{}', skipped", word));
+                    return Ok(());
+                }
+            }
+        }
+        let (position, count) = match numbers[..] {
+            [] => (None, 1),
+            [count] => (None, count),
+            [x, y] => (Some(Point { x, y }), 1),
+            [x, y, count, ..] => (Some(Point { x, y }), count),
+        };
+        self.clicks(number, pressed, position, count)
+    }
+
+    // MouseClick [Button, X, Y, Count, Speed, D|U, R]
+    fn mouse_click(&mut self, number: usize, args: &str) -> Result<(), String> {
+        let args = arguments(args);
+        let arg = |i: usize| args.get(i).map_or("", String::as_str);
+        if !arg(5).is_empty() || !arg(6).is_empty() {
+            self.warn(number, "MouseClick with D, U or R isn't supported, skipped");
+            return Ok(());
+        }
+        let Some(pressed) = button(arg(0)) else {
+            self.warn(number, format!("unknown button '{}', skipped", arg(0)));
+            return Ok(());
+        };
+        let position = match (arg(1).parse(), arg(2).parse()) {
+            (Ok(x), Ok(y)) => Some(Point { x, y }),
+            _ => None,
+        };
+        let count = arg(3).parse().unwrap_or(1);
+        self.clicks(number, pressed, position, count)
+    }
+
+    fn clicks(
+        &mut self,
+        number: usize,
+        pressed: Option<usize>,
+        position: Option<Point>,
+        count: i32,
+    ) -> Result<(), String> {
+        let Some(button) = pressed else {
+            self.warn(
+                number,
+                "only left and right clicks can be imported, skipped",
+            );
+            return Ok(());
+        };
+        if count <= 0 {
+            // Click 0 at a point only moves there
+            if let Some(position) = position {
+                self.push(SequenceStep {
+                    position: Some(position),
+                    ..Self::step(StepKind::Move)
+                })?;
+            }
+            return Ok(());
+        }
+        for _ in 0..count {
+            self.push(SequenceStep {
+                position,
+                button,
+                ..Self::step(StepKind::Click)
+            })?;
+        }
+        Ok(())
+    }
+
+    // MouseMove X, Y [, Speed, R]
+    fn mouse_move(&mut self, number: usize, args: &str) -> Result<(), String> {
+        let args = arguments(args);
+        if args.get(3).is_some_and(|a| !a.is_empty()) {
+            self.warn(number, "relative MouseMove isn't supported, skipped");
+            return Ok(());
+        }
+        match (
+            args.first().and_then(|x| x.parse().ok()),
+            args.get(1).and_then(|y| y.parse().ok()),
+        ) {
+            (Some(x), Some(y)) => self.push(SequenceStep {
+                position: Some(Point { x, y }),
+                ..Self::step(StepKind::Move)
+            }),
+            _ => {
+                self.warn(number, "MouseMove needs numeric X and Y, skipped");
+                Ok(())
+            }
+        }
+    }
+
+    // MouseClickDrag Button, X1, Y1, X2, Y2 [, Speed, R]
+    fn drag(&mut self, number: usize, args: &str) -> Result<(), String> {
+        let args = arguments(args);
+        let coord = |i: usize| args.get(i).and_then(|a| a.parse::<i32>().ok());
+        let pressed = button(args.first().map_or("", String::as_str)).flatten();
+        match (pressed, coord(1), coord(2), coord(3), coord(4)) {
+            (Some(button), Some(x1), Some(y1), Some(x2), Some(y2))
+                if args.get(6).is_none_or(|r| r.is_empty()) =>
+            {
+                self.push(SequenceStep {
+                    position: Some(Point { x: x1, y: y1 }),
+                    target: Some(Point { x: x2, y: y2 }),
+                    button,
+                    ..Self::step(StepKind::Drag)
+                })
+            }
+            _ => {
+                self.warn(
+                    number,
+                    "MouseClickDrag needs a left or right button and four screen coordinates, skipped",
+                );
+                Ok(())
+            }
+        }
+    }
+
+    // Waits add to the delay of the step before them, or start with a Wait
+    fn sleep(&mut self, number: usize, args: &str) {
+        let arg = unquote(args.trim());
+        let Ok(ms) = arg.parse::<u64>() else {
+            self.warn(
+                number,
+                format!("Sleep '{}' isn't a number of milliseconds, skipped", arg),
+            );
+            return;
+        };
+        match self.steps.last_mut() {
+            Some(step) => step.delay_ms += ms,
+            None => self.steps.push(SequenceStep {
+                delay_ms: ms,
+                ..Self::step(StepKind::Wait)
+            }),
+        }
+    }
+
+    // Plain text becomes Type steps, {Name} and `n keys become Key steps
+    fn send(&mut self, number: usize, args: &str, raw: bool) -> Result<(), String> {
+        let text = unquote(args);
+        let mut typed = String::new();
+        let mut chars = text.chars().peekable();
+        // Keys of the character just read; typed text before them is
+        // moved to pending first, so steps keep the script's order
+        let mut keys: Vec<String> = Vec::new();
+        let mut pending: Vec<(StepKind, String)> = Vec::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '`' => match chars.next() {
+                    Some('n') | Some('r') => keys.push("enter".to_string()),
+                    Some('t') => keys.push("tab".to_string()),
+                    Some(other) => typed.push(other),
+                    None => typed.push('`'),
+                },
+                '{' if !raw => {
+                    let mut inner = String::new();
+                    for c in chars.by_ref() {
+                        if c == '}' && !inner.is_empty() {
+                            break;
+                        }
+                        inner.push(c);
+                    }
+                    let (name, repeat) = match inner.rsplit_once(' ') {
+                        Some((name, n)) => (name, n.parse().unwrap_or(1)),
+                        None => (inner.as_str(), 1),
+                    };
+                    if name.chars().count() == 1 {
+                        for _ in 0..repeat {
+                            typed.push_str(name);
+                        }
+                    } else if let Some(key) = key_name(name) {
+                        for _ in 0..repeat {
+                            keys.push(key.to_string());
+                        }
+                    } else {
+                        self.warn(
+                            number,
+                            format!("key {{{}}} isn't supported, skipped", inner),
+                        );
+                    }
+                }
+                '^' | '!' | '+' | '#' if !raw => {
+                    self.warn(
+                        number,
+                        "modifier combinations (^, !, +, #) can't be imported, skipped",
+                    );
+                    // The key the modifier applies to goes too
+                    if chars.peek() == Some(&'{') {
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                break;
+                            }
+                        }
+                    } else {
+                        chars.next();
+                    }
+                }
+                c => typed.push(c),
+            }
+            if !keys.is_empty() {
+                if !typed.is_empty() {
+                    pending.push((StepKind::Type, std::mem::take(&mut typed)));
+                }
+                pending.extend(keys.drain(..).map(|key| (StepKind::Key, key)));
+            }
+        }
+        if !typed.is_empty() {
+            pending.push((StepKind::Type, typed));
+        }
+
+        for (kind, key) in pending {
+            self.push(SequenceStep {
+                key,
+                ..Self::step(kind)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+// A profile written out as an AutoHotkey v2 script, with what it leaves out
+pub struct AhkExport {
+    pub script: String,
+    pub warnings: Vec<String>,
+}
+
+// A string literal for AHK v2, with its escape character doubled
+fn quoted(text: &str) -> String {
+    let escaped = text
+        .replace('`', "``")
+        .replace('"', "`\"")
+        .replace('\n', "`n")
+        .replace('\t', "`t");
+    format!("\"{}\"", escaped)
+}
+
+// The Key step's names as AHK {Name} keys
+fn ahk_key(name: &str) -> Option<String> {
+    let named = match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => "Enter",
+        "tab" => "Tab",
+        "space" => "Space",
+        "esc" | "escape" => "Esc",
+        "backspace" => "Backspace",
+        "up" => "Up",
+        "down" => "Down",
+        "left" => "Left",
+        "right" => "Right",
+        "shift" => "Shift",
+        "ctrl" | "control" => "Ctrl",
+        "alt" => "Alt",
+        _ => {
+            let mut chars = name.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(format!("{{{}}}", c)),
+                _ => None,
+            };
+        }
+    };
+    Some(format!("{{{}}}", named))
+}
+
+// An AHK hotkey for the toggle combo, e.g. Ctrl+Alt+B as ^!b
+pub fn hotkey(combo: Option<&crate::KeyCombo>) -> String {
+    let Some(combo) = combo else {
+        return "F6".to_string();
+    };
+    let mut name = String::new();
+    for (bit, symbol) in [(2, '^'), (1, '+'), (4, '!')] {
+        if combo.mods & bit != 0 {
+            name.push(symbol);
+        }
+    }
+    if combo.key.chars().count() == 1 {
+        name.push_str(&combo.key.to_lowercase());
+    } else {
+        name.push_str(&combo.key);
+    }
+    name
+}
+
+// The condition as an AHK expression; elapsed time counts from the toggle
+fn expression(condition: &Condition) -> Option<String> {
+    match condition {
+        Condition::Pixel {
+            x,
+            y,
+            color,
+            tolerance,
+        } => Some(format!(
+            "PixelSearch(&px, &py, {x}, {y}, {x}, {y}, 0x{}, {})",
+            color.trim().trim_start_matches('#'),
+            tolerance
+        )),
+        Condition::Window { title } => Some(format!("WinActive({})", quoted(title))),
+        Condition::Elapsed { secs } => Some(format!("A_TickCount - started >= {}", secs * 1000)),
+        Condition::Variable { name, op, value } if name == "run_secs" => Some(format!(
+            "(A_TickCount - started) // 1000 {} {}",
+            match op {
+                Compare::Equal => "=",
+                other => other.symbol(),
+            },
+            value
+        )),
+        Condition::Variable { .. } => None,
+    }
+}
+
+// Writes steps as a script that runs them in a loop while `hotkey` is
+// toggled on, the way BClicker repeats a sequence
+pub fn export(profile: &str, steps: &[SequenceStep], hotkey: &str) -> AhkExport {
+    let mut warnings = Vec::new();
+    let mut body = Vec::new();
+    let mut timed = false;
+    let mut condition = |warnings: &mut Vec<String>, i: usize, condition: &Condition| {
+        let expression = expression(condition);
+        match &expression {
+            Some(e) => timed |= e.contains("started"),
+            None => warnings.push(format!(
+                "step {}: the condition '{}' has no AutoHotkey equivalent and was left out",
+                i + 1,
+                condition
+            )),
+        }
+        expression
+    };
+
+    for (i, step) in steps.iter().enumerate() {
+        if let Some(stop) = step
+            .stop_when
+            .as_ref()
+            .and_then(|c| condition(&mut warnings, i, c))
+        {
+            body.push(format!("if {} {{", stop));
+            body.push("    running := false".to_string());
+            body.push("    break".to_string());
+            body.push("}".to_string());
+        }
+
+        let action = match step.kind {
+            StepKind::Click => {
+                let right = if step.button == 1 { "\"Right\"" } else { "" };
+                Some(match (step.position, right) {
+                    (Some(p), "") => format!("Click {}, {}", p.x, p.y),
+                    (Some(p), right) => format!("Click {}, {}, {}", p.x, p.y, right),
+                    (None, right) => format!("Click {}", right).trim_end().to_string(),
+                })
+            }
+            StepKind::Key => {
+                let key = ahk_key(&step.key);
+                if key.is_none() {
+                    warnings.push(format!(
+                        "step {}: unknown key '{}' left out",
+                        i + 1,
+                        step.key
+                    ));
+                }
+                key.map(|key| format!("Send {}", quoted(&key)))
+            }
+            StepKind::Type => {
+                if step.key.contains('{') {
+                    warnings.push(format!(
+                        "step {}: variables in \"{}\" are typed literally",
+                        i + 1,
+                        step.key
+                    ));
+                }
+                Some(format!("SendText {}", quoted(&step.key)))
+            }
+            StepKind::Move => step.position.map(|p| format!("MouseMove {}, {}", p.x, p.y)),
+            StepKind::Drag => {
+                let button = if step.button == 1 { "Right" } else { "Left" };
+                let to = step.target.unwrap_or(Point { x: 0, y: 0 });
+                Some(match step.position {
+                    Some(from) => format!(
+                        "MouseClickDrag \"{}\", {}, {}, {}, {}",
+                        button, from.x, from.y, to.x, to.y
+                    ),
+                    None => format!(
+                        "MouseClickDrag \"{}\", 0, 0, {}, {}, , \"R\"",
+                        button, to.x, to.y
+                    ),
+                })
+            }
+            StepKind::Wait => None,
+        };
+        if step.count.is_some() {
+            warnings.push(format!("step {}: its counter isn't kept", i + 1));
+        }
+
+        let when = step
+            .when
+            .as_ref()
+            .and_then(|c| condition(&mut warnings, i, c));
+        let unless = step
+            .unless
+            .as_ref()
+            .and_then(|c| condition(&mut warnings, i, c))
+            .map(|c| format!("!({})", c));
+        let guard: Vec<String> = when.into_iter().chain(unless).collect();
+        match (action, guard.is_empty()) {
+            (Some(action), true) => body.push(action),
+            (Some(action), false) => {
+                body.push(format!("if {} {{", guard.join(" && ")));
+                body.push(format!("    {}", action));
+                body.push("}".to_string());
+            }
+            (None, _) => {}
+        }
+        // Skipped steps still take their delay, as in BClicker
+        body.push(format!("Sleep {}", step.delay_ms.max(1)));
+    }
+
+    let mut script = vec![
+        "#Requires AutoHotkey v2.0".to_string(),
+        format!(
+            "; Profile {} exported from BClicker: {} step{}, repeated until {} is pressed again",
+            quoted(profile),
+            steps.len(),
+            if steps.len() == 1 { "" } else { "s" },
+            hotkey
+        ),
+        "#MaxThreadsPerHotkey 2".to_string(),
+        "CoordMode \"Mouse\", \"Screen\"".to_string(),
+        "CoordMode \"Pixel\", \"Screen\"".to_string(),
+        "SetTitleMatchMode 2".to_string(),
+        "SendMode \"Input\"".to_string(),
+        String::new(),
+        "running := false".to_string(),
+        String::new(),
+        format!("{}:: {{", hotkey),
+        "    global running".to_string(),
+        "    running := !running".to_string(),
+    ];
+    if timed {
+        script.push("    started := A_TickCount".to_string());
+    }
+    script.push("    while running {".to_string());
+    script.extend(body.into_iter().map(|line| format!("        {}", line)));
+    script.push("    }".to_string());
+    script.push("}".to_string());
+
+    AhkExport {
+        script: script.join("\n") + "\n",
+        warnings,
+    }
+}
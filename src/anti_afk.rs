@@ -0,0 +1,113 @@
+use crate::{backend::ClickBackend, interval, sequence};
+use enigo::MouseButton;
+use rand::{Rng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use std::{thread, time::Duration};
+
+// Pixels the wiggle moves the cursor before putting it back
+const WIGGLE_PIXELS: i32 = 3;
+const WIGGLE_PAUSE: Duration = Duration::from_millis(30);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AfkAction {
+    Click,
+    Wiggle,
+    Key,
+}
+
+// One small action at a random point of the window, to keep idle timeouts
+// from firing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct AntiAfk {
+    pub min_secs: u64,
+    pub max_secs: u64,
+    // Picked from at random each time
+    pub actions: Vec<AfkAction>,
+    // Tapped by the key action; a sequence key name such as "shift" or "a"
+    pub key: String,
+}
+
+impl Default for AntiAfk {
+    fn default() -> Self {
+        Self {
+            min_secs: 3 * 60,
+            max_secs: 7 * 60,
+            actions: vec![AfkAction::Wiggle, AfkAction::Key],
+            key: "shift".to_string(),
+        }
+    }
+}
+
+impl AntiAfk {
+    pub fn sanitize(&mut self) {
+        self.min_secs = self.min_secs.clamp(interval::MIN_SECS, interval::MAX_SECS);
+        self.max_secs = self.max_secs.clamp(self.min_secs, interval::MAX_SECS);
+        if self.actions.is_empty() {
+            self.actions = AntiAfk::default().actions;
+        }
+        if sequence::parse_key(&self.key).is_none() {
+            self.key = AntiAfk::default().key;
+        }
+    }
+
+    // Parses a window such as "3m-7m"; a single interval means exactly that
+    pub fn parse_window(input: &str) -> Option<(u64, u64)> {
+        let (min, max) = match input.split_once('-') {
+            Some((min, max)) => (interval::parse(min)?, interval::parse(max)?),
+            None => {
+                let secs = interval::parse(input)?;
+                (secs, secs)
+            }
+        };
+        (min <= max).then_some((min, max))
+    }
+
+    // "3m-7m", as typed
+    pub fn window(&self) -> String {
+        if self.min_secs == self.max_secs {
+            interval::describe(self.min_secs)
+        } else {
+            format!(
+                "{}-{}",
+                interval::describe(self.min_secs),
+                interval::describe(self.max_secs)
+            )
+        }
+    }
+
+    pub fn next_interval(&self, rng: &mut impl Rng) -> Duration {
+        Duration::from_secs(rng.gen_range(self.min_secs..=self.max_secs.max(self.min_secs)))
+    }
+
+    pub fn average_secs(&self) -> f64 {
+        (self.min_secs + self.max_secs) as f64 / 2.0
+    }
+
+    // Returns true when it clicked, like the other engine actions
+    pub fn perform(&self, backend: &mut dyn ClickBackend) -> bool {
+        let mut rng = rand::thread_rng();
+        match self.actions.choose(&mut rng).copied() {
+            Some(AfkAction::Click) => {
+                backend.click(MouseButton::Left);
+                true
+            }
+            Some(AfkAction::Wiggle) => {
+                let dx = *[-WIGGLE_PIXELS, WIGGLE_PIXELS].choose(&mut rng).unwrap();
+                let dy = rng.gen_range(-WIGGLE_PIXELS..=WIGGLE_PIXELS);
+                backend.move_relative(dx, dy);
+                thread::sleep(WIGGLE_PAUSE);
+                backend.move_relative(-dx, -dy);
+                false
+            }
+            Some(AfkAction::Key) => {
+                if let Some(key) = sequence::parse_key(&self.key) {
+                    backend.key(key);
+                }
+                false
+            }
+            None => false,
+        }
+    }
+}
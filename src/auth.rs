@@ -0,0 +1,147 @@
+use crate::Config;
+use rand::{RngCore, rngs::OsRng};
+use serde::{Deserialize, Serialize};
+
+// Tokens shorter than this are too easy to guess and are ignored
+pub const MIN_TOKEN_LEN: usize = 16;
+
+// Random bytes in a generated token, written out as hex
+const TOKEN_BYTES: usize = 24;
+
+// What a token lets its holder do; control includes read
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    // Status and stats only
+    Read,
+    // Also starting, stopping and changing the rate or profile
+    Control,
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Scope::Read => "read",
+            Scope::Control => "control",
+        })
+    }
+}
+
+// One entry of `[[api_tokens]]`; the name is only for telling them apart
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiToken {
+    pub name: String,
+    pub token: String,
+    pub scope: Scope,
+}
+
+impl ApiToken {
+    pub fn generate(name: &str, scope: Scope) -> Self {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        Self {
+            name: name.to_string(),
+            token: bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            scope,
+        }
+    }
+}
+
+// Why a request was turned away
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Denied {
+    // No token, or one that isn't configured
+    Unauthorized,
+    // A known token without the scope the request needs
+    Forbidden,
+}
+
+impl std::fmt::Display for Denied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Denied::Unauthorized => "a valid token is needed",
+            Denied::Forbidden => "this token can only read",
+        })
+    }
+}
+
+// Compares every byte whatever the first difference, so the time taken
+// doesn't tell how much of a guess was right
+fn same(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |d, (x, y)| d | (x ^ y)) == 0
+}
+
+// Checks a presented token against the configured ones
+pub fn check(tokens: &[ApiToken], presented: Option<&str>, needed: Scope) -> Result<(), Denied> {
+    let presented = presented.ok_or(Denied::Unauthorized)?;
+    let scope = tokens
+        .iter()
+        .filter(|known| same(&known.token, presented))
+        .map(|known| known.scope)
+        .max()
+        .ok_or(Denied::Unauthorized)?;
+    if scope >= needed {
+        Ok(())
+    } else {
+        Err(Denied::Forbidden)
+    }
+}
+
+// `bclicker token [list | new NAME [--read-only] | revoke NAME]`. Changes
+// are refused while BClicker runs, since its next save would write its own
+// copy of the list back
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    let usage = "usage: bclicker token [list | new NAME [--read-only] | revoke NAME]";
+    let listing = matches!(args.first().map(String::as_str), None | Some("list"));
+    if !listing && crate::ipc::instance_running() {
+        return Err("close BClicker first, or its next save undoes the change".to_string());
+    }
+    let mut config = crate::load_config();
+    match args {
+        [] => list(&config),
+        [command] if command == "list" => list(&config),
+        [command, name, flags @ ..] if command == "new" => {
+            let scope = match flags {
+                [] => Scope::Control,
+                [flag] if flag == "--read-only" => Scope::Read,
+                _ => return Err(usage.to_string()),
+            };
+            if config.api_tokens.iter().any(|t| &t.name == name) {
+                return Err(format!("there's already a token named '{}'", name));
+            }
+            let token = ApiToken::generate(name, scope);
+            println!("[INFO] New {} token '{}': {}", scope, name, token.token);
+            config.api_tokens.push(token);
+            crate::save_config(&config);
+        }
+        [command, name] if command == "revoke" => {
+            let before = config.api_tokens.len();
+            config.api_tokens.retain(|t| &t.name != name);
+            if config.api_tokens.len() == before {
+                return Err(format!("no token named '{}'", name));
+            }
+            crate::save_config(&config);
+            println!("[INFO] Token '{}' revoked", name);
+        }
+        _ => return Err(usage.to_string()),
+    }
+    Ok(())
+}
+
+// One line per token, then the link the phone remote opens with for each
+fn list(config: &Config) {
+    if config.api_tokens.is_empty() {
+        println!(
+            "[INFO] No API tokens; BClicker makes one when the web remote, gRPC server, deck \
+             endpoint or remote OSC first starts"
+        );
+    }
+    for token in &config.api_tokens {
+        println!("{}\t{}\t{}", token.name, token.scope, token.token);
+    }
+    if let Some(web) = &config.web {
+        for token in &config.api_tokens {
+            println!("[INFO] {}: {}", token.name, web.link(&token.token));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+pub use crate::sequence::Point;
+
+use crate::pointer::Pointer;
+use enigo::{Enigo, Key, KeyboardControllable, MouseButton, MouseControllable};
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "portal", target_os = "linux"))]
+mod portal;
+#[cfg(windows)]
+mod sendinput;
+#[cfg(all(feature = "wayland", target_os = "linux"))]
+mod wayland;
+
+// Everything the engine needs from an input injection layer
+pub trait ClickBackend {
+    fn name(&self) -> &'static str;
+    fn click(&mut self, button: MouseButton);
+    fn button_down(&mut self, button: MouseButton);
+    fn button_up(&mut self, button: MouseButton);
+    fn move_to(&mut self, x: i32, y: i32);
+    fn move_relative(&mut self, dx: i32, dy: i32);
+    // Positive amounts scroll down, negative up
+    fn scroll(&mut self, amount: i32);
+    fn key(&mut self, key: Key);
+    // Held keys such as click modifiers; queued events go out before either
+    fn key_down(&mut self, key: Key);
+    fn key_up(&mut self, key: Key);
+    fn type_text(&mut self, text: &str);
+    fn cursor_position(&self) -> Option<Point>;
+    // Backends that queue events send them here; called once per engine tick
+    fn flush(&mut self) {}
+    // Whether the OS took everything flushed since the last call; None when
+    // nothing was sent or the backend can't tell
+    fn take_sent(&mut self) -> Option<bool> {
+        None
+    }
+}
+
+// Where the engine gets its backend, asked again whenever the configured kind
+// changes. The app uses BackendKind::create; tests hand in a recording mock
+pub type BackendFactory =
+    std::sync::Arc<dyn Fn(BackendKind) -> Box<dyn ClickBackend> + Send + Sync>;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    #[default]
+    Enigo,
+    // Batched Win32 SendInput; falls back to enigo on other platforms
+    SendInput,
+    // wlr-virtual-pointer on wlroots compositors; needs the `wayland` feature
+    Wayland,
+    // RemoteDesktop portal (libei on GNOME and KDE); needs the `portal` feature
+    Portal,
+    // Discards all input; for dry runs and measuring engine overhead
+    Null,
+}
+
+impl BackendKind {
+    pub const ALL: &'static [BackendKind] = &[
+        BackendKind::Enigo,
+        #[cfg(windows)]
+        BackendKind::SendInput,
+        #[cfg(all(feature = "wayland", target_os = "linux"))]
+        BackendKind::Wayland,
+        #[cfg(all(feature = "portal", target_os = "linux"))]
+        BackendKind::Portal,
+        BackendKind::Null,
+    ];
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|&k| k == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BackendKind::Enigo => "enigo",
+            BackendKind::SendInput => "sendinput (batched)",
+            BackendKind::Wayland => "wayland (virtual pointer)",
+            BackendKind::Portal => "portal (remote desktop)",
+            BackendKind::Null => "null (no input)",
+        }
+    }
+
+    // enigo injects through X11, which only reaches XWayland windows under a
+    // Wayland session
+    pub fn suited_to_session(self) -> bool {
+        !(self == BackendKind::Enigo && std::env::var_os("WAYLAND_DISPLAY").is_some())
+    }
+
+    pub fn create(self) -> Box<dyn ClickBackend> {
+        match self {
+            BackendKind::Enigo => Box::new(EnigoBackend::new()),
+            #[cfg(windows)]
+            BackendKind::SendInput => Box::new(sendinput::SendInputBackend::new()),
+            #[cfg(not(windows))]
+            BackendKind::SendInput => {
+                eprintln!("[WARNING] SendInput backend is Windows-only, using enigo");
+                Box::new(EnigoBackend::new())
+            }
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            BackendKind::Wayland => match wayland::WaylandBackend::new() {
+                Ok(backend) => Box::new(backend),
+                Err(e) => {
+                    eprintln!("[WARNING] Wayland backend unavailable ({}), using enigo", e);
+                    Box::new(EnigoBackend::new())
+                }
+            },
+            #[cfg(not(all(feature = "wayland", target_os = "linux")))]
+            BackendKind::Wayland => {
+                eprintln!("[WARNING] Built without the wayland feature, using enigo");
+                Box::new(EnigoBackend::new())
+            }
+            #[cfg(all(feature = "portal", target_os = "linux"))]
+            BackendKind::Portal => match portal::PortalBackend::new() {
+                Ok(backend) => Box::new(backend),
+                Err(e) => {
+                    eprintln!("[WARNING] Portal backend unavailable ({}), using enigo", e);
+                    Box::new(EnigoBackend::new())
+                }
+            },
+            #[cfg(not(all(feature = "portal", target_os = "linux")))]
+            BackendKind::Portal => {
+                eprintln!("[WARNING] Built without the portal feature, using enigo");
+                Box::new(EnigoBackend::new())
+            }
+            BackendKind::Null => Box::new(NullBackend),
+        }
+    }
+}
+
+pub struct EnigoBackend {
+    enigo: Enigo,
+    pointer: Pointer,
+}
+
+impl Default for EnigoBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnigoBackend {
+    pub fn new() -> Self {
+        Self {
+            enigo: Enigo::new(),
+            pointer: Pointer::new(),
+        }
+    }
+}
+
+impl ClickBackend for EnigoBackend {
+    fn name(&self) -> &'static str {
+        "enigo"
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        self.enigo.mouse_click(button);
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        self.enigo.mouse_down(button);
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        self.enigo.mouse_up(button);
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.enigo.mouse_move_to(x, y);
+    }
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.enigo.mouse_move_relative(dx, dy);
+    }
+
+    // enigo's Windows wheel direction is inverted relative to other platforms
+    fn scroll(&mut self, amount: i32) {
+        if cfg!(windows) {
+            self.enigo.mouse_scroll_y(-amount);
+        } else {
+            self.enigo.mouse_scroll_y(amount);
+        }
+    }
+
+    fn key(&mut self, key: Key) {
+        self.enigo.key_click(key);
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.enigo.key_down(key);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.enigo.key_up(key);
+    }
+
+    fn type_text(&mut self, text: &str) {
+        self.enigo.key_sequence(text);
+    }
+
+    fn cursor_position(&self) -> Option<Point> {
+        self.pointer.position()
+    }
+}
+
+pub struct NullBackend;
+
+impl ClickBackend for NullBackend {
+    fn name(&self) -> &'static str {
+        "null"
+    }
+
+    fn click(&mut self, _button: MouseButton) {}
+
+    fn button_down(&mut self, _button: MouseButton) {}
+
+    fn button_up(&mut self, _button: MouseButton) {}
+
+    fn move_to(&mut self, _x: i32, _y: i32) {}
+
+    fn move_relative(&mut self, _dx: i32, _dy: i32) {}
+
+    fn scroll(&mut self, _amount: i32) {}
+
+    fn key(&mut self, _key: Key) {}
+
+    fn key_down(&mut self, _key: Key) {}
+
+    fn key_up(&mut self, _key: Key) {}
+
+    fn type_text(&mut self, _text: &str) {}
+
+    fn cursor_position(&self) -> Option<Point> {
+        None
+    }
+}
@@ -0,0 +1,223 @@
+use super::ClickBackend;
+use crate::sequence::Point;
+use ashpd::desktop::{
+    PersistMode, Session,
+    remote_desktop::{Axis, DeviceType, KeyState, RemoteDesktop},
+};
+use enigo::{Key, MouseButton};
+use std::sync::Mutex;
+use tokio::runtime::{Builder, Runtime};
+
+// Linux input event codes the portal expects in button notifications
+const BTN_LEFT: i32 = 0x110;
+const BTN_RIGHT: i32 = 0x111;
+const BTN_MIDDLE: i32 = 0x112;
+
+// Handed back by the portal after the user allows a session, so a backend
+// recreated later in the same run starts without asking again
+static RESTORE_TOKEN: Mutex<Option<String>> = Mutex::new(None);
+
+// Pointer and keyboard injection through the RemoteDesktop portal, which
+// GNOME and KDE route to libei; the compositor asks the user once per run
+pub struct PortalBackend {
+    runtime: Runtime,
+    portal: RemoteDesktop<'static>,
+    session: Session<'static, RemoteDesktop<'static>>,
+    // Whether the portal took every event since the last take_sent; it
+    // refuses them once the user ends the session
+    sent: Option<bool>,
+}
+
+impl PortalBackend {
+    pub fn new() -> Result<Self, String> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let (portal, session) = runtime
+            .block_on(Self::connect())
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            runtime,
+            portal,
+            session,
+            sent: None,
+        })
+    }
+
+    async fn connect() -> ashpd::Result<(
+        RemoteDesktop<'static>,
+        Session<'static, RemoteDesktop<'static>>,
+    )> {
+        let portal = RemoteDesktop::new().await?;
+        let session = portal.create_session().await?;
+        let token = RESTORE_TOKEN.lock().unwrap().clone();
+        portal
+            .select_devices(
+                &session,
+                DeviceType::Pointer | DeviceType::Keyboard,
+                token.as_deref(),
+                PersistMode::Application,
+            )
+            .await?
+            .response()?;
+        let selected = portal.start(&session, None).await?.response()?;
+        if let Some(token) = selected.restore_token() {
+            *RESTORE_TOKEN.lock().unwrap() = Some(token.to_string());
+        }
+        Ok((portal, session))
+    }
+
+    fn note(&mut self, result: ashpd::Result<()>) {
+        let ok = result.is_ok();
+        self.sent = Some(self.sent.unwrap_or(true) && ok);
+    }
+
+    fn button(&mut self, button: MouseButton, pressed: bool) {
+        let code = match button {
+            MouseButton::Left => BTN_LEFT,
+            MouseButton::Right => BTN_RIGHT,
+            MouseButton::Middle => BTN_MIDDLE,
+            _ => return,
+        };
+        let result = self.runtime.block_on(self.portal.notify_pointer_button(
+            &self.session,
+            code,
+            key_state(pressed),
+        ));
+        self.note(result);
+    }
+
+    fn keysym(&mut self, key: Key, pressed: bool) {
+        let Some(keysym) = keysym(key) else {
+            return;
+        };
+        let result = self.runtime.block_on(self.portal.notify_keyboard_keysym(
+            &self.session,
+            keysym,
+            key_state(pressed),
+        ));
+        self.note(result);
+    }
+}
+
+fn key_state(pressed: bool) -> KeyState {
+    if pressed {
+        KeyState::Pressed
+    } else {
+        KeyState::Released
+    }
+}
+
+// X keysym for a key; the compositor maps it through the active layout, so
+// no keymap has to be uploaded as with the virtual keyboard protocol
+fn keysym(key: Key) -> Option<i32> {
+    let keysym = match key {
+        Key::Return => 0xff0d,
+        Key::Tab => 0xff09,
+        Key::Space => 0x20,
+        Key::Escape => 0xff1b,
+        Key::Backspace => 0xff08,
+        Key::UpArrow => 0xff52,
+        Key::DownArrow => 0xff54,
+        Key::LeftArrow => 0xff51,
+        Key::RightArrow => 0xff53,
+        Key::Shift => 0xffe1,
+        Key::Control => 0xffe3,
+        Key::Alt => 0xffe9,
+        // Latin-1 keysyms equal their code point; the rest are offset
+        Key::Layout(c) => match c as i32 {
+            code @ (0x20..=0x7e | 0xa0..=0xff) => code,
+            code => 0x0100_0000 | code,
+        },
+        _ => return None,
+    };
+    Some(keysym)
+}
+
+impl ClickBackend for PortalBackend {
+    fn name(&self) -> &'static str {
+        "portal"
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        match button {
+            MouseButton::ScrollUp => self.scroll(-1),
+            MouseButton::ScrollDown => self.scroll(1),
+            _ => {
+                self.button(button, true);
+                self.button(button, false);
+            }
+        }
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        self.button(button, true);
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        self.button(button, false);
+    }
+
+    // Absolute motion is only defined against a ScreenCast stream, which
+    // would mean asking the user to share the screen too; moves are skipped
+    fn move_to(&mut self, _x: i32, _y: i32) {}
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        let result = self.runtime.block_on(self.portal.notify_pointer_motion(
+            &self.session,
+            dx as f64,
+            dy as f64,
+        ));
+        self.note(result);
+    }
+
+    fn scroll(&mut self, amount: i32) {
+        let result = self
+            .runtime
+            .block_on(self.portal.notify_pointer_axis_discrete(
+                &self.session,
+                Axis::Vertical,
+                amount,
+            ));
+        self.note(result);
+    }
+
+    fn key(&mut self, key: Key) {
+        self.keysym(key, true);
+        self.keysym(key, false);
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.keysym(key, true);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.keysym(key, false);
+    }
+
+    fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.key(match c {
+                '\n' => Key::Return,
+                '\t' => Key::Tab,
+                c => Key::Layout(c),
+            });
+        }
+    }
+
+    // Wayland never exposes the global cursor position to clients
+    fn cursor_position(&self) -> Option<Point> {
+        None
+    }
+
+    fn take_sent(&mut self) -> Option<bool> {
+        self.sent.take()
+    }
+}
+
+impl Drop for PortalBackend {
+    fn drop(&mut self) {
+        let _ = self.runtime.block_on(self.session.close());
+    }
+}
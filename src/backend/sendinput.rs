@@ -0,0 +1,194 @@
+use super::ClickBackend;
+use crate::{POINT, sequence::Point};
+use enigo::{Enigo, Key, KeyboardControllable, MouseButton};
+use std::mem::size_of;
+
+const INPUT_MOUSE: u32 = 0;
+const MOUSEEVENTF_MOVE: u32 = 0x0001;
+const MOUSEEVENTF_LEFTDOWN: u32 = 0x0002;
+const MOUSEEVENTF_LEFTUP: u32 = 0x0004;
+const MOUSEEVENTF_RIGHTDOWN: u32 = 0x0008;
+const MOUSEEVENTF_RIGHTUP: u32 = 0x0010;
+const MOUSEEVENTF_MIDDLEDOWN: u32 = 0x0020;
+const MOUSEEVENTF_MIDDLEUP: u32 = 0x0040;
+const MOUSEEVENTF_WHEEL: u32 = 0x0800;
+const WHEEL_DELTA: i32 = 120;
+
+// Flush early rather than letting a long catch-up grow the queue unbounded
+const MAX_BATCH: usize = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MouseInput {
+    dx: i32,
+    dy: i32,
+    mouse_data: u32,
+    flags: u32,
+    time: u32,
+    extra_info: usize,
+}
+
+// Win32 INPUT with the mouse member of its union; MOUSEINPUT is the largest
+// member, so the size matches what SendInput expects
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Input {
+    kind: u32,
+    mi: MouseInput,
+}
+
+#[link(name = "user32")]
+unsafe extern "system" {
+    fn SendInput(count: u32, inputs: *const Input, size: i32) -> u32;
+    fn SetCursorPos(x: i32, y: i32) -> i32;
+    fn GetCursorPos(point: *mut POINT) -> i32;
+}
+
+// Queues mouse events and submits each engine tick's worth in one SendInput
+// call; enigo makes a separate call per down and up event
+pub struct SendInputBackend {
+    queue: Vec<Input>,
+    // Keys are rare, so they go through enigo's layout-aware key mapping
+    enigo: Enigo,
+    // Whether SendInput took every event since the last take_sent; it
+    // refuses input into windows of a higher integrity level
+    sent: Option<bool>,
+}
+
+impl SendInputBackend {
+    pub fn new() -> Self {
+        Self {
+            queue: Vec::with_capacity(MAX_BATCH),
+            enigo: Enigo::new(),
+            sent: None,
+        }
+    }
+
+    fn push(&mut self, flags: u32, dx: i32, dy: i32, mouse_data: u32) {
+        self.queue.push(Input {
+            kind: INPUT_MOUSE,
+            mi: MouseInput {
+                dx,
+                dy,
+                mouse_data,
+                flags,
+                time: 0,
+                extra_info: 0,
+            },
+        });
+        if self.queue.len() >= MAX_BATCH {
+            self.flush();
+        }
+    }
+
+    fn button_flags(button: MouseButton) -> Option<(u32, u32)> {
+        match button {
+            MouseButton::Left => Some((MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP)),
+            MouseButton::Right => Some((MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP)),
+            MouseButton::Middle => Some((MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP)),
+            _ => None,
+        }
+    }
+}
+
+impl ClickBackend for SendInputBackend {
+    fn name(&self) -> &'static str {
+        "sendinput"
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        match (button, Self::button_flags(button)) {
+            (_, Some((down, up))) => {
+                self.push(down, 0, 0, 0);
+                self.push(up, 0, 0, 0);
+            }
+            (MouseButton::ScrollUp, None) => self.scroll(-1),
+            (MouseButton::ScrollDown, None) => self.scroll(1),
+            _ => {}
+        }
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        if let Some((down, _)) = Self::button_flags(button) {
+            self.push(down, 0, 0, 0);
+        }
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        if let Some((_, up)) = Self::button_flags(button) {
+            self.push(up, 0, 0, 0);
+        }
+    }
+
+    // Absolute moves go through SetCursorPos, which isn't queued, so send
+    // anything pending first to keep events in order
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.flush();
+        unsafe {
+            SetCursorPos(x, y);
+        }
+    }
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.push(MOUSEEVENTF_MOVE, dx, dy, 0);
+    }
+
+    // Windows wheel deltas are positive away from the user, i.e. scrolling up
+    fn scroll(&mut self, amount: i32) {
+        self.push(MOUSEEVENTF_WHEEL, 0, 0, (-amount * WHEEL_DELTA) as u32);
+    }
+
+    fn key(&mut self, key: Key) {
+        self.flush();
+        self.enigo.key_click(key);
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.flush();
+        self.enigo.key_down(key);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.flush();
+        self.enigo.key_up(key);
+    }
+
+    fn type_text(&mut self, text: &str) {
+        self.flush();
+        self.enigo.key_sequence(text);
+    }
+
+    fn cursor_position(&self) -> Option<Point> {
+        let mut point = POINT { x: 0, y: 0 };
+        (unsafe { GetCursorPos(&mut point) } != 0).then_some(Point {
+            x: point.x,
+            y: point.y,
+        })
+    }
+
+    fn flush(&mut self) {
+        if self.queue.is_empty() {
+            return;
+        }
+        let inserted = unsafe {
+            SendInput(
+                self.queue.len() as u32,
+                self.queue.as_ptr(),
+                size_of::<Input>() as i32,
+            )
+        };
+        self.sent = Some(self.sent.unwrap_or(true) && inserted as usize == self.queue.len());
+        self.queue.clear();
+    }
+
+    fn take_sent(&mut self) -> Option<bool> {
+        self.sent.take()
+    }
+}
+
+impl Drop for SendInputBackend {
+    // Never leave a queued button-up unsent
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
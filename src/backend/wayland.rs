@@ -0,0 +1,244 @@
+use super::ClickBackend;
+use crate::sequence::Point;
+use enigo::{Key, MouseButton};
+use std::time::Instant;
+use wayland_client::{
+    Connection, Dispatch, EventQueue, QueueHandle, WEnum, delegate_noop,
+    globals::{GlobalListContents, registry_queue_init},
+    protocol::{wl_output, wl_pointer, wl_registry, wl_seat},
+};
+use wayland_protocols_wlr::virtual_pointer::v1::client::{
+    zwlr_virtual_pointer_manager_v1::ZwlrVirtualPointerManagerV1,
+    zwlr_virtual_pointer_v1::ZwlrVirtualPointerV1,
+};
+
+// Linux input event codes the compositor expects in button requests
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+const BTN_MIDDLE: u32 = 0x112;
+// Scroll distance of one wheel notch, matching libinput's default
+const WHEEL_STEP: f64 = 15.0;
+
+#[derive(Default, Clone, Copy)]
+struct OutputInfo {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    scale: i32,
+}
+
+// Output geometry is tracked so absolute moves can be expressed against the
+// extent of the whole output layout
+#[derive(Default)]
+struct State {
+    outputs: Vec<OutputInfo>,
+}
+
+impl State {
+    fn layout_extent(&self) -> (u32, u32) {
+        let (mut width, mut height) = (1, 1);
+        for o in &self.outputs {
+            let scale = o.scale.max(1);
+            width = width.max(o.x + o.width / scale);
+            height = height.max(o.y + o.height / scale);
+        }
+        (width as u32, height as u32)
+    }
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<wl_output::WlOutput, usize> for State {
+    fn event(
+        state: &mut Self,
+        _: &wl_output::WlOutput,
+        event: wl_output::Event,
+        index: &usize,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let output = &mut state.outputs[*index];
+        match event {
+            wl_output::Event::Geometry { x, y, .. } => {
+                output.x = x;
+                output.y = y;
+            }
+            wl_output::Event::Mode {
+                flags: WEnum::Value(flags),
+                width,
+                height,
+                ..
+            } if flags.contains(wl_output::Mode::Current) => {
+                output.width = width;
+                output.height = height;
+            }
+            wl_output::Event::Scale { factor } => output.scale = factor,
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(State: ignore wl_seat::WlSeat);
+delegate_noop!(State: ZwlrVirtualPointerManagerV1);
+delegate_noop!(State: ZwlrVirtualPointerV1);
+
+// Native pointer injection through the wlr-virtual-pointer protocol; X11
+// injection via XTest silently does nothing for native Wayland windows
+pub struct WaylandBackend {
+    conn: Connection,
+    queue: EventQueue<State>,
+    state: State,
+    pointer: ZwlrVirtualPointerV1,
+    start: Instant,
+}
+
+impl WaylandBackend {
+    pub fn new() -> Result<Self, String> {
+        let conn = Connection::connect_to_env().map_err(|e| e.to_string())?;
+        let (globals, mut queue) =
+            registry_queue_init::<State>(&conn).map_err(|e| e.to_string())?;
+        let qh = queue.handle();
+
+        let manager: ZwlrVirtualPointerManagerV1 = globals.bind(&qh, 1..=1, ()).map_err(|_| {
+            "compositor lacks zwlr_virtual_pointer_manager_v1 (wlroots-based compositors only)"
+                .to_string()
+        })?;
+        let seat: Option<wl_seat::WlSeat> = globals.bind(&qh, 1..=1, ()).ok();
+
+        let mut state = State::default();
+        for global in globals.contents().clone_list() {
+            if global.interface == "wl_output" {
+                let index = state.outputs.len();
+                state.outputs.push(OutputInfo::default());
+                globals.registry().bind::<wl_output::WlOutput, _, _>(
+                    global.name,
+                    global.version.min(2),
+                    &qh,
+                    index,
+                );
+            }
+        }
+
+        let pointer = manager.create_virtual_pointer(seat.as_ref(), &qh, ());
+        queue.roundtrip(&mut state).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            conn,
+            queue,
+            state,
+            pointer,
+            start: Instant::now(),
+        })
+    }
+
+    fn time(&self) -> u32 {
+        self.start.elapsed().as_millis() as u32
+    }
+
+    fn button(&mut self, button: MouseButton, pressed: bool) {
+        let code = match button {
+            MouseButton::Left => BTN_LEFT,
+            MouseButton::Right => BTN_RIGHT,
+            MouseButton::Middle => BTN_MIDDLE,
+            _ => return,
+        };
+        let state = if pressed {
+            wl_pointer::ButtonState::Pressed
+        } else {
+            wl_pointer::ButtonState::Released
+        };
+        self.pointer.button(self.time(), code, state);
+        self.pointer.frame();
+    }
+}
+
+impl ClickBackend for WaylandBackend {
+    fn name(&self) -> &'static str {
+        "wayland"
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        match button {
+            MouseButton::ScrollUp => self.scroll(-1),
+            MouseButton::ScrollDown => self.scroll(1),
+            _ => {
+                self.button(button, true);
+                self.button(button, false);
+            }
+        }
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        self.button(button, true);
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        self.button(button, false);
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        let (width, height) = self.state.layout_extent();
+        self.pointer.motion_absolute(
+            self.time(),
+            x.clamp(0, width as i32) as u32,
+            y.clamp(0, height as i32) as u32,
+            width,
+            height,
+        );
+        self.pointer.frame();
+    }
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.pointer.motion(self.time(), dx as f64, dy as f64);
+        self.pointer.frame();
+    }
+
+    fn scroll(&mut self, amount: i32) {
+        let time = self.time();
+        self.pointer.axis_source(wl_pointer::AxisSource::Wheel);
+        self.pointer.axis_discrete(
+            time,
+            wl_pointer::Axis::VerticalScroll,
+            amount as f64 * WHEEL_STEP,
+            amount,
+        );
+        self.pointer.frame();
+    }
+
+    // The virtual keyboard protocol needs a full XKB keymap upload, which this
+    // backend doesn't do; key steps are skipped
+    fn key(&mut self, _key: Key) {}
+
+    fn key_down(&mut self, _key: Key) {}
+
+    fn key_up(&mut self, _key: Key) {}
+
+    fn type_text(&mut self, _text: &str) {}
+
+    // Wayland never exposes the global cursor position to clients
+    fn cursor_position(&self) -> Option<Point> {
+        None
+    }
+
+    fn flush(&mut self) {
+        let _ = self.conn.flush();
+    }
+}
+
+impl Drop for WaylandBackend {
+    fn drop(&mut self) {
+        self.pointer.destroy();
+        let _ = self.conn.flush();
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{
+    Config, EngineSettings, TimingModel,
+    backend::{BackendFactory, ClickBackend, NullBackend, Point},
+    clock::Clock,
+    headless::HeadlessEngine,
+    notifications::NotificationPrefs,
+};
+use enigo::{Key, MouseButton};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+// Rates probed in order; each runs long enough for a stable average while the
+// whole suite stays within a few seconds
+const BENCHMARK_RATES: [u32; 9] = [100, 250, 500, 1000, 2000, 5000, 10_000, 20_000, 50_000];
+const RUN_DURATION: Duration = Duration::from_millis(400);
+// Lets the engine finish the run before the next rate starts
+const SETTLE: Duration = Duration::from_millis(50);
+const SUSTAINABLE_RATIO: f64 = 0.95;
+
+type Actions = Arc<Mutex<Vec<Instant>>>;
+
+// The null backend, noting when each action reached it
+struct TimedNull {
+    inner: NullBackend,
+    actions: Actions,
+}
+
+impl TimedNull {
+    fn note(&self) {
+        self.actions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Instant::now());
+    }
+}
+
+impl ClickBackend for TimedNull {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        self.inner.click(button);
+        self.note();
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        self.inner.button_down(button);
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        self.inner.button_up(button);
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.inner.move_to(x, y);
+    }
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.inner.move_relative(dx, dy);
+    }
+
+    fn scroll(&mut self, amount: i32) {
+        self.inner.scroll(amount);
+        self.note();
+    }
+
+    fn key(&mut self, key: Key) {
+        self.inner.key(key);
+        self.note();
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.inner.key_down(key);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.inner.key_up(key);
+    }
+
+    fn type_text(&mut self, text: &str) {
+        self.inner.type_text(text);
+        self.note();
+    }
+
+    fn cursor_position(&self) -> Option<Point> {
+        self.inner.cursor_position()
+    }
+
+    fn flush(&mut self) {
+        self.inner.flush();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateResult {
+    pub target_cps: u32,
+    pub achieved_cps: f64,
+    pub jitter_us: f64,
+}
+
+impl RateResult {
+    pub fn sustainable(&self) -> bool {
+        self.achieved_cps >= self.target_cps as f64 * SUSTAINABLE_RATIO
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchmarkReport {
+    pub results: Vec<RateResult>,
+    pub max_sustainable_cps: u32,
+}
+
+impl BenchmarkReport {
+    pub fn summary(&self) -> String {
+        let jitter = self
+            .results
+            .iter()
+            .find(|r| r.target_cps == 1000)
+            .map(|r| r.jitter_us)
+            .unwrap_or_default();
+        format!(
+            "max {} CPS sustainable │ jitter {:.1}µs @ 1000 CPS",
+            self.max_sustainable_cps, jitter
+        )
+    }
+}
+
+impl std::fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>10} {:>12} {:>12}  status",
+            "target", "achieved", "jitter"
+        )?;
+        for r in &self.results {
+            writeln!(
+                f,
+                "{:>10} {:>12.1} {:>10.1}µs  {}",
+                r.target_cps,
+                r.achieved_cps,
+                r.jitter_us,
+                if r.sustainable() { "ok" } else { "short" }
+            )?;
+        }
+        write!(f, "Max sustainable CPS: {}", self.max_sustainable_cps)
+    }
+}
+
+// The configured click mode and calibration at a fixed rate, with everything
+// that would pause the run, change its rate or reach outside left out
+fn bench_settings(config: &Config) -> EngineSettings {
+    let settings = EngineSettings::from_config(config);
+    EngineSettings {
+        injection_watchdog: false,
+        start_delay_secs: 0,
+        notifications: NotificationPrefs {
+            start: false,
+            stop: false,
+            countdown: false,
+            hotkey: false,
+            milestone: false,
+            error: false,
+            info: false,
+            ..settings.notifications
+        },
+        hooks: Arc::default(),
+        webhooks: Arc::from([]),
+        timing_model: TimingModel::Fixed,
+        human_pattern: None,
+        slow_interval: None,
+        anti_afk: None,
+        second_channel: None,
+        sequence: None,
+        click_pattern: None,
+        duty_cycle: None,
+        verify: None,
+        run_log: false,
+        blocked_windows: Arc::from([]),
+        cps_caps: Arc::from([]),
+        click_region: None,
+        ..settings
+    }
+}
+
+// One target rate through the engine: its scheduler, action and bookkeeping
+// run as in a real run, only the backend discards the input
+fn run_rate(
+    engine: &HeadlessEngine,
+    settings: &EngineSettings,
+    actions: &Actions,
+    target_cps: u32,
+) -> RateResult {
+    actions.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    engine.apply(EngineSettings {
+        cps: target_cps as f64,
+        ..settings.clone()
+    });
+    engine.set_running(true);
+    thread::sleep(RUN_DURATION);
+    engine.set_running(false);
+    let stopped = Instant::now();
+    thread::sleep(SETTLE);
+
+    let times: Vec<Instant> = actions
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .copied()
+        .filter(|&at| at <= stopped)
+        .collect();
+    let intervals: Vec<Duration> = times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let achieved_cps = match (times.first(), times.last()) {
+        (Some(&first), Some(&last)) if last > first => {
+            intervals.len() as f64 / (last - first).as_secs_f64()
+        }
+        _ => 0.0,
+    };
+    let target_us = 1_000_000.0 / target_cps as f64;
+    let jitter_us = if intervals.is_empty() {
+        0.0
+    } else {
+        let variance = intervals
+            .iter()
+            .map(|d| (d.as_secs_f64() * 1_000_000.0 - target_us).powi(2))
+            .sum::<f64>()
+            / intervals.len() as f64;
+        variance.sqrt()
+    };
+
+    RateResult {
+        target_cps,
+        achieved_cps,
+        jitter_us,
+    }
+}
+
+// Runs the click engine against the null backend at each rate, with the
+// click mode and calibration of config
+pub fn run_benchmark(config: &Config) -> BenchmarkReport {
+    let actions: Actions = Arc::default();
+    let shared = Arc::clone(&actions);
+    let make_backend: BackendFactory = Arc::new(move |_| {
+        Box::new(TimedNull {
+            inner: NullBackend,
+            actions: Arc::clone(&shared),
+        })
+    });
+    let settings = bench_settings(config);
+    let engine = HeadlessEngine::launch(config.clone(), make_backend, Clock::Real, None);
+    let results: Vec<RateResult> = BENCHMARK_RATES
+        .iter()
+        .map(|&cps| run_rate(&engine, &settings, &actions, cps))
+        .collect();
+    let max_sustainable_cps = results
+        .iter()
+        .filter(|r| r.sustainable())
+        .map(|r| r.target_cps)
+        .max()
+        .unwrap_or(0);
+
+    BenchmarkReport {
+        results,
+        max_sustainable_cps,
+    }
+}
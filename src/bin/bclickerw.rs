@@ -0,0 +1,8 @@
+// `bclicker daemon` as a Windows GUI program, so starting it at login opens
+// no console window; `bclicker service install` registers it. Elsewhere it's
+// the daemon with its log in a file
+#![cfg_attr(windows, windows_subsystem = "windows")]
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    bclicker::run_background()
+}
@@ -0,0 +1,173 @@
+use crate::backend::ClickBackend;
+use serde::{Deserialize, Serialize};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+// Sleep lengths probed for overshoot, each SLEEP_SAMPLES times
+const SLEEP_PROBES: [Duration; 3] = [
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(4),
+];
+const SLEEP_SAMPLES: usize = 40;
+const INJECTION_SAMPLES: usize = 30;
+
+// Headroom above the measured p99 overshoot before spinning takes over
+const SPIN_MARGIN_US: u64 = 200;
+const MIN_SPIN_US: u64 = 100;
+const MAX_SPIN_US: u64 = 20_000;
+const MAX_LEAD_US: u64 = 20_000;
+
+// Scheduler corrections measured by `bclicker calibrate`; the defaults are
+// what the scheduler used before calibration existed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct Calibration {
+    // How long before a deadline precise_sleep stops sleeping and spins
+    pub spin_threshold_us: u64,
+    // How early clicks fire so the injected event lands on the deadline
+    pub injection_latency_us: u64,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            spin_threshold_us: 1500,
+            injection_latency_us: 0,
+        }
+    }
+}
+
+impl Calibration {
+    pub fn sanitize(&mut self) {
+        self.spin_threshold_us = self.spin_threshold_us.clamp(MIN_SPIN_US, MAX_SPIN_US);
+        self.injection_latency_us = self.injection_latency_us.min(MAX_LEAD_US);
+    }
+
+    pub fn spin_threshold(&self) -> Duration {
+        Duration::from_micros(self.spin_threshold_us)
+    }
+
+    pub fn lead(&self) -> Duration {
+        Duration::from_micros(self.injection_latency_us)
+    }
+}
+
+pub struct CalibrationReport {
+    pub backend: &'static str,
+    pub sleep_median_us: u64,
+    pub sleep_p99_us: u64,
+    pub injection_median_us: u64,
+    pub calibration: Calibration,
+}
+
+impl std::fmt::Display for CalibrationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Sleep overshoot: median {}µs, p99 {}µs -> spin for the last {}µs",
+            self.sleep_median_us, self.sleep_p99_us, self.calibration.spin_threshold_us
+        )?;
+        write!(
+            f,
+            "Input injection via {}: median {}µs -> clicks fire {}µs early",
+            self.backend, self.injection_median_us, self.calibration.injection_latency_us
+        )
+    }
+}
+
+fn percentile(sorted: &[u64], fraction: f64) -> u64 {
+    let index = ((sorted.len() as f64 * fraction).ceil() as usize).clamp(1, sorted.len()) - 1;
+    sorted[index]
+}
+
+fn measure_sleep_overshoot() -> Vec<u64> {
+    let mut overshoots: Vec<u64> = SLEEP_PROBES
+        .iter()
+        .flat_map(|&duration| std::iter::repeat_n(duration, SLEEP_SAMPLES))
+        .map(|duration| {
+            let start = Instant::now();
+            thread::sleep(duration);
+            start.elapsed().saturating_sub(duration).as_micros() as u64
+        })
+        .collect();
+    overshoots.sort_unstable();
+    overshoots
+}
+
+// Time the backend takes to hand the OS a pointer event, measured with moves
+// onto the cursor's own position so nothing on screen changes
+fn measure_injection(backend: &mut dyn ClickBackend) -> Vec<u64> {
+    let mut latencies: Vec<u64> = (0..INJECTION_SAMPLES)
+        .map(|_| {
+            let position = backend.cursor_position();
+            let start = Instant::now();
+            match position {
+                Some(p) => backend.move_to(p.x, p.y),
+                None => backend.move_relative(0, 0),
+            }
+            backend.flush();
+            let elapsed = start.elapsed().as_micros() as u64;
+            thread::sleep(Duration::from_millis(2));
+            elapsed
+        })
+        .collect();
+    latencies.sort_unstable();
+    latencies
+}
+
+pub fn run(backend: &mut dyn ClickBackend) -> CalibrationReport {
+    let overshoots = measure_sleep_overshoot();
+    let latencies = measure_injection(backend);
+    let sleep_p99_us = percentile(&overshoots, 0.99);
+    let injection_median_us = percentile(&latencies, 0.5);
+    let mut calibration = Calibration {
+        spin_threshold_us: sleep_p99_us + SPIN_MARGIN_US,
+        injection_latency_us: injection_median_us,
+    };
+    calibration.sanitize();
+
+    CalibrationReport {
+        backend: backend.name(),
+        sleep_median_us: percentile(&overshoots, 0.5),
+        sleep_p99_us,
+        injection_median_us,
+        calibration,
+    }
+}
+
+// `bclicker calibrate [--reset]`: measures this system with the configured
+// backend and stores the result in the config
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    let mut config = crate::load_config();
+    match args.first().map(String::as_str) {
+        Some("--reset") => {
+            config.calibration = Calibration::default();
+            crate::save_config(&config);
+            println!("[INFO] Calibration reset to the defaults");
+            return Ok(());
+        }
+        Some(other) => {
+            return Err(format!(
+                "unknown option {}\nusage: bclicker calibrate [--reset]",
+                other
+            ));
+        }
+        None => {}
+    }
+
+    // Measure under the same 1 ms timer resolution the engine runs with
+    let timer_resolution = crate::TimerResolution::raise();
+    println!("[CALIBRATE] Measuring sleep overshoot and input injection latency...");
+    let mut backend = config.backend.create();
+    let report = run(backend.as_mut());
+    drop(timer_resolution);
+
+    println!("{}", report);
+    config.calibration = report.calibration;
+    crate::save_config(&config);
+    println!("[INFO] Calibration saved; the scheduler applies it from the next start");
+    Ok(())
+}
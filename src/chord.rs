@@ -0,0 +1,86 @@
+use crate::ActivationMode;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// A key tapped several times in quick succession, e.g. Ctrl twice within
+// 300 ms. Works with bare modifiers, which games rarely bind
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct TapChord {
+    pub key: String,
+    pub taps: u32,
+    pub window_ms: u64,
+    pub mode: ActivationMode,
+}
+
+impl Default for TapChord {
+    fn default() -> Self {
+        Self {
+            key: "Ctrl".to_string(),
+            taps: 2,
+            window_ms: 300,
+            mode: ActivationMode::Toggle,
+        }
+    }
+}
+
+impl TapChord {
+    pub fn sanitize(&mut self) {
+        self.taps = self.taps.clamp(2, 5);
+        self.window_ms = self.window_ms.clamp(100, 2000);
+    }
+}
+
+impl std::fmt::Display for TapChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}×{} ({})", self.key, self.taps, self.mode.label())
+    }
+}
+
+// Counts taps of the chord's key. Any other key in between starts over, so
+// ordinary Ctrl+C, Ctrl+V use doesn't add up to a double-tap
+#[derive(Default)]
+pub struct TapCounter {
+    taps: u32,
+    first_tap: Option<Instant>,
+    // The key is down; repeats while held aren't taps
+    down: bool,
+    // The final tap is still held, for hold mode
+    completed: bool,
+}
+
+impl TapCounter {
+    // Returns a press (true) or release (false) of the chord as a whole
+    pub fn feed(&mut self, chord: &TapChord, key: &str, pressed: bool) -> Option<bool> {
+        if !key.eq_ignore_ascii_case(&chord.key) {
+            if pressed {
+                self.taps = 0;
+            }
+            return None;
+        }
+        if !pressed {
+            self.down = false;
+            return std::mem::take(&mut self.completed).then_some(false);
+        }
+        if self.down {
+            return None;
+        }
+        self.down = true;
+
+        let now = Instant::now();
+        let window = Duration::from_millis(chord.window_ms);
+        match self.first_tap {
+            Some(first) if self.taps > 0 && now.duration_since(first) <= window => self.taps += 1,
+            _ => {
+                self.taps = 1;
+                self.first_tap = Some(now);
+            }
+        }
+        if self.taps < chord.taps {
+            return None;
+        }
+        self.taps = 0;
+        self.completed = true;
+        Some(true)
+    }
+}
@@ -0,0 +1,297 @@
+use clap::{Arg, ArgAction, Command, ValueHint, builder::PossibleValuesParser};
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+// The command line as clap sees it, for completions and the man page only:
+// each subcommand still parses its own arguments, so a new flag goes both
+// there and here
+pub fn command() -> Command {
+    Command::new("bclicker")
+        .version(env!("CARGO_PKG_VERSION"))
+        .disable_help_subcommand(true)
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .long_about(
+            "Professional auto-clicker with a terminal UI. Without a subcommand it opens \
+             the TUI; the flags override the config file for this session",
+        )
+        .args(session_args())
+        .arg(
+            Arg::new("tray-only")
+                .long("tray-only")
+                .action(ArgAction::SetTrue)
+                .help("Starts in the tray: no TUI, just the tray icon, hotkeys and notifications"),
+        )
+        .subcommand(
+            Command::new("benchmark").about("Measures the click engine against a null backend"),
+        )
+        .subcommand(
+            Command::new("calibrate")
+                .about("Measures sleep overshoot and injection latency and stores the result")
+                .arg(
+                    Arg::new("reset")
+                        .long("reset")
+                        .action(ArgAction::SetTrue)
+                        .help("Goes back to the default calibration"),
+                ),
+        )
+        .subcommand(
+            Command::new("click")
+                .about("Clicks a number of times through the configured backend and exits")
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .value_name("N")
+                        .help("Clicks to make"),
+                )
+                .arg(
+                    Arg::new("cps")
+                        .long("cps")
+                        .value_name("CPS")
+                        .help("Clicks per second"),
+                )
+                .arg(
+                    Arg::new("at")
+                        .long("at")
+                        .value_name("X,Y")
+                        .help("Screen position to click at instead of the cursor"),
+                )
+                .arg(
+                    Arg::new("button")
+                        .long("button")
+                        .value_name("BUTTON")
+                        .value_parser(PossibleValuesParser::new(["left", "right", "middle"]))
+                        .help("Mouse button"),
+                ),
+        )
+        .subcommand(
+            Command::new("token")
+                .about("Lists, creates and revokes tokens for the web remote's API")
+                .long_about(
+                    "Lists, creates and revokes tokens for the web remote's API. `new NAME` \
+                     makes a token that can also start and stop clicking, `new NAME \
+                     --read-only` one that can only read the status. Changes need \
+                     BClicker closed",
+                )
+                .arg(
+                    Arg::new("action")
+                        .value_name("ACTION")
+                        .value_parser(PossibleValuesParser::new(["list", "new", "revoke"])),
+                )
+                .arg(Arg::new("name").value_name("NAME"))
+                .arg(
+                    Arg::new("read-only")
+                        .long("read-only")
+                        .action(ArgAction::SetTrue)
+                        .help("The new token can only read the status"),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Writes settings and profiles to a file")
+                .long_about(
+                    "Writes settings and profiles to a file. A FILE ending in .ahk gets one \
+                     profile's sequence as an AutoHotkey v2 script toggled by the same hotkey",
+                )
+                .arg(
+                    Arg::new("file")
+                        .value_name("FILE")
+                        .value_hint(ValueHint::FilePath)
+                        .help("Defaults to bclicker_export.toml"),
+                )
+                .arg(
+                    Arg::new("profile")
+                        .long("profile")
+                        .value_name("NAME")
+                        .help("Profile an .ahk script is made from; defaults to the active one"),
+                ),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Previews and applies settings from an exported file")
+                .long_about(
+                    "Previews and applies settings from an exported file. An AutoHotkey \
+                     script (.ahk) with Click, MouseClick, MouseMove, MouseClickDrag, Sleep, \
+                     Send and Loop commands becomes a profile's sequence instead",
+                )
+                .arg(
+                    Arg::new("file")
+                        .value_name("FILE")
+                        .value_hint(ValueHint::FilePath)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("profile")
+                        .long("profile")
+                        .value_name("NAME")
+                        .help("Profile an .ahk script goes into; defaults to the file name"),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .short('y')
+                        .long("yes")
+                        .action(ArgAction::SetTrue)
+                        .help("Applies the changes without asking"),
+                ),
+        )
+        .subcommand(
+            Command::new("headless")
+                .about("Runs the engine without the TUI, driven by commands on stdin")
+                .long_about(
+                    "Runs the engine without the TUI. Reads one command per line on stdin \
+                     (cps N, start, stop, status, quit) and writes events to stdout as \
+                     NDJSON; closing stdin quits",
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .action(ArgAction::SetTrue)
+                        .help("Runs the full engine without sending any input"),
+                ),
+        )
+        .subcommand(
+            Command::new("status")
+                .about("Shows the state of the running instance")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(ArgAction::SetTrue)
+                        .help("One JSON object, for status bars such as waybar or polybar"),
+                ),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Prints the running instance's stats once a second as NDJSON"),
+        )
+        .subcommand(
+            Command::new("daemon")
+                .about("Runs BClicker in the background, with the TUI shown on attach")
+                .long_about(
+                    "Runs BClicker without a terminal of its own: the engine, hotkeys, tray \
+                     and servers keep going while `bclicker attach` shows the TUI and its \
+                     quit key detaches again. Takes the same flags as the TUI",
+                )
+                .args(session_args())
+                .arg(
+                    Arg::new("detach")
+                        .long("detach")
+                        .action(ArgAction::SetTrue)
+                        .help("Starts the daemon in the background and returns"),
+                )
+                .arg(
+                    Arg::new("systemd")
+                        .long("systemd")
+                        .action(ArgAction::SetTrue)
+                        .help("Runs as a Type=notify service, logging to the journal"),
+                )
+                .arg(
+                    Arg::new("dir")
+                        .long("dir")
+                        .value_name("DIR")
+                        .value_hint(ValueHint::DirPath)
+                        .help("Works in DIR and uses the config there"),
+                ),
+        )
+        .subcommand(
+            Command::new("service")
+                .about("Installs or removes a background daemon started at login")
+                .long_about(
+                    "Installs or removes a background daemon using this directory's config. \
+                     On Linux it's a systemd user unit running `bclicker daemon --systemd`, to \
+                     manage with `systemctl --user`; on Windows `bclickerw`, which has no \
+                     console window, started at every login",
+                )
+                .arg(
+                    Arg::new("action")
+                        .value_name("ACTION")
+                        .value_parser(PossibleValuesParser::new(["install", "uninstall"]))
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("attach")
+                .about("Shows the running daemon's TUI in this terminal")
+                .long_about(
+                    "Shows the running daemon's TUI in this terminal. The quit key detaches \
+                     and leaves the daemon running. Plain `bclicker` does the same when a \
+                     daemon is running",
+                ),
+        )
+        .subcommand(Command::new("quit").about("Closes the running instance"))
+        .subcommand(Command::new("start").about("Starts clicking in the running instance"))
+        .subcommand(Command::new("stop").about("Stops clicking in the running instance"))
+        .subcommand(
+            Command::new("toggle").about("Starts or stops clicking in the running instance"),
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("Prints a shell completion script")
+                .arg(
+                    Arg::new("shell")
+                        .value_name("SHELL")
+                        .value_parser(clap::value_parser!(Shell))
+                        .required(true),
+                ),
+        )
+        .subcommand(Command::new("man").about("Prints the man page in roff format"))
+        .after_long_help(
+            "Exit status: 0 on success, 1 when no instance is running or it can't be reached, \
+             2 when the running instance refused the request",
+        )
+}
+
+// The flags that shape a session, for the TUI and the daemon alike
+fn session_args() -> [Arg; 4] {
+    [
+        Arg::new("cps")
+            .long("cps")
+            .value_name("CPS")
+            .help("Clicks per second for this session"),
+        Arg::new("button")
+            .long("button")
+            .value_name("MODE")
+            .value_parser(PossibleValuesParser::new([
+                "left",
+                "right",
+                "scroll-up",
+                "scroll-down",
+                "text",
+                "alternate",
+                "hold",
+                "hover",
+            ]))
+            .help("Click mode for this session"),
+        Arg::new("set")
+            .long("set")
+            .value_name("KEY=VALUE")
+            .action(ArgAction::Append)
+            .help("Overrides any config key, e.g. start_delay_secs=5"),
+        Arg::new("dry-run")
+            .long("dry-run")
+            .action(ArgAction::SetTrue)
+            .help("Runs the full engine without sending any input"),
+    ]
+}
+
+// `bclicker completions <shell>`
+pub fn run_completions(args: &[String]) -> Result<(), String> {
+    let shells = "bash, elvish, fish, powershell or zsh";
+    let shell: Shell = match args {
+        [shell] => shell
+            .parse()
+            .map_err(|_| format!("unknown shell '{}', expected {}", shell, shells))?,
+        _ => return Err(format!("usage: bclicker completions <{}>", shells)),
+    };
+    // Written in one go, so a closed pipe is an error rather than a panic
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command(), "bclicker", &mut script);
+    io::stdout()
+        .write_all(&script)
+        .map_err(|e| format!("could not write the completions: {}", e))
+}
+
+// `bclicker man`
+pub fn run_man() -> Result<(), String> {
+    clap_mangen::Man::new(command())
+        .render(&mut io::stdout())
+        .map_err(|e| format!("could not write the man page: {}", e))
+}
@@ -0,0 +1,67 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+// Where the engine reads the time and waits. The real clock is the system
+// one; a virtual clock stands still until something sleeps on it and then
+// jumps straight to the end of the sleep, so minutes of patterns, duty cycles
+// and auto-stops replay in milliseconds and land on exact deadlines.
+// Virtual times are still Instants, a fixed offset from when the clock was
+// made, so everything that does arithmetic on them works unchanged
+#[derive(Clone, Debug, Default)]
+pub enum Clock {
+    #[default]
+    Real,
+    Virtual(Arc<VirtualTime>),
+}
+
+#[derive(Debug)]
+pub struct VirtualTime {
+    origin: Instant,
+    elapsed_nanos: AtomicU64,
+}
+
+impl Clock {
+    pub fn new_virtual() -> Self {
+        Clock::Virtual(Arc::new(VirtualTime {
+            origin: Instant::now(),
+            elapsed_nanos: AtomicU64::new(0),
+        }))
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        matches!(self, Clock::Virtual(_))
+    }
+
+    pub fn now(&self) -> Instant {
+        match self {
+            Clock::Real => Instant::now(),
+            Clock::Virtual(time) => {
+                time.origin + Duration::from_nanos(time.elapsed_nanos.load(Ordering::SeqCst))
+            }
+        }
+    }
+
+    // Time since an earlier reading of this clock
+    pub fn since(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
+    }
+
+    // A virtual sleep only yields, so other threads still get to run while
+    // the engine races through its schedule
+    pub fn sleep(&self, duration: Duration) {
+        match self {
+            Clock::Real => thread::sleep(duration),
+            Clock::Virtual(time) => {
+                time.elapsed_nanos
+                    .fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+                thread::yield_now();
+            }
+        }
+    }
+}
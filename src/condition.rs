@@ -0,0 +1,319 @@
+use crate::{
+    focus,
+    sequence::Point,
+    variables::{self, Variables},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compare {
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtMost,
+    #[serde(rename = "==")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+}
+
+impl Compare {
+    // Two-character operators first so ">=" isn't read as ">"
+    const ALL: [Compare; 6] = [
+        Compare::AtLeast,
+        Compare::AtMost,
+        Compare::Equal,
+        Compare::NotEqual,
+        Compare::Above,
+        Compare::Below,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Compare::Above => ">",
+            Compare::AtLeast => ">=",
+            Compare::Below => "<",
+            Compare::AtMost => "<=",
+            Compare::Equal => "==",
+            Compare::NotEqual => "!=",
+        }
+    }
+
+    fn holds(self, left: i64, right: i64) -> bool {
+        match self {
+            Compare::Above => left > right,
+            Compare::AtLeast => left >= right,
+            Compare::Below => left < right,
+            Compare::AtMost => left <= right,
+            Compare::Equal => left == right,
+            Compare::NotEqual => left != right,
+        }
+    }
+}
+
+// Something a sequence step can wait on, evaluated right before the step runs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Condition {
+    // Screen pixel within `tolerance` per channel of a "#RRGGBB" color
+    Pixel {
+        x: i32,
+        y: i32,
+        color: String,
+        #[serde(default)]
+        tolerance: u8,
+    },
+    // Title of the focused window contains this text, ignoring case
+    Window {
+        title: String,
+    },
+    // Clicking has been running for at least this long
+    Elapsed {
+        secs: u64,
+    },
+    // A built-in variable or counter compared with a number, e.g.
+    // clicks_this_run > 1000
+    Variable {
+        name: String,
+        op: Compare,
+        value: i64,
+    },
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Pixel { x, y, color, .. } => write!(f, "pixel {},{} is {}", x, y, color),
+            Condition::Window { title } => write!(f, "window \"{}\" focused", title),
+            Condition::Elapsed { secs } => write!(f, "{}s elapsed", secs),
+            Condition::Variable { name, op, value } => {
+                write!(f, "{} {} {}", name, op.symbol(), value)
+            }
+        }
+    }
+}
+
+impl Condition {
+    // "clicks_this_run > 1000", as written in patterns
+    pub fn parse_comparison(input: &str) -> Option<Self> {
+        let (at, op) = Compare::ALL
+            .iter()
+            .filter_map(|&op| input.find(op.symbol()).map(|at| (at, op)))
+            .min_by_key(|&(at, _)| at)?;
+        let name = input[..at].trim();
+        let value = input[at + op.symbol().len()..].trim().parse().ok()?;
+        variables::is_name(name).then(|| Condition::Variable {
+            name: name.to_string(),
+            op,
+            value,
+        })
+    }
+
+    // Config problems worth a startup warning
+    pub fn validate(&self) -> Option<String> {
+        match self {
+            Condition::Pixel { color, .. } if parse_color(color).is_none() => {
+                Some(format!("'{}' is not a #RRGGBB color", color))
+            }
+            Condition::Window { title } if title.is_empty() => {
+                Some("window condition without a title".to_string())
+            }
+            Condition::Variable { name, .. } if !variables::is_name(name) => {
+                Some(format!("'{}' is not a variable name", name))
+            }
+            _ => None,
+        }
+    }
+}
+
+// What the engine does with the step its conditions were checked for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gate {
+    Run,
+    Skip,
+    Stop,
+}
+
+pub fn parse_color(input: &str) -> Option<[u8; 3]> {
+    let hex = input.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::c_void;
+
+    const CLR_INVALID: u32 = 0xFFFF_FFFF;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn GetDC(hwnd: *mut c_void) -> *mut c_void;
+        fn ReleaseDC(hwnd: *mut c_void, hdc: *mut c_void) -> i32;
+    }
+
+    #[link(name = "gdi32")]
+    unsafe extern "system" {
+        fn GetPixel(hdc: *mut c_void, x: i32, y: i32) -> u32;
+    }
+
+    #[derive(Default)]
+    pub struct Screen;
+
+    impl Screen {
+        pub fn pixel(&mut self, x: i32, y: i32) -> Option<[u8; 3]> {
+            unsafe {
+                let hdc = GetDC(std::ptr::null_mut());
+                if hdc.is_null() {
+                    return None;
+                }
+                let color = GetPixel(hdc, x, y);
+                ReleaseDC(std::ptr::null_mut(), hdc);
+                // COLORREF is 0x00BBGGRR
+                (color != CLR_INVALID).then_some([
+                    color as u8,
+                    (color >> 8) as u8,
+                    (color >> 16) as u8,
+                ])
+            }
+        }
+    }
+}
+
+// X11 through Xlib; the display is opened on first use and kept for the
+// engine's lifetime
+#[cfg(all(unix, not(target_os = "macos")))]
+mod native {
+    use std::os::raw::{c_char, c_int, c_uint, c_ulong, c_void};
+
+    const ALL_PLANES: c_ulong = !0;
+    const Z_PIXMAP: c_int = 2;
+
+    #[link(name = "X11")]
+    unsafe extern "C" {
+        fn XOpenDisplay(name: *const c_char) -> *mut c_void;
+        fn XCloseDisplay(display: *mut c_void) -> c_int;
+        fn XDefaultRootWindow(display: *mut c_void) -> c_ulong;
+        #[allow(clippy::too_many_arguments)]
+        fn XGetImage(
+            display: *mut c_void,
+            drawable: c_ulong,
+            x: c_int,
+            y: c_int,
+            width: c_uint,
+            height: c_uint,
+            plane_mask: c_ulong,
+            format: c_int,
+        ) -> *mut c_void;
+        fn XGetPixel(image: *mut c_void, x: c_int, y: c_int) -> c_ulong;
+        fn XDestroyImage(image: *mut c_void) -> c_int;
+    }
+
+    pub struct Screen {
+        opened: bool,
+        display: *mut c_void,
+    }
+
+    impl Default for Screen {
+        fn default() -> Self {
+            Self {
+                opened: false,
+                display: std::ptr::null_mut(),
+            }
+        }
+    }
+
+    impl Screen {
+        fn open(&mut self) {
+            if !self.opened {
+                self.opened = true;
+                self.display = unsafe { XOpenDisplay(std::ptr::null()) };
+            }
+        }
+
+        // Assumes the usual 24-bit TrueColor visual
+        pub fn pixel(&mut self, x: i32, y: i32) -> Option<[u8; 3]> {
+            self.open();
+            if self.display.is_null() {
+                return None;
+            }
+            unsafe {
+                let root = XDefaultRootWindow(self.display);
+                let image = XGetImage(self.display, root, x, y, 1, 1, ALL_PLANES, Z_PIXMAP);
+                if image.is_null() {
+                    return None;
+                }
+                let pixel = XGetPixel(image, 0, 0);
+                XDestroyImage(image);
+                Some([(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8])
+            }
+        }
+    }
+
+    impl Drop for Screen {
+        fn drop(&mut self) {
+            if !self.display.is_null() {
+                unsafe { XCloseDisplay(self.display) };
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod native {
+    #[derive(Default)]
+    pub struct Screen;
+
+    impl Screen {
+        pub fn pixel(&mut self, _x: i32, _y: i32) -> Option<[u8; 3]> {
+            None
+        }
+    }
+}
+
+// Within tolerance of each other on every channel
+pub fn same_color(a: [u8; 3], b: [u8; 3], tolerance: u8) -> bool {
+    a.iter().zip(b).all(|(&a, b)| a.abs_diff(b) <= tolerance)
+}
+
+// Reads what conditions look at; owned by the engine thread. Anything that
+// can't be read, e.g. pixels under Wayland, counts as not matching
+#[derive(Default)]
+pub struct Probe {
+    screen: native::Screen,
+    focus: focus::Reader,
+}
+
+impl Probe {
+    // Also for checking what a click did
+    pub fn pixel(&mut self, p: Point) -> Option<[u8; 3]> {
+        self.screen.pixel(p.x, p.y)
+    }
+
+    pub fn check(&mut self, condition: &Condition, vars: &Variables) -> bool {
+        match condition {
+            Condition::Pixel {
+                x,
+                y,
+                color,
+                tolerance,
+            } => match (parse_color(color), self.screen.pixel(*x, *y)) {
+                (Some(want), Some(got)) => same_color(want, got, *tolerance),
+                _ => false,
+            },
+            Condition::Window { title } => self.focus.focused().is_some_and(|focused| {
+                focused.title.to_lowercase().contains(&title.to_lowercase())
+            }),
+            Condition::Elapsed { secs } => vars.run_time >= Duration::from_secs(*secs),
+            Condition::Variable { name, op, value } => op.holds(vars.get(name), *value),
+        }
+    }
+}
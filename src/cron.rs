@@ -0,0 +1,312 @@
+use crate::interval;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How far ahead the next run is looked for; every valid expression fires
+// within a year, except impossible dates such as "0 0 30 2 *"
+const LOOKAHEAD_DAYS: i64 = 366;
+
+#[cfg(windows)]
+mod system {
+    #[repr(C)]
+    struct SystemTime {
+        fields: [u16; 8],
+    }
+
+    #[repr(C)]
+    struct TimeZoneInformation {
+        bias: i32,
+        standard_name: [u16; 32],
+        standard_date: SystemTime,
+        standard_bias: i32,
+        daylight_name: [u16; 32],
+        daylight_date: SystemTime,
+        daylight_bias: i32,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetTimeZoneInformation(info: *mut TimeZoneInformation) -> u32;
+    }
+
+    const TIME_ZONE_ID_STANDARD: u32 = 1;
+    const TIME_ZONE_ID_DAYLIGHT: u32 = 2;
+
+    // Seconds east of UTC right now, daylight saving included
+    pub fn utc_offset(_now: i64) -> i64 {
+        unsafe {
+            let mut info: TimeZoneInformation = std::mem::zeroed();
+            let bias = match GetTimeZoneInformation(&mut info) {
+                TIME_ZONE_ID_STANDARD => info.bias + info.standard_bias,
+                TIME_ZONE_ID_DAYLIGHT => info.bias + info.daylight_bias,
+                _ => info.bias,
+            };
+            -(bias as i64) * 60
+        }
+    }
+}
+
+#[cfg(unix)]
+mod system {
+    // Seconds east of UTC at that moment, daylight saving included
+    pub fn utc_offset(now: i64) -> i64 {
+        let now = now as libc::time_t;
+        unsafe {
+            let mut tm: libc::tm = std::mem::zeroed();
+            if libc::localtime_r(&now, &mut tm).is_null() {
+                return 0;
+            }
+            tm.tm_gmtoff as i64
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod system {
+    pub fn utc_offset(_now: i64) -> i64 {
+        0
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+// Wall clock fields of a local time, as cron sees them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocalTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    // 0 is Sunday
+    pub weekday: u32,
+}
+
+impl LocalTime {
+    // From seconds since the epoch, already shifted to local time
+    fn from_local_secs(secs: i64) -> Self {
+        let days = secs.div_euclid(86_400);
+        let of_day = secs.rem_euclid(86_400);
+        // Days to civil date, after Howard Hinnant's algorithm
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Self {
+            year,
+            month,
+            day,
+            hour: (of_day / 3600) as u32,
+            minute: (of_day / 60 % 60) as u32,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 4).rem_euclid(7) as u32,
+        }
+    }
+
+    pub fn now() -> Self {
+        let now = unix_now();
+        Self::from_local_secs(now + system::utc_offset(now))
+    }
+}
+
+impl std::fmt::Display for LocalTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+// Minutes since the epoch, local time; changes once per wall clock minute
+pub fn current_minute() -> i64 {
+    let now = unix_now();
+    (now + system::utc_offset(now)).div_euclid(60)
+}
+
+// One field as a bit set of the values it allows
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let mut allowed = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|&s| s > 0)
+                    .ok_or_else(|| format!("bad step '{}' in the {} field", step, name))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let number = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|v| (min..=max).contains(v))
+                .ok_or_else(|| {
+                    format!(
+                        "{} must be between {} and {}, got '{}'",
+                        name, min, max, value
+                    )
+                })
+        };
+        let (from, to) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((from, to)) => (number(from)?, number(to)?),
+                // "5/15" runs from 5 to the end, like most crons
+                None if step > 1 => (number(range)?, max),
+                None => {
+                    let value = number(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if from > to {
+            return Err(format!("backwards range '{}' in the {} field", range, name));
+        }
+        for value in (from..=to).step_by(step as usize) {
+            allowed |= 1 << value;
+        }
+    }
+    Ok(allowed)
+}
+
+// Standard five-field cron expression: minute, hour, day of month, month and
+// day of week, each a "*", a number, a range, a list or a "/step"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // As in cron, a restricted day of month and day of week match either
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronExpr {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = input.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        };
+        let mut weekdays = parse_field(weekday, 0, 7, "weekday")?;
+        // Both 0 and 7 are Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days: parse_field(day, 1, 31, "day")?,
+            months: parse_field(month, 1, 12, "month")?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    fn matches_day(&self, time: &LocalTime) -> bool {
+        if self.months & (1 << time.month) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << time.day) != 0;
+        let weekday = self.weekdays & (1 << time.weekday) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    pub fn matches(&self, time: &LocalTime) -> bool {
+        self.matches_day(time)
+            && self.hours & (1 << time.hour) != 0
+            && self.minutes & (1 << time.minute) != 0
+    }
+
+    // The first matching minute after now. Uses today's UTC offset
+    // throughout, so a run past a daylight saving change shows an hour off;
+    // the runs themselves go by the live clock
+    pub fn next_run(&self) -> Option<LocalTime> {
+        let now = unix_now();
+        let start = (now + system::utc_offset(now)).div_euclid(60) * 60 + 60;
+        let mut secs = start;
+        while secs < start + LOOKAHEAD_DAYS * 86_400 {
+            let time = LocalTime::from_local_secs(secs);
+            if !self.matches_day(&time) {
+                secs = (secs.div_euclid(86_400) + 1) * 86_400;
+            } else if self.hours & (1 << time.hour) == 0 {
+                secs = (secs.div_euclid(3600) + 1) * 3600;
+            } else if self.minutes & (1 << time.minute) == 0 {
+                secs += 60;
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+}
+
+// Starts the profile it belongs to whenever the expression matches and stops
+// it again after duration_secs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub cron: String,
+    pub duration_secs: u64,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Schedule {
+    // "0 */2 * * * for 30m", as typed on the Scheduler screen
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (cron, duration) = input
+            .rsplit_once(" for ")
+            .ok_or_else(|| "expected CRON for DURATION, e.g. 0 */2 * * * for 30m".to_string())?;
+        CronExpr::parse(cron)?;
+        let duration_secs = interval::parse(duration).ok_or_else(|| {
+            format!(
+                "duration must be between {} and {}",
+                interval::describe(interval::MIN_SECS),
+                interval::describe(interval::MAX_SECS)
+            )
+        })?;
+        Ok(Self {
+            cron: cron.split_whitespace().collect::<Vec<_>>().join(" "),
+            duration_secs,
+            enabled: true,
+        })
+    }
+
+    // Problem with a hand-edited entry, if any
+    pub fn validate(&self) -> Result<CronExpr, String> {
+        if !(interval::MIN_SECS..=interval::MAX_SECS).contains(&self.duration_secs) {
+            return Err(format!(
+                "duration must be between {} and {} seconds",
+                interval::MIN_SECS,
+                interval::MAX_SECS
+            ));
+        }
+        CronExpr::parse(&self.cron)
+    }
+}
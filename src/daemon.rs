@@ -0,0 +1,258 @@
+use crate::{
+    AppEvent,
+    ipc::{Attach, Stream},
+};
+use serde_json::json;
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+use tui::{Terminal, TerminalOptions, Viewport, backend::CrosstermBackend, layout::Rect};
+
+pub const ALREADY_RUNNING: &str = "BClicker is already running; `bclicker attach` shows it";
+
+// How long `daemon --detach` waits for the new daemon to take the socket
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+// The TUI of a daemon, drawn into the terminal of the client that attached
+// last. Its size is whatever the client says, never looked up
+pub struct RemoteScreen {
+    pub session: u64,
+    pub terminal: Terminal<CrosstermBackend<Stream>>,
+}
+
+impl RemoteScreen {
+    // Answers the client with its session, which its key connection names
+    pub fn accept(attach: Attach, session: u64) -> io::Result<Self> {
+        let mut stream = attach.stream;
+        writeln!(stream, "{}", json!({ "session": session }))?;
+        let area = Rect::new(0, 0, attach.cols, attach.rows);
+        let mut terminal = Terminal::with_options(
+            CrosstermBackend::new(stream),
+            TerminalOptions {
+                viewport: Viewport::fixed(area),
+            },
+        )?;
+        terminal.clear()?;
+        Ok(Self { session, terminal })
+    }
+
+    pub fn resize(&mut self, cols: u16, rows: u16) -> io::Result<()> {
+        self.terminal.resize(Rect::new(0, 0, cols, rows))
+    }
+}
+
+// An instance in a terminal of its own has no screen to share
+pub fn refuse(attach: Attach) {
+    let mut stream = attach.stream;
+    let error = "this BClicker runs in its own terminal; start it with `bclicker daemon` to attach";
+    let _ = writeln!(stream, "{}", json!({ "error": error, "refused": true }));
+}
+
+// Where a detached daemon writes what it would have printed
+fn log_path() -> PathBuf {
+    crate::get_config_path().with_file_name("bclicker_daemon.log")
+}
+
+#[cfg(unix)]
+unsafe extern "C" {
+    fn setsid() -> i32;
+    fn dup2(from: i32, to: i32) -> i32;
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn SetStdHandle(which: u32, handle: isize) -> i32;
+    fn GetConsoleProcessList(list: *mut u32, count: u32) -> u32;
+    fn FreeConsole() -> i32;
+}
+
+// Points stdout and stderr at the log, for a daemon with no console or
+// terminal to print to
+pub fn log_to_file() -> Result<(), String> {
+    let log_path = log_path();
+    let log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|e| format!("could not open {}: {}", log_path.display(), e))?;
+    #[cfg(unix)]
+    unsafe {
+        let fd = std::os::fd::IntoRawFd::into_raw_fd(log);
+        dup2(fd, 1);
+        dup2(fd, 2);
+    }
+    // Printing looks the handles up every time, so this takes effect
+    // straight away
+    #[cfg(windows)]
+    unsafe {
+        const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+        const STD_ERROR_HANDLE: u32 = -12i32 as u32;
+        let handle = std::os::windows::io::IntoRawHandle::into_raw_handle(log) as isize;
+        SetStdHandle(STD_OUTPUT_HANDLE, handle);
+        SetStdHandle(STD_ERROR_HANDLE, handle);
+    }
+    Ok(())
+}
+
+// `--tray-only` started from Explorer or a shortcut: Windows gave it a
+// console of its own, which would sit there empty, so it goes and the log
+// takes its place. One shared with a shell, which was started from, stays
+#[cfg(windows)]
+pub fn leave_console() -> Result<(), String> {
+    let mut list = [0u32; 2];
+    if unsafe { GetConsoleProcessList(list.as_mut_ptr(), 2) } != 1 {
+        return Ok(());
+    }
+    log_to_file()?;
+    unsafe {
+        FreeConsole();
+    }
+    Ok(())
+}
+
+// A terminal stays with the shell that started it
+#[cfg(not(windows))]
+pub fn leave_console() -> Result<(), String> {
+    Ok(())
+}
+
+// The console program, also when this is `bclickerw`
+fn console_exe() -> io::Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    let name = format!("bclicker{}", std::env::consts::EXE_SUFFIX);
+    Ok(exe.with_file_name(name))
+}
+
+// The tray's Show for a daemon: `bclicker attach` in a terminal of its own,
+// a new console window on Windows and $TERMINAL or the system's default
+// terminal elsewhere
+pub fn open_attached_terminal() {
+    let exe = match console_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            println!("[WARNING] Could not open a terminal: {}", e);
+            return;
+        }
+    };
+    #[cfg(windows)]
+    let opened = {
+        const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+        let mut command = Command::new(&exe);
+        command.arg("attach");
+        std::os::windows::process::CommandExt::creation_flags(&mut command, CREATE_NEW_CONSOLE);
+        command.spawn().is_ok()
+    };
+    #[cfg(not(windows))]
+    let opened = std::env::var("TERMINAL")
+        .ok()
+        .into_iter()
+        .chain(["x-terminal-emulator".to_string()])
+        .any(|terminal| {
+            Command::new(terminal)
+                .arg("-e")
+                .arg(&exe)
+                .arg("attach")
+                .spawn()
+                .is_ok()
+        });
+    if !opened {
+        println!("[WARNING] Could not open a terminal; run `bclicker attach` in one");
+    }
+}
+
+// `bclicker daemon --detach`: starts the daemon again in the background, in
+// a session of its own so closing this terminal doesn't end it, and returns
+// once it answers on the control socket
+pub fn detach(args: &[String]) -> Result<(), String> {
+    if crate::ipc::instance_running() {
+        return Err(ALREADY_RUNNING.to_string());
+    }
+    let log_path = log_path();
+    let log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|e| format!("could not open {}: {}", log_path.display(), e))?;
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut command = Command::new(exe);
+    command
+        .arg("daemon")
+        .args(args.iter().filter(|a| *a != "--detach"))
+        .stdin(Stdio::null())
+        .stdout(log.try_clone().map_err(|e| e.to_string())?)
+        .stderr(log);
+    #[cfg(unix)]
+    unsafe {
+        std::os::unix::process::CommandExt::pre_exec(&mut command, || {
+            setsid();
+            Ok(())
+        });
+    }
+    #[cfg(windows)]
+    {
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        std::os::windows::process::CommandExt::creation_flags(
+            &mut command,
+            DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP,
+        );
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("could not start the daemon: {}", e))?;
+    let deadline = Instant::now() + STARTUP_TIMEOUT;
+    while Instant::now() < deadline {
+        if crate::ipc::instance_running() {
+            println!(
+                "[INFO] BClicker daemon started (pid {}); `bclicker attach` shows it, \
+                 and its log is {}",
+                child.id(),
+                log_path.display()
+            );
+            return Ok(());
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(format!(
+                "the daemon exited ({}); see {}",
+                status,
+                log_path.display()
+            ));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(format!(
+        "the daemon didn't answer in time; see {}",
+        log_path.display()
+    ))
+}
+
+// SIGTERM from a service manager and Ctrl+C in the foreground close the
+// daemon the way `bclicker quit` does, saving the config on the way out
+#[cfg(unix)]
+pub fn quit_on_signals(ui: mpsc::Sender<AppEvent>) {
+    use signal_hook::{
+        consts::{SIGINT, SIGTERM},
+        iterator::Signals,
+    };
+    match Signals::new([SIGTERM, SIGINT]) {
+        Ok(mut signals) => {
+            thread::spawn(move || {
+                if signals.forever().next().is_some() {
+                    let _ = ui.send(AppEvent::Quit);
+                }
+            });
+        }
+        Err(e) => println!("[WARNING] Signals won't close BClicker cleanly: {}", e),
+    }
+}
+
+// Windows has no SIGTERM; the tray and `bclicker quit` close it
+#[cfg(not(unix))]
+pub fn quit_on_signals(_ui: mpsc::Sender<AppEvent>) {}
@@ -0,0 +1,195 @@
+use crate::{
+    auth::{self, ApiToken, Scope},
+    ipc::{Control, IpcFeed},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{Arc, atomic::Ordering},
+    thread,
+    time::Duration,
+};
+
+// How long a client's read waits before the state is looked at again, so
+// changes reach the keys within about this long
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// Lines longer than this close the connection instead of growing forever
+const MAX_LINE: usize = 1024;
+
+// Local TCP endpoint for Stream Deck plugins and similar control surfaces.
+// Only loopback is listened on, and a client sends an API token before
+// anything else, since any local program or user can connect
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct DeckConfig {
+    pub port: u16,
+}
+
+impl Default for DeckConfig {
+    fn default() -> Self {
+        Self { port: 28620 }
+    }
+}
+
+// What a key can show; pushed to every client whenever it changes
+#[derive(Serialize, Clone, Debug, PartialEq)]
+struct DeckState {
+    running: bool,
+    paused: bool,
+    // Seconds left of the start delay
+    countdown: u32,
+    cps: f64,
+    profile: String,
+}
+
+impl DeckState {
+    fn read(feed: &IpcFeed) -> Self {
+        let settings = feed.engine_settings.get();
+        let running = feed.running.load(Ordering::SeqCst);
+        Self {
+            running,
+            paused: running && feed.paused.load(Ordering::SeqCst),
+            countdown: feed.countdown.load(Ordering::SeqCst),
+            cps: settings.cps,
+            profile: settings.profile_name.to_string(),
+        }
+    }
+
+    fn line(&self) -> String {
+        let mut line = serde_json::to_value(self).unwrap_or_default();
+        line["event"] = json!("state");
+        line.to_string()
+    }
+}
+
+// Answers one command line; `state` asks for the state again, anything else
+// is a control request. Both are refused until the token allows them
+fn reply(feed: &IpcFeed, scope: Option<Scope>, command: &str) -> Option<String> {
+    let control = match command {
+        "state" => None,
+        _ => Some(Control::parse(command)?),
+    };
+    let needed = match control {
+        Some(_) => Scope::Control,
+        None => Scope::Read,
+    };
+    if let Err(e) = allowed(scope, needed) {
+        let error = e.to_string();
+        return Some(json!({ "event": "error", "command": command, "error": error }).to_string());
+    }
+    let Some(control) = control else {
+        return Some(DeckState::read(feed).line());
+    };
+    let reply = match feed.control(control) {
+        Ok(reply) => json!({
+            "event": "reply",
+            "command": command,
+            "running": reply.running,
+            "changed": reply.changed,
+        })
+        .to_string(),
+        Err(e) => json!({ "event": "error", "command": command, "error": e }).to_string(),
+    };
+    Some(reply)
+}
+
+fn allowed(scope: Option<Scope>, needed: Scope) -> Result<(), auth::Denied> {
+    match scope {
+        Some(scope) if scope >= needed => Ok(()),
+        Some(_) => Err(auth::Denied::Forbidden),
+        None => Err(auth::Denied::Unauthorized),
+    }
+}
+
+// The scope of the strongest configured token matching
+fn scope_of(tokens: &[ApiToken], token: &str) -> Option<Scope> {
+    [Scope::Control, Scope::Read]
+        .into_iter()
+        .find(|&scope| auth::check(tokens, Some(token), scope).is_ok())
+}
+
+// One line of JSON per message each way is all a plugin needs to handle:
+// `auth <token>` first, then commands in, replies and state changes out
+fn serve(feed: &IpcFeed, tokens: &[ApiToken], stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    writeln!(writer, "{}", json!({ "event": "hello", "version": 1 }))?;
+    let mut scope = None;
+    let mut shown = None;
+    let mut line = Vec::new();
+    loop {
+        let state = DeckState::read(feed);
+        if allowed(scope, Scope::Read).is_ok() && shown.as_ref() != Some(&state) {
+            writeln!(writer, "{}", state.line())?;
+            shown = Some(state);
+        }
+        // A read that times out keeps what it got so far for the next try
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) if line.ends_with(b"\n") => {
+                let command = String::from_utf8_lossy(&line);
+                let command = command.trim();
+                if let Some(token) = command.strip_prefix("auth ") {
+                    // A wrong token closes the connection, so guesses come
+                    // one connection at a time
+                    let Some(granted) = scope_of(tokens, token.trim()) else {
+                        let error = auth::Denied::Unauthorized.to_string();
+                        writeln!(writer, "{}", json!({ "event": "error", "error": error }))?;
+                        return Ok(());
+                    };
+                    scope = Some(granted);
+                    writeln!(writer, "{}", json!({ "event": "auth", "scope": granted }))?;
+                } else if !command.is_empty() {
+                    // Also what a web page posting to the port gets for its
+                    // request line, before its body could be read as a command
+                    let Some(reply) = reply(feed, scope, command) else {
+                        let error = format!("unknown command '{}'", command);
+                        writeln!(writer, "{}", json!({ "event": "error", "error": error }))?;
+                        return Ok(());
+                    };
+                    writeln!(writer, "{}", reply)?;
+                }
+                line.clear();
+            }
+            Ok(_) => {}
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => return Err(e),
+        }
+        if line.len() > MAX_LINE {
+            return Ok(());
+        }
+    }
+}
+
+// Listens until the process exits, a thread per client
+pub fn spawn(config: DeckConfig, tokens: Vec<ApiToken>, feed: IpcFeed) -> Result<(), String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, config.port))
+        .map_err(|e| format!("port {}: {}", config.port, e))?;
+    let tokens = Arc::new(tokens);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let feed = feed.clone();
+                    let tokens = Arc::clone(&tokens);
+                    thread::spawn(move || {
+                        let _ = serve(&feed, &tokens, stream);
+                    });
+                }
+                Err(e) => {
+                    println!("[WARNING] Deck endpoint: {}", e);
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::sequence::Point;
+
+// Without per-monitor DPI awareness Windows virtualises coordinates on scaled
+// displays, so fixed-point clicks land short of their target at 125%/150%
+#[cfg(windows)]
+mod win {
+    use crate::POINT;
+    use std::ffi::c_void;
+
+    pub const PROCESS_PER_MONITOR_DPI_AWARE: u32 = 2;
+    pub const MDT_EFFECTIVE_DPI: u32 = 0;
+    pub const MONITOR_DEFAULTTONEAREST: u32 = 2;
+
+    #[link(name = "shcore")]
+    unsafe extern "system" {
+        pub fn SetProcessDpiAwareness(value: u32) -> i32;
+        pub fn GetDpiForMonitor(monitor: *mut c_void, kind: u32, x: *mut u32, y: *mut u32) -> i32;
+    }
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        pub fn MonitorFromPoint(pt: POINT, flags: u32) -> *mut c_void;
+    }
+}
+
+// Called once at startup before any window or input work
+#[cfg(windows)]
+pub fn enable_dpi_awareness() {
+    // Fails harmlessly when a manifest already set the awareness
+    unsafe {
+        win::SetProcessDpiAwareness(win::PROCESS_PER_MONITOR_DPI_AWARE);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn enable_dpi_awareness() {}
+
+// Scale factor of the monitor nearest to the point (1.0 = 96 DPI)
+#[cfg(windows)]
+pub fn scale_at(p: Point) -> f64 {
+    let (mut dpi_x, mut dpi_y) = (96, 96);
+    unsafe {
+        let monitor = win::MonitorFromPoint(
+            crate::POINT { x: p.x, y: p.y },
+            win::MONITOR_DEFAULTTONEAREST,
+        );
+        if win::GetDpiForMonitor(monitor, win::MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) != 0 {
+            return 1.0;
+        }
+    }
+    dpi_x as f64 / 96.0
+}
+
+// Other platforms already report and accept the same coordinate space
+#[cfg(not(windows))]
+pub fn scale_at(_p: Point) -> f64 {
+    1.0
+}
+
+// Logical (scaled) coordinates as shown by most apps to the physical pixels
+// the input APIs use once the process is DPI aware
+pub fn to_physical(p: Point) -> Point {
+    let scale = scale_at(p);
+    Point {
+        x: (p.x as f64 * scale).round() as i32,
+        y: (p.y as f64 * scale).round() as i32,
+    }
+}
+
+// Physical pixels back to the logical coordinates a config in that space
+// stores, e.g. for a corner picked up from the cursor
+pub fn to_logical(p: Point) -> Point {
+    let scale = scale_at(p);
+    Point {
+        x: (p.x as f64 / scale).round() as i32,
+        y: (p.y as f64 / scale).round() as i32,
+    }
+}
@@ -0,0 +1,56 @@
+use crate::interval;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DutyPhase {
+    Clicking,
+    Paused,
+}
+
+// Click for on_secs, pause for off_secs, repeat, counted from the start of
+// the run
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DutyCycle {
+    pub on_secs: u64,
+    pub off_secs: u64,
+}
+
+impl DutyCycle {
+    // Parses "30s/10s" or "2m/30s": the on time, then the pause
+    pub fn parse(input: &str) -> Option<Self> {
+        let (on, off) = input.split_once('/')?;
+        Some(Self {
+            on_secs: interval::parse(on)?,
+            off_secs: interval::parse(off)?,
+        })
+    }
+
+    pub fn sanitize(&mut self) {
+        self.on_secs = self.on_secs.clamp(interval::MIN_SECS, interval::MAX_SECS);
+        self.off_secs = self.off_secs.clamp(interval::MIN_SECS, interval::MAX_SECS);
+    }
+
+    // Phase at this point of the run and how long it has left
+    pub fn phase(&self, elapsed: Duration) -> (DutyPhase, Duration) {
+        let on = Duration::from_secs(self.on_secs);
+        let cycle = on + Duration::from_secs(self.off_secs);
+        let into = Duration::from_nanos((elapsed.as_nanos() % cycle.as_nanos()) as u64);
+        if into < on {
+            (DutyPhase::Clicking, on - into)
+        } else {
+            (DutyPhase::Paused, cycle - into)
+        }
+    }
+}
+
+impl std::fmt::Display for DutyCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            interval::describe(self.on_secs),
+            interval::describe(self.off_secs)
+        )
+    }
+}
@@ -0,0 +1,341 @@
+use crate::AppEvent;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex, mpsc},
+    thread,
+    time::{Duration, Instant},
+};
+
+// How often the watcher looks at which window has focus
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// How often the engine looks again while it has windows to keep out of or
+// to cap the rate in
+const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+// The window with keyboard focus: its title and the file name of the
+// program that owns it, e.g. "javaw.exe" or "firefox"
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FocusedWindow {
+    pub title: String,
+    pub process: String,
+}
+
+// Names the way people write them: "javaw", "javaw.exe" and "JAVAW.EXE" are
+// the same program
+fn same_process(pattern: &str, process: &str) -> bool {
+    let stem = |name: &str| {
+        let name = name.trim().to_lowercase();
+        match name.strip_suffix(".exe") {
+            Some(stem) => stem.to_string(),
+            None => name,
+        }
+    };
+    !process.is_empty() && stem(pattern) == stem(process)
+}
+
+// Process name and/or title text picking out windows; the title matches
+// anywhere in the window title, ignoring case, like the window condition
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct WindowMatch {
+    pub process: Option<String>,
+    pub title: Option<String>,
+}
+
+impl WindowMatch {
+    // Both parts have to match when both are given
+    pub fn matches(&self, window: &FocusedWindow) -> bool {
+        self.process
+            .as_deref()
+            .is_none_or(|process| same_process(process, &window.process))
+            && self
+                .title
+                .as_deref()
+                .is_none_or(|title| window.title.to_lowercase().contains(&title.to_lowercase()))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let empty = |part: &Option<String>| part.as_deref().is_none_or(|s| s.trim().is_empty());
+        if empty(&self.process) && empty(&self.title) {
+            return Err("needs a process or a title".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for WindowMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.process, &self.title) {
+            (Some(process), Some(title)) => write!(f, "{} \"{}\"", process, title),
+            (Some(process), None) => write!(f, "{}", process),
+            (None, Some(title)) => write!(f, "\"{}\"", title),
+            (None, None) => write!(f, "any window"),
+        }
+    }
+}
+
+// Switches to a profile while a matching window has focus, e.g. javaw.exe
+// to the Minecraft profile
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProfileRule {
+    #[serde(flatten)]
+    pub window: WindowMatch,
+    // Profile name
+    pub profile: String,
+}
+
+// The focused window as last seen by the watcher, for whoever wants it
+// between focus changes
+pub type SharedFocus = Arc<Mutex<Option<FocusedWindow>>>;
+
+// Polls the focused window and wakes the TUI with it whenever it changes.
+// Stays quiet where focus can't be read, e.g. native Wayland windows
+pub fn spawn(focus: SharedFocus, ui: mpsc::Sender<AppEvent>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = Reader::default();
+        let mut seen = None;
+        loop {
+            let window = reader.focused();
+            if window != seen {
+                *focus.lock().unwrap_or_else(|e| e.into_inner()) = window.clone();
+                if ui.send(AppEvent::Focus(window.clone())).is_err() {
+                    return;
+                }
+                seen = window;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    })
+}
+
+// Caps the clicking rate while a matching window has focus, e.g. 10 CPS in
+// one game whatever the preset says
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CpsCap {
+    #[serde(flatten)]
+    pub window: WindowMatch,
+    pub max_cps: f64,
+}
+
+// The engine's view of the focused window, for the blocked windows and the
+// CPS caps. Focus is read at most every CHECK_INTERVAL rather than on every
+// click, and only while one of the lists has entries
+#[derive(Default)]
+pub struct FocusCheck {
+    reader: Reader,
+    checked_at: Option<Instant>,
+    window: Option<FocusedWindow>,
+}
+
+impl FocusCheck {
+    fn focused(&mut self) -> Option<&FocusedWindow> {
+        if self
+            .checked_at
+            .is_none_or(|at| at.elapsed() >= CHECK_INTERVAL)
+        {
+            self.checked_at = Some(Instant::now());
+            self.window = self.reader.focused();
+        }
+        self.window.as_ref()
+    }
+
+    // The focused window if it's on the list. A window that can't be read
+    // isn't blocked
+    pub fn blocked(&mut self, blocked: &[WindowMatch]) -> Option<&FocusedWindow> {
+        if blocked.is_empty() {
+            return None;
+        }
+        self.focused()
+            .filter(|window| blocked.iter().any(|entry| entry.matches(window)))
+    }
+
+    // The lowest cap matching the focused window
+    pub fn cps_cap(&mut self, caps: &[CpsCap]) -> Option<f64> {
+        if caps.is_empty() {
+            return None;
+        }
+        let window = self.focused()?;
+        caps.iter()
+            .filter(|cap| cap.window.matches(window))
+            .map(|cap| cap.max_cps)
+            .min_by(f64::total_cmp)
+    }
+}
+
+// Reads the focused window; one per thread
+#[derive(Default)]
+pub struct Reader {
+    native: native::Focus,
+}
+
+impl Reader {
+    pub fn focused(&mut self) -> Option<FocusedWindow> {
+        self.native.focused()
+    }
+}
+
+#[cfg(windows)]
+mod native {
+    use super::FocusedWindow;
+    use std::ffi::c_void;
+
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn GetForegroundWindow() -> *mut c_void;
+        fn GetWindowTextW(hwnd: *mut c_void, text: *mut u16, max: i32) -> i32;
+        fn GetWindowThreadProcessId(hwnd: *mut c_void, pid: *mut u32) -> u32;
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn QueryFullProcessImageNameW(
+            process: *mut c_void,
+            flags: u32,
+            name: *mut u16,
+            size: *mut u32,
+        ) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    #[derive(Default)]
+    pub struct Focus;
+
+    impl Focus {
+        pub fn focused(&mut self) -> Option<FocusedWindow> {
+            unsafe {
+                let hwnd = GetForegroundWindow();
+                if hwnd.is_null() {
+                    return None;
+                }
+                let mut buf = [0u16; 512];
+                let len = GetWindowTextW(hwnd, buf.as_mut_ptr(), buf.len() as i32);
+                let title = String::from_utf16_lossy(&buf[..len.max(0) as usize]);
+
+                let mut pid = 0;
+                GetWindowThreadProcessId(hwnd, &mut pid);
+                let mut process = String::new();
+                let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+                if !handle.is_null() {
+                    let mut path = [0u16; 1024];
+                    let mut size = path.len() as u32;
+                    if QueryFullProcessImageNameW(handle, 0, path.as_mut_ptr(), &mut size) != 0 {
+                        let path = String::from_utf16_lossy(&path[..size as usize]);
+                        process = path.rsplit('\\').next().unwrap_or_default().to_string();
+                    }
+                    CloseHandle(handle);
+                }
+                Some(FocusedWindow { title, process })
+            }
+        }
+    }
+}
+
+// X11 through libxdo, which enigo already links; the process comes from
+// /proc on Linux
+#[cfg(all(unix, not(target_os = "macos")))]
+mod native {
+    use super::FocusedWindow;
+    use std::os::raw::{c_char, c_int, c_ulong, c_void};
+
+    #[link(name = "xdo")]
+    unsafe extern "C" {
+        fn xdo_new(display: *const c_char) -> *mut c_void;
+        fn xdo_free(xdo: *mut c_void);
+        fn xdo_get_active_window(xdo: *const c_void, window: *mut c_ulong) -> c_int;
+        fn xdo_get_window_name(
+            xdo: *const c_void,
+            window: c_ulong,
+            name: *mut *mut u8,
+            len: *mut c_int,
+            kind: *mut c_int,
+        ) -> c_int;
+        fn xdo_get_pid_window(xdo: *const c_void, window: c_ulong) -> c_int;
+    }
+
+    #[link(name = "X11")]
+    unsafe extern "C" {
+        fn XFree(data: *mut c_void) -> c_int;
+    }
+
+    // Opened on first use and kept for the owner's lifetime
+    pub struct Focus {
+        opened: bool,
+        xdo: *mut c_void,
+    }
+
+    impl Default for Focus {
+        fn default() -> Self {
+            Self {
+                opened: false,
+                xdo: std::ptr::null_mut(),
+            }
+        }
+    }
+
+    fn process_name(pid: c_int) -> String {
+        if pid <= 0 {
+            return String::new();
+        }
+        std::fs::read_link(format!("/proc/{}/exe", pid))
+            .ok()
+            .and_then(|exe| Some(exe.file_name()?.to_string_lossy().into_owned()))
+            .or_else(|| std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok())
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    impl Focus {
+        pub fn focused(&mut self) -> Option<FocusedWindow> {
+            if !self.opened {
+                self.opened = true;
+                self.xdo = unsafe { xdo_new(std::ptr::null()) };
+            }
+            if self.xdo.is_null() {
+                return None;
+            }
+            unsafe {
+                let mut window = 0;
+                if xdo_get_active_window(self.xdo, &mut window) != 0 {
+                    return None;
+                }
+                let (mut name, mut len, mut kind) = (std::ptr::null_mut(), 0, 0);
+                let mut title = String::new();
+                if xdo_get_window_name(self.xdo, window, &mut name, &mut len, &mut kind) == 0
+                    && !name.is_null()
+                {
+                    let bytes = std::slice::from_raw_parts(name, len.max(0) as usize);
+                    title = String::from_utf8_lossy(bytes).into_owned();
+                    XFree(name.cast());
+                }
+                let process = process_name(xdo_get_pid_window(self.xdo, window));
+                Some(FocusedWindow { title, process })
+            }
+        }
+    }
+
+    impl Drop for Focus {
+        fn drop(&mut self) {
+            if !self.xdo.is_null() {
+                unsafe { xdo_free(self.xdo) };
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod native {
+    use super::FocusedWindow;
+
+    #[derive(Default)]
+    pub struct Focus;
+
+    impl Focus {
+        pub fn focused(&mut self) -> Option<FocusedWindow> {
+            None
+        }
+    }
+}
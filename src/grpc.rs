@@ -0,0 +1,266 @@
+// gRPC Control and Stats services next to the web remote's REST API, from
+// proto/bclicker.proto. Built with the `grpc` feature, which also makes the
+// generated clients public here for other Rust tools:
+//
+//   let channel = Channel::from_static("http://127.0.0.1:50051").connect().await?;
+//   let mut control = ControlClient::with_interceptor(channel, BearerToken::new(token)?);
+//   control.toggle(Empty {}).await?;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct GrpcConfig {
+    pub bind: IpAddr,
+    pub port: u16,
+}
+
+// Only this machine by default: integrations usually run next to BClicker,
+// and the calls aren't encrypted
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            bind: IpAddr::from([127, 0, 0, 1]),
+            port: 50051,
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+pub mod proto {
+    tonic::include_proto!("bclicker.v1");
+}
+
+#[cfg(feature = "grpc")]
+pub use client::BearerToken;
+#[cfg(feature = "grpc")]
+pub use proto::{control_client::ControlClient, stats_client::StatsClient};
+#[cfg(feature = "grpc")]
+pub(crate) use server::spawn;
+
+#[cfg(feature = "grpc")]
+mod client {
+    use tonic::{
+        Request, Status,
+        metadata::{AsciiMetadataValue, errors::InvalidMetadataValue},
+        service::Interceptor,
+    };
+
+    // Adds the `authorization` metadata every call needs
+    #[derive(Clone, Debug)]
+    pub struct BearerToken(AsciiMetadataValue);
+
+    impl BearerToken {
+        pub fn new(token: &str) -> Result<Self, InvalidMetadataValue> {
+            format!("Bearer {}", token).parse().map(Self)
+        }
+    }
+
+    impl Interceptor for BearerToken {
+        fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+            request
+                .metadata_mut()
+                .insert("authorization", self.0.clone());
+            Ok(request)
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+mod server {
+    use super::{
+        GrpcConfig,
+        proto::{
+            self, ControlReply, Empty, SetCpsRequest, SetProfileRequest, StatsRecord, WatchRequest,
+            control_server::{Control, ControlServer},
+            stats_server::{Stats, StatsServer},
+        },
+    };
+    use crate::{
+        auth::{self, ApiToken, Denied, Scope},
+        ipc::{self, IpcFeed},
+    };
+    use std::{
+        net::TcpListener,
+        sync::Arc,
+        thread,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+    use tonic::{Request, Response, Status, transport::Server};
+
+    // Bounds of a Watch stream's interval
+    const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+    const MIN_WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+    impl From<ipc::Status> for proto::Status {
+        fn from(status: ipc::Status) -> Self {
+            Self {
+                running: status.running,
+                paused: status.paused,
+                countdown: status.countdown,
+                cps: status.cps,
+                achieved_cps: status.achieved_cps,
+                button: status.button,
+                profile: status.profile,
+                session_clicks: status.session_clicks,
+                total_clicks: status.total_clicks,
+                uptime_secs: status.uptime_secs,
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    struct Service {
+        feed: IpcFeed,
+    }
+
+    impl Service {
+        // Control requests wait on the TUI, so they're kept off the runtime
+        async fn act(&self, control: ipc::Control) -> Result<Response<ControlReply>, Status> {
+            let feed = self.feed.clone();
+            let reply = tokio::task::spawn_blocking(move || feed.control(control))
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .map_err(Status::failed_precondition)?;
+            Ok(Response::new(ControlReply {
+                running: reply.running,
+                changed: reply.changed,
+            }))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Control for Service {
+        async fn start(&self, _: Request<Empty>) -> Result<Response<ControlReply>, Status> {
+            self.act(ipc::Control::Start).await
+        }
+
+        async fn stop(&self, _: Request<Empty>) -> Result<Response<ControlReply>, Status> {
+            self.act(ipc::Control::Stop).await
+        }
+
+        async fn toggle(&self, _: Request<Empty>) -> Result<Response<ControlReply>, Status> {
+            self.act(ipc::Control::Toggle).await
+        }
+
+        async fn set_cps(
+            &self,
+            request: Request<SetCpsRequest>,
+        ) -> Result<Response<ControlReply>, Status> {
+            let cps = crate::parse_cps(&request.get_ref().cps.to_string()).ok_or_else(|| {
+                Status::invalid_argument(format!("cps must be at least {}", crate::MIN_CPS))
+            })?;
+            self.act(ipc::Control::SetCps(cps)).await
+        }
+
+        async fn set_profile(
+            &self,
+            request: Request<SetProfileRequest>,
+        ) -> Result<Response<ControlReply>, Status> {
+            self.act(ipc::Control::Profile(request.into_inner().name))
+                .await
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Stats for Service {
+        type WatchStream = ReceiverStream<Result<StatsRecord, Status>>;
+
+        async fn get_status(&self, _: Request<Empty>) -> Result<Response<proto::Status>, Status> {
+            Ok(Response::new(self.feed.status().into()))
+        }
+
+        // Ends when the client goes away
+        async fn watch(
+            &self,
+            request: Request<WatchRequest>,
+        ) -> Result<Response<Self::WatchStream>, Status> {
+            let interval = match request.get_ref().interval_ms {
+                0 => DEFAULT_WATCH_INTERVAL,
+                ms => Duration::from_millis(ms as u64).max(MIN_WATCH_INTERVAL),
+            };
+            let (records, stream) = mpsc::channel(4);
+            let feed = self.feed.clone();
+            tokio::spawn(async move {
+                let mut ticks = tokio::time::interval(interval);
+                let mut last_clicks = None;
+                loop {
+                    ticks.tick().await;
+                    let status = feed.status();
+                    let new_clicks =
+                        last_clicks.map_or(0, |last| status.session_clicks.saturating_sub(last));
+                    last_clicks = Some(status.session_clicks);
+                    let record = StatsRecord {
+                        timestamp: SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                        status: Some(status.into()),
+                        new_clicks,
+                    };
+                    if records.send(Ok(record)).await.is_err() {
+                        return;
+                    }
+                }
+            });
+            Ok(Response::new(ReceiverStream::new(stream)))
+        }
+    }
+
+    // Lets through calls whose `authorization: Bearer <token>` metadata has
+    // the scope the service needs
+    fn guard(
+        tokens: Arc<[ApiToken]>,
+        scope: Scope,
+    ) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone {
+        move |request| {
+            let token = request
+                .metadata()
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .map(str::trim);
+            match auth::check(&tokens, token, scope) {
+                Ok(()) => Ok(request),
+                Err(e @ Denied::Unauthorized) => Err(Status::unauthenticated(e.to_string())),
+                Err(e @ Denied::Forbidden) => Err(Status::permission_denied(e.to_string())),
+            }
+        }
+    }
+
+    // Serves on a runtime of its own until the process exits. The port is
+    // bound here, so a port in use is reported straight away
+    pub fn spawn(config: GrpcConfig, tokens: Vec<ApiToken>, feed: IpcFeed) -> Result<(), String> {
+        let address = format!("{}:{}", config.bind, config.port);
+        let listener = TcpListener::bind((config.bind, config.port))
+            .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+            .map_err(|e| format!("{}: {}", address, e))?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let listener = {
+            let _runtime = runtime.enter();
+            tokio::net::TcpListener::from_std(listener).map_err(|e| e.to_string())?
+        };
+        let tokens: Arc<[ApiToken]> = tokens.into();
+        let service = Service { feed };
+        let control =
+            ControlServer::with_interceptor(service.clone(), guard(tokens.clone(), Scope::Control));
+        let stats = StatsServer::with_interceptor(service, guard(tokens, Scope::Read));
+        thread::spawn(move || {
+            let served = runtime.block_on(
+                Server::builder()
+                    .add_service(control)
+                    .add_service(stats)
+                    .serve_with_incoming(TcpListenerStream::new(listener)),
+            );
+            if let Err(e) = served {
+                println!("[WARNING] gRPC server stopped: {}", e);
+            }
+        });
+        Ok(())
+    }
+}
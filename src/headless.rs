@@ -0,0 +1,301 @@
+use crate::{
+    AudioManager, Config, ConfigFormat, CpsChoice, EngineSettings, EngineShared, MAX_CPS, MIN_CPS,
+    SharedSettings,
+    backend::{BackendFactory, BackendKind},
+    clock::Clock,
+    hooks::HookEvent,
+    i18n,
+    i18n::Language,
+    job_queue::JobQueue,
+    milestone_hook_env, parse_cps,
+    scheduler::RateStatus,
+    start_clicker_thread,
+    stats::Statistics,
+};
+use serde_json::json;
+use std::{
+    fs,
+    io::{self, BufRead},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+const USAGE: &str = "usage: bclicker headless [--dry-run]";
+
+// How often `bclicker headless` looks for state changes to report
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// The click engine on its own: no TUI, tray, hotkeys or sound, started and
+// stopped by hand and clicking through whatever backend it's given. Runs the
+// same supervised thread as the app, so a test sees what a user would
+pub struct HeadlessEngine {
+    shared: EngineShared,
+    config: Mutex<Config>,
+}
+
+impl HeadlessEngine {
+    // config is the text of a bclicker_config.toml; nothing is read from or
+    // written to disk
+    pub fn start(config: &str, make_backend: BackendFactory) -> Result<Self, String> {
+        Ok(Self::launch(
+            ConfigFormat::Toml.parse(config)?,
+            make_backend,
+            Clock::Real,
+            None,
+        ))
+    }
+
+    // Runs on a virtual clock with the timing jitter drawn from seed, so the
+    // same config and seed always click at the same virtual times. The
+    // backend should timestamp with the same clock. Stop conditions such as
+    // "until run_secs >= 60" end a replay; stopping it by hand lands at
+    // whatever virtual time the engine has raced to by then
+    pub fn replay(
+        config: &str,
+        make_backend: BackendFactory,
+        clock: Clock,
+        seed: u64,
+    ) -> Result<Self, String> {
+        Ok(Self::launch(
+            ConfigFormat::Toml.parse(config)?,
+            make_backend,
+            clock,
+            Some(seed),
+        ))
+    }
+
+    pub(crate) fn launch(
+        mut config: Config,
+        make_backend: BackendFactory,
+        clock: Clock,
+        rng_seed: Option<u64>,
+    ) -> Self {
+        config.sanitize();
+        i18n::set_language(config.language.unwrap_or_else(Language::detect));
+
+        let shared = EngineShared {
+            auto_clicker_running: Arc::new(AtomicBool::new(false)),
+            auto_clicker_paused: Arc::new(AtomicBool::new(false)),
+            countdown: Arc::new(AtomicU32::new(0)),
+            engine_settings: Arc::new(SharedSettings::new(EngineSettings::from_config(&config))),
+            stats_tracker: Arc::new(Mutex::new(Statistics::default())),
+            rate_status: Arc::new(Mutex::new(RateStatus::default())),
+            audio_manager: Arc::new(Mutex::new(AudioManager::new(false, None, config.tones))),
+            tray_manager: Arc::new(Mutex::new(None)),
+            jobs: JobQueue::new(),
+            engine_fault: Arc::new(Mutex::new(None)),
+            stop_reason: Arc::new(Mutex::new(None)),
+            make_backend,
+            clock,
+            rng_seed,
+            shutdown: Arc::new(AtomicBool::new(false)),
+        };
+        start_clicker_thread(shared.clone());
+        Self {
+            shared,
+            config: Mutex::new(config),
+        }
+    }
+
+    pub fn set_running(&self, running: bool) {
+        self.shared
+            .auto_clicker_running
+            .store(running, Ordering::SeqCst);
+    }
+
+    // False again once a stop condition or the auto-stop ends the run
+    pub fn is_running(&self) -> bool {
+        self.shared.auto_clicker_running.load(Ordering::SeqCst)
+    }
+
+    // Switches to a custom rate, applied from the next click
+    pub fn set_cps(&self, cps: f64) -> Result<(), String> {
+        if !(MIN_CPS..=MAX_CPS).contains(&cps) {
+            return Err(format!("CPS must be between {} and {}", MIN_CPS, MAX_CPS));
+        }
+        let mut config = self.config.lock().unwrap_or_else(|e| e.into_inner());
+        config.custom_cps_value = Some(cps);
+        config.apply_choice(CpsChoice::Custom);
+        self.shared
+            .engine_settings
+            .set(EngineSettings::from_config(&config));
+        Ok(())
+    }
+
+    // Hands the engine settings built by the caller rather than the config,
+    // applied from the next click
+    pub(crate) fn apply(&self, settings: EngineSettings) {
+        self.shared.engine_settings.set(settings);
+    }
+
+    pub fn cps(&self) -> f64 {
+        self.shared.engine_settings.cps()
+    }
+
+    // Mouse clicks so far; the engine adds its tally to the statistics a few
+    // times a second and when a run stops
+    pub fn total_clicks(&self) -> u64 {
+        self.shared
+            .stats_tracker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .total_clicks
+    }
+
+    // Why the engine last crashed, if it has
+    pub fn fault(&self) -> Option<String> {
+        self.shared
+            .engine_fault
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|fault| fault.error.clone())
+    }
+}
+
+impl Drop for HeadlessEngine {
+    fn drop(&mut self) {
+        self.set_running(false);
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+    }
+}
+
+// A line written to stdout: one JSON object with an "event" field
+fn emit(event: &str, mut fields: serde_json::Value) {
+    fields["event"] = json!(event);
+    println!("{}", fields);
+}
+
+enum Request {
+    Cps(f64),
+    Start,
+    Stop,
+    Status,
+    Quit,
+}
+
+fn parse_request(line: &str) -> Result<Request, String> {
+    let mut words = line.split_whitespace();
+    let request = match (words.next(), words.next()) {
+        (Some("cps"), Some(value)) => Request::Cps(
+            parse_cps(value)
+                .filter(|&cps| cps <= MAX_CPS)
+                .ok_or_else(|| format!("invalid CPS '{}'", value))?,
+        ),
+        (Some("start"), None) => Request::Start,
+        (Some("stop"), None) => Request::Stop,
+        (Some("status"), None) => Request::Status,
+        (Some("quit"), None) => Request::Quit,
+        _ => return Err(format!("unknown command '{}'", line)),
+    };
+    match words.next() {
+        Some(_) => Err(format!("unknown command '{}'", line)),
+        None => Ok(request),
+    }
+}
+
+// `bclicker headless`: the engine without the TUI, driven by commands on
+// stdin, one per line (cps 40, start, stop, status, quit), and reporting on
+// stdout as NDJSON, so other tools can run it as a child process. Closing
+// stdin quits too. Diagnostics go to stderr. The config file is read but
+// never written, and clicks don't count towards the statistics
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    let dry_run = match args {
+        [] => false,
+        [flag] if flag == "--dry-run" => true,
+        _ => return Err(USAGE.to_string()),
+    };
+    let path = crate::get_config_path();
+    let mut config = match fs::read_to_string(&path) {
+        Ok(text) => ConfigFormat::from_path(&path)
+            .parse(&text)
+            .map_err(|e| format!("{}: {}", path.display(), e))?,
+        Err(_) => Config::default(),
+    };
+    config.dry_run = dry_run;
+    let engine = HeadlessEngine::launch(config, Arc::new(BackendKind::create), Clock::Real, None);
+
+    let (lines, requests) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if lines.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let backend = engine.shared.engine_settings.get().backend;
+    emit(
+        "ready",
+        json!({ "cps": engine.cps(), "backend": backend.label(), "dry_run": dry_run }),
+    );
+    let mut was_running = false;
+    let mut fault_seen = None;
+    let mut milestone_checked = 0;
+    loop {
+        match requests.recv_timeout(POLL_INTERVAL) {
+            Ok(line) if line.trim().is_empty() => {}
+            Ok(line) => match parse_request(line.trim()) {
+                Ok(Request::Cps(cps)) => match engine.set_cps(cps) {
+                    Ok(()) => emit("cps", json!({ "cps": engine.cps() })),
+                    Err(e) => emit("error", json!({ "message": e })),
+                },
+                Ok(Request::Start) => engine.set_running(true),
+                Ok(Request::Stop) => engine.set_running(false),
+                Ok(Request::Status) => emit(
+                    "status",
+                    json!({
+                        "running": engine.is_running(),
+                        "cps": engine.cps(),
+                        "total_clicks": engine.total_clicks(),
+                    }),
+                ),
+                Ok(Request::Quit) => break,
+                Err(e) => emit("error", json!({ "message": e })),
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        // Stop conditions and crashes change the state too, so it's
+        // compared rather than reported per command
+        let running = engine.is_running();
+        if running != was_running {
+            let event = if running { "started" } else { "stopped" };
+            emit(event, json!({}));
+            was_running = running;
+        }
+        let total_clicks = engine.total_clicks();
+        let config = engine.config.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(milestone) = config.reached_milestone(milestone_checked, total_clicks) {
+            emit("milestone", json!({ "clicks": milestone }));
+            config.hooks.run(
+                HookEvent::Milestone,
+                &milestone_hook_env(&config, milestone, total_clicks),
+            );
+        }
+        drop(config);
+        milestone_checked = total_clicks;
+        let fault = engine.fault();
+        if let Some(error) = fault.as_ref().filter(|_| fault != fault_seen) {
+            let message = format!("click engine crashed: {}", error);
+            emit("error", json!({ "message": message }));
+        }
+        fault_seen = fault;
+    }
+    engine.set_running(false);
+    // Lets the engine release anything it holds and add its last clicks to
+    // the tally before the process exits
+    thread::sleep(POLL_INTERVAL);
+    if was_running {
+        emit("stopped", json!({}));
+    }
+    emit("exited", json!({ "total_clicks": engine.total_clicks() }));
+    Ok(())
+}
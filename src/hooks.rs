@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    process::{Command, Stdio},
+    thread,
+};
+
+// Shell commands run when clicking starts or stops and at click milestones,
+// e.g. to pause music or log runs elsewhere. Each gets BCLICKER_* variables
+// describing the event; output is discarded so it can't draw over the TUI
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Hooks {
+    pub on_start: Option<String>,
+    pub on_stop: Option<String>,
+    pub on_milestone: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookEvent {
+    Start,
+    Stop,
+    Milestone,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::Start => "start",
+            HookEvent::Stop => "stop",
+            HookEvent::Milestone => "milestone",
+        }
+    }
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+impl Hooks {
+    // Starts the event's command without waiting for it; a thread reaps it
+    // and reports a failure
+    pub fn run(&self, event: HookEvent, env: &[(&str, String)]) {
+        let command = match event {
+            HookEvent::Start => &self.on_start,
+            HookEvent::Stop => &self.on_stop,
+            HookEvent::Milestone => &self.on_milestone,
+        };
+        let Some(command) = command.as_deref().filter(|c| !c.trim().is_empty()) else {
+            return;
+        };
+        let mut child = shell(command);
+        child
+            .env("BCLICKER_EVENT", event.name())
+            .envs(env.iter().map(|(name, value)| (name, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        match child.spawn() {
+            Ok(mut child) => {
+                let name = event.name();
+                thread::spawn(move || match child.wait() {
+                    Ok(status) if !status.success() => {
+                        eprintln!("[WARNING] on_{} hook exited with {}", name, status)
+                    }
+                    Err(e) => eprintln!("[WARNING] on_{} hook: {}", name, e),
+                    Ok(_) => {}
+                });
+            }
+            Err(e) => eprintln!(
+                "[WARNING] Could not run the on_{} hook: {}",
+                event.name(),
+                e
+            ),
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use crate::KeyCombo;
+use std::sync::{Arc, Mutex};
+
+#[cfg(any(windows, target_os = "linux"))]
+pub use registered::GlobalHotkeys;
+
+// The global shortcuts the hotkey thread looks after
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HotkeyId {
+    Toggle,
+    Faster,
+    Slower,
+    Pause,
+}
+
+impl HotkeyId {
+    pub const ALL: [HotkeyId; 4] = [
+        HotkeyId::Toggle,
+        HotkeyId::Faster,
+        HotkeyId::Slower,
+        HotkeyId::Pause,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            HotkeyId::Toggle => "toggle",
+            HotkeyId::Faster => "faster",
+            HotkeyId::Slower => "slower",
+            HotkeyId::Pause => "pause",
+        }
+    }
+}
+
+// What the hotkey thread waits on: a press or release of a registered combo,
+// or the TUI asking it to pick up changed combos
+pub enum HotkeyMessage {
+    Changed,
+    Key(HotkeyId, bool),
+}
+
+// A source of global shortcuts. Presses and releases of registered combos
+// arrive as HotkeyMessage::Key on the hotkey thread's channel
+pub trait HotkeyBackend: Send {
+    fn register(&mut self, id: HotkeyId, combo: &KeyCombo) -> Result<(), String>;
+    fn unregister(&mut self, id: HotkeyId);
+
+    // False where no global key input can be read at all
+    fn supported(&self) -> bool {
+        true
+    }
+}
+
+fn modifier_bit(key: &str) -> Option<u8> {
+    match key {
+        "Shift" => Some(1),
+        "Ctrl" => Some(2),
+        "Alt" => Some(4),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Matcher {
+    combos: Vec<(HotkeyId, KeyCombo)>,
+    // Shift, Ctrl and Alt held right now, as KeyCombo mods bits
+    mods: u8,
+    // Combos pressed and not yet released; auto-repeat doesn't press again
+    down: Vec<HotkeyId>,
+}
+
+// Matches combos against the global key stream of input_hook (evdev on
+// Linux, an event tap on macOS), where combos can't be registered with the
+// OS: under Wayland, and on macOS, whose hotkeys need the main thread. Any
+// combo "registers", since nothing here can tell whether another app uses it,
+// and the keystroke still reaches the focused window
+#[derive(Clone, Default)]
+pub struct KeyListener {
+    matcher: Arc<Mutex<Matcher>>,
+}
+
+impl KeyListener {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Presses (true) and releases (false) of registered combos caused by
+    // one key event
+    pub fn feed(&self, key: &str, pressed: bool) -> Vec<(HotkeyId, bool)> {
+        let mut matcher = self.matcher.lock().unwrap_or_else(|e| e.into_inner());
+        let Matcher { combos, mods, down } = &mut *matcher;
+        if let Some(bit) = modifier_bit(key) {
+            if pressed {
+                *mods |= bit;
+            } else {
+                *mods &= !bit;
+            }
+            return Vec::new();
+        }
+
+        let mut fired = Vec::new();
+        for (id, combo) in combos.iter() {
+            if !combo.key.eq_ignore_ascii_case(key) {
+                continue;
+            }
+            let held = down.contains(id);
+            if pressed && !held && combo.mods == *mods {
+                down.push(*id);
+                fired.push((*id, true));
+            } else if !pressed && held {
+                // Released whatever the modifiers are doing by now
+                down.retain(|d| d != id);
+                fired.push((*id, false));
+            }
+        }
+        fired
+    }
+}
+
+impl HotkeyBackend for KeyListener {
+    fn register(&mut self, id: HotkeyId, combo: &KeyCombo) -> Result<(), String> {
+        if combo.key.is_empty() || modifier_bit(&combo.key).is_some() {
+            return Err(format!("'{}' can't be a hotkey on its own", combo));
+        }
+        let mut matcher = self.matcher.lock().unwrap_or_else(|e| e.into_inner());
+        matcher.combos.retain(|(c, _)| *c != id);
+        matcher.down.retain(|d| *d != id);
+        matcher.combos.push((id, combo.clone()));
+        Ok(())
+    }
+
+    fn unregister(&mut self, id: HotkeyId) {
+        let mut matcher = self.matcher.lock().unwrap_or_else(|e| e.into_inner());
+        matcher.combos.retain(|(c, _)| *c != id);
+        matcher.down.retain(|d| *d != id);
+    }
+}
+
+// Stands in when hotkeys can't be registered and the global key stream is
+// unavailable, e.g. on Wayland without read access to /dev/input
+pub struct NoHotkeys(pub String);
+
+impl HotkeyBackend for NoHotkeys {
+    fn register(&mut self, _id: HotkeyId, _combo: &KeyCombo) -> Result<(), String> {
+        Err(self.0.clone())
+    }
+
+    fn unregister(&mut self, _id: HotkeyId) {}
+
+    fn supported(&self) -> bool {
+        false
+    }
+}
+
+// Combos registered with the OS through the global-hotkey crate: RegisterHotKey
+// on Windows and a key grab on X11. The OS refuses a combo another app holds,
+// which is reported as the conflict it is
+#[cfg(any(windows, target_os = "linux"))]
+mod registered {
+    use super::{HotkeyBackend, HotkeyId, HotkeyMessage, modifier_bit};
+    use crate::KeyCombo;
+    use global_hotkey::{
+        GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
+        hotkey::{HotKey, Modifiers},
+    };
+    use std::{
+        sync::{Arc, Mutex, mpsc},
+        thread,
+    };
+
+    enum Request {
+        Register(HotKey, mpsc::Sender<Result<(), String>>),
+        Unregister(HotKey),
+    }
+
+    // The manager stays on a thread of its own. On Windows it's a hidden
+    // window, so that thread also runs the message loop that hears the
+    // hotkeys, and is woken with a thread message when a request comes in
+    pub struct GlobalHotkeys {
+        requests: mpsc::Sender<Request>,
+        // The combo each hotkey holds, to tell events and clashes apart
+        registered: Arc<Mutex<Vec<(HotkeyId, HotKey)>>>,
+        // The manager's thread, woken by its id on Windows
+        thread_id: u32,
+    }
+
+    // KeyCombo names mostly match the crate's; the media keys don't
+    fn hotkey(combo: &KeyCombo) -> Result<HotKey, String> {
+        if combo.key.is_empty() || modifier_bit(&combo.key).is_some() {
+            return Err(format!("'{}' can't be a hotkey on its own", combo));
+        }
+        let key = match combo.key.as_str() {
+            "MediaNext" => "MediaTrackNext",
+            "MediaPrev" => "MediaTrackPrevious",
+            key => key,
+        };
+        let code = key
+            .parse::<HotKey>()
+            .map_err(|_| format!("'{}' can't be a global hotkey", combo.key))?
+            .key;
+        let mut mods = Modifiers::empty();
+        for (bit, modifier) in [
+            (1, Modifiers::SHIFT),
+            (2, Modifiers::CONTROL),
+            (4, Modifiers::ALT),
+        ] {
+            if combo.mods & bit != 0 {
+                mods |= modifier;
+            }
+        }
+        Ok(HotKey::new(Some(mods), code))
+    }
+
+    fn handle(manager: &GlobalHotKeyManager, request: Request) {
+        match request {
+            Request::Register(hotkey, reply) => {
+                let result = manager.register(hotkey).map_err(|e| match e {
+                    global_hotkey::Error::AlreadyRegistered(_) => {
+                        "already in use by another application".to_string()
+                    }
+                    e => e.to_string(),
+                });
+                let _ = reply.send(result);
+            }
+            Request::Unregister(hotkey) => {
+                let _ = manager.unregister(hotkey);
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetCurrentThreadId() -> u32;
+    }
+
+    #[cfg(windows)]
+    fn current_thread_id() -> u32 {
+        unsafe { GetCurrentThreadId() }
+    }
+
+    #[cfg(not(windows))]
+    fn current_thread_id() -> u32 {
+        0
+    }
+
+    #[cfg(windows)]
+    fn serve(manager: GlobalHotKeyManager, requests: mpsc::Receiver<Request>) {
+        use windows::Win32::{
+            Foundation::HWND,
+            UI::WindowsAndMessaging::{
+                DispatchMessageW, GetMessageW, MSG, TranslateMessage, WM_APP,
+            },
+        };
+        let mut msg = MSG::default();
+        unsafe {
+            while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                if msg.hwnd == HWND::default() && msg.message == WM_APP {
+                    for request in requests.try_iter() {
+                        handle(&manager, request);
+                    }
+                } else {
+                    TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+        }
+    }
+
+    // The crate has a thread of its own reading X events
+    #[cfg(not(windows))]
+    fn serve(manager: GlobalHotKeyManager, requests: mpsc::Receiver<Request>) {
+        for request in requests {
+            handle(&manager, request);
+        }
+    }
+
+    impl GlobalHotkeys {
+        // Presses and releases go to events. Fails where combos can't be
+        // registered: a key grab only works in an X11 session, not under
+        // Wayland, even with XWayland around
+        pub fn start(events: mpsc::Sender<HotkeyMessage>) -> Result<Self, String> {
+            #[cfg(target_os = "linux")]
+            {
+                if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+                    return Err("combos can only be registered in an X11 session".to_string());
+                }
+                x11rb::connect(None).map_err(|e| format!("no X11 display: {}", e))?;
+            }
+
+            let registered: Arc<Mutex<Vec<(HotkeyId, HotKey)>>> = Arc::default();
+            let owners = Arc::clone(&registered);
+            GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+                let owners = owners.lock().unwrap_or_else(|e| e.into_inner());
+                for (id, _) in owners.iter().filter(|(_, hotkey)| hotkey.id() == event.id) {
+                    let pressed = event.state == HotKeyState::Pressed;
+                    let _ = events.send(HotkeyMessage::Key(*id, pressed));
+                }
+            }));
+
+            let (requests, incoming) = mpsc::channel();
+            let (ready_tx, ready_rx) = mpsc::channel();
+            thread::spawn(move || match GlobalHotKeyManager::new() {
+                Ok(manager) => {
+                    let _ = ready_tx.send(Ok(current_thread_id()));
+                    serve(manager, incoming);
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e.to_string()));
+                }
+            });
+            let thread_id = ready_rx.recv().map_err(|e| e.to_string())??;
+            Ok(Self {
+                requests,
+                registered,
+                thread_id,
+            })
+        }
+
+        fn send(&self, request: Request) {
+            let _ = self.requests.send(request);
+            #[cfg(windows)]
+            unsafe {
+                use windows::Win32::{
+                    Foundation::{LPARAM, WPARAM},
+                    UI::WindowsAndMessaging::{PostThreadMessageW, WM_APP},
+                };
+                let _ = PostThreadMessageW(self.thread_id, WM_APP, WPARAM(0), LPARAM(0));
+            }
+        }
+    }
+
+    impl HotkeyBackend for GlobalHotkeys {
+        fn register(&mut self, id: HotkeyId, combo: &KeyCombo) -> Result<(), String> {
+            let hotkey = hotkey(combo)?;
+            let owner = self
+                .registered
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .find(|(other, held)| *other != id && *held == hotkey)
+                .map(|(other, _)| *other);
+            if let Some(other) = owner {
+                return Err(format!("already BClicker's {} hotkey", other.name()));
+            }
+            let (reply, result) = mpsc::channel();
+            self.send(Request::Register(hotkey, reply));
+            result
+                .recv()
+                .map_err(|_| "the hotkey thread has stopped".to_string())??;
+            self.registered
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push((id, hotkey));
+            Ok(())
+        }
+
+        fn unregister(&mut self, id: HotkeyId) {
+            let removed = {
+                let mut registered = self.registered.lock().unwrap_or_else(|e| e.into_inner());
+                let at = registered.iter().position(|(held, _)| *held == id);
+                at.map(|at| registered.remove(at).1)
+            };
+            if let Some(hotkey) = removed {
+                self.send(Request::Unregister(hotkey));
+            }
+        }
+    }
+}
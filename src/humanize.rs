@@ -0,0 +1,97 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Normal sample via the Box-Muller transform, clamped to a tenth of the mean so
+// outliers never produce a near-zero or negative interval
+pub fn gaussian_micros(rng: &mut impl Rng, mean: f64, std_dev: f64) -> Duration {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    Duration::from_micros((mean + z * std_dev).max(mean * 0.1) as u64)
+}
+
+// Presets that mimic real manual clicking techniques
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HumanPattern {
+    Jitter,
+    Butterfly,
+    Drag,
+}
+
+struct PatternSpec {
+    // Rate between clicks inside a burst
+    burst_cps: f64,
+    // Interval variance as a percentage of the mean
+    variance_percent: f64,
+    // Clicks per burst; 1 means a steady stream without pauses
+    burst_size: u64,
+    burst_pause_ms: f64,
+}
+
+impl HumanPattern {
+    pub const ALL: [HumanPattern; 3] = [
+        HumanPattern::Jitter,
+        HumanPattern::Butterfly,
+        HumanPattern::Drag,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HumanPattern::Jitter => "Jitter click",
+            HumanPattern::Butterfly => "Butterfly click",
+            HumanPattern::Drag => "Drag click",
+        }
+    }
+
+    fn spec(self) -> PatternSpec {
+        match self {
+            // Tensed forearm vibration: a fast but uneven single stream
+            HumanPattern::Jitter => PatternSpec {
+                burst_cps: 12.0,
+                variance_percent: 20.0,
+                burst_size: 1,
+                burst_pause_ms: 0.0,
+            },
+            // Two fingers alternating on one button: tight pairs of clicks
+            HumanPattern::Butterfly => PatternSpec {
+                burst_cps: 60.0,
+                variance_percent: 25.0,
+                burst_size: 2,
+                burst_pause_ms: 95.0,
+            },
+            // Finger dragged across the button: long fast bursts, then a reset
+            HumanPattern::Drag => PatternSpec {
+                burst_cps: 40.0,
+                variance_percent: 30.0,
+                burst_size: 15,
+                burst_pause_ms: 300.0,
+            },
+        }
+    }
+
+    // Interval after the click with the given zero-based index
+    pub fn next_interval(self, rng: &mut impl Rng, click_index: u64) -> Duration {
+        let spec = self.spec();
+        let ends_burst = spec.burst_size > 1 && (click_index + 1).is_multiple_of(spec.burst_size);
+        let mean = if ends_burst {
+            spec.burst_pause_ms * 1000.0
+        } else {
+            1_000_000.0 / spec.burst_cps
+        };
+        gaussian_micros(rng, mean, mean * spec.variance_percent / 100.0)
+    }
+
+    // Long-run average rate including burst pauses
+    pub fn average_cps(self) -> f64 {
+        let spec = self.spec();
+        let burst_secs = (spec.burst_size - 1) as f64 / spec.burst_cps
+            + if spec.burst_size > 1 {
+                spec.burst_pause_ms / 1000.0
+            } else {
+                1.0 / spec.burst_cps
+            };
+        spec.burst_size as f64 / burst_secs
+    }
+}
@@ -0,0 +1,134 @@
+use fluent_bundle::{FluentResource, concurrent::FluentBundle};
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    OnceLock,
+    atomic::{AtomicUsize, Ordering},
+};
+
+pub use fluent_bundle::FluentArgs;
+
+// Languages of the TUI and notifications; console output stays English
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    pub fn locale(self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::Spanish => "es",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en-US.ftl"),
+            Language::Spanish => include_str!("../locales/es.ftl"),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&l| l == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    // From LC_ALL, LC_MESSAGES or LANG, e.g. "es_ES.UTF-8"; English otherwise
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|lang| {
+                let code = &lang.locale()[..2];
+                locale.starts_with(code)
+                    && !locale[code.len()..].starts_with(|c: char| c.is_ascii_alphabetic())
+            })
+            .unwrap_or(Language::English)
+    }
+}
+
+// Each language's own name, so the setting can be found without reading it
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        })
+    }
+}
+
+// Indexed like Language::ALL
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    BUNDLES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|lang| {
+                let locale = lang.locale().parse().unwrap_or_default();
+                let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+                // Terminals draw the bidi isolation marks as stray characters
+                bundle.set_use_isolating(false);
+                // A syntax error drops only the broken message, which then
+                // falls back to English
+                let resource = FluentResource::try_new(lang.source().to_string())
+                    .unwrap_or_else(|(resource, _)| resource);
+                let _ = bundle.add_resource(resource);
+                bundle
+            })
+            .collect()
+    })
+}
+
+pub fn set_language(lang: Language) {
+    let i = Language::ALL.iter().position(|&l| l == lang).unwrap_or(0);
+    CURRENT.store(i, Ordering::SeqCst);
+}
+
+pub fn language() -> Language {
+    Language::ALL[CURRENT.load(Ordering::SeqCst)]
+}
+
+// Message in the current language, else English, else the id itself so a
+// missing string shows up without breaking anything
+pub fn tr(id: &str, args: Option<&FluentArgs>) -> String {
+    let bundles = bundles();
+    [CURRENT.load(Ordering::SeqCst), 0]
+        .into_iter()
+        .find_map(|i| {
+            let bundle = &bundles[i];
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(
+                bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned(),
+            )
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+// t!("status-idle") or t!("status-starting", secs = 3)
+macro_rules! t {
+    ($id:expr) => {
+        $crate::i18n::tr($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::tr($id, Some(&args))
+    }};
+}
+
+pub(crate) use t;
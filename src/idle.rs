@@ -0,0 +1,107 @@
+use crate::{pointer::Pointer, sequence::Point};
+use std::time::{Duration, Instant};
+
+// Cursor lookups go to the display server, so don't ask every UI tick
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+#[cfg(windows)]
+mod system {
+    use std::{mem::size_of, time::Duration};
+
+    #[repr(C)]
+    struct LastInputInfo {
+        size: u32,
+        time: u32,
+    }
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn GetLastInputInfo(info: *mut LastInputInfo) -> i32;
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetTickCount() -> u32;
+    }
+
+    // Time since the last keyboard or mouse input anywhere on the desktop
+    pub fn idle_time() -> Option<Duration> {
+        let mut info = LastInputInfo {
+            size: size_of::<LastInputInfo>() as u32,
+            time: 0,
+        };
+        unsafe {
+            if GetLastInputInfo(&mut info) == 0 {
+                return None;
+            }
+            let elapsed = GetTickCount().wrapping_sub(info.time);
+            Some(Duration::from_millis(elapsed as u64))
+        }
+    }
+}
+
+// No portable system-wide input timestamp elsewhere; cursor motion stands in
+#[cfg(not(windows))]
+mod system {
+    use std::time::Duration;
+
+    pub fn idle_time() -> Option<Duration> {
+        None
+    }
+}
+
+// Tracks how long the user has been away from mouse and keyboard
+pub struct IdleMonitor {
+    pointer: Pointer,
+    last_position: Option<Point>,
+    last_activity: Instant,
+    last_sample: Instant,
+    // Set while clicking was started by the idle trigger rather than the user
+    pub triggered: bool,
+}
+
+impl IdleMonitor {
+    pub fn new() -> Self {
+        let pointer = Pointer::new();
+        Self {
+            last_position: pointer.position(),
+            pointer,
+            last_activity: Instant::now(),
+            last_sample: Instant::now(),
+            triggered: false,
+        }
+    }
+
+    pub fn note_activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    pub fn idle_for(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
+    // Returns true when user input was seen since the last sample. Injected
+    // input also resets the system input timer, so while clicking only cursor
+    // motion counts (watch_system = false)
+    pub fn poll(&mut self, watch_system: bool) -> bool {
+        if self.last_sample.elapsed() < SAMPLE_INTERVAL {
+            return false;
+        }
+        self.last_sample = Instant::now();
+
+        let position = self.pointer.position();
+        let mut active = position != self.last_position;
+        self.last_position = position;
+
+        if watch_system {
+            if let Some(idle) = system::idle_time() {
+                active |= idle < self.last_activity.elapsed();
+            }
+        }
+
+        if active {
+            self.note_activity();
+        }
+        active
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{
+    HotkeyState,
+    chord::TapCounter,
+    hotkeys::{HotkeyMessage, KeyListener},
+    mouse_trigger::SideButton,
+};
+use std::{
+    sync::{Arc, Mutex, atomic::AtomicBool, mpsc},
+    thread,
+};
+
+// Global input seen by the side-button and tap-chord triggers
+enum InputEvent {
+    // Press (true) or release (false) of a side button, from any mouse
+    SideButton(SideButton, bool),
+    // Press or release of a key, by its keycodes name
+    Key(String, bool),
+}
+
+// Low-level hooks; they run on the message loop of the installing thread
+#[cfg(windows)]
+mod native {
+    use super::InputEvent;
+    use crate::{keycodes, mouse_trigger::SideButton};
+    use std::{
+        sync::{
+            OnceLock,
+            mpsc::{self, Sender},
+        },
+        thread,
+    };
+    use windows::Win32::{
+        Foundation::{HMODULE, HWND, LPARAM, LRESULT, WPARAM},
+        UI::WindowsAndMessaging::{
+            CallNextHookEx, GetMessageW, HHOOK, KBDLLHOOKSTRUCT, LLKHF_INJECTED, MSG,
+            MSLLHOOKSTRUCT, SetWindowsHookExW, WH_KEYBOARD_LL, WH_MOUSE_LL, WM_KEYDOWN, WM_KEYUP,
+            WM_SYSKEYDOWN, WM_SYSKEYUP, WM_XBUTTONDOWN, WM_XBUTTONUP,
+        },
+    };
+
+    // Hook procedures take no user data, so the sender is global
+    static EVENTS: OnceLock<Sender<InputEvent>> = OnceLock::new();
+
+    fn send(event: InputEvent) {
+        if let Some(events) = EVENTS.get() {
+            let _ = events.send(event);
+        }
+    }
+
+    unsafe extern "system" fn mouse_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        unsafe {
+            let message = wparam.0 as u32;
+            if code >= 0 && (message == WM_XBUTTONDOWN || message == WM_XBUTTONUP) {
+                let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+                // The high word of mouseData names the X button
+                let button = match info.mouseData >> 16 {
+                    1 => Some(SideButton::Mouse4),
+                    2 => Some(SideButton::Mouse5),
+                    _ => None,
+                };
+                if let Some(button) = button {
+                    send(InputEvent::SideButton(button, message == WM_XBUTTONDOWN));
+                }
+            }
+            CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+        }
+    }
+
+    unsafe extern "system" fn keyboard_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        unsafe {
+            let pressed = match wparam.0 as u32 {
+                WM_KEYDOWN | WM_SYSKEYDOWN => Some(true),
+                WM_KEYUP | WM_SYSKEYUP => Some(false),
+                _ => None,
+            };
+            let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+            // Keys typed by the engine itself aren't the user's
+            let injected = info.flags.0 & LLKHF_INJECTED.0 != 0;
+            if code >= 0 && !injected {
+                if let (Some(pressed), Some(name)) = (pressed, keycodes::vk_name(info.vkCode)) {
+                    send(InputEvent::Key(name, pressed));
+                }
+            }
+            CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+        }
+    }
+
+    pub fn listen(events: Sender<InputEvent>) -> Result<(), String> {
+        EVENTS
+            .set(events)
+            .map_err(|_| "input hooks already installed".to_string())?;
+        let (ready_tx, ready_rx) = mpsc::channel();
+        thread::spawn(move || unsafe {
+            let mut installed =
+                SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook), HMODULE::default(), 0).map(|_| ());
+            if installed.is_ok() {
+                installed =
+                    SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), HMODULE::default(), 0)
+                        .map(|_| ());
+            }
+            let ok = installed.is_ok();
+            let _ = ready_tx.send(installed.map_err(|e| e.to_string()));
+            if ok {
+                let mut msg = MSG::default();
+                while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {}
+            }
+        });
+        ready_rx.recv().map_err(|e| e.to_string())?
+    }
+}
+
+// Reads every evdev device for side buttons and keys
+#[cfg(target_os = "linux")]
+mod native {
+    use super::InputEvent;
+    use crate::{keycodes, mouse_trigger::SideButton};
+    use std::{
+        fs::{self, File},
+        io::Read,
+        mem::size_of,
+        sync::mpsc::Sender,
+        thread,
+    };
+
+    const EV_KEY: u16 = 0x01;
+    const BTN_SIDE: u16 = 0x113;
+    const BTN_EXTRA: u16 = 0x114;
+    // struct input_event: a timeval of two longs, then type, code and value
+    const TIMEVAL_SIZE: usize = 2 * size_of::<usize>();
+    const EVENT_SIZE: usize = TIMEVAL_SIZE + 8;
+
+    fn read_events(mut device: File, events: Sender<InputEvent>) {
+        let mut buf = [0u8; EVENT_SIZE];
+        while device.read_exact(&mut buf).is_ok() {
+            let field = |at: usize| u16::from_ne_bytes([buf[at], buf[at + 1]]);
+            let (kind, code) = (field(TIMEVAL_SIZE), field(TIMEVAL_SIZE + 2));
+            let value = i32::from_ne_bytes(buf[TIMEVAL_SIZE + 4..].try_into().unwrap());
+            // value 2 is autorepeat
+            if kind != EV_KEY || value == 2 {
+                continue;
+            }
+            let pressed = value == 1;
+            let event = match code {
+                BTN_SIDE => InputEvent::SideButton(SideButton::Mouse4, pressed),
+                BTN_EXTRA => InputEvent::SideButton(SideButton::Mouse5, pressed),
+                code => match keycodes::evdev_name(code) {
+                    Some(name) => InputEvent::Key(name, pressed),
+                    None => continue,
+                },
+            };
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+    }
+
+    pub fn listen(events: Sender<InputEvent>) -> Result<(), String> {
+        let devices: Vec<File> = fs::read_dir("/dev/input")
+            .map_err(|e| format!("could not list /dev/input: {}", e))?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("event"))
+            .filter_map(|entry| File::open(entry.path()).ok())
+            .collect();
+        if devices.is_empty() {
+            return Err("no readable /dev/input devices (add your user to the input group)".into());
+        }
+        for device in devices {
+            let events = events.clone();
+            thread::spawn(move || read_events(device, events));
+        }
+        Ok(())
+    }
+}
+
+// An event tap through rdev, which needs BClicker (or the terminal running
+// it) allowed under Accessibility. macOS reports no side buttons through it,
+// so only keys come from here
+#[cfg(target_os = "macos")]
+mod native {
+    use super::InputEvent;
+    use crate::keycodes;
+    use rdev::EventType;
+    use std::{
+        sync::mpsc::{self, Sender},
+        thread,
+        time::Duration,
+    };
+
+    // listen only returns when the tap couldn't be made
+    const TAP_TIMEOUT: Duration = Duration::from_millis(500);
+
+    pub fn listen(events: Sender<InputEvent>) -> Result<(), String> {
+        let (failed_tx, failed_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let listened = rdev::listen(move |event| {
+                let (key, pressed) = match event.event_type {
+                    EventType::KeyPress(key) => (key, true),
+                    EventType::KeyRelease(key) => (key, false),
+                    _ => return,
+                };
+                if let Some(name) = keycodes::rdev_name(key) {
+                    let _ = events.send(InputEvent::Key(name, pressed));
+                }
+            });
+            if let Err(e) = listened {
+                let _ = failed_tx.send(format!("{:?}", e));
+            }
+        });
+        match failed_rx.recv_timeout(TAP_TIMEOUT) {
+            Ok(e) => Err(format!(
+                "could not read global keys ({}); allow BClicker under Privacy & Security > \
+                 Accessibility",
+                e
+            )),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+mod native {
+    use super::InputEvent;
+    use std::sync::mpsc::Sender;
+
+    pub fn listen(_events: Sender<InputEvent>) -> Result<(), String> {
+        Err("global hotkeys and triggers need Windows, Linux or macOS".to_string())
+    }
+}
+
+// Applies side-button and key events to the clicker according to the
+// triggers in the shared hotkey state, so the TUI can change them at runtime,
+// and passes keys through the hotkey listener to the hotkey thread
+pub fn spawn(
+    hotkey_state: Arc<Mutex<HotkeyState>>,
+    auto_clicker_running: Arc<AtomicBool>,
+    hotkeys: KeyListener,
+    hotkey_events: mpsc::Sender<HotkeyMessage>,
+) -> Result<thread::JoinHandle<()>, String> {
+    let (tx, rx) = mpsc::channel();
+    native::listen(tx)?;
+
+    Ok(thread::spawn(move || {
+        let mut taps = TapCounter::default();
+        for event in rx {
+            let (mouse_trigger, tap_trigger) = {
+                let state = hotkey_state.lock().unwrap_or_else(|e| e.into_inner());
+                (state.mouse_trigger, state.tap_trigger.clone())
+            };
+            match event {
+                InputEvent::SideButton(button, pressed) => {
+                    if let Some(trigger) = mouse_trigger.filter(|t| t.button == button) {
+                        trigger.mode.apply(pressed, &auto_clicker_running);
+                    }
+                }
+                InputEvent::Key(key, pressed) => {
+                    for (id, pressed) in hotkeys.feed(&key, pressed) {
+                        let _ = hotkey_events.send(HotkeyMessage::Key(id, pressed));
+                    }
+                    let Some(chord) = tap_trigger else {
+                        continue;
+                    };
+                    if let Some(pressed) = taps.feed(&chord, &key, pressed) {
+                        chord.mode.apply(pressed, &auto_clicker_running);
+                    }
+                }
+            }
+        }
+    }))
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+// Slow mode intervals: one click every second up to once a day
+pub const MIN_SECS: u64 = 1;
+pub const MAX_SECS: u64 = 24 * 60 * 60;
+
+// Seconds in an interval written like 90, 90s, 20m or 1h30m
+pub fn parse(input: &str) -> Option<u64> {
+    let input = input.trim().to_ascii_lowercase();
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in input.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            's' | 'm' | 'h' if !number.is_empty() => {
+                let unit = match c {
+                    's' => 1,
+                    'm' => 60,
+                    _ => 3600,
+                };
+                total = total.checked_add(number.parse::<u64>().ok()?.checked_mul(unit)?)?;
+                number.clear();
+            }
+            ' ' => {}
+            _ => return None,
+        }
+    }
+    // A bare number means seconds
+    if !number.is_empty() {
+        if total > 0 {
+            return None;
+        }
+        total = number.parse().ok()?;
+    }
+    (MIN_SECS..=MAX_SECS).contains(&total).then_some(total)
+}
+
+// The same notation back, e.g. 1h30m
+pub fn describe(secs: u64) -> String {
+    let parts: Vec<String> = [(secs / 3600, 'h'), (secs / 60 % 60, 'm'), (secs % 60, 's')]
+        .into_iter()
+        .filter(|&(n, _)| n > 0)
+        .map(|(n, unit)| format!("{}{}", n, unit))
+        .collect();
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.concat()
+    }
+}
+
+// Countdown for the status bar: 19:42, or 1:05:09 past an hour
+pub fn clock(remaining: Duration) -> String {
+    let secs = remaining.as_secs_f64().ceil() as u64;
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
@@ -0,0 +1,801 @@
+use crate::{
+    AppEvent, BUTTON_MODES, SharedSettings, interval, scheduler::RateStatus, stats::Statistics,
+};
+use crossterm::{
+    cursor,
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEvent},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+// How long a client waits for the instance to answer
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Time between two `bclicker watch` records
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+// How often an attached client checks whether the daemon let it go
+const ATTACH_POLL: Duration = Duration::from_millis(100);
+
+pub use transport::Stream;
+
+// State a running instance reports to its command line clients; shared with
+// the engine and the TUI
+#[derive(Clone)]
+pub struct IpcFeed {
+    pub running: Arc<AtomicBool>,
+    pub paused: Arc<AtomicBool>,
+    pub countdown: Arc<AtomicU32>,
+    pub engine_settings: Arc<SharedSettings>,
+    pub stats: Arc<Mutex<Statistics>>,
+    pub rate_status: Arc<Mutex<RateStatus>>,
+    pub started: Instant,
+    // Start and stop requests go to the TUI, which owns the run
+    pub ui: mpsc::Sender<AppEvent>,
+}
+
+// Answer to a status request, one JSON object per line
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Status {
+    pub running: bool,
+    pub paused: bool,
+    // Seconds left of the start delay, 0 when there is none
+    pub countdown: u32,
+    pub cps: f64,
+    // Measured over the last second or so; 0 while stopped
+    pub achieved_cps: f64,
+    pub button: String,
+    pub profile: String,
+    pub session_clicks: u64,
+    pub total_clicks: u64,
+    pub uptime_secs: u64,
+}
+
+// A `bclicker watch` line: the status, when it was taken and the clicks
+// since the line before
+#[derive(Serialize)]
+struct WatchRecord {
+    timestamp: u64,
+    #[serde(flatten)]
+    status: Status,
+    new_clicks: u64,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = if self.paused {
+            "Paused"
+        } else if self.countdown > 0 {
+            "Starting"
+        } else if self.running {
+            "Running"
+        } else {
+            "Stopped"
+        };
+        write!(
+            f,
+            "{} │ {} CPS │ {} │ profile {} │ {} clicks this session │ up {}",
+            state,
+            self.cps,
+            self.button,
+            self.profile,
+            self.session_clicks,
+            interval::describe(self.uptime_secs)
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Control {
+    Start,
+    Stop,
+    Toggle,
+    // Switches to a custom rate, e.g. `cps 12.5`
+    SetCps(f64),
+    // Makes the profile of that name the active one, e.g. `profile Minecraft`
+    Profile(String),
+}
+
+impl Control {
+    pub fn parse(request: &str) -> Option<Self> {
+        match request {
+            "start" => Some(Control::Start),
+            "stop" => Some(Control::Stop),
+            "toggle" => Some(Control::Toggle),
+            _ => {
+                if let Some(name) = request.strip_prefix("profile ") {
+                    return Some(Control::Profile(name.trim().to_string()));
+                }
+                crate::parse_cps(request.strip_prefix("cps ")?).map(Control::SetCps)
+            }
+        }
+    }
+}
+
+// A client taking over a daemon's screen with `attach COLS ROWS`. Nothing
+// is read from this connection: keys come on a second one, `input SESSION`,
+// since a Windows pipe can't be read and written at the same time
+#[derive(Debug)]
+pub struct Attach {
+    pub stream: Stream,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+// What an attached client sends, a JSON line each
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteInput {
+    Key(KeyEvent),
+    Resize(u16, u16),
+}
+
+// Answer to a control request; changed is false when the instance was
+// already in the state asked for
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ControlReply {
+    pub running: bool,
+    pub changed: bool,
+}
+
+// A control request waiting for the TUI; an Err reply is a refusal
+#[derive(Debug)]
+pub struct ControlRequest {
+    pub control: Control,
+    pub reply: mpsc::Sender<Result<ControlReply, String>>,
+}
+
+impl IpcFeed {
+    pub fn status(&self) -> Status {
+        let settings = self.engine_settings.get();
+        let (session_clicks, total_clicks) = {
+            let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+            (stats.session_clicks, stats.total_clicks)
+        };
+        let running = self.running.load(Ordering::SeqCst);
+        Status {
+            running,
+            paused: running && self.paused.load(Ordering::SeqCst),
+            countdown: self.countdown.load(Ordering::SeqCst),
+            cps: settings.cps,
+            achieved_cps: if running {
+                self.rate_status
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .achieved_cps
+            } else {
+                0.0
+            },
+            button: BUTTON_MODES
+                .get(settings.button)
+                .copied()
+                .unwrap_or(BUTTON_MODES[0])
+                .to_string(),
+            profile: settings.profile_name.to_string(),
+            session_clicks,
+            total_clicks,
+            uptime_secs: self.started.elapsed().as_secs(),
+        }
+    }
+
+    // One request line per connection
+    fn serve(&self, stream: transport::Stream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+        let request = request.trim();
+        if let Some(size) = request.strip_prefix("attach ") {
+            return self.attach(writer, size);
+        }
+        if let Some(session) = request.strip_prefix("input ") {
+            return self.forward_input(reader, session);
+        }
+        let reply = if request == "quit" {
+            self.ui.send(AppEvent::Quit).map_err(io::Error::other)?;
+            serde_json::json!({ "quitting": true }).to_string()
+        } else if request == "status" {
+            serde_json::to_string(&self.status()).map_err(io::Error::other)?
+        } else if let Some(control) = Control::parse(request) {
+            match self.control(control) {
+                Ok(reply) => serde_json::to_string(&reply).map_err(io::Error::other)?,
+                Err(reason) => serde_json::json!({ "error": reason, "refused": true }).to_string(),
+            }
+        } else {
+            serde_json::json!({ "error": format!("unknown request '{}'", request) }).to_string()
+        };
+        writeln!(writer, "{}", reply)?;
+        writer.flush()
+    }
+
+    // Hands the connection to the TUI, which answers with the session or a
+    // refusal and then draws into it
+    fn attach(&self, mut stream: Stream, size: &str) -> io::Result<()> {
+        let mut size = size.split_whitespace().map(str::parse::<u16>);
+        let (Some(Ok(cols)), Some(Ok(rows))) = (size.next(), size.next()) else {
+            let error = serde_json::json!({ "error": "usage: attach COLS ROWS" });
+            return writeln!(stream, "{}", error);
+        };
+        // A client that stops reading mustn't hold up the TUI
+        transport::set_write_timeout(&stream, REPLY_TIMEOUT)?;
+        self.ui
+            .send(AppEvent::Attach(Attach { stream, cols, rows }))
+            .map_err(io::Error::other)
+    }
+
+    // Keys and resizes for as long as the client stays; those of a client
+    // that's been replaced are dropped by the TUI
+    fn forward_input(&self, reader: BufReader<Stream>, session: &str) -> io::Result<()> {
+        let session: u64 = session.parse().map_err(io::Error::other)?;
+        for line in reader.lines() {
+            let Ok(input) = serde_json::from_str(&line?) else {
+                continue;
+            };
+            if self.ui.send(AppEvent::Remote(session, input)).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn control(&self, control: Control) -> Result<ControlReply, String> {
+        let (reply, answer) = mpsc::channel();
+        self.ui
+            .send(AppEvent::Control(ControlRequest { control, reply }))
+            .map_err(|_| "BClicker is shutting down".to_string())?;
+        answer
+            .recv_timeout(REPLY_TIMEOUT)
+            .map_err(|_| "BClicker didn't act on the request".to_string())?
+    }
+
+    // Requests applied in order on a thread of their own, for sources that
+    // can't wait on the TUI, like a knob turning. Rates come in faster than
+    // that, so one replaced by a newer rate in the queue is skipped
+    pub fn queue(&self, source: &'static str) -> mpsc::Sender<Control> {
+        let (requests, received) = mpsc::channel();
+        let feed = self.clone();
+        thread::spawn(move || {
+            while let Ok(control) = received.recv() {
+                let mut pending = vec![control];
+                pending.extend(received.try_iter());
+                let newest_cps = pending
+                    .iter()
+                    .rposition(|control| matches!(control, Control::SetCps(_)));
+                for (i, control) in pending.into_iter().enumerate() {
+                    if matches!(control, Control::SetCps(_)) && Some(i) != newest_cps {
+                        continue;
+                    }
+                    if let Err(e) = feed.control(control) {
+                        println!("[WARNING] {}: {}", source, e);
+                    }
+                }
+            }
+        });
+        requests
+    }
+}
+
+pub enum IpcError {
+    NotRunning,
+    // The instance answered but wouldn't do what was asked
+    Refused(String),
+    Failed(String),
+}
+
+impl IpcError {
+    // For scripts and window manager bindings: 1 when there is no instance
+    // to talk to or it can't be reached, 2 when it said no
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            IpcError::Refused(_) => 2,
+            IpcError::NotRunning | IpcError::Failed(_) => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for IpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IpcError::NotRunning => write!(f, "BClicker isn't running"),
+            IpcError::Refused(e) | IpcError::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+// Keeps the endpoint open; on Unix the socket file goes when it's dropped
+pub struct IpcServer {
+    _listener: transport::Cleanup,
+}
+
+// Serves clients on their own threads until the process exits. Fails when
+// another instance already holds the endpoint
+pub fn spawn(feed: IpcFeed) -> Result<IpcServer, String> {
+    let (mut listener, cleanup) = transport::bind()?;
+    let feed = Arc::new(feed);
+    thread::spawn(move || {
+        loop {
+            match listener.accept() {
+                Ok(stream) => {
+                    let feed = Arc::clone(&feed);
+                    thread::spawn(move || {
+                        let _ = feed.serve(stream);
+                    });
+                }
+                Err(e) => {
+                    println!("[WARNING] Control socket: {}", e);
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        }
+    });
+    Ok(IpcServer { _listener: cleanup })
+}
+
+// Whether another process holds the endpoint, without asking it anything
+pub fn instance_running() -> bool {
+    transport::connect().is_ok()
+}
+
+fn connect() -> Result<Stream, IpcError> {
+    transport::connect().map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused => IpcError::NotRunning,
+        _ => IpcError::Failed(format!("could not reach BClicker: {}", e)),
+    })
+}
+
+// Sends one request to the running instance and returns its first reply,
+// with the connection for anything that follows
+fn open(request: &str) -> Result<(serde_json::Value, BufReader<Stream>), IpcError> {
+    let stream = connect()?;
+    let failed = |e: io::Error| IpcError::Failed(format!("no answer from BClicker: {}", e));
+    transport::set_timeout(&stream, Some(REPLY_TIMEOUT)).map_err(failed)?;
+    let mut reader = BufReader::new(stream.try_clone().map_err(failed)?);
+    let mut writer = stream;
+    writeln!(writer, "{}", request).map_err(failed)?;
+    writer.flush().map_err(failed)?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(failed)?;
+    let reply: serde_json::Value = serde_json::from_str(&line)
+        .map_err(|e| IpcError::Failed(format!("unreadable answer from BClicker: {}", e)))?;
+    let refused = reply.get("refused").and_then(|r| r.as_bool()) == Some(true);
+    match reply.get("error").and_then(|e| e.as_str()) {
+        Some(error) if refused => Err(IpcError::Refused(error.to_string())),
+        Some(error) => Err(IpcError::Failed(error.to_string())),
+        None => Ok((reply, reader)),
+    }
+}
+
+fn request(request: &str) -> Result<serde_json::Value, IpcError> {
+    open(request).map(|(reply, _)| reply)
+}
+
+// `bclicker status [--json]`
+pub fn run_status_cli(args: &[String]) -> Result<(), IpcError> {
+    let json = match args {
+        [] => false,
+        [flag] if flag == "--json" => true,
+        _ => {
+            return Err(IpcError::Failed(
+                "usage: bclicker status [--json]".to_string(),
+            ));
+        }
+    };
+    let reply = request("status")?;
+    let status: Status = serde_json::from_value(reply)
+        .map_err(|e| IpcError::Failed(format!("unreadable status: {}", e)))?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&status).map_err(|e| IpcError::Failed(e.to_string()))?
+        );
+    } else {
+        println!("{}", status);
+    }
+    Ok(())
+}
+
+// `bclicker watch`: a status record per second until the instance exits or
+// the reader goes away. Each record asks afresh, so a restarted instance
+// just shows up as a drop in uptime
+pub fn run_watch_cli(args: &[String]) -> Result<(), IpcError> {
+    if !args.is_empty() {
+        return Err(IpcError::Failed("usage: bclicker watch".to_string()));
+    }
+    let start = Instant::now();
+    let mut last_clicks = None;
+    for tick in 1.. {
+        let reply = match request("status") {
+            // Watched to the end rather than failed
+            Err(IpcError::NotRunning) if tick > 1 => {
+                eprintln!("[INFO] BClicker has exited");
+                return Ok(());
+            }
+            reply => reply?,
+        };
+        let status: Status = serde_json::from_value(reply)
+            .map_err(|e| IpcError::Failed(format!("unreadable status: {}", e)))?;
+        let new_clicks = last_clicks.map_or(0, |last| status.session_clicks.saturating_sub(last));
+        last_clicks = Some(status.session_clicks);
+        let record = WatchRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            status,
+            new_clicks,
+        };
+        let line = serde_json::to_string(&record).map_err(|e| IpcError::Failed(e.to_string()))?;
+        let mut stdout = io::stdout().lock();
+        // A closed pipe, e.g. `| head`, ends the watch quietly
+        if writeln!(stdout, "{}", line)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            return Ok(());
+        }
+        drop(stdout);
+        // Paced from the start, so the records don't drift
+        thread::sleep((start + WATCH_INTERVAL * tick).saturating_duration_since(Instant::now()));
+    }
+    Ok(())
+}
+
+// `bclicker start`, `stop` and `toggle`
+pub fn run_control_cli(verb: &str, args: &[String]) -> Result<(), IpcError> {
+    if !args.is_empty() {
+        return Err(IpcError::Failed(format!("usage: bclicker {}", verb)));
+    }
+    let reply = request(verb)?;
+    let reply: ControlReply = serde_json::from_value(reply)
+        .map_err(|e| IpcError::Failed(format!("unreadable answer: {}", e)))?;
+    let message = match (reply.running, reply.changed) {
+        (true, true) => "Clicking started",
+        (true, false) => "Already clicking",
+        (false, true) => "Clicking stopped",
+        (false, false) => "Already stopped",
+    };
+    println!("[INFO] {}", message);
+    Ok(())
+}
+
+// `bclicker quit`: closes the running instance the way its quit key does
+pub fn run_quit_cli(args: &[String]) -> Result<(), IpcError> {
+    if !args.is_empty() {
+        return Err(IpcError::Failed("usage: bclicker quit".to_string()));
+    }
+    request("quit")?;
+    println!("[INFO] BClicker is closing");
+    Ok(())
+}
+
+// `bclicker attach`: shows the daemon's TUI in this terminal until its quit
+// key detaches or the daemon exits. Clicking carries on either way
+pub fn run_attach_cli(args: &[String]) -> Result<(), IpcError> {
+    if !args.is_empty() {
+        return Err(IpcError::Failed("usage: bclicker attach".to_string()));
+    }
+    let failed = |e: io::Error| IpcError::Failed(e.to_string());
+    let (cols, rows) = terminal::size().map_err(failed)?;
+    let (reply, mut screen) = open(&format!("attach {} {}", cols, rows))?;
+    let session = reply["session"]
+        .as_u64()
+        .ok_or_else(|| IpcError::Failed("unreadable answer from BClicker".to_string()))?;
+    transport::set_timeout(screen.get_ref(), None).map_err(failed)?;
+    let mut input = connect()?;
+    writeln!(input, "input {}", session).map_err(failed)?;
+
+    terminal::enable_raw_mode().map_err(failed)?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture).map_err(failed)?;
+    // The daemon's frames go straight to this terminal; it closes the
+    // connection to let go
+    let released = Arc::new(AtomicBool::new(false));
+    let copier = {
+        let released = Arc::clone(&released);
+        thread::spawn(move || {
+            let mut buffer = [0u8; 8192];
+            let mut stdout = io::stdout();
+            while let Ok(read @ 1..) = screen.read(&mut buffer) {
+                if stdout
+                    .write_all(&buffer[..read])
+                    .and_then(|_| stdout.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            released.store(true, Ordering::SeqCst);
+        })
+    };
+    let forwarded = (|| -> io::Result<()> {
+        while !released.load(Ordering::SeqCst) {
+            if !event::poll(ATTACH_POLL)? {
+                continue;
+            }
+            let message = match event::read()? {
+                Event::Key(key) => RemoteInput::Key(key),
+                Event::Resize(cols, rows) => RemoteInput::Resize(cols, rows),
+                _ => continue,
+            };
+            writeln!(input, "{}", serde_json::to_string(&message)?)?;
+        }
+        Ok(())
+    })();
+    let restored = terminal::disable_raw_mode().and_then(|_| {
+        execute!(
+            stdout,
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            cursor::Show
+        )
+    });
+    drop(input);
+    if released.load(Ordering::SeqCst) {
+        let _ = copier.join();
+    }
+    forwarded.and(restored).map_err(failed)?;
+    if instance_running() {
+        println!("[INFO] Detached; BClicker keeps running. `bclicker attach` shows it again");
+    } else {
+        println!("[INFO] BClicker has exited");
+    }
+    Ok(())
+}
+
+// A Unix socket readable only by its owner, in the runtime directory
+#[cfg(unix)]
+mod transport {
+    use std::{
+        fs, io,
+        os::unix::net::{UnixListener, UnixStream},
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    pub type Stream = UnixStream;
+
+    fn path() -> PathBuf {
+        match std::env::var_os("XDG_RUNTIME_DIR") {
+            Some(dir) => PathBuf::from(dir).join("bclicker.sock"),
+            None => {
+                std::env::temp_dir().join(format!("bclicker-{}.sock", unsafe { libc::getuid() }))
+            }
+        }
+    }
+
+    // Creates the socket as 0600 from the start; a chmod after bind would
+    // leave a moment in which other users could connect
+    fn bind_private(path: &Path) -> io::Result<UnixListener> {
+        let old = unsafe { libc::umask(0o177) };
+        let listener = UnixListener::bind(path);
+        unsafe { libc::umask(old) };
+        listener
+    }
+
+    pub struct Listener(UnixListener);
+
+    impl Listener {
+        pub fn accept(&mut self) -> io::Result<Stream> {
+            self.0.accept().map(|(stream, _)| stream)
+        }
+    }
+
+    pub struct Cleanup(PathBuf);
+
+    impl Drop for Cleanup {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    pub fn bind() -> Result<(Listener, Cleanup), String> {
+        let path = path();
+        let listener = match bind_private(&path) {
+            Ok(listener) => listener,
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse => {
+                if UnixStream::connect(&path).is_ok() {
+                    return Err(format!(
+                        "another instance is listening on {}",
+                        path.display()
+                    ));
+                }
+                // Left behind by an instance that didn't exit cleanly
+                let _ = fs::remove_file(&path);
+                bind_private(&path).map_err(|e| format!("{}: {}", path.display(), e))?
+            }
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        Ok((Listener(listener), Cleanup(path)))
+    }
+
+    pub fn connect() -> io::Result<Stream> {
+        UnixStream::connect(path())
+    }
+
+    pub fn set_timeout(stream: &Stream, timeout: Option<Duration>) -> io::Result<()> {
+        stream.set_read_timeout(timeout)
+    }
+
+    pub fn set_write_timeout(stream: &Stream, timeout: Duration) -> io::Result<()> {
+        stream.set_write_timeout(Some(timeout))
+    }
+}
+
+// A named pipe that refuses remote clients, one per user
+#[cfg(windows)]
+mod transport {
+    use std::{
+        ffi::c_void,
+        fs::{File, OpenOptions},
+        io,
+        os::windows::io::{FromRawHandle, RawHandle},
+        thread,
+        time::Duration,
+    };
+
+    pub type Stream = File;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn CreateNamedPipeW(
+            name: *const u16,
+            open_mode: u32,
+            pipe_mode: u32,
+            max_instances: u32,
+            out_buffer: u32,
+            in_buffer: u32,
+            default_timeout: u32,
+            security: *const c_void,
+        ) -> isize;
+        fn ConnectNamedPipe(pipe: isize, overlapped: *mut c_void) -> i32;
+    }
+
+    const PIPE_ACCESS_DUPLEX: u32 = 0x3;
+    const FILE_FLAG_FIRST_PIPE_INSTANCE: u32 = 0x0008_0000;
+    const PIPE_REJECT_REMOTE_CLIENTS: u32 = 0x8;
+    const PIPE_UNLIMITED_INSTANCES: u32 = 255;
+    const INVALID_HANDLE_VALUE: isize = -1;
+    const ERROR_PIPE_BUSY: i32 = 231;
+    const ERROR_PIPE_CONNECTED: i32 = 535;
+    const ERROR_ACCESS_DENIED: i32 = 5;
+
+    fn name() -> String {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        format!(r"\\.\pipe\bclicker-{}", user)
+    }
+
+    // One pipe instance per client; the first one fails if another process
+    // already owns the name
+    fn create(first: bool) -> io::Result<isize> {
+        let name: Vec<u16> = name().encode_utf16().chain(Some(0)).collect();
+        let open_mode = PIPE_ACCESS_DUPLEX
+            | if first {
+                FILE_FLAG_FIRST_PIPE_INSTANCE
+            } else {
+                0
+            };
+        let pipe = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                open_mode,
+                PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                4096,
+                4096,
+                0,
+                std::ptr::null(),
+            )
+        };
+        if pipe == INVALID_HANDLE_VALUE {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(pipe)
+        }
+    }
+
+    pub struct Listener {
+        next: Option<isize>,
+    }
+
+    impl Listener {
+        pub fn accept(&mut self) -> io::Result<Stream> {
+            let pipe = match self.next.take() {
+                Some(pipe) => pipe,
+                None => create(false)?,
+            };
+            let file = unsafe { File::from_raw_handle(pipe as RawHandle) };
+            if unsafe { ConnectNamedPipe(pipe, std::ptr::null_mut()) } == 0 {
+                let error = io::Error::last_os_error();
+                if error.raw_os_error() != Some(ERROR_PIPE_CONNECTED) {
+                    return Err(error);
+                }
+            }
+            // The next client finds an instance waiting while this one is served
+            self.next = create(false).ok();
+            Ok(file)
+        }
+    }
+
+    // Pipes vanish with their last handle
+    pub struct Cleanup;
+
+    pub fn bind() -> Result<(Listener, Cleanup), String> {
+        match create(true) {
+            Ok(pipe) => Ok((Listener { next: Some(pipe) }, Cleanup)),
+            Err(e) if e.raw_os_error() == Some(ERROR_ACCESS_DENIED) => {
+                Err(format!("another instance is listening on {}", name()))
+            }
+            Err(e) => Err(format!("{}: {}", name(), e)),
+        }
+    }
+
+    pub fn connect() -> io::Result<Stream> {
+        // Every instance busy means a client is between connecting and the
+        // server making the next instance; that takes moments
+        for _ in 0..20 {
+            match OpenOptions::new().read(true).write(true).open(name()) {
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                    thread::sleep(Duration::from_millis(50))
+                }
+                result => return result,
+            }
+        }
+        OpenOptions::new().read(true).write(true).open(name())
+    }
+
+    // Pipe reads and writes can't time out without overlapped I/O; the
+    // instance answers straight away or not at all
+    pub fn set_timeout(_stream: &Stream, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn set_write_timeout(_stream: &Stream, _timeout: Duration) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// No local endpoint elsewhere; status and control need Unix or Windows
+#[cfg(not(any(unix, windows)))]
+mod transport {
+    use std::{io, time::Duration};
+
+    pub type Stream = std::net::TcpStream;
+
+    pub struct Listener;
+
+    impl Listener {
+        pub fn accept(&mut self) -> io::Result<Stream> {
+            Err(io::Error::from(io::ErrorKind::Unsupported))
+        }
+    }
+
+    pub struct Cleanup;
+
+    pub fn bind() -> Result<(Listener, Cleanup), String> {
+        Err("not supported on this platform".to_string())
+    }
+
+    pub fn connect() -> io::Result<Stream> {
+        Err(io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    pub fn set_timeout(_stream: &Stream, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn set_write_timeout(_stream: &Stream, _timeout: Duration) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    CoordinateSpace, MAX_CPS, MIN_CPS, backend::ClickBackend, dpi, parse_cps, sequence::Point,
+};
+use enigo::MouseButton;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+// Jobs waiting at once; more are refused rather than piling up unseen
+const MAX_QUEUED_JOBS: usize = 100;
+
+// One-off task: N clicks at rate R, optionally at point P
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClickJob {
+    pub count: u64,
+    pub cps: f64,
+    // Physical pixels once queued; feeders convert logical input first
+    pub at: Option<Point>,
+    pub button: MouseButton,
+}
+
+impl Default for ClickJob {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            cps: 10.0,
+            at: None,
+            button: MouseButton::Left,
+        }
+    }
+}
+
+fn parse_button(name: &str) -> Option<MouseButton> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "left" => Some(MouseButton::Left),
+        "right" => Some(MouseButton::Right),
+        "middle" => Some(MouseButton::Middle),
+        _ => None,
+    }
+}
+
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+        _ => "left",
+    }
+}
+
+fn valid_cps(cps: f64) -> Option<f64> {
+    Some(cps).filter(|&c| (MIN_CPS..=MAX_CPS).contains(&c))
+}
+
+impl ClickJob {
+    // "50x@20cps", with an optional "(right)" or "(middle)" and "at X,Y",
+    // e.g. "50x@20cps(right) at 800,600"
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (run, at) = match input.split_once(" at ") {
+            Some((run, at)) => (
+                run,
+                Some(Point::parse(at).ok_or_else(|| format!("bad point '{}'", at.trim()))?),
+            ),
+            None => (input, None),
+        };
+        let (run, button) = match run.split_once('(') {
+            Some((run, button)) => {
+                let button = button
+                    .trim()
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("missing ')' in '{}'", input))?;
+                let button =
+                    parse_button(button).ok_or_else(|| format!("unknown button '{}'", button))?;
+                (run, button)
+            }
+            None => (run, MouseButton::Left),
+        };
+        let (count, rate) = run
+            .split_once('@')
+            .ok_or_else(|| format!("expected COUNTx@RATEcps, got '{}'", input.trim()))?;
+        let count = count
+            .trim()
+            .strip_suffix(['x', 'X'])
+            .and_then(|n| n.trim().parse().ok())
+            .filter(|&n: &u64| n > 0)
+            .ok_or_else(|| format!("bad click count in '{}'", input.trim()))?;
+        let cps = rate
+            .trim()
+            .to_ascii_lowercase()
+            .strip_suffix("cps")
+            .and_then(parse_cps)
+            .and_then(valid_cps)
+            .ok_or_else(|| format!("rate must be between {} and {} CPS", MIN_CPS, MAX_CPS))?;
+        Ok(Self {
+            count,
+            cps,
+            at,
+            button,
+        })
+    }
+
+    // Command line flags of `bclicker click`
+    pub fn from_args(args: &[String], usage: &str) -> Result<Self, String> {
+        let mut job = ClickJob::default();
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{} needs a value\n{}", flag, usage))?;
+            match flag.as_str() {
+                "--count" => {
+                    job.count = value
+                        .parse()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .ok_or("--count must be a positive number")?
+                }
+                "--cps" => {
+                    job.cps = parse_cps(value)
+                        .and_then(valid_cps)
+                        .ok_or(format!("--cps must be between {} and {}", MIN_CPS, MAX_CPS))?
+                }
+                "--at" => job.at = Some(Point::parse(value).ok_or("--at expects X,Y")?),
+                "--button" => {
+                    job.button =
+                        parse_button(value).ok_or("--button must be left, right or middle")?
+                }
+                _ => return Err(format!("unknown option {}\n{}", flag, usage)),
+            }
+        }
+        Ok(job)
+    }
+
+    // Scales a point given in the configured coordinate space to pixels
+    pub fn in_physical(self, space: CoordinateSpace) -> Self {
+        Self {
+            at: self.at.map(|p| match space {
+                CoordinateSpace::Physical => p,
+                CoordinateSpace::Logical => dpi::to_physical(p),
+            }),
+            ..self
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.cps)
+    }
+
+    pub fn perform(&self, backend: &mut dyn ClickBackend) {
+        if let Some(p) = self.at {
+            backend.move_to(p.x, p.y);
+        }
+        backend.click(self.button);
+    }
+}
+
+impl std::fmt::Display for ClickJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x@{}cps", self.count, self.cps)?;
+        if self.button != MouseButton::Left {
+            write!(f, "({})", button_name(self.button))?;
+        }
+        match self.at {
+            Some(p) => write!(f, " at {}", p),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Jobs {
+    pending: VecDeque<(u64, ClickJob)>,
+    next_id: u64,
+}
+
+// Jobs the engine drains one after another, ahead of the main clicking and
+// without touching its settings. Cloned handles share one queue; the TUI
+// feeds it, and so can anything else holding a handle
+#[derive(Clone, Default)]
+pub struct JobQueue {
+    jobs: Arc<Mutex<Jobs>>,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns how many jobs are now queued, including this one
+    pub fn push(&self, job: ClickJob) -> Result<usize, String> {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        if jobs.pending.len() >= MAX_QUEUED_JOBS {
+            return Err(format!("the queue is full ({} jobs)", MAX_QUEUED_JOBS));
+        }
+        let id = jobs.next_id;
+        jobs.next_id += 1;
+        jobs.pending.push_back((id, job));
+        Ok(jobs.pending.len())
+    }
+
+    // The job being run, with an id that tells it apart from an identical
+    // job queued after a clear
+    pub fn front(&self) -> Option<(u64, ClickJob)> {
+        let jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        jobs.pending.front().copied()
+    }
+
+    // Drops the front job once the engine has run it, unless the queue was
+    // cleared in the meantime
+    pub fn finish(&self, id: u64) {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        if jobs.pending.front().is_some_and(|&(front, _)| front == id) {
+            jobs.pending.pop_front();
+        }
+    }
+
+    // Also cancels the job in progress
+    pub fn clear(&self) -> usize {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        let cleared = jobs.pending.len();
+        jobs.pending.clear();
+        cleared
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pending
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,320 @@
+use crossterm::event::{KeyCode, KeyEventState, MediaKeyCode};
+
+// Unshifted key for a shifted punctuation character, US layout, so Shift+;
+// is stored as ";" with the Shift modifier rather than as ":"
+fn unshifted(c: char) -> char {
+    match c {
+        ':' => ';',
+        '+' => '=',
+        '<' => ',',
+        '_' => '-',
+        '>' => '.',
+        '?' => '/',
+        '~' => '`',
+        '{' => '[',
+        '|' => '\\',
+        '}' => ']',
+        '"' => '\'',
+        '!' => '1',
+        '@' => '2',
+        '#' => '3',
+        '$' => '4',
+        '%' => '5',
+        '^' => '6',
+        '&' => '7',
+        '*' => '8',
+        '(' => '9',
+        ')' => '0',
+        c => c.to_ascii_uppercase(),
+    }
+}
+
+// Name stored in a KeyCombo for a captured key. Esc is left out since it
+// cancels the capture. Numpad and media keys only arrive from terminals that
+// report them; they can also be written into the config by name
+pub fn capture_name(code: KeyCode, state: KeyEventState) -> Option<String> {
+    let name = match code {
+        KeyCode::Char(c) if state.contains(KeyEventState::KEYPAD) => match c {
+            '0'..='9' => return Some(format!("Num{}", c)),
+            '+' => "NumAdd",
+            '-' => "NumSubtract",
+            '*' => "NumMultiply",
+            '/' => "NumDivide",
+            '.' => "NumDecimal",
+            _ => return None,
+        },
+        KeyCode::Char(' ') => "Space",
+        KeyCode::Char(c) if c.is_ascii_graphic() => return Some(unshifted(c).to_string()),
+        KeyCode::F(n) => return Some(format!("F{}", n)),
+        KeyCode::Up => "Up",
+        KeyCode::Down => "Down",
+        KeyCode::Left => "Left",
+        KeyCode::Right => "Right",
+        KeyCode::Home => "Home",
+        KeyCode::End => "End",
+        KeyCode::PageUp => "PageUp",
+        KeyCode::PageDown => "PageDown",
+        KeyCode::Insert => "Insert",
+        KeyCode::Delete => "Delete",
+        KeyCode::Backspace => "Backspace",
+        KeyCode::Enter => "Enter",
+        KeyCode::Tab => "Tab",
+        KeyCode::Pause => "Pause",
+        KeyCode::ScrollLock => "ScrollLock",
+        KeyCode::NumLock => "NumLock",
+        KeyCode::PrintScreen => "PrintScreen",
+        KeyCode::Media(media) => match media {
+            MediaKeyCode::Play | MediaKeyCode::Pause | MediaKeyCode::PlayPause => "MediaPlayPause",
+            MediaKeyCode::Stop => "MediaStop",
+            MediaKeyCode::TrackNext => "MediaNext",
+            MediaKeyCode::TrackPrevious => "MediaPrev",
+            MediaKeyCode::RaiseVolume => "VolumeUp",
+            MediaKeyCode::LowerVolume => "VolumeDown",
+            MediaKeyCode::MuteVolume => "VolumeMute",
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+// Win32 virtual-key codes of the named keys, letters and digits aside
+#[cfg(windows)]
+const NAMED_VK: [(&str, u32); 36] = [
+    ("Space", 0x20),
+    ("Backspace", 0x08),
+    ("Tab", 0x09),
+    ("Enter", 0x0D),
+    ("Pause", 0x13),
+    ("PageUp", 0x21),
+    ("PageDown", 0x22),
+    ("End", 0x23),
+    ("Home", 0x24),
+    ("Left", 0x25),
+    ("Up", 0x26),
+    ("Right", 0x27),
+    ("Down", 0x28),
+    ("PrintScreen", 0x2C),
+    ("Insert", 0x2D),
+    ("Delete", 0x2E),
+    ("NumMultiply", 0x6A),
+    ("NumAdd", 0x6B),
+    ("NumSubtract", 0x6D),
+    ("NumDecimal", 0x6E),
+    ("NumDivide", 0x6F),
+    ("NumLock", 0x90),
+    ("ScrollLock", 0x91),
+    ("VolumeMute", 0xAD),
+    ("VolumeDown", 0xAE),
+    ("VolumeUp", 0xAF),
+    ("MediaNext", 0xB0),
+    ("MediaPrev", 0xB1),
+    ("MediaStop", 0xB2),
+    ("MediaPlayPause", 0xB3),
+    // Only for tap chords; RegisterHotKey takes modifiers as flags
+    ("Shift", 0x10),
+    ("Ctrl", 0x11),
+    ("Alt", 0x12),
+    ("Shift", 0xA0),
+    ("Ctrl", 0xA2),
+    ("Alt", 0xA4),
+];
+
+// US layout OEM keys
+#[cfg(windows)]
+const PUNCTUATION_VK: [(char, u32); 11] = [
+    (';', 0xBA),
+    ('=', 0xBB),
+    (',', 0xBC),
+    ('-', 0xBD),
+    ('.', 0xBE),
+    ('/', 0xBF),
+    ('`', 0xC0),
+    ('[', 0xDB),
+    ('\\', 0xDC),
+    (']', 0xDD),
+    ('\'', 0xDE),
+];
+
+// Win32 virtual-key code for a key name; names are case-insensitive
+#[cfg(windows)]
+pub fn vk_code(name: &str) -> Option<u32> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return match unshifted(c) {
+            c @ ('A'..='Z' | '0'..='9') => Some(c as u32),
+            c => PUNCTUATION_VK
+                .iter()
+                .find(|&&(p, _)| p == c)
+                .map(|&(_, vk)| vk),
+        };
+    }
+
+    let lower = name.to_ascii_lowercase();
+    if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u32>().ok()) {
+        return (1..=24).contains(&n).then(|| 0x6F + n);
+    }
+    if let Some(n) = lower
+        .strip_prefix("num")
+        .and_then(|n| n.parse::<u32>().ok())
+    {
+        return (n <= 9).then(|| 0x60 + n);
+    }
+    NAMED_VK
+        .iter()
+        .find(|(named, _)| named.eq_ignore_ascii_case(name))
+        .map(|&(_, vk)| vk)
+}
+
+// Key name for a virtual-key code, as reported by the keyboard hook; left
+// and right modifiers share a name
+#[cfg(windows)]
+pub fn vk_name(vk: u32) -> Option<String> {
+    match vk {
+        0x30..=0x39 | 0x41..=0x5A => char::from_u32(vk).map(String::from),
+        0x60..=0x69 => Some(format!("Num{}", vk - 0x60)),
+        0x70..=0x87 => Some(format!("F{}", vk - 0x6F)),
+        0xA1 | 0xA3 | 0xA5 => vk_name(vk - 1),
+        _ => NAMED_VK
+            .iter()
+            .find(|&&(_, code)| code == vk)
+            .map(|&(name, _)| name.to_string())
+            .or_else(|| {
+                PUNCTUATION_VK
+                    .iter()
+                    .find(|&&(_, code)| code == vk)
+                    .map(|&(c, _)| c.to_string())
+            }),
+    }
+}
+
+// Key name for an evdev key code (linux/input-event-codes.h), US layout
+#[cfg(target_os = "linux")]
+pub fn evdev_name(code: u16) -> Option<String> {
+    const LETTER_ROWS: [(u16, &str); 3] = [(16, "QWERTYUIOP"), (30, "ASDFGHJKL"), (44, "ZXCVBNM")];
+    for (first, row) in LETTER_ROWS {
+        if let Some(c) = code
+            .checked_sub(first)
+            .and_then(|i| row.chars().nth(i as usize))
+        {
+            return Some(c.to_string());
+        }
+    }
+    let name = match code {
+        2..=10 => return Some((code - 1).to_string()),
+        59..=68 => return Some(format!("F{}", code - 58)),
+        183..=194 => return Some(format!("F{}", code - 170)),
+        71..=73 => return Some(format!("Num{}", code - 64)),
+        75..=77 => return Some(format!("Num{}", code - 71)),
+        79..=81 => return Some(format!("Num{}", code - 78)),
+        11 => "0",
+        12 => "-",
+        13 => "=",
+        14 => "Backspace",
+        15 => "Tab",
+        26 => "[",
+        27 => "]",
+        28 | 96 => "Enter",
+        29 | 97 => "Ctrl",
+        39 => ";",
+        40 => "'",
+        41 => "`",
+        42 | 54 => "Shift",
+        43 => "\\",
+        51 => ",",
+        52 => ".",
+        53 => "/",
+        55 => "NumMultiply",
+        56 | 100 => "Alt",
+        57 => "Space",
+        69 => "NumLock",
+        70 => "ScrollLock",
+        74 => "NumSubtract",
+        78 => "NumAdd",
+        82 => "Num0",
+        83 => "NumDecimal",
+        87 => "F11",
+        88 => "F12",
+        98 => "NumDivide",
+        99 => "PrintScreen",
+        102 => "Home",
+        103 => "Up",
+        104 => "PageUp",
+        105 => "Left",
+        106 => "Right",
+        107 => "End",
+        108 => "Down",
+        109 => "PageDown",
+        110 => "Insert",
+        111 => "Delete",
+        113 => "VolumeMute",
+        114 => "VolumeDown",
+        115 => "VolumeUp",
+        119 => "Pause",
+        163 => "MediaNext",
+        164 => "MediaPlayPause",
+        165 => "MediaPrev",
+        166 => "MediaStop",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+// Key name for a key reported by rdev's event tap on macOS, US layout
+#[cfg(target_os = "macos")]
+pub fn rdev_name(key: rdev::Key) -> Option<String> {
+    use rdev::Key::*;
+    let debug = format!("{:?}", key);
+    let name = match key {
+        KeyA | KeyB | KeyC | KeyD | KeyE | KeyF | KeyG | KeyH | KeyI | KeyJ | KeyK | KeyL
+        | KeyM | KeyN | KeyO | KeyP | KeyQ | KeyR | KeyS | KeyT | KeyU | KeyV | KeyW | KeyX
+        | KeyY | KeyZ => return debug.strip_prefix("Key").map(String::from),
+        // rdev's Num keys are the digit row; its keypad is Kp
+        Num0 | Num1 | Num2 | Num3 | Num4 | Num5 | Num6 | Num7 | Num8 | Num9 => {
+            return debug.strip_prefix("Num").map(String::from);
+        }
+        Kp0 | Kp1 | Kp2 | Kp3 | Kp4 | Kp5 | Kp6 | Kp7 | Kp8 | Kp9 => {
+            return debug.strip_prefix("Kp").map(|n| format!("Num{}", n));
+        }
+        F1 | F2 | F3 | F4 | F5 | F6 | F7 | F8 | F9 | F10 | F11 | F12 => return Some(debug),
+        ShiftLeft | ShiftRight => "Shift",
+        ControlLeft | ControlRight => "Ctrl",
+        Alt | AltGr => "Alt",
+        Space => "Space",
+        Backspace => "Backspace",
+        Tab => "Tab",
+        Return | KpReturn => "Enter",
+        Pause => "Pause",
+        PageUp => "PageUp",
+        PageDown => "PageDown",
+        End => "End",
+        Home => "Home",
+        LeftArrow => "Left",
+        UpArrow => "Up",
+        RightArrow => "Right",
+        DownArrow => "Down",
+        PrintScreen => "PrintScreen",
+        Insert => "Insert",
+        Delete => "Delete",
+        NumLock => "NumLock",
+        ScrollLock => "ScrollLock",
+        KpMultiply => "NumMultiply",
+        KpPlus => "NumAdd",
+        KpMinus => "NumSubtract",
+        KpDelete => "NumDecimal",
+        KpDivide => "NumDivide",
+        SemiColon => ";",
+        Equal => "=",
+        Comma => ",",
+        Minus => "-",
+        Dot => ".",
+        Slash => "/",
+        BackQuote => "`",
+        LeftBracket => "[",
+        BackSlash => "\\",
+        RightBracket => "]",
+        Quote => "'",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
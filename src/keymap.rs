@@ -0,0 +1,525 @@
+use crate::i18n::t;
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+// Main screen actions that can be rebound; arrow keys always navigate too
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Button,
+    ScrollAmount,
+    RepeatText,
+    StartDelay,
+    IdleTrigger,
+    Overlay,
+    Position,
+    ReturnToOrigin,
+    Region,
+    Modifier,
+    Backend,
+    CustomCps,
+    Presets,
+    Range,
+    SlowMode,
+    AntiAfk,
+    DutyCycle,
+    Pattern,
+    SecondChannel,
+    KeyRotation,
+    HoldKey,
+    QueueJob,
+    StdDev,
+    Timing,
+    Pause,
+    Hotkey,
+    HotkeyMode,
+    MouseTrigger,
+    CpsUpHotkey,
+    CpsDownHotkey,
+    PauseHotkey,
+    Audio,
+    AudioDevice,
+    Theme,
+    Language,
+    Hide,
+    ResetStats,
+    Stats,
+    Benchmark,
+    TestFire,
+    DryRun,
+    Sequence,
+    Scheduler,
+    SaveSlot,
+    ExportConfig,
+    ImportConfig,
+    Help,
+    Quit,
+    Down,
+    Up,
+}
+
+impl Action {
+    // Also the order of the instruction bar
+    pub const ALL: [Action; 50] = [
+        Action::Button,
+        Action::ScrollAmount,
+        Action::RepeatText,
+        Action::StartDelay,
+        Action::IdleTrigger,
+        Action::Overlay,
+        Action::Position,
+        Action::ReturnToOrigin,
+        Action::Region,
+        Action::Modifier,
+        Action::Backend,
+        Action::CustomCps,
+        Action::Presets,
+        Action::Range,
+        Action::SlowMode,
+        Action::AntiAfk,
+        Action::DutyCycle,
+        Action::Pattern,
+        Action::SecondChannel,
+        Action::KeyRotation,
+        Action::HoldKey,
+        Action::QueueJob,
+        Action::StdDev,
+        Action::Timing,
+        Action::Pause,
+        Action::Hotkey,
+        Action::HotkeyMode,
+        Action::MouseTrigger,
+        Action::CpsUpHotkey,
+        Action::CpsDownHotkey,
+        Action::PauseHotkey,
+        Action::Audio,
+        Action::AudioDevice,
+        Action::Theme,
+        Action::Language,
+        Action::Hide,
+        Action::ResetStats,
+        Action::Stats,
+        Action::Benchmark,
+        Action::TestFire,
+        Action::DryRun,
+        Action::Sequence,
+        Action::Scheduler,
+        Action::SaveSlot,
+        Action::ExportConfig,
+        Action::ImportConfig,
+        Action::Help,
+        Action::Quit,
+        Action::Down,
+        Action::Up,
+    ];
+
+    // Name of the action's entry in the [keymap] config section
+    pub fn config_name(self) -> &'static str {
+        match self {
+            Action::Button => "button",
+            Action::ScrollAmount => "scroll_amount",
+            Action::RepeatText => "repeat_text",
+            Action::StartDelay => "start_delay",
+            Action::IdleTrigger => "idle_trigger",
+            Action::Overlay => "overlay",
+            Action::Position => "position",
+            Action::ReturnToOrigin => "return_to_origin",
+            Action::Region => "region",
+            Action::Modifier => "modifier",
+            Action::Backend => "backend",
+            Action::CustomCps => "custom_cps",
+            Action::Presets => "presets",
+            Action::Range => "range",
+            Action::SlowMode => "slow_mode",
+            Action::AntiAfk => "anti_afk",
+            Action::DutyCycle => "duty_cycle",
+            Action::Pattern => "pattern",
+            Action::SecondChannel => "second_channel",
+            Action::KeyRotation => "key_rotation",
+            Action::HoldKey => "hold_key",
+            Action::QueueJob => "queue_job",
+            Action::StdDev => "std_dev",
+            Action::Timing => "timing",
+            Action::Pause => "pause",
+            Action::Hotkey => "hotkey",
+            Action::HotkeyMode => "hotkey_mode",
+            Action::MouseTrigger => "mouse_trigger",
+            Action::CpsUpHotkey => "cps_up_hotkey",
+            Action::CpsDownHotkey => "cps_down_hotkey",
+            Action::PauseHotkey => "pause_hotkey",
+            Action::Audio => "audio",
+            Action::AudioDevice => "audio_device",
+            Action::Theme => "theme",
+            Action::Language => "language",
+            Action::Hide => "hide",
+            Action::ResetStats => "reset_stats",
+            Action::Stats => "stats",
+            Action::Benchmark => "benchmark",
+            Action::TestFire => "test_fire",
+            Action::DryRun => "dry_run",
+            Action::Sequence => "sequence",
+            Action::Scheduler => "scheduler",
+            Action::SaveSlot => "save_slot",
+            Action::ExportConfig => "export_config",
+            Action::ImportConfig => "import_config",
+            Action::Help => "help",
+            Action::Quit => "quit",
+            Action::Down => "down",
+            Action::Up => "up",
+        }
+    }
+
+    // Word shown in the instruction bar; None keeps the action off the bar
+    pub fn short_label(self) -> Option<String> {
+        match self {
+            Action::StdDev
+            | Action::CpsUpHotkey
+            | Action::CpsDownHotkey
+            | Action::PauseHotkey
+            | Action::ExportConfig
+            | Action::ImportConfig
+            | Action::Down
+            | Action::Up => None,
+            _ => Some(t!(&format!("key-{}", self.config_name()))),
+        }
+    }
+
+    // Localized, from the action-* messages
+    pub fn description(self) -> String {
+        t!(&format!("action-{}", self.config_name()))
+    }
+}
+
+// Key names accepted in the keymap: a single character (case-sensitive, so
+// "E" means Shift+E), or Tab, Space, Enter, Backspace and F1-F12. An empty
+// name leaves the action unbound, reachable through the command palette
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return (!c.is_whitespace()).then_some(KeyCode::Char(c));
+    }
+    match name.to_ascii_lowercase().as_str() {
+        "tab" => Some(KeyCode::Tab),
+        "space" => Some(KeyCode::Char(' ')),
+        "enter" => Some(KeyCode::Enter),
+        "backspace" => Some(KeyCode::Backspace),
+        f => f
+            .strip_prefix('f')
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|n| (1..=12).contains(n))
+            .map(KeyCode::F),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct Keymap {
+    pub button: String,
+    pub scroll_amount: String,
+    pub repeat_text: String,
+    pub start_delay: String,
+    pub idle_trigger: String,
+    pub overlay: String,
+    pub position: String,
+    pub return_to_origin: String,
+    pub region: String,
+    pub modifier: String,
+    pub backend: String,
+    pub custom_cps: String,
+    pub presets: String,
+    pub range: String,
+    pub slow_mode: String,
+    pub anti_afk: String,
+    pub duty_cycle: String,
+    pub pattern: String,
+    pub second_channel: String,
+    pub key_rotation: String,
+    pub hold_key: String,
+    pub queue_job: String,
+    pub std_dev: String,
+    pub timing: String,
+    pub pause: String,
+    pub hotkey: String,
+    pub hotkey_mode: String,
+    pub mouse_trigger: String,
+    pub cps_up_hotkey: String,
+    pub cps_down_hotkey: String,
+    pub pause_hotkey: String,
+    pub audio: String,
+    pub audio_device: String,
+    pub theme: String,
+    pub language: String,
+    pub hide: String,
+    pub reset_stats: String,
+    pub stats: String,
+    pub benchmark: String,
+    pub test_fire: String,
+    pub dry_run: String,
+    pub sequence: String,
+    pub scheduler: String,
+    pub save_slot: String,
+    pub export_config: String,
+    pub import_config: String,
+    pub help: String,
+    pub quit: String,
+    pub down: String,
+    pub up: String,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            button: "Tab".to_string(),
+            scroll_amount: "w".to_string(),
+            repeat_text: "x".to_string(),
+            start_delay: "d".to_string(),
+            idle_trigger: "z".to_string(),
+            overlay: "v".to_string(),
+            position: "p".to_string(),
+            return_to_origin: "o".to_string(),
+            region: "F".to_string(),
+            modifier: "H".to_string(),
+            backend: "i".to_string(),
+            custom_cps: "e".to_string(),
+            presets: "P".to_string(),
+            range: "n".to_string(),
+            slow_mode: "L".to_string(),
+            anti_afk: "A".to_string(),
+            duty_cycle: "D".to_string(),
+            pattern: "R".to_string(),
+            second_channel: "B".to_string(),
+            key_rotation: "K".to_string(),
+            hold_key: "W".to_string(),
+            queue_job: "J".to_string(),
+            std_dev: "g".to_string(),
+            timing: "t".to_string(),
+            pause: "Space".to_string(),
+            hotkey: "s".to_string(),
+            hotkey_mode: "f".to_string(),
+            mouse_trigger: "u".to_string(),
+            cps_up_hotkey: String::new(),
+            cps_down_hotkey: String::new(),
+            pause_hotkey: String::new(),
+            audio: "m".to_string(),
+            audio_device: "M".to_string(),
+            theme: "T".to_string(),
+            language: "G".to_string(),
+            hide: "h".to_string(),
+            reset_stats: "r".to_string(),
+            stats: "a".to_string(),
+            benchmark: "b".to_string(),
+            test_fire: "c".to_string(),
+            dry_run: "y".to_string(),
+            sequence: "l".to_string(),
+            scheduler: "C".to_string(),
+            save_slot: "S".to_string(),
+            export_config: String::new(),
+            import_config: String::new(),
+            help: "?".to_string(),
+            quit: "q".to_string(),
+            down: "j".to_string(),
+            up: "k".to_string(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn binding(&self, action: Action) -> &str {
+        match action {
+            Action::Button => &self.button,
+            Action::ScrollAmount => &self.scroll_amount,
+            Action::RepeatText => &self.repeat_text,
+            Action::StartDelay => &self.start_delay,
+            Action::IdleTrigger => &self.idle_trigger,
+            Action::Overlay => &self.overlay,
+            Action::Position => &self.position,
+            Action::ReturnToOrigin => &self.return_to_origin,
+            Action::Region => &self.region,
+            Action::Modifier => &self.modifier,
+            Action::Backend => &self.backend,
+            Action::CustomCps => &self.custom_cps,
+            Action::Presets => &self.presets,
+            Action::Range => &self.range,
+            Action::SlowMode => &self.slow_mode,
+            Action::AntiAfk => &self.anti_afk,
+            Action::DutyCycle => &self.duty_cycle,
+            Action::Pattern => &self.pattern,
+            Action::SecondChannel => &self.second_channel,
+            Action::KeyRotation => &self.key_rotation,
+            Action::HoldKey => &self.hold_key,
+            Action::QueueJob => &self.queue_job,
+            Action::StdDev => &self.std_dev,
+            Action::Timing => &self.timing,
+            Action::Pause => &self.pause,
+            Action::Hotkey => &self.hotkey,
+            Action::HotkeyMode => &self.hotkey_mode,
+            Action::MouseTrigger => &self.mouse_trigger,
+            Action::CpsUpHotkey => &self.cps_up_hotkey,
+            Action::CpsDownHotkey => &self.cps_down_hotkey,
+            Action::PauseHotkey => &self.pause_hotkey,
+            Action::Audio => &self.audio,
+            Action::AudioDevice => &self.audio_device,
+            Action::Theme => &self.theme,
+            Action::Language => &self.language,
+            Action::Hide => &self.hide,
+            Action::ResetStats => &self.reset_stats,
+            Action::Stats => &self.stats,
+            Action::Benchmark => &self.benchmark,
+            Action::TestFire => &self.test_fire,
+            Action::DryRun => &self.dry_run,
+            Action::Sequence => &self.sequence,
+            Action::Scheduler => &self.scheduler,
+            Action::SaveSlot => &self.save_slot,
+            Action::ExportConfig => &self.export_config,
+            Action::ImportConfig => &self.import_config,
+            Action::Help => &self.help,
+            Action::Quit => &self.quit,
+            Action::Down => &self.down,
+            Action::Up => &self.up,
+        }
+    }
+
+    fn binding_mut(&mut self, action: Action) -> &mut String {
+        match action {
+            Action::Button => &mut self.button,
+            Action::ScrollAmount => &mut self.scroll_amount,
+            Action::RepeatText => &mut self.repeat_text,
+            Action::StartDelay => &mut self.start_delay,
+            Action::IdleTrigger => &mut self.idle_trigger,
+            Action::Overlay => &mut self.overlay,
+            Action::Position => &mut self.position,
+            Action::ReturnToOrigin => &mut self.return_to_origin,
+            Action::Region => &mut self.region,
+            Action::Modifier => &mut self.modifier,
+            Action::Backend => &mut self.backend,
+            Action::CustomCps => &mut self.custom_cps,
+            Action::Presets => &mut self.presets,
+            Action::Range => &mut self.range,
+            Action::SlowMode => &mut self.slow_mode,
+            Action::AntiAfk => &mut self.anti_afk,
+            Action::DutyCycle => &mut self.duty_cycle,
+            Action::Pattern => &mut self.pattern,
+            Action::SecondChannel => &mut self.second_channel,
+            Action::KeyRotation => &mut self.key_rotation,
+            Action::HoldKey => &mut self.hold_key,
+            Action::QueueJob => &mut self.queue_job,
+            Action::StdDev => &mut self.std_dev,
+            Action::Timing => &mut self.timing,
+            Action::Pause => &mut self.pause,
+            Action::Hotkey => &mut self.hotkey,
+            Action::HotkeyMode => &mut self.hotkey_mode,
+            Action::MouseTrigger => &mut self.mouse_trigger,
+            Action::CpsUpHotkey => &mut self.cps_up_hotkey,
+            Action::CpsDownHotkey => &mut self.cps_down_hotkey,
+            Action::PauseHotkey => &mut self.pause_hotkey,
+            Action::Audio => &mut self.audio,
+            Action::AudioDevice => &mut self.audio_device,
+            Action::Theme => &mut self.theme,
+            Action::Language => &mut self.language,
+            Action::Hide => &mut self.hide,
+            Action::ResetStats => &mut self.reset_stats,
+            Action::Stats => &mut self.stats,
+            Action::Benchmark => &mut self.benchmark,
+            Action::TestFire => &mut self.test_fire,
+            Action::DryRun => &mut self.dry_run,
+            Action::Sequence => &mut self.sequence,
+            Action::Scheduler => &mut self.scheduler,
+            Action::SaveSlot => &mut self.save_slot,
+            Action::ExportConfig => &mut self.export_config,
+            Action::ImportConfig => &mut self.import_config,
+            Action::Help => &mut self.help,
+            Action::Quit => &mut self.quit,
+            Action::Down => &mut self.down,
+            Action::Up => &mut self.up,
+        }
+    }
+
+    pub fn action_for(&self, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Down => Some(Action::Down),
+            KeyCode::Up => Some(Action::Up),
+            code => Action::ALL
+                .into_iter()
+                .find(|&action| parse_key(self.binding(action)) == Some(code)),
+        }
+    }
+
+    pub fn is(&self, action: Action, code: KeyCode) -> bool {
+        parse_key(self.binding(action)) == Some(code)
+    }
+
+    // How a binding is written in help text: letters upper-cased the way the
+    // help screen always showed them, with Shift spelled out for capitals
+    pub fn label(&self, action: Action) -> String {
+        let key = self.binding(action);
+        match parse_key(key) {
+            Some(KeyCode::Char(c)) if c.is_ascii_uppercase() => format!("Shift+{}", c),
+            Some(KeyCode::Char(' ')) => "Space".to_string(),
+            Some(KeyCode::Char(c)) => c.to_ascii_uppercase().to_string(),
+            _ if key.is_empty() => "Ctrl+P".to_string(),
+            _ => key.to_string(),
+        }
+    }
+
+    pub fn instruction_bar(&self) -> String {
+        let entries: Vec<String> = Action::ALL
+            .into_iter()
+            .filter_map(|action| {
+                action
+                    .short_label()
+                    .map(|short| format!("{}={}", self.label(action), short))
+            })
+            .collect();
+        format!("{} │ {}", t!("bar-navigation"), entries.join(" │ "))
+    }
+
+    // Reverts unparseable or clashing bindings to their defaults and returns
+    // a description of each problem; falls back to the default keymap if the
+    // reverts clash again
+    pub fn validate(&mut self) -> Vec<String> {
+        let defaults = Keymap::default();
+        let mut problems = Vec::new();
+
+        for action in Action::ALL {
+            let key = self.binding(action);
+            if !key.is_empty() && parse_key(key).is_none() {
+                problems.push(format!(
+                    "'{}' is not a valid key for {}",
+                    self.binding(action),
+                    action.config_name()
+                ));
+                *self.binding_mut(action) = defaults.binding(action).to_string();
+            }
+        }
+
+        for (i, &first) in Action::ALL.iter().enumerate() {
+            for &second in &Action::ALL[i + 1..] {
+                let key = parse_key(self.binding(first));
+                if key.is_some() && key == parse_key(self.binding(second)) {
+                    problems.push(format!(
+                        "'{}' is bound to both {} and {}",
+                        self.binding(second),
+                        first.config_name(),
+                        second.config_name()
+                    ));
+                    *self.binding_mut(second) = defaults.binding(second).to_string();
+                }
+            }
+        }
+
+        if !problems.is_empty() && self.has_conflicts() {
+            problems.push("keymap reset to defaults".to_string());
+            *self = defaults;
+        }
+        problems
+    }
+
+    fn has_conflicts(&self) -> bool {
+        let keys: Vec<_> = Action::ALL
+            .iter()
+            .filter_map(|&action| parse_key(self.binding(action)))
+            .collect();
+        keys.iter()
+            .enumerate()
+            .any(|(i, key)| keys[i + 1..].contains(key))
+    }
+}
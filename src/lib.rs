@@ -0,0 +1,7718 @@
+#![allow(unused)] // Suppresses ALL unused warnings
+#![allow(dead_code, unused_imports, unused_variables)]
+mod ahk;
+mod anti_afk;
+mod auth;
+pub mod backend;
+mod benchmark;
+pub mod calibration;
+mod chord;
+mod cli;
+pub mod clock;
+mod condition;
+pub mod cron;
+mod daemon;
+mod deck;
+mod dpi;
+mod duty_cycle;
+mod focus;
+pub mod grpc;
+pub mod headless;
+mod hooks;
+mod hotkeys;
+mod humanize;
+mod i18n;
+mod idle;
+mod input_hook;
+mod interval;
+mod ipc;
+mod job_queue;
+mod keycodes;
+mod keymap;
+mod mdns;
+mod midi;
+mod modifier;
+mod motion;
+mod mouse_trigger;
+mod mqtt;
+mod notifications;
+mod oneshot;
+mod osc;
+mod overlay;
+mod overrides;
+mod palette;
+mod pattern;
+mod pointer;
+mod quick_slots;
+mod region;
+mod rotation;
+mod run_log;
+pub mod scheduler;
+mod second_channel;
+mod sequence;
+mod service;
+mod stats;
+mod theme;
+mod tones;
+mod transfer;
+mod variables;
+mod verify;
+mod watchdog;
+mod web;
+mod webhooks;
+
+use anti_afk::AntiAfk;
+use auth::{ApiToken, Scope};
+use backend::{BackendFactory, BackendKind, ClickBackend};
+use benchmark::BenchmarkReport;
+use calibration::Calibration;
+use chord::TapChord;
+use clock::Clock;
+use condition::{Gate, Probe};
+use cron::{CronExpr, LocalTime, Schedule};
+use crossterm::{
+    cursor,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event as CEvent, KeyCode, KeyModifiers,
+    },
+    execute,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{
+        self, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use deck::DeckConfig;
+use duty_cycle::{DutyCycle, DutyPhase};
+use enigo::{Key, MouseButton};
+use focus::{CpsCap, FocusCheck, FocusedWindow, ProfileRule, SharedFocus, WindowMatch};
+use grpc::GrpcConfig;
+use hooks::{HookEvent, Hooks};
+#[cfg(any(windows, target_os = "linux"))]
+use hotkeys::GlobalHotkeys;
+use hotkeys::{HotkeyBackend, HotkeyId, HotkeyMessage, KeyListener, NoHotkeys};
+use humanize::HumanPattern;
+use i18n::{Language, t};
+use idle::IdleMonitor;
+use ipc::IpcFeed;
+use job_queue::{ClickJob, JobQueue};
+use keymap::{Action, Keymap};
+use midi::MidiConfig;
+use modifier::ClickModifier;
+use motion::{Glide, HumanMotion};
+use mouse_trigger::MouseTrigger;
+use mqtt::MqttConfig;
+use notifications::{NotificationPrefs, NotifyEvent};
+use osc::OscConfig;
+use overlay::OverlayFeed;
+use overrides::{Override, Overrides};
+use palette::Command;
+use pattern::ClickPattern;
+use quick_slots::QuickSlot;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use region::{Region, RegionCheck};
+use rodio::{
+    DeviceTrait, OutputStream, OutputStreamHandle, Sink, Source,
+    cpal::{self, traits::HostTrait},
+};
+use rotation::KeyRotation;
+use run_log::{RunHistory, RunLog, RunRecord};
+use scheduler::{ClickScheduler, IntervalTracker, RateMeter, RateStatus, TimingAccuracy};
+use second_channel::SecondChannel;
+use sequence::{DragAction, Point, SequenceStep, StepKind};
+use serde::{Deserialize, Serialize};
+use stats::{ClickTally, Statistics};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Stdout, Write},
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use theme::{Theme, ThemeName};
+use tones::{Tone, Tones};
+use transfer::PendingImport;
+use tray_item::{IconSource, TrayItem};
+use tui::{
+    Terminal,
+    backend::{Backend, CrosstermBackend},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color as TuiColor, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{BarChart, Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use variables::Variables;
+use verify::ClickVerify;
+use watchdog::{InjectionWatchdog, Watched};
+use web::WebConfig;
+use webhooks::{RunInfo, StopReason, Webhook, WebhookEvent};
+
+// Win32 POINT, shared by the SendInput backend and DPI lookups
+#[cfg(windows)]
+#[repr(C)]
+struct POINT {
+    x: i32,
+    y: i32,
+}
+
+#[cfg(windows)]
+#[link(name = "winmm")]
+unsafe extern "system" {
+    fn timeBeginPeriod(uPeriod: u32) -> u32;
+    fn timeEndPeriod(uPeriod: u32) -> u32;
+}
+
+// Keeps the Windows timer resolution at 1 ms until dropped, so the sleep phase
+// of precise_sleep doesn't overshoot by a full 15.6 ms scheduler tick. A no-op
+// elsewhere
+struct TimerResolution;
+
+impl TimerResolution {
+    fn raise() -> Self {
+        #[cfg(windows)]
+        unsafe {
+            timeBeginPeriod(1);
+        }
+        Self
+    }
+}
+
+impl Drop for TimerResolution {
+    fn drop(&mut self) {
+        #[cfg(windows)]
+        unsafe {
+            timeEndPeriod(1);
+        }
+    }
+}
+
+// FIXED: Event system for responsive input handling
+#[derive(Debug)]
+enum AppEvent {
+    Input(crossterm::event::KeyEvent),
+    // Another thread changed something the TUI reacts to, e.g. a hotkey
+    Wake,
+    // `bclicker start`, `stop` or `toggle` from another process
+    Control(ipc::ControlRequest),
+    // Another window got focus, or none can be read any more
+    Focus(Option<FocusedWindow>),
+    // A client taking over the daemon's screen
+    Attach(ipc::Attach),
+    // Keys and resizes from the attached client of that session
+    Remote(u64, ipc::RemoteInput),
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KeyCombo {
+    mods: u8,
+    key: String,
+    #[serde(default)]
+    mode: ActivationMode,
+}
+
+impl std::fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if self.mods & 2 != 0 {
+            parts.push("Ctrl");
+        }
+        if self.mods & 1 != 0 {
+            parts.push("Shift");
+        }
+        if self.mods & 4 != 0 {
+            parts.push("Alt");
+        }
+        parts.push(&self.key);
+        write!(f, "{}", parts.join("+"))
+    }
+}
+
+// How an activation binding drives the clicker: a press flips it on or off,
+// or it clicks only while held
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ActivationMode {
+    #[default]
+    Toggle,
+    Hold,
+}
+
+impl ActivationMode {
+    fn label(self) -> &'static str {
+        match self {
+            ActivationMode::Toggle => "toggle",
+            ActivationMode::Hold => "hold",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            ActivationMode::Toggle => ActivationMode::Hold,
+            ActivationMode::Hold => ActivationMode::Toggle,
+        }
+    }
+
+    // Every listener reports presses and releases of its binding through this
+    fn apply(self, pressed: bool, auto_clicker_running: &AtomicBool) {
+        match self {
+            ActivationMode::Toggle if pressed => {
+                auto_clicker_running.fetch_xor(true, Ordering::SeqCst);
+            }
+            ActivationMode::Toggle => {}
+            ActivationMode::Hold => auto_clicker_running.store(pressed, Ordering::SeqCst),
+        }
+    }
+}
+
+// What the hotkey thread made of the requested combo
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum HotkeyStatus {
+    Unset,
+    Registered,
+    // Another app holds the combo, the key can't be a hotkey, or there's no
+    // way to get global keys at all
+    Failed(String),
+    Unsupported,
+}
+
+// Shared with the hotkey thread, which re-registers whenever `requested`
+// changes so a new combo applies without a restart
+struct HotkeyState {
+    requested: Option<KeyCombo>,
+    status: HotkeyStatus,
+    // Faster then slower rate hotkeys, registered the same way
+    nudge_requested: [Option<KeyCombo>; 2],
+    nudge_status: [HotkeyStatus; 2],
+    // Rate steps pressed since the TUI last looked: +1 faster, -1 slower
+    pending_nudges: i32,
+    // Pause/resume hotkey, also applied by the TUI
+    pause_requested: Option<KeyCombo>,
+    pause_status: HotkeyStatus,
+    pause_pressed: bool,
+    // While a click region is being captured the toggle hotkey marks a
+    // corner instead of starting or stopping clicking
+    capturing_region: bool,
+    corner_pressed: bool,
+    mouse_trigger: Option<MouseTrigger>,
+    tap_trigger: Option<TapChord>,
+    // Wakes the hotkey thread to pick up changed combos
+    wake: Option<mpsc::Sender<HotkeyMessage>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+struct CpsRange {
+    min: f64,
+    max: f64,
+}
+
+impl CpsRange {
+    fn is_valid(&self) -> bool {
+        self.min >= MIN_CPS && self.min <= self.max && self.max <= MAX_SAFE_CPS
+    }
+
+    // Hand-edited and imported ranges may be empty, inverted, too fast or not
+    // numbers at all: both ends are kept to MIN_CPS-MAX_SAFE_CPS and put in
+    // order, and NaN or infinite ends give the default range
+    fn sanitized(self) -> Self {
+        if !self.min.is_finite() || !self.max.is_finite() {
+            return Profile::default().cps_range;
+        }
+        let clamp = |cps: f64| round_cps(cps.clamp(MIN_CPS, MAX_SAFE_CPS));
+        let (min, max) = (clamp(self.min), clamp(self.max));
+        let range = Self {
+            min: min.min(max),
+            max: min.max(max),
+        };
+        if range.is_valid() {
+            range
+        } else {
+            Profile::default().cps_range
+        }
+    }
+}
+
+impl std::fmt::Display for CpsRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+// Space that configured coordinates are written in; logical coordinates are
+// scaled by the monitor's DPI before use (only differs on Windows)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+enum CoordinateSpace {
+    #[default]
+    Physical,
+    Logical,
+}
+
+// How click intervals are drawn around the selected rate
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+enum TimingModel {
+    #[default]
+    Fixed,
+    Uniform,
+    Gaussian,
+}
+
+impl TimingModel {
+    fn next(self) -> Self {
+        match self {
+            TimingModel::Fixed => TimingModel::Uniform,
+            TimingModel::Uniform => TimingModel::Gaussian,
+            TimingModel::Gaussian => TimingModel::Fixed,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TimingModel::Fixed => "Fixed",
+            TimingModel::Uniform => "Uniform",
+            TimingModel::Gaussian => "Gaussian",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+struct Profile {
+    name: String,
+    timing_model: TimingModel,
+    cps_range: CpsRange,
+    // Standard deviation of the gaussian model as a percentage of the mean interval
+    std_dev_percent: u32,
+    sequence: Vec<SequenceStep>,
+    // When enabled the engine runs the sequence instead of plain clicks
+    sequence_enabled: bool,
+    // Standalone drag mode: each engine action is this drag instead of a click
+    drag: Option<DragAction>,
+    // Fixed-coordinate clicking; None clicks wherever the cursor is
+    click_position: Option<Point>,
+    // Put the cursor back where it was after each fixed-coordinate action
+    return_to_origin: bool,
+    // Checks each click at a fixed point by the pixel under it and retries
+    verify: Option<ClickVerify>,
+    // Clicks only while the cursor is inside this box and holds off outside
+    click_region: Option<Region>,
+    // Visited in turn by the Hover mode; without any it traces a small square
+    hover_points: Vec<Point>,
+    // Held down around each click, e.g. Shift for shift-clicking items
+    modifier: Option<ClickModifier>,
+    // Click/pause cycles, e.g. 30 s on and 10 s off
+    duty_cycle: Option<DutyCycle>,
+    // Click routine such as "20x@30cps; wait 2s; 5x@10cps(right)", run
+    // instead of plain clicks when there's no enabled sequence
+    pattern: Option<String>,
+    // Keys such as "1@1.5s, 2@800ms, 3@800ms" pressed in turn by the Type
+    // Text mode instead of the repeat text
+    key_rotation: Option<String>,
+    // Cron expressions that start this profile for a while, e.g. every two
+    // hours for 30 minutes
+    schedules: Vec<Schedule>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            timing_model: TimingModel::Fixed,
+            cps_range: CpsRange {
+                min: 20.0,
+                max: 40.0,
+            },
+            std_dev_percent: 15,
+            sequence: Vec::new(),
+            sequence_enabled: false,
+            drag: None,
+            click_position: None,
+            return_to_origin: false,
+            verify: None,
+            click_region: None,
+            hover_points: Vec::new(),
+            modifier: None,
+            duty_cycle: None,
+            pattern: None,
+            key_rotation: None,
+            schedules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+struct Config {
+    // Rates may be fractional: 0.5 is one click every 2 s
+    cps_presets: Vec<f64>,
+    selected_preset: usize,
+    custom_cps_value: Option<f64>,
+    using_custom_cps: bool,
+    // Slow mode: one click every this many seconds, for idle games and
+    // keep-alive clicking
+    slow_interval_secs: Option<u64>,
+    using_slow_interval: bool,
+    // Anti-AFK: a small random action at a random point of a window of
+    // minutes, to keep idle timeouts from kicking in
+    anti_afk: AntiAfk,
+    using_anti_afk: bool,
+    human_pattern: Option<HumanPattern>,
+    // Curved, hand-like moves to fixed targets instead of jumping there
+    human_motion: Option<HumanMotion>,
+    selected_button: usize,
+    // Buttons the Alternate mode clicks in turn (0 = left, 1 = right)
+    alternate_order: Vec<usize>,
+    // Held down by the Hold Key mode for as long as it's active, e.g. w to
+    // walk in games
+    hold_key: String,
+    // Second button clicking alongside the main action at its own rate
+    second_channel: Option<SecondChannel>,
+    // Wheel notches per event in the scroll modes
+    scroll_amount: i32,
+    // Countdown between the start trigger and the first click
+    start_delay_secs: u32,
+    // Start clicking after this long without user input; 0 disables
+    idle_start_secs: u32,
+    // Typed repeatedly in the Type Text mode, optionally followed by Enter
+    repeat_text: String,
+    repeat_text_enter: bool,
+    coordinate_space: CoordinateSpace,
+    // Input injection layer the engine clicks through
+    backend: BackendKind,
+    // Stop a run whose input visibly goes nowhere
+    injection_watchdog: bool,
+    // Session-only: run the engine against the null backend so patterns and
+    // rates can be checked without clicking anything
+    #[serde(skip)]
+    dry_run: bool,
+    toggle_keybind: Option<KeyCombo>,
+    // Mouse side button that also starts/stops clicking
+    mouse_trigger: Option<MouseTrigger>,
+    // Key tapped repeatedly, e.g. Ctrl twice, that also starts/stops clicking
+    tap_trigger: Option<TapChord>,
+    // Global hotkeys that step the rate up/down by cps_step, e.g. while the
+    // TUI is hidden
+    cps_up_keybind: Option<KeyCombo>,
+    cps_down_keybind: Option<KeyCombo>,
+    cps_step: u32,
+    // Global hotkey that pauses and resumes a run without stopping it
+    pause_keybind: Option<KeyCombo>,
+    // Main screen keys; the global hotkey is toggle_keybind
+    keymap: Keymap,
+    // Only read, to move the stats of older configs into their own file
+    #[serde(rename = "statistics", default, skip_serializing)]
+    legacy_statistics: Option<Statistics>,
+    // Record every run in bclicker_runs.db; needs the `sqlite` feature
+    run_log: bool,
+    // Scheduler corrections measured by `bclicker calibrate`
+    calibration: Calibration,
+    sound_enabled: bool,
+    // Output device for the sound cues by name; None uses the system default
+    audio_device: Option<String>,
+    // Frequency, length and waveform of each sound cue
+    tones: Tones,
+    // Color palette of the TUI
+    theme: ThemeName,
+    // Language of the TUI and notifications; None follows the system locale
+    language: Option<Language>,
+    // Always-on-top activity indicator for use over fullscreen games
+    overlay_enabled: bool,
+    notifications: NotificationPrefs,
+    // A milestone fires whenever total clicks cross a multiple of one of these
+    milestones: Vec<u64>,
+    milestone_sound: bool,
+    // Shell commands run on start, stop and milestones
+    hooks: Hooks,
+    // Endpoints POSTed to on start, stop and errors
+    webhooks: Vec<Webhook>,
+    // Broker for state, stats and remote commands, e.g. Home Assistant's
+    mqtt: Option<MqttConfig>,
+    // Local endpoint for Stream Deck plugins and other control surfaces
+    deck: Option<DeckConfig>,
+    // Notes and controls of a MIDI controller mapped to actions
+    midi: Option<MidiConfig>,
+    // OSC server for TouchOSC and similar tablet layouts
+    osc: Option<OscConfig>,
+    // Phone-friendly remote control page served on the LAN
+    web: Option<WebConfig>,
+    // gRPC Control and Stats services; needs the `grpc` feature
+    grpc: Option<GrpcConfig>,
+    // Tokens the web, gRPC and deck APIs and remote OSC take, each allowed
+    // to read or also to control; never exported
+    api_tokens: Vec<ApiToken>,
+    profiles: Vec<Profile>,
+    active_profile: usize,
+    // Profile and rate applied by the number keys of the main screen
+    quick_slots: Vec<QuickSlot>,
+    // Profiles switched to while a matching window has focus; the first
+    // matching rule wins
+    profile_rules: Vec<ProfileRule>,
+    // Windows the engine never clicks into, e.g. a banking site or a chat
+    // app; clicking holds off while one has focus
+    blocked_windows: Vec<WindowMatch>,
+    // Highest rate while a matching window has focus, whatever the preset
+    // or profile asks for; the lowest matching cap wins
+    cps_caps: Vec<CpsCap>,
+}
+
+impl Config {
+    // Hand-edited files may drop every profile or point past the end
+    fn sanitize(&mut self) {
+        self.cps_presets
+            .retain(|cps| (MIN_CPS..=MAX_CPS).contains(cps));
+        for cps in &mut self.cps_presets {
+            *cps = round_cps(*cps);
+        }
+        self.custom_cps_value = self
+            .custom_cps_value
+            .map(round_cps)
+            .filter(|cps| (MIN_CPS..=MAX_CPS).contains(cps));
+        if self.cps_presets.is_empty() {
+            self.cps_presets = Config::default().cps_presets;
+        }
+        if self.selected_preset >= self.cps_presets.len() {
+            self.selected_preset = 0;
+        }
+        if self.profiles.is_empty() {
+            self.profiles.push(Profile::default());
+        }
+        if self.active_profile >= self.profiles.len() {
+            self.active_profile = 0;
+        }
+        for profile in &mut self.profiles {
+            if let Some(duty) = &mut profile.duty_cycle {
+                duty.sanitize();
+            }
+            profile.click_region = profile.click_region.map(Region::normalized);
+            if !profile.cps_range.is_valid() {
+                let range = profile.cps_range.sanitized();
+                println!(
+                    "[WARNING] Profile {}: cps_range {} is out of range, using {}",
+                    profile.name, profile.cps_range, range
+                );
+                profile.cps_range = range;
+            }
+            if let Some(Err(e)) = profile.verify.as_mut().map(ClickVerify::validate) {
+                println!(
+                    "[WARNING] Profile {}: ignoring click verification: {}",
+                    profile.name, e
+                );
+                profile.verify = None;
+            }
+            if let Some(Err(e)) = profile.pattern.as_deref().map(ClickPattern::parse) {
+                println!(
+                    "[WARNING] Profile {}: ignoring pattern: {}",
+                    profile.name, e
+                );
+                profile.pattern = None;
+            }
+            if let Some(Err(e)) = profile.key_rotation.as_deref().map(KeyRotation::parse) {
+                println!(
+                    "[WARNING] Profile {}: ignoring key rotation: {}",
+                    profile.name, e
+                );
+                profile.key_rotation = None;
+            }
+            profile
+                .schedules
+                .retain(|schedule| match schedule.validate() {
+                    Ok(_) => true,
+                    Err(e) => {
+                        println!(
+                            "[WARNING] Profile {}: ignoring schedule '{}': {}",
+                            profile.name, schedule.cron, e
+                        );
+                        false
+                    }
+                });
+            for (i, step) in profile.sequence.iter().enumerate() {
+                for problem in step.conditions().filter_map(|c| c.validate()) {
+                    println!(
+                        "[WARNING] Profile {}, step {}: {}",
+                        profile.name,
+                        i + 1,
+                        problem
+                    );
+                }
+            }
+        }
+        quick_slots::sanitize(&mut self.quick_slots, self.profiles.len());
+        let profiles = &self.profiles;
+        self.profile_rules.retain(|rule| {
+            let problem = rule.window.validate().err().or_else(|| {
+                (!profiles.iter().any(|p| p.name == rule.profile))
+                    .then(|| format!("no profile named '{}'", rule.profile))
+            });
+            if let Some(problem) = &problem {
+                println!(
+                    "[WARNING] Ignoring profile rule for {}: {}",
+                    rule.window, problem
+                );
+            }
+            problem.is_none()
+        });
+        self.blocked_windows
+            .retain(|window| match window.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    println!("[WARNING] Ignoring blocked window {}: {}", window, e);
+                    false
+                }
+            });
+        if let Some(motion) = &mut self.human_motion {
+            motion.sanitize();
+        }
+        self.cps_caps.retain(|cap| {
+            let problem = cap.window.validate().err().or_else(|| {
+                (!(MIN_CPS..=MAX_CPS).contains(&cap.max_cps))
+                    .then(|| format!("max_cps must be {}-{}", MIN_CPS, MAX_CPS))
+            });
+            if let Some(problem) = &problem {
+                println!("[WARNING] Ignoring CPS cap for {}: {}", cap.window, problem);
+            }
+            problem.is_none()
+        });
+        if self.selected_button >= BUTTON_MODES.len() {
+            self.selected_button = 0;
+        }
+        self.alternate_order.retain(|&button| button <= 1);
+        if self.alternate_order.is_empty() {
+            self.alternate_order = vec![0, 1];
+        }
+        if sequence::parse_key(&self.hold_key).is_none() {
+            println!("[WARNING] Unknown hold_key '{}', using w", self.hold_key);
+            self.hold_key = "w".to_string();
+        }
+        self.scroll_amount = self.scroll_amount.clamp(1, MAX_SCROLL_AMOUNT);
+        if let Some(chord) = &mut self.tap_trigger {
+            chord.sanitize();
+        }
+        self.cps_step = self.cps_step.clamp(1, MAX_CPS_STEP);
+        self.slow_interval_secs = self
+            .slow_interval_secs
+            .map(|secs| secs.clamp(interval::MIN_SECS, interval::MAX_SECS));
+        self.anti_afk.sanitize();
+        self.tones.sanitize();
+        self.calibration.sanitize();
+        self.second_channel = self.second_channel.filter(SecondChannel::is_valid);
+        for problem in self.keymap.validate() {
+            println!("[WARNING] Keymap: {}", problem);
+        }
+        self.webhooks.retain(|webhook| match webhook.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                println!("[WARNING] Ignoring webhook: {}", e);
+                false
+            }
+        });
+        self.api_tokens.retain(|token| {
+            let long_enough = token.token.len() >= auth::MIN_TOKEN_LEN;
+            if !long_enough {
+                println!(
+                    "[WARNING] Ignoring API token '{}': shorter than {} characters",
+                    token.name,
+                    auth::MIN_TOKEN_LEN
+                );
+            }
+            long_enough
+        });
+        if let Some(Err(e)) = self.mqtt.as_mut().map(MqttConfig::validate) {
+            println!("[WARNING] Ignoring MQTT settings: {}", e);
+            self.mqtt = None;
+        }
+        for problem in self.midi.iter_mut().flat_map(MidiConfig::validate) {
+            println!("[WARNING] MIDI: {}", problem);
+        }
+    }
+
+    // Entries of the CPS list in display order
+    fn cps_choices(&self) -> Vec<CpsChoice> {
+        let mut choices: Vec<CpsChoice> =
+            (0..self.cps_presets.len()).map(CpsChoice::Preset).collect();
+        if self.custom_cps_value.is_some() {
+            choices.push(CpsChoice::Custom);
+        }
+        if self.slow_interval_secs.is_some() {
+            choices.push(CpsChoice::Slow);
+        }
+        choices.extend(HumanPattern::ALL.iter().map(|&p| CpsChoice::Pattern(p)));
+        choices.push(CpsChoice::AntiAfk);
+        choices
+    }
+
+    fn current_choice(&self) -> CpsChoice {
+        if self.using_anti_afk {
+            CpsChoice::AntiAfk
+        } else if let Some(pattern) = self.human_pattern {
+            CpsChoice::Pattern(pattern)
+        } else if self.using_slow_interval && self.slow_interval_secs.is_some() {
+            CpsChoice::Slow
+        } else if self.using_custom_cps {
+            CpsChoice::Custom
+        } else {
+            CpsChoice::Preset(self.selected_preset)
+        }
+    }
+
+    fn apply_choice(&mut self, choice: CpsChoice) {
+        self.human_pattern = None;
+        self.using_custom_cps = false;
+        self.using_slow_interval = false;
+        self.using_anti_afk = false;
+        match choice {
+            CpsChoice::Preset(i) => self.selected_preset = i,
+            CpsChoice::Custom => self.using_custom_cps = true,
+            CpsChoice::Slow => self.using_slow_interval = true,
+            CpsChoice::Pattern(p) => self.human_pattern = Some(p),
+            CpsChoice::AntiAfk => self.using_anti_afk = true,
+        }
+    }
+
+    // The preset editor keeps the selection on the same preset as the list
+    // changes around it
+    fn insert_preset(&mut self, at: usize, cps: f64) {
+        self.cps_presets.insert(at, cps);
+        if self.selected_preset >= at && self.cps_presets.len() > 1 {
+            self.selected_preset += 1;
+        }
+    }
+
+    fn remove_preset(&mut self, i: usize) {
+        self.cps_presets.remove(i);
+        if self.selected_preset > i || self.selected_preset >= self.cps_presets.len() {
+            self.selected_preset = self.selected_preset.saturating_sub(1);
+        }
+    }
+
+    fn swap_presets(&mut self, a: usize, b: usize) {
+        self.cps_presets.swap(a, b);
+        if self.selected_preset == a {
+            self.selected_preset = b;
+        } else if self.selected_preset == b {
+            self.selected_preset = a;
+        }
+    }
+
+    // Largest milestone crossed going from `previous` to `total` clicks
+    fn reached_milestone(&self, previous: u64, total: u64) -> Option<u64> {
+        if total <= previous {
+            return None;
+        }
+        self.milestones
+            .iter()
+            .filter(|&&every| every > 0 && previous / every != total / every)
+            .max()
+            .map(|&every| total / every * every)
+    }
+
+    fn active_profile(&self) -> &Profile {
+        &self.profiles[self.active_profile]
+    }
+
+    fn active_profile_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.active_profile]
+    }
+
+    // Every profile's schedules in one list, as (profile, schedule) indexes
+    fn schedule_entries(&self) -> Vec<(usize, usize)> {
+        self.profiles
+            .iter()
+            .enumerate()
+            .flat_map(|(p, profile)| (0..profile.schedules.len()).map(move |i| (p, i)))
+            .collect()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cps_presets: vec![20.0, 30.0, 40.0, 50.0],
+            selected_preset: 0,
+            custom_cps_value: None,
+            using_custom_cps: false,
+            slow_interval_secs: None,
+            using_slow_interval: false,
+            anti_afk: AntiAfk::default(),
+            using_anti_afk: false,
+            human_pattern: None,
+            human_motion: None,
+            selected_button: 0,
+            alternate_order: vec![0, 1],
+            hold_key: "w".to_string(),
+            second_channel: None,
+            scroll_amount: 3,
+            start_delay_secs: 0,
+            idle_start_secs: 0,
+            repeat_text: String::new(),
+            repeat_text_enter: true,
+            coordinate_space: CoordinateSpace::Physical,
+            backend: BackendKind::Enigo,
+            injection_watchdog: true,
+            dry_run: false,
+            toggle_keybind: Some(KeyCombo {
+                mods: 6, // Ctrl+Shift
+                key: "B".to_string(),
+                mode: ActivationMode::Toggle,
+            }),
+            mouse_trigger: None,
+            tap_trigger: None,
+            cps_up_keybind: None,
+            cps_down_keybind: None,
+            pause_keybind: None,
+            cps_step: 5,
+            keymap: Keymap::default(),
+            legacy_statistics: None,
+            run_log: false,
+            calibration: Calibration::default(),
+            sound_enabled: true,
+            audio_device: None,
+            tones: Tones::default(),
+            theme: ThemeName::default(),
+            language: None,
+            overlay_enabled: false,
+            notifications: NotificationPrefs::default(),
+            milestones: vec![1_000, 10_000, 100_000],
+            milestone_sound: true,
+            hooks: Hooks::default(),
+            webhooks: Vec::new(),
+            mqtt: None,
+            deck: None,
+            midi: None,
+            osc: None,
+            web: None,
+            grpc: None,
+            api_tokens: Vec::new(),
+            profiles: vec![Profile::default()],
+            active_profile: 0,
+            quick_slots: Vec::new(),
+            profile_rules: Vec::new(),
+            blocked_windows: Vec::new(),
+            cps_caps: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CpsChoice {
+    Preset(usize),
+    Custom,
+    Slow,
+    Pattern(HumanPattern),
+    AntiAfk,
+}
+
+// Tab cycles through these; indices match Config::selected_button
+const BUTTON_MODES: [&str; 8] = [
+    "Left Click",
+    "Right Click",
+    "Scroll Up",
+    "Scroll Down",
+    "Type Text",
+    "Alternate",
+    "Hold Key",
+    "Hover",
+];
+const TYPE_TEXT_MODE: usize = 4;
+const ALTERNATE_MODE: usize = 5;
+const HOLD_KEY_MODE: usize = 6;
+// Moves the cursor without clicking, for presence and keep-alive
+const HOVER_MODE: usize = 7;
+// Start delays the D key cycles through; any value can be set in the config
+const START_DELAY_PRESETS: [u32; 4] = [0, 3, 5, 10];
+// Idle periods the Z key cycles through, in seconds
+const IDLE_START_PRESETS: [u32; 5] = [0, 60, 120, 300, 600];
+const MAX_REPEAT_TEXT_LEN: usize = 200;
+const MAX_PATTERN_LEN: usize = 200;
+const MAX_SCROLL_AMOUNT: i32 = 50;
+const MAX_CPS_STEP: u32 = 100;
+
+// Main loop update rates while something on screen is counting, and otherwise
+const ACTIVE_UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+const IDLE_UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+
+// Rates above MAX_SAFE_CPS need an explicit confirmation before they apply
+const MAX_SAFE_CPS: f64 = 1000.0;
+const MAX_CPS: f64 = 50_000.0;
+// One click every 100 s
+const MIN_CPS: f64 = 0.01;
+
+// Rates are kept to two decimals so they display the way they were typed
+fn round_cps(cps: f64) -> f64 {
+    (cps * 100.0).round() / 100.0
+}
+
+// A typed rate such as 20, 2.5 or 0.5; upper limits are up to the caller
+fn parse_cps(input: &str) -> Option<f64> {
+    input
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|cps| cps.is_finite())
+        .map(round_cps)
+        .filter(|&cps| cps >= MIN_CPS)
+}
+
+// Snapshot of the click settings the engine thread reads each iteration
+#[derive(Clone, Debug)]
+struct EngineSettings {
+    backend: BackendKind,
+    injection_watchdog: bool,
+    start_delay_secs: u32,
+    notifications: NotificationPrefs,
+    hooks: Arc<Hooks>,
+    webhooks: Arc<[Webhook]>,
+    cps: f64,
+    button: usize,
+    alternate_order: Arc<[usize]>,
+    // Held instead of clicking in the Hold Key mode
+    hold_key: Option<Key>,
+    scroll_amount: i32,
+    repeat_text: Arc<str>,
+    repeat_text_enter: bool,
+    timing_model: TimingModel,
+    cps_range: CpsRange,
+    std_dev_percent: u32,
+    human_pattern: Option<HumanPattern>,
+    human_motion: Option<HumanMotion>,
+    // Slow mode replaces the rate and timing model with a fixed interval
+    slow_interval: Option<Duration>,
+    // Anti-AFK replaces the action as well as the timing
+    anti_afk: Option<AntiAfk>,
+    // Clicks on its own schedule next to the main action; off with anti-AFK
+    second_channel: Option<SecondChannel>,
+    sequence: Option<Arc<Vec<SequenceStep>>>,
+    click_pattern: Option<Arc<ClickPattern>>,
+    // Replaces the repeat text in the Type Text mode
+    key_rotation: Option<Arc<KeyRotation>>,
+    drag: Option<DragAction>,
+    click_position: Option<Point>,
+    return_to_origin: bool,
+    verify: Option<ClickVerify>,
+    hover_points: Arc<[Point]>,
+    modifier: Option<ClickModifier>,
+    duty_cycle: Option<DutyCycle>,
+    // Runs go to the run log under this profile name; dry runs never do
+    run_log: bool,
+    profile_name: Arc<str>,
+    calibration: Calibration,
+    blocked_windows: Arc<[WindowMatch]>,
+    cps_caps: Arc<[CpsCap]>,
+    // Left out when the action moves the cursor itself
+    click_region: Option<Region>,
+}
+
+// The EngineSettings the TUI hands to the engine. The engine keeps its own
+// copy and only locks to take a new one after the version moves, instead of
+// locking and cloning on every click
+struct SharedSettings {
+    settings: Mutex<EngineSettings>,
+    version: AtomicU64,
+    // Rate of the stored settings, readable without the lock
+    cps_bits: AtomicU64,
+}
+
+impl SharedSettings {
+    fn new(settings: EngineSettings) -> Self {
+        Self {
+            cps_bits: AtomicU64::new(settings.cps.to_bits()),
+            settings: Mutex::new(settings),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    fn get(&self) -> EngineSettings {
+        self.settings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn set(&self, settings: EngineSettings) {
+        self.cps_bits
+            .store(settings.cps.to_bits(), Ordering::SeqCst);
+        *self.settings.lock().unwrap_or_else(|e| e.into_inner()) = settings;
+        self.version.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn cps(&self) -> f64 {
+        f64::from_bits(self.cps_bits.load(Ordering::SeqCst))
+    }
+
+    fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    // Replaces cached with the stored settings if they changed since seen
+    fn refresh(&self, seen: &mut u64, cached: &mut EngineSettings) {
+        let version = self.version.load(Ordering::SeqCst);
+        if version != *seen {
+            *seen = version;
+            *cached = self.get();
+        }
+    }
+}
+
+impl EngineSettings {
+    fn from_config(config: &Config) -> Self {
+        let cps = if config.using_custom_cps {
+            config.custom_cps_value.unwrap_or(20.0)
+        } else {
+            config
+                .cps_presets
+                .get(config.selected_preset)
+                .copied()
+                .unwrap_or(20.0)
+        };
+
+        let to_screen = |p: Point| match config.coordinate_space {
+            CoordinateSpace::Physical => p,
+            CoordinateSpace::Logical => dpi::to_physical(p),
+        };
+        let profile = config.active_profile();
+        let steps: Vec<SequenceStep> = profile
+            .sequence
+            .iter()
+            .map(|step| SequenceStep {
+                position: step.position.map(to_screen),
+                target: step.target.map(to_screen),
+                ..step.clone()
+            })
+            .collect();
+
+        let mut settings = Self {
+            backend: if config.dry_run {
+                BackendKind::Null
+            } else {
+                config.backend
+            },
+            injection_watchdog: config.injection_watchdog,
+            start_delay_secs: config.start_delay_secs,
+            notifications: config.notifications,
+            hooks: Arc::new(config.hooks.clone()),
+            webhooks: config.webhooks.clone().into(),
+            cps,
+            button: config.selected_button,
+            alternate_order: Arc::from(config.alternate_order.as_slice()),
+            hold_key: (config.selected_button == HOLD_KEY_MODE
+                && config.current_choice() != CpsChoice::AntiAfk)
+                .then(|| sequence::parse_key(&config.hold_key))
+                .flatten(),
+            scroll_amount: config.scroll_amount,
+            repeat_text: Arc::from(config.repeat_text.as_str()),
+            repeat_text_enter: config.repeat_text_enter,
+            timing_model: profile.timing_model,
+            cps_range: profile.cps_range,
+            std_dev_percent: profile.std_dev_percent,
+            human_pattern: config.human_pattern,
+            // The Hover mode glides even without motion settings of its own
+            human_motion: config
+                .human_motion
+                .or((config.selected_button == HOVER_MODE).then(HumanMotion::default)),
+            slow_interval: match config.current_choice() {
+                CpsChoice::Slow => config.slow_interval_secs.map(Duration::from_secs),
+                _ => None,
+            },
+            anti_afk: (config.current_choice() == CpsChoice::AntiAfk)
+                .then(|| config.anti_afk.clone()),
+            second_channel: config
+                .second_channel
+                .filter(|_| config.current_choice() != CpsChoice::AntiAfk),
+            sequence: (profile.sequence_enabled && !steps.is_empty()).then(|| Arc::new(steps)),
+            click_pattern: profile
+                .pattern
+                .as_deref()
+                .and_then(|pattern| ClickPattern::parse(pattern).ok())
+                .map(Arc::new),
+            key_rotation: profile
+                .key_rotation
+                .as_deref()
+                .filter(|_| config.selected_button == TYPE_TEXT_MODE)
+                .and_then(|rotation| KeyRotation::parse(rotation).ok())
+                .map(Arc::new),
+            drag: profile.drag.map(|drag| DragAction {
+                from: drag.from.map(to_screen),
+                to: to_screen(drag.to),
+                ..drag
+            }),
+            click_position: profile.click_position.map(to_screen),
+            hover_points: profile
+                .hover_points
+                .iter()
+                .copied()
+                .map(to_screen)
+                .collect(),
+            return_to_origin: profile.return_to_origin,
+            verify: profile.verify.clone(),
+            modifier: profile.modifier,
+            duty_cycle: profile.duty_cycle,
+            run_log: config.run_log && cfg!(feature = "sqlite") && !config.dry_run,
+            profile_name: Arc::from(profile.name.as_str()),
+            calibration: config.calibration,
+            blocked_windows: Arc::from(config.blocked_windows.as_slice()),
+            cps_caps: Arc::from(config.cps_caps.as_slice()),
+            click_region: None,
+        };
+        settings.click_region = profile
+            .click_region
+            .filter(|_| !settings.moves_cursor())
+            .map(|region| region.map(to_screen));
+        settings
+    }
+
+    fn sequence_step(&self, click_index: u64) -> Option<&SequenceStep> {
+        let steps = self.sequence.as_ref()?;
+        Some(&steps[(click_index % steps.len() as u64) as usize])
+    }
+
+    // Conditions of the sequence step at this index; other actions always run
+    fn gate(&self, probe: &mut Probe, click_index: u64, vars: &Variables) -> Gate {
+        if self.anti_afk.is_some() {
+            return Gate::Run;
+        }
+        if let Some(step) = self.sequence_step(click_index) {
+            return step.gate(probe, vars);
+        }
+        match self.click_pattern.as_ref().and_then(|p| p.until()) {
+            Some(until) if probe.check(until, vars) => Gate::Stop,
+            _ => Gate::Run,
+        }
+    }
+
+    // Counter the action at this index bumps when it runs
+    fn counter(&self, click_index: u64) -> Option<&str> {
+        if self.anti_afk.is_some() {
+            return None;
+        }
+        self.sequence_step(click_index)?.count.as_deref()
+    }
+
+    // Whether any action moves the cursor, which the idle trigger would
+    // otherwise take for the user returning
+    fn moves_cursor(&self) -> bool {
+        // Anti-AFK input registers as activity whichever action it picks
+        if self.anti_afk.is_some() {
+            return true;
+        }
+        self.click_position.is_some()
+            || self.button == HOVER_MODE
+            || self.drag.is_some()
+            || self.sequence.as_ref().is_some_and(|steps| {
+                steps
+                    .iter()
+                    .any(|s| s.position.is_some() || s.kind == StepKind::Drag)
+            })
+    }
+
+    // Whether the action at this index warps the cursor to a fixed point
+    fn targets_fixed_point(&self, click_index: u64) -> bool {
+        if self.anti_afk.is_some() || (self.sequence.is_none() && self.click_pattern.is_some()) {
+            return false;
+        }
+        match (self.sequence_step(click_index), self.drag) {
+            (Some(step), _) => step.position.is_some(),
+            (None, Some(drag)) => drag.from.is_some(),
+            // Hovering wanders off on purpose
+            (None, None) => self.click_position.is_some() && self.button != HOVER_MODE,
+        }
+    }
+
+    // Point the action at this index clicks a mouse button at, for click
+    // verification; drags, hovering, scrolling and typing have none
+    fn click_target(&self, click_index: u64) -> Option<Point> {
+        if self.anti_afk.is_some() || (self.sequence.is_none() && self.click_pattern.is_some()) {
+            return None;
+        }
+        match (self.sequence_step(click_index), self.drag) {
+            (Some(step), _) => step.position.filter(|_| step.kind == StepKind::Click),
+            (None, Some(_)) => None,
+            (None, None) => self
+                .click_position
+                .filter(|_| self.button < 2 || self.button == ALTERNATE_MODE),
+        }
+    }
+
+    // Name the statistics file the action for click_index under
+    fn action_label(&self, click_index: u64) -> &'static str {
+        if self.anti_afk.is_some() {
+            "Anti-AFK"
+        } else if self.sequence_step(click_index).is_some() {
+            "Sequence"
+        } else if self.click_pattern.is_some() {
+            "Pattern"
+        } else if self.drag.is_some() {
+            "Drag"
+        } else if self.key_rotation.is_some() {
+            "Key Rotation"
+        } else if self.button == ALTERNATE_MODE {
+            // Counted under the button actually pressed
+            BUTTON_MODES[self.alternate_button(click_index)]
+        } else {
+            BUTTON_MODES[self.button.min(BUTTON_MODES.len() - 1)]
+        }
+    }
+
+    // Button the Alternate mode presses on click_index, cycling through the order
+    fn alternate_button(&self, click_index: u64) -> usize {
+        match self.alternate_order.len() {
+            0 => 0,
+            len => self.alternate_order[(click_index % len as u64) as usize],
+        }
+    }
+
+    // Runs one engine action and returns true when it pressed a mouse button;
+    // wheel events and typing drive the rate but aren't counted as clicks
+    fn perform_action(
+        &self,
+        backend: &mut dyn ClickBackend,
+        click_index: u64,
+        vars: &Variables,
+    ) -> bool {
+        if let Some(anti_afk) = &self.anti_afk {
+            return anti_afk.perform(backend);
+        }
+        if let Some(step) = self.sequence_step(click_index) {
+            return step.execute(backend, vars);
+        }
+        if let Some(pattern) = &self.click_pattern {
+            pattern.perform(backend, click_index);
+            return true;
+        }
+        if let Some(drag) = self.drag {
+            drag.perform(backend);
+            return true;
+        }
+
+        if self.button == HOVER_MODE {
+            motion::hover(backend, &self.hover_points, click_index);
+            return false;
+        }
+        if let Some(p) = self.click_position {
+            backend.move_to(p.x, p.y);
+        }
+        match self.button {
+            1 => backend.click(MouseButton::Right),
+            2 => backend.scroll(-self.scroll_amount),
+            3 => backend.scroll(self.scroll_amount),
+            TYPE_TEXT_MODE => match &self.key_rotation {
+                Some(rotation) => rotation.perform(backend, click_index),
+                None => {
+                    backend.type_text(&self.repeat_text);
+                    if self.repeat_text_enter {
+                        backend.key(Key::Return);
+                    }
+                }
+            },
+            ALTERNATE_MODE => {
+                backend.click(sequence::mouse_button(self.alternate_button(click_index)))
+            }
+            _ => backend.click(MouseButton::Left),
+        }
+        self.button < 2 || self.button == ALTERNATE_MODE
+    }
+
+    // The profile's modifier, except for typed text and anti-AFK actions,
+    // which it would turn into shortcuts
+    fn modifier_for(&self, click_index: u64) -> Option<ClickModifier> {
+        let typing = self.sequence_step(click_index).is_none()
+            && self.click_pattern.is_none()
+            && self.drag.is_none()
+            && matches!(self.button, TYPE_TEXT_MODE | HOVER_MODE);
+        self.modifier.filter(|_| self.anti_afk.is_none() && !typing)
+    }
+
+    // perform_action with the modifier held, putting the cursor back
+    // afterwards when return-to-origin applies to this action
+    fn fire(&self, backend: &mut dyn ClickBackend, click_index: u64, vars: &Variables) -> bool {
+        let origin = (self.return_to_origin && self.targets_fixed_point(click_index))
+            .then(|| backend.cursor_position())
+            .flatten();
+        let clicked = modifier::hold(self.modifier_for(click_index), backend, |backend| {
+            self.perform_action(backend, click_index, vars)
+        });
+        if let Some(origin) = origin {
+            backend.move_to(origin.x, origin.y);
+        }
+        clicked
+    }
+
+    // Sequences and human patterns override the rate and timing model entirely
+    fn next_interval(&self, rng: &mut impl Rng, click_index: u64) -> Duration {
+        if let Some(anti_afk) = &self.anti_afk {
+            return anti_afk.next_interval(rng);
+        }
+        if let Some(step) = self.sequence_step(click_index) {
+            return step.delay();
+        }
+        if let Some(pattern) = &self.click_pattern {
+            return pattern.next_interval(click_index);
+        }
+        if let Some(rotation) = &self.key_rotation {
+            return rotation.next_interval(click_index);
+        }
+        if let Some(pattern) = self.human_pattern {
+            return pattern.next_interval(rng, click_index);
+        }
+        if let Some(interval) = self.slow_interval {
+            return interval;
+        }
+
+        let mean = 1_000_000.0 / self.cps;
+        match self.timing_model {
+            TimingModel::Fixed => Duration::from_micros(mean as u64),
+            // Uniform draws each interval between the intervals of the max and
+            // min rates of the range, ignoring the selected CPS
+            TimingModel::Uniform => {
+                let shortest = (1_000_000.0 / self.cps_range.max.max(MIN_CPS)) as u64;
+                let longest = (1_000_000.0 / self.cps_range.min.max(MIN_CPS)) as u64;
+                Duration::from_micros(rng.gen_range(shortest.min(longest)..=longest.max(shortest)))
+            }
+            TimingModel::Gaussian => {
+                let std_dev = mean * self.std_dev_percent as f64 / 100.0;
+                humanize::gaussian_micros(rng, mean, std_dev)
+            }
+        }
+    }
+
+    // Average rate the engine should achieve with these settings
+    fn target_cps(&self) -> f64 {
+        if let Some(anti_afk) = &self.anti_afk {
+            return 1.0 / anti_afk.average_secs();
+        }
+        if let Some(steps) = &self.sequence {
+            return steps.len() as f64 / sequence::cycle_time(steps).as_secs_f64();
+        }
+        if let Some(pattern) = &self.click_pattern {
+            return pattern.cycle_clicks() as f64 / pattern.cycle_time().as_secs_f64();
+        }
+        if let Some(rotation) = &self.key_rotation {
+            return rotation.key_count() as f64 / rotation.cycle_time().as_secs_f64();
+        }
+        if let Some(pattern) = self.human_pattern {
+            return pattern.average_cps();
+        }
+
+        // A drag blocks the engine for its whole move time
+        let rate = self.base_target_cps();
+        match self.drag {
+            Some(drag) if drag.duration_ms > 0 => rate.min(1000.0 / drag.duration_ms as f64),
+            _ => rate,
+        }
+    }
+
+    fn base_target_cps(&self) -> f64 {
+        if let Some(interval) = self.slow_interval {
+            return 1.0 / interval.as_secs_f64();
+        }
+        match self.timing_model {
+            TimingModel::Uniform => 2.0 / (1.0 / self.cps_range.min + 1.0 / self.cps_range.max),
+            TimingModel::Fixed | TimingModel::Gaussian => self.cps,
+        }
+    }
+
+    fn rate_text(&self) -> String {
+        if self.hold_key.is_some() {
+            return "Held while active".to_string();
+        }
+        if let Some(anti_afk) = &self.anti_afk {
+            return format!("Anti-AFK every {}", anti_afk.window());
+        }
+        if let Some(steps) = &self.sequence {
+            return format!(
+                "Sequence ({} steps, {:.2}s cycle)",
+                steps.len(),
+                sequence::cycle_time(steps).as_secs_f64()
+            );
+        }
+        if let Some(pattern) = &self.click_pattern {
+            return format!(
+                "Pattern ({} clicks, {:.2}s cycle)",
+                pattern.cycle_clicks(),
+                pattern.cycle_time().as_secs_f64()
+            );
+        }
+        if let Some(rotation) = &self.key_rotation {
+            return format!(
+                "Rotation ({} keys, {:.2}s cycle)",
+                rotation.key_count(),
+                rotation.cycle_time().as_secs_f64()
+            );
+        }
+        if let Some(pattern) = self.human_pattern {
+            return format!("{} (~{:.0} CPS)", pattern.label(), pattern.average_cps());
+        }
+        let slow = self
+            .slow_interval
+            .map(|interval| format!("Every {}", interval::describe(interval.as_secs())));
+        if let Some(drag) = self.drag {
+            return match slow {
+                Some(every) => format!("{} {}", drag, every.to_lowercase()),
+                None => format!("{} @ {:.0}/s", drag, self.target_cps()),
+            };
+        }
+        if let Some(every) = slow {
+            return every;
+        }
+
+        match self.timing_model {
+            TimingModel::Fixed => format!("{} CPS", self.cps),
+            TimingModel::Uniform => format!("{} CPS (uniform)", self.cps_range),
+            TimingModel::Gaussian => {
+                format!("{} CPS (gaussian ±{}%)", self.cps, self.std_dev_percent)
+            }
+        }
+    }
+}
+
+// Sequence step field currently being typed into
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SequenceField {
+    Position,
+    Target,
+    Duration,
+    Delay,
+    Key,
+}
+
+// What the number typed into the preset editor becomes
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PresetField {
+    Add,
+    Edit,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InputMode {
+    Normal,
+    EditingCps,
+    ConfirmingHighCps,
+    EditingRange,
+    EditingInterval,
+    EditingAntiAfk,
+    EditingDutyCycle,
+    EditingPattern,
+    EditingSecondChannel,
+    EditingKeyRotation,
+    EditingHoldKey,
+    EditingJob,
+    EditingStdDev,
+    EditingScroll,
+    EditingPosition,
+    CapturingRegion,
+    EditingText,
+    EditingSequence,
+    EditingPresets,
+    EditingSchedules,
+    SelectingAudioDevice,
+    SettingKeybind,
+    AwaitingKeybind,
+    ShowingHelp,
+    ShowingStats,
+    CommandPalette,
+    EditingExportPath,
+    EditingImportPath,
+    ConfirmingImport,
+    HotkeyConflict,
+    AssigningSlot,
+}
+
+// Global hotkeys the capture screen can set
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KeybindTarget {
+    Toggle,
+    Faster,
+    Slower,
+    Pause,
+}
+
+#[allow(dead_code)]
+struct TrayManager {
+    tray: TrayItem,
+    flash_active: Arc<AtomicBool>,
+    flash_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl TrayManager {
+    // A daemon has no TUI of its own to show, so Show opens a terminal
+    // attached to it instead. Exit quits the way the quit key does, saving
+    // the config and restoring the terminal
+    fn new(
+        show_tui: Arc<AtomicBool>,
+        auto_clicker_running: Arc<AtomicBool>,
+        daemon: bool,
+        events: mpsc::Sender<AppEvent>,
+    ) -> Option<Self> {
+        let show_tui_clone = Arc::clone(&show_tui);
+        let running_clone = Arc::clone(&auto_clicker_running);
+
+        let mut tray = TrayItem::new("BClicker Pro", IconSource::Resource("")).ok()?;
+
+        tray.add_menu_item(&t!("tray-show"), move || {
+            if daemon {
+                daemon::open_attached_terminal();
+            } else {
+                show_tui_clone.store(true, Ordering::SeqCst);
+            }
+        })
+        .ok()?;
+
+        tray.add_menu_item(&t!("tray-toggle"), move || {
+            let current = running_clone.load(Ordering::SeqCst);
+            running_clone.store(!current, Ordering::SeqCst);
+        })
+        .ok()?;
+
+        tray.add_menu_item(&t!("tray-exit"), move || {
+            let _ = events.send(AppEvent::Quit);
+        })
+        .ok()?;
+
+        Some(Self {
+            tray,
+            flash_active: Arc::new(AtomicBool::new(false)),
+            flash_handle: None,
+        })
+    }
+
+    fn start_flashing(&mut self) {
+        self.flash_active.store(true, Ordering::SeqCst);
+        let flash_active = Arc::clone(&self.flash_active);
+
+        self.flash_handle = Some(thread::spawn(move || {
+            let mut toggle = false;
+            while flash_active.load(Ordering::SeqCst) {
+                toggle = !toggle;
+                thread::sleep(Duration::from_millis(500));
+            }
+        }));
+    }
+
+    fn stop_flashing(&mut self) {
+        self.flash_active.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.flash_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+// Clones share their settings, so the engine thread's copy follows changes
+// made in the TUI
+#[derive(Clone)]
+struct AudioManager {
+    enabled: Arc<AtomicBool>,
+    // Output device by name; None plays through the system default
+    device: Arc<Mutex<Option<String>>>,
+    tones: Arc<Mutex<Tones>>,
+}
+
+impl AudioManager {
+    fn new(enabled: bool, device: Option<String>, tones: Tones) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            device: Arc::new(Mutex::new(device)),
+            tones: Arc::new(Mutex::new(tones)),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    fn set_device(&self, device: Option<String>) {
+        *self.device.lock().unwrap_or_else(|e| e.into_inner()) = device;
+    }
+
+    fn set_tones(&self, tones: Tones) {
+        *self.tones.lock().unwrap_or_else(|e| e.into_inner()) = tones;
+    }
+
+    fn tones(&self) -> Tones {
+        *self.tones.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Names of the output devices of the default audio host
+    fn output_devices() -> Vec<String> {
+        cpal::default_host()
+            .output_devices()
+            .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    // A device that has gone away, e.g. unplugged headphones, falls back to
+    // the default rather than going silent
+    fn open_stream(device: Option<&str>) -> Option<(OutputStream, OutputStreamHandle)> {
+        let named = device.and_then(|name| {
+            cpal::default_host()
+                .output_devices()
+                .ok()?
+                .find(|d| d.name().is_ok_and(|n| n == name))
+        });
+        match named {
+            Some(device) => OutputStream::try_from_device(&device).ok(),
+            None => OutputStream::try_default().ok(),
+        }
+    }
+
+    fn play_tone(&self, tone: Tone) {
+        if !self.is_enabled() || tone.duration_ms == 0 {
+            return;
+        }
+
+        let device = self
+            .device
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        thread::spawn(move || {
+            if let Some((_stream, stream_handle)) = Self::open_stream(device.as_deref()) {
+                if let Ok(sink) = Sink::try_new(&stream_handle) {
+                    sink.append(tone.source().amplify(0.1));
+                    sink.sleep_until_end();
+                }
+            }
+        });
+    }
+
+    fn play_start_sound(&self) {
+        self.play_tone(self.tones().start);
+    }
+
+    fn play_stop_sound(&self) {
+        self.play_tone(self.tones().stop);
+    }
+
+    fn play_countdown_beep(&self) {
+        self.play_tone(self.tones().countdown);
+    }
+
+    fn play_milestone_sound(&self) {
+        self.play_tone(self.tones().milestone);
+    }
+
+    fn toggle_sound(&self) {
+        self.set_enabled(!self.is_enabled());
+    }
+}
+
+struct App {
+    config: Config,
+    // Startup overrides from the environment and CLI, kept out of the file
+    overrides: Overrides,
+    auto_clicker_running: Arc<AtomicBool>,
+    // Holds a run where it is without stopping it; only means anything while
+    // running, and cleared by the engine whenever clicking starts or stops
+    auto_clicker_paused: Arc<AtomicBool>,
+    // Seconds left before clicking starts; 0 when no countdown is running
+    countdown: Arc<AtomicU32>,
+    overlay_enabled: Arc<AtomicBool>,
+    custom_cps_input: String,
+    input_mode: InputMode,
+    keybind_wait_start: Option<Instant>,
+    hotkey_state: Arc<Mutex<HotkeyState>>,
+    // Last status drawn, to notice when the hotkey thread reports back
+    hotkey_status_seen: HotkeyStatus,
+    nudge_status_seen: [HotkeyStatus; 2],
+    pause_status_seen: HotkeyStatus,
+    // Which global hotkey the capture screen is setting
+    keybind_target: KeybindTarget,
+    // Why side buttons and tapped keys can't be listened to, if they can't
+    input_hook_error: Option<String>,
+    session_start: Instant,
+    #[allow(dead_code)]
+    tray_manager: Option<TrayManager>,
+    show_tui: Arc<AtomicBool>,
+    engine_settings: Arc<SharedSettings>,
+    range_input: String,
+    interval_input: String,
+    anti_afk_input: String,
+    duty_input: String,
+    pattern_input: String,
+    second_channel_input: String,
+    rotation_input: String,
+    hold_key_input: String,
+    job_input: String,
+    std_dev_input: String,
+    scroll_input: String,
+    position_input: String,
+    // First corner of the click region being captured
+    region_corner: Option<Point>,
+    text_input: String,
+    text_input_enter: bool,
+    palette_query: String,
+    palette_cursor: usize,
+    transfer_input: String,
+    pending_import: Option<PendingImport>,
+    sequence_cursor: usize,
+    sequence_field: Option<SequenceField>,
+    sequence_input: String,
+    preset_cursor: usize,
+    preset_field: Option<PresetField>,
+    preset_input: String,
+    schedule_cursor: usize,
+    // Text of a schedule being added, None while browsing
+    schedule_input: Option<String>,
+    // Wall clock minute the schedules were last checked against
+    schedule_checked_minute: i64,
+    // When the run a schedule started is due to stop
+    scheduled_stop: Option<Instant>,
+    // The focused window as the focus watcher last saw it
+    focused_window: SharedFocus,
+    // While a profile rule is in effect: the profile it switched to and the
+    // one to go back to when focus moves on
+    rule_switch: Option<(usize, usize)>,
+    // Output devices found when the picker opened; the list shows "System
+    // default" above them
+    audio_devices: Vec<String>,
+    audio_device_cursor: usize,
+    stats_tracker: Arc<Mutex<Statistics>>,
+    // Last statistics written to the stats file, and when that was checked
+    saved_stats: Statistics,
+    stats_saved_at: Instant,
+    // Run log summary for the statistics view; None when run_log is off
+    run_history: Option<Result<RunHistory, String>>,
+    rate_status: Arc<Mutex<RateStatus>>,
+    // One-off click jobs the engine runs ahead of the main clicking
+    jobs: JobQueue,
+    // Set by the engine supervisor when the engine thread panics
+    engine_fault: Arc<Mutex<Option<EngineFault>>>,
+    engine_fault_seen: Option<EngineFault>,
+    // Left for the engine when the app ends a run itself, for the webhooks
+    stop_reason: StopReason,
+    main_cache: MainScreenCache,
+    rate_warning_shown: bool,
+    // The engine stopped the last run because its input wasn't arriving
+    injection_blocked_shown: bool,
+    last_live_refresh: Instant,
+    pending_high_cps: Option<f64>,
+    // Total clicks at the last milestone check
+    milestone_checked_total: u64,
+    idle_monitor: IdleMonitor,
+    benchmark_running: Arc<AtomicBool>,
+    benchmark_report: Arc<Mutex<Option<BenchmarkReport>>>,
+    benchmark_in_progress: bool,
+    theme: Theme,
+    audio_manager: AudioManager,
+    help_scroll: usize,
+    should_quit: bool,
+    needs_redraw: bool, // FIXED: Only redraw when needed
+}
+
+impl App {
+    fn new(requested: &[Override], dry_run: bool) -> Self {
+        let mut config = load_config();
+        let overrides = Overrides::apply(&mut config, requested);
+        config.sanitize();
+        config.dry_run = dry_run;
+        let mut statistics = stats::load();
+        statistics.total_sessions += 1;
+        statistics.session_clicks = 0;
+        statistics.last_session_start = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let engine_settings = EngineSettings::from_config(&config);
+
+        let theme = Theme::named(config.theme);
+        i18n::set_language(config.language.unwrap_or_else(Language::detect));
+        let audio_manager = AudioManager::new(
+            config.sound_enabled,
+            config.audio_device.clone(),
+            config.tones,
+        );
+        let total_clicks = statistics.total_clicks;
+
+        Self {
+            config: config.clone(),
+            overrides,
+            auto_clicker_running: Arc::new(AtomicBool::new(false)),
+            auto_clicker_paused: Arc::new(AtomicBool::new(false)),
+            countdown: Arc::new(AtomicU32::new(0)),
+            overlay_enabled: Arc::new(AtomicBool::new(config.overlay_enabled)),
+            custom_cps_input: String::new(),
+            input_mode: InputMode::Normal,
+            keybind_wait_start: None,
+            hotkey_state: Arc::new(Mutex::new(HotkeyState {
+                requested: config.toggle_keybind.clone(),
+                status: HotkeyStatus::Unset,
+                nudge_requested: [
+                    config.cps_up_keybind.clone(),
+                    config.cps_down_keybind.clone(),
+                ],
+                nudge_status: [HotkeyStatus::Unset, HotkeyStatus::Unset],
+                pending_nudges: 0,
+                pause_requested: config.pause_keybind.clone(),
+                pause_status: HotkeyStatus::Unset,
+                pause_pressed: false,
+                capturing_region: false,
+                corner_pressed: false,
+                mouse_trigger: config.mouse_trigger,
+                tap_trigger: config.tap_trigger.clone(),
+                wake: None,
+            })),
+            hotkey_status_seen: HotkeyStatus::Unset,
+            nudge_status_seen: [HotkeyStatus::Unset, HotkeyStatus::Unset],
+            pause_status_seen: HotkeyStatus::Unset,
+            keybind_target: KeybindTarget::Toggle,
+            input_hook_error: None,
+            session_start: Instant::now(),
+            tray_manager: None,
+            show_tui: Arc::new(AtomicBool::new(true)),
+            engine_settings: Arc::new(SharedSettings::new(engine_settings)),
+            range_input: String::new(),
+            interval_input: String::new(),
+            anti_afk_input: String::new(),
+            duty_input: String::new(),
+            pattern_input: String::new(),
+            second_channel_input: String::new(),
+            rotation_input: String::new(),
+            hold_key_input: String::new(),
+            job_input: String::new(),
+            std_dev_input: String::new(),
+            scroll_input: String::new(),
+            position_input: String::new(),
+            region_corner: None,
+            text_input: String::new(),
+            text_input_enter: true,
+            palette_query: String::new(),
+            palette_cursor: 0,
+            transfer_input: String::new(),
+            pending_import: None,
+            sequence_cursor: 0,
+            sequence_field: None,
+            sequence_input: String::new(),
+            preset_cursor: 0,
+            preset_field: None,
+            preset_input: String::new(),
+            schedule_cursor: 0,
+            schedule_input: None,
+            schedule_checked_minute: 0,
+            scheduled_stop: None,
+            focused_window: Arc::new(Mutex::new(None)),
+            rule_switch: None,
+            audio_devices: Vec::new(),
+            audio_device_cursor: 0,
+            stats_tracker: Arc::new(Mutex::new(statistics)),
+            // Never matches, so the new session is written on the first save
+            saved_stats: Statistics::default(),
+            rate_status: Arc::new(Mutex::new(RateStatus::default())),
+            jobs: JobQueue::new(),
+            engine_fault: Arc::new(Mutex::new(None)),
+            engine_fault_seen: None,
+            stop_reason: Arc::new(Mutex::new(None)),
+            main_cache: MainScreenCache::default(),
+            stats_saved_at: Instant::now(),
+            run_history: None,
+            rate_warning_shown: false,
+            injection_blocked_shown: false,
+            last_live_refresh: Instant::now(),
+            pending_high_cps: None,
+            milestone_checked_total: total_clicks,
+            idle_monitor: IdleMonitor::new(),
+            benchmark_running: Arc::new(AtomicBool::new(false)),
+            benchmark_report: Arc::new(Mutex::new(None)),
+            benchmark_in_progress: false,
+            theme,
+            audio_manager,
+            help_scroll: 0,
+            should_quit: false,
+            needs_redraw: true,
+        }
+    }
+
+    fn save_config(&mut self) {
+        save_config(&self.overrides.for_saving(&self.config));
+    }
+
+    // Writes the stats file when anything changed since the last write
+    fn save_statistics(&mut self) {
+        let stats = self
+            .stats_tracker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if stats != self.saved_stats {
+            stats::save(&stats);
+            self.saved_stats = stats;
+        }
+        self.stats_saved_at = Instant::now();
+    }
+
+    fn get_engine_settings(&self) -> EngineSettings {
+        self.engine_settings.get()
+    }
+
+    fn sync_engine_settings(&mut self) {
+        self.engine_settings
+            .set(EngineSettings::from_config(&self.config));
+        self.needs_redraw = true;
+    }
+
+    fn set_capturing_region(&self, capturing: bool) {
+        let mut state = self.hotkey_state.lock().unwrap_or_else(|e| e.into_inner());
+        state.capturing_region = capturing;
+        state.corner_pressed = false;
+    }
+
+    // Takes the cursor position as the next corner of the click region; the
+    // second corner sets the region on the active profile
+    fn mark_region_corner(&mut self) {
+        let Some(position) = pointer::Pointer::new().position() else {
+            self.show_notification(
+                NotifyEvent::Error,
+                &t!("title-region"),
+                &t!("notify-region-unreadable"),
+            );
+            self.finish_region_capture();
+            return;
+        };
+        let position = match self.config.coordinate_space {
+            CoordinateSpace::Physical => position,
+            CoordinateSpace::Logical => dpi::to_logical(position),
+        };
+        let Some(first) = self.region_corner else {
+            self.region_corner = Some(position);
+            // Also marked by the hotkey, outside handle_input
+            self.main_cache.built_for = None;
+            self.needs_redraw = true;
+            return;
+        };
+        let region = Region::from_corners(first, position);
+        self.config.active_profile_mut().click_region = Some(region);
+        self.sync_engine_settings();
+        let message = if self.get_engine_settings().click_region.is_some() {
+            t!("notify-region", region = region.to_string())
+        } else {
+            t!("notify-region-unused", region = region.to_string())
+        };
+        self.show_notification(NotifyEvent::Info, &t!("title-region"), &message);
+        self.finish_region_capture();
+    }
+
+    fn finish_region_capture(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.region_corner = None;
+        self.set_capturing_region(false);
+        self.needs_redraw = true;
+    }
+
+    fn get_rate_status(&self) -> RateStatus {
+        *self.rate_status.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update_cps(&mut self) {
+        self.sync_engine_settings();
+    }
+
+    fn get_current_button_text(&self) -> String {
+        let mode = BUTTON_MODES
+            .get(self.config.selected_button)
+            .copied()
+            .unwrap_or(BUTTON_MODES[0]);
+        if self.config.selected_button == TYPE_TEXT_MODE {
+            let rotation = self.config.active_profile().key_rotation.as_deref();
+            if let Some(Ok(rotation)) = rotation.map(KeyRotation::parse) {
+                return format!("Keys {}", rotation.summary());
+            }
+            return format!(
+                "Type \"{}\"{}",
+                self.config.repeat_text,
+                if self.config.repeat_text_enter {
+                    " ⏎"
+                } else {
+                    ""
+                }
+            );
+        }
+        if self.config.selected_button == HOLD_KEY_MODE {
+            return format!("Hold {}", self.config.hold_key);
+        }
+        if self.config.selected_button == HOVER_MODE {
+            return match self.config.active_profile().hover_points.len() {
+                0 => "Hover ⬜".to_string(),
+                points => format!("Hover {} points", points),
+            };
+        }
+        let mode = if self.config.selected_button == ALTERNATE_MODE {
+            let order: Vec<&str> = self
+                .config
+                .alternate_order
+                .iter()
+                .map(|&button| if button == 1 { "R" } else { "L" })
+                .collect();
+            format!("{} {}", mode, order.join("→"))
+        } else {
+            mode.to_string()
+        };
+        let profile = self.config.active_profile();
+        let mode = match profile.modifier {
+            Some(modifier) => format!("{}+{}", modifier.label(), mode),
+            None => mode,
+        };
+        match profile.click_position {
+            Some(p) if profile.return_to_origin => format!("{} @ {} ↩", mode, p),
+            Some(p) => format!("{} @ {}", mode, p),
+            None => mode,
+        }
+    }
+
+    // The engine thread dies with the process, so a key held by the hold mode
+    // has to be let go of here or it stays down in the OS
+    fn release_held_key(&self) {
+        let settings = self.engine_settings.get();
+        if let (Some(key), true) = (
+            settings.hold_key,
+            self.auto_clicker_running.load(Ordering::SeqCst),
+        ) {
+            self.auto_clicker_running.store(false, Ordering::SeqCst);
+            let mut backend = settings.backend.create();
+            backend.key_up(key);
+            backend.flush();
+        }
+    }
+
+    fn cycle_button(&mut self) {
+        self.config.selected_button = (self.config.selected_button + 1) % BUTTON_MODES.len();
+        self.sync_engine_settings();
+    }
+
+    fn move_selection_up(&mut self) {
+        let choices = self.config.cps_choices();
+        let current = self.config.current_choice();
+        let idx = choices.iter().position(|&c| c == current).unwrap_or(0);
+        let prev = if idx == 0 { choices.len() - 1 } else { idx - 1 };
+        self.config.apply_choice(choices[prev]);
+        self.update_cps();
+    }
+
+    fn move_selection_down(&mut self) {
+        let choices = self.config.cps_choices();
+        let current = self.config.current_choice();
+        let idx = choices.iter().position(|&c| c == current).unwrap_or(0);
+        self.config.apply_choice(choices[(idx + 1) % choices.len()]);
+        self.update_cps();
+    }
+
+    fn handle_sequence_input(&mut self, key_event: crossterm::event::KeyEvent) {
+        if let Some(field) = self.sequence_field {
+            // Type steps hold a line of text in the key field
+            let max_len = if field == SequenceField::Key {
+                MAX_REPEAT_TEXT_LEN
+            } else {
+                16
+            };
+            match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.sequence_input.trim().to_string();
+                    let cursor = self.sequence_cursor;
+                    if let Some(step) = self.config.active_profile_mut().sequence.get_mut(cursor) {
+                        match field {
+                            SequenceField::Position => {
+                                if input.is_empty() {
+                                    step.position = None;
+                                } else if let Some(p) = Point::parse(&input) {
+                                    step.position = Some(p);
+                                }
+                            }
+                            SequenceField::Target => {
+                                if input.is_empty() {
+                                    step.target = None;
+                                } else if let Some(p) = Point::parse(&input) {
+                                    step.target = Some(p);
+                                }
+                            }
+                            SequenceField::Duration => {
+                                if let Ok(ms) = input.parse::<u64>() {
+                                    step.duration_ms = ms;
+                                }
+                            }
+                            SequenceField::Delay => {
+                                if let Ok(ms) = input.parse::<u64>() {
+                                    step.delay_ms = ms;
+                                }
+                            }
+                            SequenceField::Key => step.key = input,
+                        }
+                    }
+                    self.sequence_field = None;
+                    self.sync_engine_settings();
+                }
+                KeyCode::Esc => {
+                    self.sequence_field = None;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.sequence_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.sequence_input.len() < max_len => {
+                    let allowed = match field {
+                        SequenceField::Position | SequenceField::Target => {
+                            c.is_ascii_digit() || c == ',' || c == '-'
+                        }
+                        SequenceField::Duration | SequenceField::Delay => c.is_ascii_digit(),
+                        SequenceField::Key => true,
+                    };
+                    if allowed {
+                        self.sequence_input.push(c);
+                        self.needs_redraw = true;
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let cursor = self.sequence_cursor;
+        let len = self.config.active_profile().sequence.len();
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('l') => {
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') if cursor + 1 < len => {
+                self.sequence_cursor += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if cursor > 0 => {
+                self.sequence_cursor -= 1;
+            }
+            KeyCode::Char('J') if cursor + 1 < len => {
+                self.config
+                    .active_profile_mut()
+                    .sequence
+                    .swap(cursor, cursor + 1);
+                self.sequence_cursor += 1;
+            }
+            KeyCode::Char('K') if cursor > 0 => {
+                self.config
+                    .active_profile_mut()
+                    .sequence
+                    .swap(cursor, cursor - 1);
+                self.sequence_cursor -= 1;
+            }
+            KeyCode::Char('a') => {
+                let at = if len == 0 { 0 } else { cursor + 1 };
+                self.config
+                    .active_profile_mut()
+                    .sequence
+                    .insert(at, SequenceStep::default());
+                self.sequence_cursor = at;
+            }
+            KeyCode::Char('c') if cursor < len => {
+                let sequence = &mut self.config.active_profile_mut().sequence;
+                sequence.insert(cursor + 1, sequence[cursor].clone());
+                self.sequence_cursor += 1;
+            }
+            KeyCode::Char('d') if cursor < len => {
+                self.config.active_profile_mut().sequence.remove(cursor);
+                self.sequence_cursor = cursor.min(len.saturating_sub(2));
+            }
+            KeyCode::Char('t') if cursor < len => {
+                let step = &mut self.config.active_profile_mut().sequence[cursor];
+                step.kind = step.kind.next();
+            }
+            KeyCode::Char('b') if cursor < len => {
+                let step = &mut self.config.active_profile_mut().sequence[cursor];
+                step.button = (step.button + 1) % 2;
+            }
+            KeyCode::Char(c @ ('p' | 'g' | 'u' | 'w' | 'e')) if cursor < len => {
+                let step = &self.config.active_profile().sequence[cursor];
+                let (field, current) = match c {
+                    'p' => (
+                        SequenceField::Position,
+                        step.position.map(|p| p.to_string()).unwrap_or_default(),
+                    ),
+                    'g' => (
+                        SequenceField::Target,
+                        step.target.map(|p| p.to_string()).unwrap_or_default(),
+                    ),
+                    'u' => (SequenceField::Duration, step.duration_ms.to_string()),
+                    'w' => (SequenceField::Delay, step.delay_ms.to_string()),
+                    _ => (SequenceField::Key, step.key.clone()),
+                };
+                self.sequence_field = Some(field);
+                self.sequence_input = current;
+            }
+            KeyCode::Char('x') => {
+                let profile = self.config.active_profile_mut();
+                profile.sequence_enabled = !profile.sequence_enabled;
+            }
+            _ => {}
+        }
+        self.sync_engine_settings();
+    }
+
+    fn handle_preset_input(&mut self, key_event: crossterm::event::KeyEvent) {
+        if let Some(field) = self.preset_field {
+            match key_event.code {
+                KeyCode::Enter => {
+                    match parse_cps(&self.preset_input) {
+                        Some(cps) if cps <= MAX_SAFE_CPS => {
+                            let cursor = self.preset_cursor;
+                            match field {
+                                PresetField::Add => {
+                                    let at = (cursor + 1).min(self.config.cps_presets.len());
+                                    self.config.insert_preset(at, cps);
+                                    self.preset_cursor = at;
+                                }
+                                PresetField::Edit => self.config.cps_presets[cursor] = cps,
+                            }
+                        }
+                        _ => self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-presets"),
+                            &t!("notify-preset-range", min = MIN_CPS, max = MAX_SAFE_CPS),
+                        ),
+                    }
+                    self.preset_field = None;
+                    self.sync_engine_settings();
+                }
+                KeyCode::Esc => self.preset_field = None,
+                KeyCode::Backspace => {
+                    self.preset_input.pop();
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || c == '.') && self.preset_input.len() < 7 =>
+                {
+                    self.preset_input.push(c);
+                }
+                _ => {}
+            }
+            self.needs_redraw = true;
+            return;
+        }
+
+        let cursor = self.preset_cursor;
+        let len = self.config.cps_presets.len();
+        match key_event.code {
+            code if code == KeyCode::Esc
+                || code == KeyCode::Char('q')
+                || self.config.keymap.is(Action::Presets, code) =>
+            {
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') if cursor + 1 < len => self.preset_cursor += 1,
+            KeyCode::Up | KeyCode::Char('k') if cursor > 0 => self.preset_cursor -= 1,
+            KeyCode::Char('J') if cursor + 1 < len => {
+                self.config.swap_presets(cursor, cursor + 1);
+                self.preset_cursor += 1;
+            }
+            KeyCode::Char('K') if cursor > 0 => {
+                self.config.swap_presets(cursor, cursor - 1);
+                self.preset_cursor -= 1;
+            }
+            KeyCode::Char('a') => {
+                self.preset_field = Some(PresetField::Add);
+                self.preset_input.clear();
+            }
+            KeyCode::Char('e') | KeyCode::Enter => {
+                self.preset_field = Some(PresetField::Edit);
+                self.preset_input = self.config.cps_presets[cursor].to_string();
+            }
+            // The CPS list needs at least one preset to fall back on
+            KeyCode::Char('d') if len > 1 => {
+                self.config.remove_preset(cursor);
+                self.preset_cursor = cursor.min(len - 2);
+            }
+            _ => {}
+        }
+        self.needs_redraw = true;
+        self.sync_engine_settings();
+    }
+
+    fn handle_schedule_input(&mut self, key_event: crossterm::event::KeyEvent) {
+        if let Some(input) = &mut self.schedule_input {
+            match key_event.code {
+                KeyCode::Enter => {
+                    match Schedule::parse(input) {
+                        Ok(schedule) => {
+                            let profile = self.config.active_profile;
+                            self.config.profiles[profile].schedules.push(schedule);
+                            let entries = self.config.schedule_entries();
+                            let added =
+                                (profile, self.config.profiles[profile].schedules.len() - 1);
+                            self.schedule_cursor =
+                                entries.iter().position(|&e| e == added).unwrap_or(0);
+                            self.save_config();
+                        }
+                        Err(e) => {
+                            self.show_notification(NotifyEvent::Info, &t!("title-scheduler"), &e)
+                        }
+                    }
+                    self.schedule_input = None;
+                }
+                KeyCode::Esc => self.schedule_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) if input.len() < 80 => input.push(c),
+                _ => {}
+            }
+            self.needs_redraw = true;
+            return;
+        }
+
+        let entries = self.config.schedule_entries();
+        let cursor = self.schedule_cursor;
+        match key_event.code {
+            code if code == KeyCode::Esc
+                || code == KeyCode::Char('q')
+                || self.config.keymap.is(Action::Scheduler, code) =>
+            {
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') if cursor + 1 < entries.len() => {
+                self.schedule_cursor += 1
+            }
+            KeyCode::Up | KeyCode::Char('k') if cursor > 0 => self.schedule_cursor -= 1,
+            KeyCode::Char('a') => self.schedule_input = Some(String::new()),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some(&(p, i)) = entries.get(cursor) {
+                    let schedule = &mut self.config.profiles[p].schedules[i];
+                    schedule.enabled = !schedule.enabled;
+                    self.save_config();
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(&(p, i)) = entries.get(cursor) {
+                    self.config.profiles[p].schedules.remove(i);
+                    self.schedule_cursor = cursor.min(entries.len().saturating_sub(2));
+                    self.save_config();
+                }
+            }
+            _ => {}
+        }
+        self.needs_redraw = true;
+    }
+
+    fn handle_audio_device_input(&mut self, key_event: crossterm::event::KeyEvent) {
+        let cursor = self.audio_device_cursor;
+        match key_event.code {
+            code if code == KeyCode::Esc
+                || code == KeyCode::Char('q')
+                || self.config.keymap.is(Action::AudioDevice, code) =>
+            {
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') if cursor < self.audio_devices.len() => {
+                self.audio_device_cursor += 1
+            }
+            KeyCode::Up | KeyCode::Char('k') if cursor > 0 => self.audio_device_cursor -= 1,
+            KeyCode::Enter => {
+                let device = cursor
+                    .checked_sub(1)
+                    .and_then(|i| self.audio_devices.get(i))
+                    .cloned();
+                let name = device.clone().unwrap_or_else(|| t!("audio-default"));
+                self.config.audio_device = device.clone();
+                self.audio_manager.set_device(device);
+                self.input_mode = InputMode::Normal;
+                self.show_notification(
+                    NotifyEvent::Info,
+                    &t!("title-audio"),
+                    &t!("notify-audio-output", device = name),
+                );
+                // A test beep, so the choice can be heard right away
+                self.audio_manager.play_start_sound();
+            }
+            _ => {}
+        }
+        self.needs_redraw = true;
+    }
+
+    fn apply_custom_cps(&mut self, val: f64) {
+        self.config.custom_cps_value = Some(val);
+        self.config.apply_choice(CpsChoice::Custom);
+        self.update_cps();
+        self.show_notification(
+            NotifyEvent::Info,
+            &t!("title-cps-updated"),
+            &t!("notify-custom-cps", cps = val.to_string()),
+        );
+    }
+
+    // Rate hotkeys step from whatever rate is in effect into the custom CPS.
+    // They stop at MAX_SAFE_CPS unless the rate was already confirmed above
+    // it, and at 1 CPS unless it was already slower
+    fn nudge_cps(&mut self, steps: i32) {
+        let current = self.engine_settings.cps();
+        let (floor, ceiling) = (current.min(1.0), current.max(MAX_SAFE_CPS));
+        let target =
+            round_cps(current + steps as f64 * self.config.cps_step as f64).clamp(floor, ceiling);
+        self.config.custom_cps_value = Some(target);
+        self.config.apply_choice(CpsChoice::Custom);
+        self.update_cps();
+        self.save_config();
+        self.show_notification(
+            NotifyEvent::Hotkey,
+            "CPS",
+            &t!("notify-cps", cps = target.to_string()),
+        );
+        self.needs_redraw = true;
+    }
+
+    fn store_quick_slot(&mut self, key: u8) {
+        let slot = QuickSlot::capture(key, &self.config);
+        let message = t!(
+            "notify-slot-stored",
+            slot = slot.label(&self.config),
+            key = key
+        );
+        self.config.quick_slots.retain(|s| s.key != key);
+        self.config.quick_slots.push(slot);
+        self.config.quick_slots.sort_by_key(|s| s.key);
+        self.show_notification(NotifyEvent::Info, &t!("title-quick-slot"), &message);
+    }
+
+    fn apply_quick_slot(&mut self, key: u8) {
+        let Some(slot) = self
+            .config
+            .quick_slots
+            .iter()
+            .find(|s| s.key == key)
+            .cloned()
+        else {
+            let message = t!(
+                "notify-slot-empty",
+                key = key,
+                save = self.config.keymap.label(Action::SaveSlot)
+            );
+            self.show_notification(NotifyEvent::Info, &t!("title-quick-slot"), &message);
+            return;
+        };
+        slot.apply(&mut self.config);
+        self.sequence_cursor = 0;
+        self.update_cps();
+        self.show_notification(
+            NotifyEvent::Info,
+            &t!("title-quick-slot"),
+            &slot.label(&self.config),
+        );
+        self.needs_redraw = true;
+    }
+
+    fn start_benchmark(&mut self) {
+        if self.benchmark_in_progress || self.auto_clicker_running.load(Ordering::SeqCst) {
+            return;
+        }
+
+        self.benchmark_in_progress = true;
+        self.benchmark_running.store(true, Ordering::SeqCst);
+        let running = Arc::clone(&self.benchmark_running);
+        let report = Arc::clone(&self.benchmark_report);
+        let config = self.config.clone();
+        thread::spawn(move || {
+            let result = benchmark::run_benchmark(&config);
+            *report.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+            running.store(false, Ordering::SeqCst);
+        });
+        self.needs_redraw = true;
+    }
+
+    // Starts clicking once the user has been away for idle_start_secs and
+    // stops a run it started as soon as the mouse moves again
+    fn update_idle_trigger(&mut self) {
+        if self.config.idle_start_secs == 0 {
+            self.idle_monitor.triggered = false;
+            return;
+        }
+
+        let running = self.auto_clicker_running.load(Ordering::SeqCst);
+        // The clicker's own events reset the system input timer
+        let active = self.idle_monitor.poll(!running);
+
+        if !running {
+            self.idle_monitor.triggered = false;
+            if self.idle_monitor.idle_for()
+                >= Duration::from_secs(self.config.idle_start_secs as u64)
+            {
+                self.idle_monitor.triggered = true;
+                self.auto_clicker_running.store(true, Ordering::SeqCst);
+                self.show_notification(
+                    NotifyEvent::Info,
+                    &t!("title-idle"),
+                    &t!("notify-idle-started"),
+                );
+                self.needs_redraw = true;
+            }
+        } else if self.idle_monitor.triggered
+            && active
+            && !self.get_engine_settings().moves_cursor()
+        {
+            self.idle_monitor.triggered = false;
+            self.leave_stop_reason(t!("notify-idle-stopped"));
+            self.auto_clicker_running.store(false, Ordering::SeqCst);
+            self.show_notification(
+                NotifyEvent::Info,
+                &t!("title-idle"),
+                &t!("notify-idle-stopped"),
+            );
+            self.needs_redraw = true;
+        }
+    }
+
+    // Tells the engine the run it's about to see stop ended on its own
+    fn leave_stop_reason(&self, message: String) {
+        *self.stop_reason.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((WebhookEvent::AutoStop, message));
+    }
+
+    // Starts a profile when one of its schedules matches the current minute
+    // and stops that run once its duration is up
+    fn update_schedules(&mut self) {
+        let running = self.auto_clicker_running.load(Ordering::SeqCst);
+        if let Some(stop_at) = self.scheduled_stop {
+            if !running {
+                // Stopped by hand before the time was up
+                self.scheduled_stop = None;
+            } else if Instant::now() >= stop_at {
+                self.scheduled_stop = None;
+                let profile = self.config.active_profile().name.clone();
+                let message = t!("notify-schedule-stopped", profile = profile);
+                self.leave_stop_reason(message.clone());
+                self.auto_clicker_running.store(false, Ordering::SeqCst);
+                self.show_notification(NotifyEvent::Info, &t!("title-scheduler"), &message);
+                self.needs_redraw = true;
+                return;
+            }
+        }
+
+        let minute = cron::current_minute();
+        if minute == self.schedule_checked_minute {
+            return;
+        }
+        self.schedule_checked_minute = minute;
+        // A run already going, scheduled or not, is left alone
+        if running {
+            return;
+        }
+        let now = LocalTime::now();
+        let due = self
+            .config
+            .profiles
+            .iter()
+            .enumerate()
+            .find_map(|(p, profile)| {
+                profile
+                    .schedules
+                    .iter()
+                    .filter(|s| s.enabled)
+                    .find(|s| CronExpr::parse(&s.cron).is_ok_and(|cron| cron.matches(&now)))
+                    .map(|s| (p, s.clone()))
+            });
+        let Some((profile, schedule)) = due else {
+            return;
+        };
+
+        self.config.active_profile = profile;
+        self.sequence_cursor = 0;
+        self.update_cps();
+        self.scheduled_stop = Some(Instant::now() + Duration::from_secs(schedule.duration_secs));
+        self.auto_clicker_running.store(true, Ordering::SeqCst);
+        self.show_notification(
+            NotifyEvent::Info,
+            &t!("title-scheduler"),
+            &t!(
+                "notify-schedule-started",
+                profile = self.config.profiles[profile].name.clone(),
+                cron = schedule.cron,
+                duration = interval::describe(schedule.duration_secs)
+            ),
+        );
+        self.needs_redraw = true;
+    }
+
+    // Follows the focused window through the profile rules. Once focus moves
+    // on to a window no rule matches, the profile from before comes back,
+    // unless it was changed by hand in the meantime
+    fn apply_profile_rules(&mut self, window: Option<&FocusedWindow>) {
+        let matched = window.and_then(|window| {
+            let rule = self
+                .config
+                .profile_rules
+                .iter()
+                .find(|rule| rule.window.matches(window))?;
+            let profile = self
+                .config
+                .profiles
+                .iter()
+                .position(|p| p.name == rule.profile)?;
+            Some((profile, window))
+        });
+        let active = self.config.active_profile;
+        match (matched, self.rule_switch) {
+            (Some((profile, window)), switch) => {
+                let previous = match switch {
+                    Some((switched_to, previous)) if switched_to == active => previous,
+                    _ => active,
+                };
+                self.rule_switch = Some((profile, previous));
+                if profile != active {
+                    self.switch_profile(profile);
+                    let window = if window.process.is_empty() {
+                        &window.title
+                    } else {
+                        &window.process
+                    };
+                    self.show_notification(
+                        NotifyEvent::Info,
+                        &t!("title-profile-rules"),
+                        &t!(
+                            "notify-rule-switched",
+                            profile = self.config.profiles[profile].name.clone(),
+                            window = window.clone()
+                        ),
+                    );
+                }
+            }
+            (None, Some((switched_to, previous))) => {
+                self.rule_switch = None;
+                if active == switched_to && previous < self.config.profiles.len() {
+                    self.switch_profile(previous);
+                    self.show_notification(
+                        NotifyEvent::Info,
+                        &t!("title-profile-rules"),
+                        &t!(
+                            "notify-rule-restored",
+                            profile = self.config.profiles[previous].name.clone()
+                        ),
+                    );
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn switch_profile(&mut self, profile: usize) {
+        self.config.active_profile = profile;
+        self.sequence_cursor = 0;
+        self.update_cps();
+    }
+
+    fn show_notification(&self, event: NotifyEvent, title: &str, message: &str) {
+        self.config.notifications.send(event, title, message);
+    }
+
+    fn export_config(&mut self, path: &std::path::Path) {
+        self.save_config();
+        match transfer::export(&self.config, path) {
+            Ok(()) => self.show_notification(
+                NotifyEvent::Info,
+                &t!("title-exported"),
+                &t!("notify-exported", path = path.display().to_string()),
+            ),
+            Err(e) => self.show_notification(NotifyEvent::Error, &t!("title-export-failed"), &e),
+        }
+    }
+
+    // Validates the file and shows what it would change before applying
+    fn start_import(&mut self, path: &std::path::Path) {
+        match transfer::prepare_import(&self.config, path) {
+            Ok(pending) if pending.changes.is_empty() => self.show_notification(
+                NotifyEvent::Info,
+                &t!("title-import"),
+                &t!("notify-import-same", path = path.display().to_string()),
+            ),
+            Ok(pending) => {
+                self.pending_import = Some(pending);
+                self.input_mode = InputMode::ConfirmingImport;
+            }
+            Err(e) => self.show_notification(NotifyEvent::Error, &t!("title-import-failed"), &e),
+        }
+    }
+
+    fn apply_import(&mut self, pending: PendingImport) {
+        let dry_run = self.config.dry_run;
+        self.config = pending.config;
+        self.config.dry_run = dry_run;
+        self.audio_manager.set_enabled(self.config.sound_enabled);
+        self.audio_manager
+            .set_device(self.config.audio_device.clone());
+        self.audio_manager.set_tones(self.config.tones);
+        self.theme = Theme::named(self.config.theme);
+        i18n::set_language(self.config.language.unwrap_or_else(Language::detect));
+        self.overlay_enabled
+            .store(self.config.overlay_enabled, Ordering::SeqCst);
+        self.sync_engine_settings();
+
+        self.request_hotkey();
+        self.sync_triggers();
+        let message = t!("notify-imported", count = pending.changes.len());
+        self.show_notification(NotifyEvent::Info, &t!("title-imported"), &message);
+    }
+
+    fn hotkey_status(&self) -> HotkeyStatus {
+        self.hotkey_state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .status
+            .clone()
+    }
+
+    // Hands the configured combos to the hotkey thread to (re-)register; the
+    // thread only reports back on the ones that changed
+    fn request_hotkey(&mut self) {
+        let mut state = self.hotkey_state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.requested != self.config.toggle_keybind {
+            state.requested = self.config.toggle_keybind.clone();
+            state.status = HotkeyStatus::Unset;
+        }
+        let nudges = [
+            self.config.cps_up_keybind.clone(),
+            self.config.cps_down_keybind.clone(),
+        ];
+        for (i, combo) in nudges.into_iter().enumerate() {
+            if state.nudge_requested[i] != combo {
+                state.nudge_requested[i] = combo;
+                state.nudge_status[i] = HotkeyStatus::Unset;
+            }
+        }
+        if state.pause_requested != self.config.pause_keybind {
+            state.pause_requested = self.config.pause_keybind.clone();
+            state.pause_status = HotkeyStatus::Unset;
+        }
+        if let Some(wake) = &state.wake {
+            let _ = wake.send(HotkeyMessage::Changed);
+        }
+    }
+
+    fn sync_triggers(&self) {
+        let mut state = self.hotkey_state.lock().unwrap_or_else(|e| e.into_inner());
+        state.mouse_trigger = self.config.mouse_trigger;
+        state.tap_trigger = self.config.tap_trigger.clone();
+    }
+
+    fn set_keybind(&mut self, mods: u8, key: String) {
+        let message = match self.keybind_target {
+            KeybindTarget::Toggle => {
+                // A new combo keeps the toggle/hold choice of the old one
+                let mode = self
+                    .config
+                    .toggle_keybind
+                    .as_ref()
+                    .map_or_else(ActivationMode::default, |k| k.mode);
+                let combo = KeyCombo { mods, key, mode };
+                let message = t!("notify-new-hotkey", combo = combo.to_string());
+                self.config.toggle_keybind = Some(combo);
+                message
+            }
+            KeybindTarget::Pause => {
+                let combo = KeyCombo {
+                    mods,
+                    key,
+                    mode: ActivationMode::Toggle,
+                };
+                let message = t!("notify-pause-hotkey", combo = combo.to_string());
+                self.config.pause_keybind = Some(combo);
+                message
+            }
+            target => {
+                let combo = KeyCombo {
+                    mods,
+                    key,
+                    mode: ActivationMode::Toggle,
+                };
+                let (slot, sign) = if target == KeybindTarget::Faster {
+                    (&mut self.config.cps_up_keybind, '+')
+                } else {
+                    (&mut self.config.cps_down_keybind, '-')
+                };
+                let message = t!(
+                    "notify-step-hotkey",
+                    combo = combo.to_string(),
+                    step = format!("{}{}", sign, self.config.cps_step)
+                );
+                *slot = Some(combo);
+                message
+            }
+        };
+        self.request_hotkey();
+        self.input_mode = InputMode::Normal;
+        self.show_notification(NotifyEvent::Hotkey, &t!("title-hotkey-updated"), &message);
+        self.needs_redraw = true;
+    }
+
+    // One action with the current settings, through the engine's backend, to
+    // check targeting before a long run. Not counted in the statistics
+    // Variables as they stand outside a run
+    fn variables(&self) -> Variables {
+        let stats = self.stats_tracker.lock().unwrap_or_else(|e| e.into_inner());
+        Variables {
+            total_clicks: stats.total_clicks,
+            counters: stats.counters.clone(),
+            ..Variables::default()
+        }
+    }
+
+    fn test_fire(&mut self) {
+        if self.auto_clicker_running.load(Ordering::SeqCst) {
+            self.show_notification(
+                NotifyEvent::Info,
+                &t!("title-test-fire"),
+                &t!("notify-test-fire-busy"),
+            );
+            return;
+        }
+        let settings = self.get_engine_settings();
+        let mut backend = settings.backend.create();
+        let mut rng = rand::thread_rng();
+        let mut glide = Glide::new(backend.as_mut(), settings.human_motion, &mut rng);
+        settings.fire(&mut glide, 0, &self.variables());
+        backend.flush();
+
+        let message = t!(
+            "notify-test-fire",
+            button = self.get_current_button_text(),
+            backend = backend.name()
+        );
+        self.show_notification(NotifyEvent::Info, &t!("title-test-fire"), &message);
+    }
+
+    // Pausing keeps the run's place, unlike stopping; there is nothing to
+    // pause while stopped
+    fn toggle_pause(&mut self) {
+        if !self.auto_clicker_running.load(Ordering::SeqCst) {
+            self.show_notification(
+                NotifyEvent::Info,
+                &t!("title-pause"),
+                &t!("notify-pause-stopped"),
+            );
+            return;
+        }
+        let paused = !self.auto_clicker_paused.load(Ordering::SeqCst);
+        self.auto_clicker_paused.store(paused, Ordering::SeqCst);
+        let message = if paused {
+            t!("notify-paused")
+        } else {
+            t!("notify-resumed")
+        };
+        self.show_notification(NotifyEvent::Hotkey, &t!("title-pause"), &message);
+        self.needs_redraw = true;
+    }
+
+    fn open_palette(&mut self) {
+        self.input_mode = InputMode::CommandPalette;
+        self.palette_query.clear();
+        self.palette_cursor = 0;
+        self.needs_redraw = true;
+    }
+
+    fn run_command(&mut self, command: Command) {
+        match command {
+            Command::ToggleClicking => {
+                let current = self.auto_clicker_running.load(Ordering::SeqCst);
+                self.auto_clicker_running.store(!current, Ordering::SeqCst);
+            }
+            Command::Run(action) => self.run_action(action),
+        }
+    }
+
+    // Start and stop from the command line, the way the toggle hotkey does
+    // it. Starting is refused while the engine is down for good, since
+    // nothing would click; starting a paused run resumes it
+    fn handle_control(&mut self, control: ipc::Control) -> Result<ipc::ControlReply, String> {
+        let running = self.auto_clicker_running.load(Ordering::SeqCst);
+        let paused = running && self.auto_clicker_paused.load(Ordering::SeqCst);
+        let start = match control {
+            ipc::Control::Start => true,
+            ipc::Control::Stop => false,
+            ipc::Control::Toggle => !running,
+            ipc::Control::SetCps(cps) => return self.set_remote_cps(cps),
+            ipc::Control::Profile(name) => return self.set_remote_profile(&name),
+        };
+        if start && !running {
+            let fault = self
+                .engine_fault
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            if let Some(EngineFault {
+                error,
+                restarted: false,
+            }) = fault
+            {
+                return Err(format!(
+                    "the click engine stopped after an error: {}",
+                    error
+                ));
+            }
+        }
+        let changed = start != running || (start && paused);
+        if start && paused {
+            self.auto_clicker_paused.store(false, Ordering::SeqCst);
+        }
+        self.auto_clicker_running.store(start, Ordering::SeqCst);
+        if changed {
+            self.needs_redraw = true;
+        }
+        Ok(ipc::ControlReply {
+            running: start,
+            changed,
+        })
+    }
+
+    // Rates above MAX_SAFE_CPS still need confirming at the keyboard
+    fn set_remote_cps(&mut self, cps: f64) -> Result<ipc::ControlReply, String> {
+        if cps > MAX_SAFE_CPS {
+            return Err(format!(
+                "rates above {} CPS have to be confirmed in BClicker",
+                MAX_SAFE_CPS
+            ));
+        }
+        // Without a notification, which a knob turning would flood
+        let changed = cps != self.engine_settings.cps();
+        if changed {
+            self.config.custom_cps_value = Some(cps);
+            self.config.apply_choice(CpsChoice::Custom);
+            self.update_cps();
+            self.needs_redraw = true;
+        }
+        Ok(ipc::ControlReply {
+            running: self.auto_clicker_running.load(Ordering::SeqCst),
+            changed,
+        })
+    }
+
+    fn set_remote_profile(&mut self, name: &str) -> Result<ipc::ControlReply, String> {
+        let profile = self
+            .config
+            .profiles
+            .iter()
+            .position(|p| p.name == name)
+            .ok_or_else(|| format!("no profile named '{}'", name))?;
+        let changed = profile != self.config.active_profile;
+        if changed {
+            self.switch_profile(profile);
+            self.needs_redraw = true;
+        }
+        Ok(ipc::ControlReply {
+            running: self.auto_clicker_running.load(Ordering::SeqCst),
+            changed,
+        })
+    }
+
+    fn handle_palette_input(&mut self, key_event: crossterm::event::KeyEvent) {
+        let matches = palette::filter(&self.palette_query);
+        match key_event.code {
+            KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+                if let Some(&command) = matches.get(self.palette_cursor) {
+                    self.run_command(command);
+                }
+            }
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down if self.palette_cursor + 1 < matches.len() => {
+                self.palette_cursor += 1;
+            }
+            KeyCode::Up => {
+                self.palette_cursor = self.palette_cursor.saturating_sub(1);
+            }
+            KeyCode::Backspace => {
+                self.palette_query.pop();
+                self.palette_cursor = 0;
+            }
+            KeyCode::Char(c) if self.palette_query.len() < 40 => {
+                self.palette_query.push(c);
+                self.palette_cursor = 0;
+            }
+            _ => {}
+        }
+        self.needs_redraw = true;
+    }
+
+    // Everything a main screen key can do; also what the palette runs
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => {
+                self.should_quit = true;
+            }
+            Action::ExportConfig => {
+                self.input_mode = InputMode::EditingExportPath;
+                self.transfer_input = transfer::DEFAULT_EXPORT_FILE.to_string();
+                self.needs_redraw = true;
+            }
+            Action::ImportConfig => {
+                self.input_mode = InputMode::EditingImportPath;
+                self.transfer_input = transfer::DEFAULT_EXPORT_FILE.to_string();
+                self.needs_redraw = true;
+            }
+            Action::Stats => {
+                // Queried once per visit rather than on every frame
+                self.run_history = self
+                    .config
+                    .run_log
+                    .then(|| RunLog::open().and_then(|log| log.history()));
+                self.input_mode = InputMode::ShowingStats;
+                self.needs_redraw = true;
+            }
+            Action::Help => {
+                self.input_mode = InputMode::ShowingHelp;
+                self.help_scroll = 0;
+                self.needs_redraw = true;
+            }
+            Action::HotkeyMode => {
+                let Some(keybind) = self.config.toggle_keybind.as_mut() else {
+                    self.show_notification(
+                        NotifyEvent::Hotkey,
+                        &t!("title-hotkey-mode"),
+                        &t!("notify-no-hotkey"),
+                    );
+                    return;
+                };
+                keybind.mode = keybind.mode.next();
+                let message = match keybind.mode {
+                    ActivationMode::Toggle => {
+                        t!("notify-hotkey-toggle", combo = keybind.to_string())
+                    }
+                    ActivationMode::Hold => t!("notify-hotkey-hold", combo = keybind.to_string()),
+                };
+                self.request_hotkey();
+                self.show_notification(NotifyEvent::Hotkey, &t!("title-hotkey-mode"), &message);
+            }
+            Action::MouseTrigger => {
+                self.config.mouse_trigger = MouseTrigger::next(self.config.mouse_trigger);
+                self.sync_triggers();
+                let message = match (self.config.mouse_trigger, &self.input_hook_error) {
+                    (None, _) => t!("notify-mouse-off"),
+                    (Some(trigger), None) => {
+                        t!("notify-hotkey-toggle", combo = trigger.to_string())
+                    }
+                    (Some(trigger), Some(e)) => t!(
+                        "notify-mouse-unavailable",
+                        trigger = trigger.to_string(),
+                        error = e.as_str()
+                    ),
+                };
+                self.show_notification(NotifyEvent::Hotkey, &t!("title-mouse-trigger"), &message);
+            }
+            Action::Hide => {
+                // FIXED: Toggle hide/show without freeze
+                let current = self.show_tui.load(Ordering::SeqCst);
+                self.show_tui.store(!current, Ordering::SeqCst);
+                self.show_notification(
+                    NotifyEvent::Info,
+                    "BClicker",
+                    &if current {
+                        t!("notify-hidden")
+                    } else {
+                        t!("notify-shown")
+                    },
+                );
+            }
+            Action::Down => {
+                self.move_selection_down();
+            }
+            Action::Up => {
+                self.move_selection_up();
+            }
+            Action::CustomCps => {
+                self.input_mode = InputMode::EditingCps;
+                self.custom_cps_input.clear();
+                self.needs_redraw = true;
+            }
+            Action::Presets => {
+                self.input_mode = InputMode::EditingPresets;
+                self.preset_field = None;
+                self.preset_cursor = self.config.selected_preset;
+                self.needs_redraw = true;
+            }
+            Action::Range => {
+                self.input_mode = InputMode::EditingRange;
+                self.range_input.clear();
+                self.needs_redraw = true;
+            }
+            Action::SlowMode => {
+                self.input_mode = InputMode::EditingInterval;
+                self.interval_input = self
+                    .config
+                    .slow_interval_secs
+                    .map(interval::describe)
+                    .unwrap_or_default();
+                self.needs_redraw = true;
+            }
+            Action::AntiAfk => {
+                self.input_mode = InputMode::EditingAntiAfk;
+                self.anti_afk_input = self.config.anti_afk.window();
+                self.needs_redraw = true;
+            }
+            Action::DutyCycle => {
+                self.input_mode = InputMode::EditingDutyCycle;
+                self.duty_input = self
+                    .config
+                    .active_profile()
+                    .duty_cycle
+                    .map(|duty| duty.to_string())
+                    .unwrap_or_default();
+                self.needs_redraw = true;
+            }
+            Action::Pattern => {
+                self.input_mode = InputMode::EditingPattern;
+                self.pattern_input = self
+                    .config
+                    .active_profile()
+                    .pattern
+                    .clone()
+                    .unwrap_or_default();
+                self.needs_redraw = true;
+            }
+            Action::SecondChannel => {
+                self.input_mode = InputMode::EditingSecondChannel;
+                self.second_channel_input = self
+                    .config
+                    .second_channel
+                    .map(|channel| channel.to_string())
+                    .unwrap_or_default();
+                self.needs_redraw = true;
+            }
+            Action::KeyRotation => {
+                self.input_mode = InputMode::EditingKeyRotation;
+                self.rotation_input = self
+                    .config
+                    .active_profile()
+                    .key_rotation
+                    .clone()
+                    .unwrap_or_default();
+                self.needs_redraw = true;
+            }
+            Action::HoldKey => {
+                self.input_mode = InputMode::EditingHoldKey;
+                self.hold_key_input = self.config.hold_key.clone();
+                self.needs_redraw = true;
+            }
+            Action::QueueJob => {
+                self.input_mode = InputMode::EditingJob;
+                self.job_input.clear();
+                self.needs_redraw = true;
+            }
+            Action::StdDev => {
+                self.input_mode = InputMode::EditingStdDev;
+                self.std_dev_input.clear();
+                self.needs_redraw = true;
+            }
+            Action::ScrollAmount => {
+                self.input_mode = InputMode::EditingScroll;
+                self.scroll_input.clear();
+                self.needs_redraw = true;
+            }
+            Action::Position => {
+                self.input_mode = InputMode::EditingPosition;
+                self.position_input = self
+                    .config
+                    .active_profile()
+                    .click_position
+                    .map(|p| p.to_string())
+                    .unwrap_or_default();
+                self.needs_redraw = true;
+            }
+            Action::Region => {
+                self.input_mode = InputMode::CapturingRegion;
+                self.region_corner = None;
+                self.set_capturing_region(true);
+                self.needs_redraw = true;
+            }
+            Action::RepeatText => {
+                self.input_mode = InputMode::EditingText;
+                self.text_input = self.config.repeat_text.clone();
+                self.text_input_enter = self.config.repeat_text_enter;
+                self.needs_redraw = true;
+            }
+            Action::Overlay => {
+                self.config.overlay_enabled = !self.config.overlay_enabled;
+                self.overlay_enabled
+                    .store(self.config.overlay_enabled, Ordering::SeqCst);
+                let status = if self.config.overlay_enabled {
+                    t!("notify-overlay-shown")
+                } else {
+                    t!("notify-overlay-hidden")
+                };
+                self.show_notification(NotifyEvent::Info, &t!("title-overlay"), &status);
+            }
+            Action::StartDelay => {
+                let idx = START_DELAY_PRESETS
+                    .iter()
+                    .position(|&s| s == self.config.start_delay_secs)
+                    .map_or(0, |i| (i + 1) % START_DELAY_PRESETS.len());
+                self.config.start_delay_secs = START_DELAY_PRESETS[idx];
+                self.sync_engine_settings();
+                let message = match self.config.start_delay_secs {
+                    0 => t!("notify-no-delay"),
+                    secs => t!("notify-delay", secs = secs),
+                };
+                self.show_notification(NotifyEvent::Info, &t!("title-start-delay"), &message);
+            }
+            Action::IdleTrigger => {
+                let idx = IDLE_START_PRESETS
+                    .iter()
+                    .position(|&s| s == self.config.idle_start_secs)
+                    .map_or(0, |i| (i + 1) % IDLE_START_PRESETS.len());
+                self.config.idle_start_secs = IDLE_START_PRESETS[idx];
+                self.idle_monitor.note_activity();
+                let message = match self.config.idle_start_secs {
+                    0 => t!("notify-idle-off"),
+                    secs => t!("notify-idle-after", mins = secs / 60),
+                };
+                self.show_notification(NotifyEvent::Info, &t!("title-idle"), &message);
+            }
+            Action::ReturnToOrigin => {
+                let profile = self.config.active_profile_mut();
+                profile.return_to_origin = !profile.return_to_origin;
+                let status = if profile.return_to_origin {
+                    t!("notify-return-on")
+                } else {
+                    t!("notify-return-off")
+                };
+                self.sync_engine_settings();
+                self.show_notification(NotifyEvent::Info, &t!("title-return"), &status);
+            }
+            Action::Modifier => {
+                let profile = self.config.active_profile_mut();
+                profile.modifier = ClickModifier::cycle(profile.modifier);
+                let status = match profile.modifier {
+                    Some(modifier) => t!("notify-modifier", key = modifier.label()),
+                    None => t!("notify-modifier-off"),
+                };
+                self.sync_engine_settings();
+                self.show_notification(NotifyEvent::Info, &t!("title-modifier"), &status);
+            }
+            Action::Backend => {
+                self.config.backend = self.config.backend.next();
+                self.sync_engine_settings();
+                self.show_notification(
+                    NotifyEvent::Info,
+                    &t!("title-backend"),
+                    &t!("notify-backend", backend = self.config.backend.label()),
+                );
+            }
+            Action::Timing => {
+                let profile = self.config.active_profile_mut();
+                profile.timing_model = profile.timing_model.next();
+                let label = profile.timing_model.label();
+                self.sync_engine_settings();
+                self.show_notification(
+                    NotifyEvent::Info,
+                    &t!("title-timing"),
+                    &t!("notify-timing", model = label),
+                );
+            }
+            Action::Pause => self.toggle_pause(),
+            Action::Hotkey | Action::CpsUpHotkey | Action::CpsDownHotkey | Action::PauseHotkey => {
+                self.keybind_target = match action {
+                    Action::CpsUpHotkey => KeybindTarget::Faster,
+                    Action::CpsDownHotkey => KeybindTarget::Slower,
+                    Action::PauseHotkey => KeybindTarget::Pause,
+                    _ => KeybindTarget::Toggle,
+                };
+                self.input_mode = InputMode::AwaitingKeybind;
+                self.keybind_wait_start = Some(Instant::now());
+                self.needs_redraw = true;
+            }
+            Action::Button => {
+                self.cycle_button();
+            }
+            Action::Audio => {
+                self.audio_manager.toggle_sound();
+                self.config.sound_enabled = self.audio_manager.is_enabled();
+                let status = if self.audio_manager.is_enabled() {
+                    t!("notify-sound-on")
+                } else {
+                    t!("notify-sound-off")
+                };
+                self.show_notification(NotifyEvent::Info, &t!("title-audio"), &status);
+                self.needs_redraw = true;
+            }
+            Action::Theme => {
+                self.config.theme = self.config.theme.next();
+                self.theme = Theme::named(self.config.theme);
+                let message = t!("notify-theme", theme = self.config.theme.to_string());
+                self.show_notification(NotifyEvent::Info, &t!("title-theme"), &message);
+                self.needs_redraw = true;
+            }
+            Action::Language => {
+                let language = i18n::language().next();
+                self.config.language = Some(language);
+                i18n::set_language(language);
+                let message = t!("notify-language", language = language.to_string());
+                self.show_notification(NotifyEvent::Info, &t!("title-language"), &message);
+                self.needs_redraw = true;
+            }
+            Action::AudioDevice => {
+                self.audio_devices = AudioManager::output_devices();
+                // A saved device that isn't plugged in stays listed so the
+                // cursor can show it
+                if let Some(saved) = &self.config.audio_device {
+                    if !self.audio_devices.contains(saved) {
+                        self.audio_devices.push(saved.clone());
+                    }
+                }
+                self.audio_device_cursor = self
+                    .config
+                    .audio_device
+                    .as_ref()
+                    .and_then(|saved| self.audio_devices.iter().position(|d| d == saved))
+                    .map_or(0, |i| i + 1);
+                self.input_mode = InputMode::SelectingAudioDevice;
+                self.needs_redraw = true;
+            }
+            Action::Benchmark => {
+                self.start_benchmark();
+            }
+            Action::TestFire => self.test_fire(),
+            Action::DryRun => {
+                self.config.dry_run = !self.config.dry_run;
+                self.sync_engine_settings();
+                let status = if self.config.dry_run {
+                    t!("notify-dry-run-on")
+                } else {
+                    t!("notify-dry-run-off", backend = self.config.backend.label())
+                };
+                self.show_notification(NotifyEvent::Info, &t!("title-dry-run"), &status);
+            }
+            Action::SaveSlot => {
+                self.input_mode = InputMode::AssigningSlot;
+                self.needs_redraw = true;
+            }
+            Action::Sequence => {
+                self.input_mode = InputMode::EditingSequence;
+                self.sequence_field = None;
+                self.sequence_cursor = 0;
+                self.needs_redraw = true;
+            }
+            Action::Scheduler => {
+                self.input_mode = InputMode::EditingSchedules;
+                self.schedule_input = None;
+                self.schedule_cursor = 0;
+                self.needs_redraw = true;
+            }
+            Action::ResetStats => {
+                if let Ok(mut stats) = self.stats_tracker.lock() {
+                    *stats = Statistics::default();
+                    self.session_start = Instant::now();
+                }
+                self.save_statistics();
+                self.show_notification(
+                    NotifyEvent::Info,
+                    &t!("title-statistics"),
+                    &t!("notify-stats-reset"),
+                );
+                self.needs_redraw = true;
+            }
+        }
+    }
+
+    // FIXED: Fast input handling without lag
+    fn handle_input(&mut self, key_event: crossterm::event::KeyEvent) {
+        self.idle_monitor.note_activity();
+        self.main_cache.built_for = None;
+        match self.input_mode {
+            InputMode::ShowingHelp => match key_event.code {
+                code if code == KeyCode::Esc
+                    || self.config.keymap.is(Action::Help, code)
+                    || self.config.keymap.is(Action::Quit, code) =>
+                {
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Down | KeyCode::Char('j') if self.help_scroll < 20 => {
+                    self.help_scroll += 1;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Up | KeyCode::Char('k') if self.help_scroll > 0 => {
+                    self.help_scroll -= 1;
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::CommandPalette => self.handle_palette_input(key_event),
+            InputMode::ShowingStats => {
+                let code = key_event.code;
+                if code == KeyCode::Esc
+                    || self.config.keymap.is(Action::Stats, code)
+                    || self.config.keymap.is(Action::Quit, code)
+                {
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+            }
+            InputMode::Normal => {
+                if key_event.code == KeyCode::Char('p')
+                    && key_event.modifiers.contains(KeyModifiers::CONTROL)
+                {
+                    self.open_palette();
+                } else if let Some(action) = self.config.keymap.action_for(key_event.code) {
+                    self.run_action(action);
+                } else if let KeyCode::Char(c @ '1'..='9') = key_event.code {
+                    self.apply_quick_slot(c as u8 - b'0');
+                }
+            }
+            InputMode::AssigningSlot => {
+                if let KeyCode::Char(c @ '1'..='9') = key_event.code {
+                    self.store_quick_slot(c as u8 - b'0');
+                }
+                self.input_mode = InputMode::Normal;
+                self.needs_redraw = true;
+            }
+            InputMode::EditingCps => match key_event.code {
+                KeyCode::Enter => {
+                    self.input_mode = InputMode::Normal;
+                    if let Some(val) = parse_cps(&self.custom_cps_input) {
+                        if val > MAX_SAFE_CPS && val <= MAX_CPS {
+                            self.pending_high_cps = Some(val);
+                            self.input_mode = InputMode::ConfirmingHighCps;
+                        } else if val <= MAX_SAFE_CPS {
+                            self.apply_custom_cps(val);
+                        }
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || c == '.') && self.custom_cps_input.len() < 8 =>
+                {
+                    self.custom_cps_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.custom_cps_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.custom_cps_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingRange => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.range_input.trim();
+                    if input.is_empty() {
+                        self.config.active_profile_mut().timing_model = TimingModel::Fixed;
+                        self.sync_engine_settings();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-range"),
+                            &t!("notify-range-off"),
+                        );
+                    } else if let Some((min, max)) = input.split_once('-') {
+                        if let (Some(min), Some(max)) = (parse_cps(min), parse_cps(max)) {
+                            let range = CpsRange { min, max };
+                            if range.is_valid() {
+                                let profile = self.config.active_profile_mut();
+                                profile.cps_range = range;
+                                profile.timing_model = TimingModel::Uniform;
+                                self.sync_engine_settings();
+                                self.show_notification(
+                                    NotifyEvent::Info,
+                                    &t!("title-range"),
+                                    &t!("notify-range", range = range.to_string()),
+                                );
+                            }
+                        }
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || c == '-' || c == '.')
+                        && self.range_input.len() < 13 =>
+                {
+                    self.range_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.range_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.range_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingInterval => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.interval_input.trim();
+                    if input.is_empty() {
+                        if self.config.current_choice() == CpsChoice::Slow {
+                            self.config
+                                .apply_choice(CpsChoice::Preset(self.config.selected_preset));
+                        }
+                        self.config.slow_interval_secs = None;
+                        self.update_cps();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-slow"),
+                            &t!("notify-slow-off"),
+                        );
+                    } else if let Some(secs) = interval::parse(input) {
+                        self.config.slow_interval_secs = Some(secs);
+                        self.config.apply_choice(CpsChoice::Slow);
+                        self.update_cps();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-slow"),
+                            &t!("notify-slow", interval = interval::describe(secs)),
+                        );
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || "smhSMH".contains(c))
+                        && self.interval_input.len() < 12 =>
+                {
+                    self.interval_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.interval_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.interval_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingAntiAfk => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.anti_afk_input.trim();
+                    if input.is_empty() {
+                        if self.config.current_choice() == CpsChoice::AntiAfk {
+                            self.config
+                                .apply_choice(CpsChoice::Preset(self.config.selected_preset));
+                            self.update_cps();
+                        }
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-anti-afk"),
+                            &t!("notify-anti-afk-off"),
+                        );
+                    } else if let Some((min, max)) = AntiAfk::parse_window(input) {
+                        self.config.anti_afk.min_secs = min;
+                        self.config.anti_afk.max_secs = max;
+                        self.config.apply_choice(CpsChoice::AntiAfk);
+                        self.update_cps();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-anti-afk"),
+                            &t!("notify-anti-afk", window = self.config.anti_afk.window()),
+                        );
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || "smhSMH-".contains(c))
+                        && self.anti_afk_input.len() < 20 =>
+                {
+                    self.anti_afk_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.anti_afk_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.anti_afk_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingDutyCycle => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.duty_input.trim();
+                    let duty = if input.is_empty() {
+                        Some(None)
+                    } else {
+                        DutyCycle::parse(input).map(Some)
+                    };
+                    if let Some(duty) = duty {
+                        self.config.active_profile_mut().duty_cycle = duty;
+                        self.sync_engine_settings();
+                        let message = match duty {
+                            Some(duty) => t!(
+                                "notify-duty",
+                                on = interval::describe(duty.on_secs),
+                                off = interval::describe(duty.off_secs)
+                            ),
+                            None => t!("notify-duty-off"),
+                        };
+                        self.show_notification(NotifyEvent::Info, &t!("title-duty"), &message);
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || "smhSMH/".contains(c))
+                        && self.duty_input.len() < 20 =>
+                {
+                    self.duty_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.duty_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.duty_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingSecondChannel => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.second_channel_input.trim();
+                    if input.is_empty() {
+                        self.config.second_channel = None;
+                        self.sync_engine_settings();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-second-channel"),
+                            &t!("notify-second-channel-off"),
+                        );
+                        self.input_mode = InputMode::Normal;
+                    } else {
+                        // A bad entry stays in the field to be fixed
+                        match SecondChannel::parse(input) {
+                            Ok(channel) => {
+                                self.config.second_channel = Some(channel);
+                                self.sync_engine_settings();
+                                self.show_notification(
+                                    NotifyEvent::Info,
+                                    &t!("title-second-channel"),
+                                    &t!(
+                                        "notify-second-channel",
+                                        button = channel.action_label(),
+                                        cps = channel.cps.to_string()
+                                    ),
+                                );
+                                self.input_mode = InputMode::Normal;
+                            }
+                            Err(e) => self.show_notification(
+                                NotifyEvent::Error,
+                                &t!("title-second-channel"),
+                                &e,
+                            ),
+                        }
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.second_channel_input.len() < 20 => {
+                    self.second_channel_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.second_channel_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.second_channel_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingJob => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.job_input.trim();
+                    if input.is_empty() {
+                        let cleared = self.jobs.clear();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-job"),
+                            &t!("notify-jobs-cleared", count = cleared),
+                        );
+                        self.input_mode = InputMode::Normal;
+                    } else {
+                        // A bad job stays in the field to be fixed
+                        let queued = ClickJob::parse(input).and_then(|job| {
+                            let job = job.in_physical(self.config.coordinate_space);
+                            self.jobs.push(job).map(|queued| (job, queued))
+                        });
+                        match queued {
+                            Ok((job, queued)) => {
+                                self.show_notification(
+                                    NotifyEvent::Info,
+                                    &t!("title-job"),
+                                    &t!("notify-job", job = job.to_string(), queued = queued),
+                                );
+                                self.input_mode = InputMode::Normal;
+                            }
+                            Err(e) => {
+                                self.show_notification(NotifyEvent::Error, &t!("title-job"), &e)
+                            }
+                        }
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.job_input.len() < MAX_PATTERN_LEN => {
+                    self.job_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.job_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.job_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingHoldKey => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.hold_key_input.trim().to_string();
+                    // An unknown key stays in the field to be fixed
+                    if sequence::parse_key(&input).is_some() {
+                        self.config.hold_key = input;
+                        self.config.selected_button = HOLD_KEY_MODE;
+                        self.sync_engine_settings();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-hold-key"),
+                            &t!("notify-hold-key", key = self.config.hold_key.as_str()),
+                        );
+                        self.input_mode = InputMode::Normal;
+                    } else {
+                        self.show_notification(
+                            NotifyEvent::Error,
+                            &t!("title-hold-key"),
+                            &t!("error-hold-key", key = input),
+                        );
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.hold_key_input.len() < 20 => {
+                    self.hold_key_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.hold_key_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.hold_key_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingKeyRotation => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.rotation_input.trim().to_string();
+                    if input.is_empty() {
+                        self.config.active_profile_mut().key_rotation = None;
+                        self.sync_engine_settings();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-rotation"),
+                            &t!("notify-rotation-off"),
+                        );
+                        self.input_mode = InputMode::Normal;
+                    } else {
+                        // A bad rotation stays in the field to be fixed
+                        match KeyRotation::parse(&input) {
+                            Ok(rotation) => {
+                                self.config.active_profile_mut().key_rotation = Some(input);
+                                self.config.selected_button = TYPE_TEXT_MODE;
+                                self.sync_engine_settings();
+                                self.show_notification(
+                                    NotifyEvent::Info,
+                                    &t!("title-rotation"),
+                                    &t!(
+                                        "notify-rotation",
+                                        keys = rotation.key_count(),
+                                        secs =
+                                            format!("{:.2}", rotation.cycle_time().as_secs_f64())
+                                    ),
+                                );
+                                self.input_mode = InputMode::Normal;
+                            }
+                            Err(e) => self.show_notification(
+                                NotifyEvent::Error,
+                                &t!("title-rotation"),
+                                &e,
+                            ),
+                        }
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.rotation_input.len() < MAX_PATTERN_LEN => {
+                    self.rotation_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.rotation_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.rotation_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingPattern => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.pattern_input.trim().to_string();
+                    if input.is_empty() {
+                        self.config.active_profile_mut().pattern = None;
+                        self.sync_engine_settings();
+                        self.show_notification(
+                            NotifyEvent::Info,
+                            &t!("title-pattern"),
+                            &t!("notify-pattern-off"),
+                        );
+                        self.input_mode = InputMode::Normal;
+                    } else {
+                        // A bad pattern stays in the field to be fixed
+                        match ClickPattern::parse(&input) {
+                            Ok(pattern) => {
+                                self.config.active_profile_mut().pattern = Some(input);
+                                self.sync_engine_settings();
+                                self.show_notification(
+                                    NotifyEvent::Info,
+                                    &t!("title-pattern"),
+                                    &t!(
+                                        "notify-pattern",
+                                        clicks = pattern.cycle_clicks(),
+                                        secs = format!("{:.2}", pattern.cycle_time().as_secs_f64())
+                                    ),
+                                );
+                                self.input_mode = InputMode::Normal;
+                            }
+                            Err(e) => {
+                                self.show_notification(NotifyEvent::Error, &t!("title-pattern"), &e)
+                            }
+                        }
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.pattern_input.len() < MAX_PATTERN_LEN => {
+                    self.pattern_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.pattern_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.pattern_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingSequence => self.handle_sequence_input(key_event),
+            InputMode::EditingPresets => self.handle_preset_input(key_event),
+            InputMode::EditingSchedules => self.handle_schedule_input(key_event),
+            InputMode::SelectingAudioDevice => self.handle_audio_device_input(key_event),
+            InputMode::EditingStdDev => match key_event.code {
+                KeyCode::Enter => {
+                    if let Ok(val) = self.std_dev_input.trim().parse::<u32>() {
+                        if val > 0 && val <= 100 {
+                            let profile = self.config.active_profile_mut();
+                            profile.std_dev_percent = val;
+                            profile.timing_model = TimingModel::Gaussian;
+                            self.sync_engine_settings();
+                            self.show_notification(
+                                NotifyEvent::Info,
+                                &t!("title-timing"),
+                                &t!("notify-gaussian", percent = val),
+                            );
+                        }
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() && self.std_dev_input.len() < 3 => {
+                    self.std_dev_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.std_dev_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.std_dev_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingPosition => match key_event.code {
+                KeyCode::Enter => {
+                    let input = self.position_input.trim();
+                    let position = if input.is_empty() {
+                        Some(None)
+                    } else {
+                        Point::parse(input).map(Some)
+                    };
+                    if let Some(position) = position {
+                        self.config.active_profile_mut().click_position = position;
+                        self.sync_engine_settings();
+                        let message = match position {
+                            Some(p) => t!("notify-position", point = p.to_string()),
+                            None => t!("notify-position-cursor"),
+                        };
+                        self.show_notification(NotifyEvent::Info, &t!("title-position"), &message);
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c)
+                    if (c.is_ascii_digit() || c == ',' || c == '-')
+                        && self.position_input.len() < 16 =>
+                {
+                    self.position_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.position_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.position_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::CapturingRegion => match key_event.code {
+                KeyCode::Enter => self.mark_region_corner(),
+                KeyCode::Backspace | KeyCode::Delete => {
+                    self.config.active_profile_mut().click_region = None;
+                    self.sync_engine_settings();
+                    self.show_notification(
+                        NotifyEvent::Info,
+                        &t!("title-region"),
+                        &t!("notify-region-cleared"),
+                    );
+                    self.finish_region_capture();
+                }
+                KeyCode::Esc => self.finish_region_capture(),
+                _ => {}
+            },
+            InputMode::EditingExportPath | InputMode::EditingImportPath => match key_event.code {
+                KeyCode::Enter => {
+                    let path = PathBuf::from(self.transfer_input.trim());
+                    if self.input_mode == InputMode::EditingExportPath {
+                        self.input_mode = InputMode::Normal;
+                        self.export_config(&path);
+                    } else {
+                        self.input_mode = InputMode::Normal;
+                        self.start_import(&path);
+                    }
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.transfer_input.len() < 200 => {
+                    self.transfer_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.transfer_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.transfer_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::ConfirmingImport => {
+                let pending = self.pending_import.take();
+                if let (KeyCode::Char('y') | KeyCode::Char('Y'), Some(pending)) =
+                    (key_event.code, pending)
+                {
+                    self.apply_import(pending);
+                }
+                self.input_mode = InputMode::Normal;
+                self.needs_redraw = true;
+            }
+            InputMode::HotkeyConflict => {
+                if key_event.code == KeyCode::Enter {
+                    self.keybind_target = KeybindTarget::Toggle;
+                    self.input_mode = InputMode::AwaitingKeybind;
+                    self.keybind_wait_start = Some(Instant::now());
+                } else {
+                    self.input_mode = InputMode::Normal;
+                }
+                self.needs_redraw = true;
+            }
+            // Free text, so only Enter, Tab and Esc act as commands here
+            InputMode::EditingText => match key_event.code {
+                KeyCode::Enter => {
+                    self.config.repeat_text = std::mem::take(&mut self.text_input);
+                    self.config.repeat_text_enter = self.text_input_enter;
+                    self.config.selected_button = TYPE_TEXT_MODE;
+                    self.sync_engine_settings();
+                    self.show_notification(
+                        NotifyEvent::Info,
+                        &t!("title-text"),
+                        &t!("notify-text", text = self.config.repeat_text.as_str()),
+                    );
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Tab => {
+                    self.text_input_enter = !self.text_input_enter;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if self.text_input.chars().count() < MAX_REPEAT_TEXT_LEN => {
+                    self.text_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.text_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.text_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::EditingScroll => match key_event.code {
+                KeyCode::Enter => {
+                    if let Ok(val) = self.scroll_input.trim().parse::<i32>() {
+                        if val > 0 && val <= MAX_SCROLL_AMOUNT {
+                            self.config.scroll_amount = val;
+                            self.sync_engine_settings();
+                            self.show_notification(
+                                NotifyEvent::Info,
+                                &t!("title-scroll"),
+                                &t!("notify-scroll", amount = val),
+                            );
+                        }
+                    }
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() && self.scroll_input.len() < 2 => {
+                    self.scroll_input.push(c);
+                    self.needs_redraw = true;
+                }
+                KeyCode::Backspace => {
+                    self.scroll_input.pop();
+                    self.needs_redraw = true;
+                }
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.scroll_input.clear();
+                    self.needs_redraw = true;
+                }
+                _ => {}
+            },
+            InputMode::ConfirmingHighCps => {
+                if let Some(val) = self.pending_high_cps.take() {
+                    if matches!(key_event.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                        self.apply_custom_cps(val);
+                    }
+                }
+                self.custom_cps_input.clear();
+                self.input_mode = InputMode::Normal;
+                self.needs_redraw = true;
+            }
+            InputMode::AwaitingKeybind => {
+                if let Some(wait_start) = self.keybind_wait_start {
+                    if Instant::now().duration_since(wait_start) > Duration::from_millis(800) {
+                        self.input_mode = InputMode::SettingKeybind;
+                        self.keybind_wait_start = None;
+                        self.needs_redraw = true;
+                    }
+                }
+            }
+            InputMode::SettingKeybind => match key_event.code {
+                KeyCode::Esc => {
+                    self.input_mode = InputMode::Normal;
+                    self.needs_redraw = true;
+                }
+                code => {
+                    let Some(key) = keycodes::capture_name(code, key_event.state) else {
+                        return;
+                    };
+                    let mut mods = 0u8;
+                    if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                        mods |= 2;
+                    }
+                    if key_event.modifiers.contains(KeyModifiers::SHIFT) {
+                        mods |= 1;
+                    }
+                    if key_event.modifiers.contains(KeyModifiers::ALT) {
+                        mods |= 4;
+                    }
+
+                    self.set_keybind(mods, key);
+                }
+            },
+        }
+
+        self.save_config();
+    }
+
+    // How long the main loop may sleep before the next update(): short while
+    // the screen shows something counting, long when idle. Hotkeys wake it
+    // early either way
+    // Rebuilds the cached parts of the main screen if what they show moved
+    fn refresh_main_cache(&mut self) {
+        let key = (self.engine_settings.version(), self.input_mode);
+        if self.main_cache.built_for != Some(key) {
+            self.main_cache = MainScreenCache::build(self);
+        }
+    }
+
+    fn update_interval(&self) -> Duration {
+        let busy = self.show_tui.load(Ordering::SeqCst)
+            && (self.auto_clicker_running.load(Ordering::SeqCst)
+                || self.countdown.load(Ordering::SeqCst) > 0
+                || self.benchmark_in_progress
+                || self.input_mode == InputMode::AwaitingKeybind);
+        if busy {
+            ACTIVE_UPDATE_INTERVAL
+        } else {
+            IDLE_UPDATE_INTERVAL
+        }
+    }
+
+    fn update(&mut self) {
+        // Update any time-based state changes
+        if self.stats_saved_at.elapsed() >= stats::SAVE_INTERVAL {
+            self.save_statistics();
+        }
+
+        // Keep the live counters and achieved CPS fresh while clicking
+        if self.auto_clicker_running.load(Ordering::SeqCst)
+            && self.last_live_refresh.elapsed() >= Duration::from_millis(250)
+        {
+            self.last_live_refresh = Instant::now();
+            self.needs_redraw = true;
+        }
+
+        let rate_status = self.get_rate_status();
+        if rate_status.injection_blocked != self.injection_blocked_shown {
+            self.injection_blocked_shown = rate_status.injection_blocked;
+            self.needs_redraw = true;
+        }
+
+        let rate_unachievable = rate_status.unachievable;
+        if rate_unachievable != self.rate_warning_shown {
+            self.rate_warning_shown = rate_unachievable;
+            if rate_unachievable {
+                self.show_notification(
+                    NotifyEvent::Error,
+                    &t!("title-unachievable"),
+                    &t!(
+                        "notify-unachievable",
+                        rate = self.get_engine_settings().rate_text()
+                    ),
+                );
+            }
+            self.needs_redraw = true;
+        }
+
+        let total_clicks = self
+            .stats_tracker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .total_clicks;
+        if let Some(milestone) = self
+            .config
+            .reached_milestone(self.milestone_checked_total, total_clicks)
+        {
+            self.show_notification(
+                NotifyEvent::Milestone,
+                &t!("title-milestone"),
+                &t!("notify-milestone", clicks = milestone),
+            );
+            if self.config.milestone_sound {
+                self.audio_manager.play_milestone_sound();
+            }
+            self.config.hooks.run(
+                HookEvent::Milestone,
+                &milestone_hook_env(&self.config, milestone, total_clicks),
+            );
+        }
+        self.milestone_checked_total = total_clicks;
+
+        self.update_idle_trigger();
+        self.update_schedules();
+
+        let engine_fault = self
+            .engine_fault
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        // Nothing would click with the engine down for good, so don't let
+        // the status bar say ACTIVE
+        if matches!(
+            engine_fault,
+            Some(EngineFault {
+                restarted: false,
+                ..
+            })
+        ) && self.auto_clicker_running.swap(false, Ordering::SeqCst)
+        {
+            self.needs_redraw = true;
+        }
+        if engine_fault != self.engine_fault_seen {
+            self.engine_fault_seen = engine_fault;
+            self.needs_redraw = true;
+        }
+
+        let hotkey_status = self.hotkey_status();
+        if hotkey_status != self.hotkey_status_seen {
+            // A failure only interrupts the main screen; the status bar keeps
+            // showing it until a new combo registers
+            if matches!(hotkey_status, HotkeyStatus::Failed(_))
+                && self.input_mode == InputMode::Normal
+            {
+                self.input_mode = InputMode::HotkeyConflict;
+            }
+            self.hotkey_status_seen = hotkey_status;
+            self.needs_redraw = true;
+        }
+
+        let (nudges, nudge_status, pause_pressed, pause_status, corner_pressed) = {
+            let mut state = self.hotkey_state.lock().unwrap_or_else(|e| e.into_inner());
+            (
+                std::mem::take(&mut state.pending_nudges),
+                state.nudge_status.clone(),
+                std::mem::take(&mut state.pause_pressed),
+                state.pause_status.clone(),
+                std::mem::take(&mut state.corner_pressed),
+            )
+        };
+        if nudges != 0 {
+            self.nudge_cps(nudges);
+        }
+        if pause_pressed {
+            self.toggle_pause();
+        }
+        if corner_pressed && self.input_mode == InputMode::CapturingRegion {
+            self.mark_region_corner();
+        }
+        if pause_status != self.pause_status_seen {
+            if let (HotkeyStatus::Failed(reason), Some(combo)) =
+                (&pause_status, &self.config.pause_keybind)
+            {
+                self.show_notification(
+                    NotifyEvent::Error,
+                    &t!("title-pause-unavailable"),
+                    &format!("{}: {}", combo, reason),
+                );
+            }
+            self.pause_status_seen = pause_status;
+        }
+        for (i, status) in nudge_status.into_iter().enumerate() {
+            if status == self.nudge_status_seen[i] {
+                continue;
+            }
+            if let HotkeyStatus::Failed(reason) = &status {
+                let (title, combo) = if i == 0 {
+                    ("title-faster-unavailable", &self.config.cps_up_keybind)
+                } else {
+                    ("title-slower-unavailable", &self.config.cps_down_keybind)
+                };
+                let combo = combo.as_ref().map(|k| k.to_string()).unwrap_or_default();
+                self.show_notification(
+                    NotifyEvent::Error,
+                    &t!(title),
+                    &format!("{}: {}", combo, reason),
+                );
+            }
+            self.nudge_status_seen[i] = status;
+        }
+
+        if self.benchmark_in_progress && !self.benchmark_running.load(Ordering::SeqCst) {
+            self.benchmark_in_progress = false;
+            if let Some(report) = self
+                .benchmark_report
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_ref()
+            {
+                self.show_notification(
+                    NotifyEvent::Info,
+                    &t!("title-benchmark"),
+                    &report.summary(),
+                );
+            }
+            self.needs_redraw = true;
+        }
+
+        if self.input_mode == InputMode::AwaitingKeybind {
+            if let Some(wait_start) = self.keybind_wait_start {
+                if Instant::now().duration_since(wait_start) > Duration::from_millis(800) {
+                    self.input_mode = InputMode::SettingKeybind;
+                    self.keybind_wait_start = None;
+                    self.needs_redraw = true;
+                }
+            }
+        }
+    }
+}
+
+// FIXED: Optimized hotkey display function with proper lifetimes
+fn create_hotkey_spans<'a>(keybind: &'a KeyCombo, theme: &'a Theme) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+
+    if keybind.mods & 2 != 0 {
+        spans.push(Span::styled(
+            "Ctrl",
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("+"));
+    }
+    if keybind.mods & 4 != 0 {
+        spans.push(Span::styled(
+            "Alt",
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("+"));
+    }
+    if keybind.mods & 1 != 0 {
+        spans.push(Span::styled(
+            "Shift",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("+"));
+    }
+
+    spans.push(Span::styled(
+        &keybind.key,
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    ));
+    if keybind.mode == ActivationMode::Hold {
+        spans.push(Span::raw(" (hold)"));
+    }
+    spans
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    // Unknown extensions are read and written as TOML
+    fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => ConfigFormat::Json,
+            Some("yaml" | "yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Toml,
+        }
+    }
+
+    fn parse<T: serde::de::DeserializeOwned>(self, contents: &str) -> Result<T, String> {
+        match self {
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| e.to_string()),
+        }
+    }
+
+    fn serialize<T: Serialize>(self, value: &T) -> Result<String, String> {
+        match self {
+            ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+// The format follows whichever of these files exists, so a dotfiles manager
+// can template JSON or YAML; new installs get TOML
+const CONFIG_EXTENSIONS: [&str; 4] = ["toml", "json", "yaml", "yml"];
+
+fn get_config_path() -> PathBuf {
+    let dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    CONFIG_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("bclicker_config.{}", ext)))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| dir.join("bclicker_config.toml"))
+}
+
+fn load_config() -> Config {
+    let path = get_config_path();
+    match fs::read_to_string(&path) {
+        Ok(contents) => {
+            let mut config: Config = ConfigFormat::from_path(&path)
+                .parse(&contents)
+                .unwrap_or_else(|e| {
+                    println!("Warning: Invalid config file ({}), using defaults", e);
+                    Config::default()
+                });
+            if let Some(legacy) = config.legacy_statistics.take() {
+                stats::migrate(legacy);
+            }
+            config
+        }
+        Err(_) => {
+            println!("Config file not found, creating with defaults");
+            Config::default()
+        }
+    }
+}
+
+fn save_config(config: &Config) {
+    let path = get_config_path();
+    match ConfigFormat::from_path(&path).serialize(config) {
+        Ok(contents) => {
+            if let Err(e) = fs::write(&path, contents) {
+                eprintln!("Warning: Could not save config: {}", e);
+            }
+        }
+        Err(e) => {
+            eprintln!("Warning: Could not serialize config: {}", e);
+        }
+    }
+}
+
+// The combo requested for each hotkey, in HotkeyId::ALL order
+fn requested_combos(state: &HotkeyState) -> [Option<KeyCombo>; 4] {
+    [
+        state.requested.clone(),
+        state.nudge_requested[0].clone(),
+        state.nudge_requested[1].clone(),
+        state.pause_requested.clone(),
+    ]
+}
+
+fn set_hotkey_status(state: &mut HotkeyState, id: HotkeyId, status: HotkeyStatus) {
+    match id {
+        HotkeyId::Toggle => state.status = status,
+        HotkeyId::Faster => state.nudge_status[0] = status,
+        HotkeyId::Slower => state.nudge_status[1] = status,
+        HotkeyId::Pause => state.pause_status = status,
+    }
+}
+
+// Sleeps until a hotkey fires or the TUI changes a combo, and only
+// re-registers the combos that changed. Hold bindings end on their own
+// release event, so nothing here polls
+fn setup_global_hotkey(
+    hotkey_state: Arc<Mutex<HotkeyState>>,
+    auto_clicker_running: Arc<AtomicBool>,
+    mut backend: Box<dyn HotkeyBackend>,
+    messages: mpsc::Receiver<HotkeyMessage>,
+    ui: mpsc::Sender<AppEvent>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        // The combo last tried for each hotkey
+        let mut attempted: [Option<KeyCombo>; 4] = Default::default();
+        // The toggle binding is in hold mode and held down
+        let mut holding = false;
+
+        for message in std::iter::once(HotkeyMessage::Changed).chain(messages) {
+            match message {
+                HotkeyMessage::Changed => {
+                    let requested =
+                        requested_combos(&hotkey_state.lock().unwrap_or_else(|e| e.into_inner()));
+                    for (i, id) in HotkeyId::ALL.into_iter().enumerate() {
+                        if requested[i] == attempted[i] && backend.supported() {
+                            continue;
+                        }
+                        if id == HotkeyId::Toggle && holding {
+                            holding = false;
+                            ActivationMode::Hold.apply(false, &auto_clicker_running);
+                        }
+                        backend.unregister(id);
+                        let status = match &requested[i] {
+                            _ if !backend.supported() => HotkeyStatus::Unsupported,
+                            Some(combo) => match backend.register(id, combo) {
+                                Ok(()) => HotkeyStatus::Registered,
+                                Err(reason) => HotkeyStatus::Failed(reason),
+                            },
+                            None => HotkeyStatus::Unset,
+                        };
+                        attempted[i] = requested[i].clone();
+                        set_hotkey_status(
+                            &mut hotkey_state.lock().unwrap_or_else(|e| e.into_inner()),
+                            id,
+                            status,
+                        );
+                    }
+                }
+                HotkeyMessage::Key(HotkeyId::Toggle, pressed) => {
+                    let mut state = hotkey_state.lock().unwrap_or_else(|e| e.into_inner());
+                    // A held hotkey still stops clicking when let go
+                    if state.capturing_region && !holding {
+                        state.corner_pressed |= pressed;
+                    } else if let Some(combo) = &attempted[0] {
+                        combo.mode.apply(pressed, &auto_clicker_running);
+                        holding = pressed && combo.mode == ActivationMode::Hold;
+                    }
+                }
+                // The TUI applies these, even while hidden
+                HotkeyMessage::Key(id @ (HotkeyId::Faster | HotkeyId::Slower), true) => {
+                    let step = if id == HotkeyId::Faster { 1 } else { -1 };
+                    hotkey_state
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .pending_nudges += step;
+                }
+                HotkeyMessage::Key(HotkeyId::Pause, true) => {
+                    hotkey_state
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .pause_pressed = true;
+                }
+                HotkeyMessage::Key(_, false) => {}
+            }
+            // Lets the TUI apply and show it now rather than on its next
+            // idle update
+            if matches!(message, HotkeyMessage::Key(..)) {
+                let _ = ui.send(AppEvent::Wake);
+            }
+        }
+    })
+}
+
+// Beeps once per second until the delay elapses; returns false if the clicker
+// was toggled off in the meantime
+fn run_countdown(
+    secs: u32,
+    notifications: &NotificationPrefs,
+    auto_clicker_running: &AtomicBool,
+    countdown: &AtomicU32,
+    audio_manager: &Mutex<AudioManager>,
+    clock: &Clock,
+) -> bool {
+    notifications.send_for(
+        NotifyEvent::Countdown,
+        "BClicker",
+        &t!("notify-countdown", secs = secs),
+        secs * 1000,
+    );
+
+    for remaining in (1..=secs).rev() {
+        countdown.store(remaining, Ordering::SeqCst);
+        if let Ok(audio) = audio_manager.lock() {
+            audio.play_countdown_beep();
+        }
+        let second_start = clock.now();
+        while clock.since(second_start) < Duration::from_secs(1) {
+            if !auto_clicker_running.load(Ordering::SeqCst) {
+                countdown.store(0, Ordering::SeqCst);
+                return false;
+            }
+            clock.sleep(Duration::from_millis(20));
+        }
+    }
+    countdown.store(0, Ordering::SeqCst);
+    true
+}
+
+// Why the engine thread last died, shown in the status bar until clicking
+// starts again
+#[derive(Clone, Debug, PartialEq)]
+struct EngineFault {
+    error: String,
+    // False once the supervisor has given up on restarting it
+    restarted: bool,
+}
+
+// Crashes within ENGINE_CRASH_WINDOW after which the engine stays down
+// instead of crashing in a loop
+const MAX_ENGINE_CRASHES: usize = 5;
+const ENGINE_CRASH_WINDOW: Duration = Duration::from_secs(60);
+
+// Everything the engine thread shares with the TUI
+#[derive(Clone)]
+struct EngineShared {
+    auto_clicker_running: Arc<AtomicBool>,
+    auto_clicker_paused: Arc<AtomicBool>,
+    countdown: Arc<AtomicU32>,
+    engine_settings: Arc<SharedSettings>,
+    stats_tracker: Arc<Mutex<Statistics>>,
+    rate_status: Arc<Mutex<RateStatus>>,
+    audio_manager: Arc<Mutex<AudioManager>>,
+    tray_manager: Arc<Mutex<Option<TrayManager>>>,
+    jobs: JobQueue,
+    engine_fault: Arc<Mutex<Option<EngineFault>>>,
+    stop_reason: StopReason,
+    make_backend: BackendFactory,
+    // Virtual in replays, with a fixed seed so the jitter repeats too
+    clock: Clock,
+    rng_seed: Option<u64>,
+    // Ends the engine thread for good; the app never sets it
+    shutdown: Arc<AtomicBool>,
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+// Runs the engine on a thread of its own and starts a fresh one whenever it
+// panics, e.g. on an input backend failure, so the clicker can't die quietly
+// while the TUI still says ACTIVE. The crashed run is stopped rather than
+// resumed; it was mid-click and may fail the same way again
+fn start_clicker_thread(shared: EngineShared) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut crashes: Vec<Instant> = Vec::new();
+        loop {
+            let engine = shared.clone();
+            let Err(payload) = thread::spawn(move || run_engine(engine)).join() else {
+                return;
+            };
+            let error = panic_message(payload.as_ref());
+            // Engine diagnostics go to stderr; `bclicker headless` keeps
+            // stdout for its events
+            eprintln!("[ERROR] Click engine crashed: {}", error);
+
+            shared.auto_clicker_running.store(false, Ordering::SeqCst);
+            shared.auto_clicker_paused.store(false, Ordering::SeqCst);
+            shared.countdown.store(0, Ordering::SeqCst);
+            // The engine may have died holding any of these
+            shared.stats_tracker.clear_poison();
+            shared.rate_status.clear_poison();
+            shared.audio_manager.clear_poison();
+            shared.tray_manager.clear_poison();
+            *shared.rate_status.lock().unwrap_or_else(|e| e.into_inner()) = RateStatus::default();
+            if let Ok(mut tray) = shared.tray_manager.lock() {
+                if let Some(tray) = tray.as_mut() {
+                    tray.stop_flashing();
+                }
+            }
+
+            crashes.retain(|at| at.elapsed() < ENGINE_CRASH_WINDOW);
+            crashes.push(Instant::now());
+            let restarted = crashes.len() < MAX_ENGINE_CRASHES;
+            let settings = shared.engine_settings.get();
+            let message = if restarted {
+                t!("notify-engine-restarted", error = error.clone())
+            } else {
+                eprintln!(
+                    "[ERROR] Click engine crashed {} times within a minute, not restarting it",
+                    crashes.len()
+                );
+                t!("notify-engine-stopped", error = error.clone())
+            };
+            settings
+                .notifications
+                .send(NotifyEvent::Error, &t!("title-engine"), &message);
+            let run = RunInfo {
+                profile: settings.profile_name.to_string(),
+                total_clicks: shared
+                    .stats_tracker
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .total_clicks,
+                cps: settings.target_cps(),
+                ..RunInfo::default()
+            };
+            webhooks::post(&settings.webhooks, WebhookEvent::Error, &message, &run);
+            *shared
+                .engine_fault
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(EngineFault { error, restarted });
+            if !restarted {
+                return;
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    })
+}
+
+// What the on_milestone hook is told
+fn milestone_hook_env(
+    config: &Config,
+    milestone: u64,
+    total_clicks: u64,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("BCLICKER_PROFILE", config.active_profile().name.clone()),
+        ("BCLICKER_MILESTONE", milestone.to_string()),
+        ("BCLICKER_TOTAL_CLICKS", total_clicks.to_string()),
+    ]
+}
+
+// Adds the engine's pending actions to the statistics and picks up changes
+// made on the TUI side, such as a reset
+fn flush_tally(tally: &mut ClickTally, stats: &mut Statistics, vars: &mut Variables) {
+    tally.flush(stats);
+    vars.total_clicks = stats.total_clicks;
+    vars.counters.clone_from(&stats.counters);
+}
+
+fn run_engine(shared: EngineShared) {
+    let EngineShared {
+        auto_clicker_running,
+        auto_clicker_paused,
+        countdown,
+        engine_settings,
+        stats_tracker,
+        rate_status,
+        audio_manager,
+        tray_manager,
+        jobs,
+        engine_fault,
+        stop_reason,
+        make_backend,
+        clock,
+        rng_seed,
+        shutdown,
+    } = shared;
+
+    // The engine's own copy of the settings, taken again whenever the TUI
+    // stores new ones
+    let mut settings = engine_settings.get();
+    let mut settings_version = u64::MAX;
+    let mut backend_kind = settings.backend;
+    let mut backend = make_backend(backend_kind);
+    // Actions not yet added to the statistics, and the variables conditions
+    // read, kept current between flushes
+    let mut tally = ClickTally::default();
+    let mut vars = Variables::default();
+    flush_tally(
+        &mut tally,
+        &mut stats_tracker.lock().unwrap_or_else(|e| e.into_inner()),
+        &mut vars,
+    );
+    let mut rng = match rng_seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut probe = Probe::default();
+    let mut focus_check = FocusCheck::default();
+    // Whether clicking is being held off for a blocked window
+    let mut was_blocked = false;
+    // CPS cap last reported to the status bar
+    let mut shown_cap: Option<f64> = None;
+    let mut region_check = RegionCheck::default();
+    let mut watchdog = InjectionWatchdog::default();
+    // Whether the run is waiting for the cursor to come back into the region
+    let mut was_outside = false;
+    let calibration = settings.calibration;
+    let mut scheduler = ClickScheduler::with_clock(calibration, clock.clone());
+    let mut second_scheduler = ClickScheduler::with_clock(calibration, clock.clone());
+    let mut job_scheduler = ClickScheduler::with_clock(calibration, clock.clone());
+    // Queued job being run and the clicks it has made
+    let mut job_run: Option<(u64, u64)> = None;
+    let mut rate_meter = RateMeter::with_clock(clock.clone());
+    let mut interval_tracker = IntervalTracker::new();
+    // Whether the status bar is being given a countdown to the next click
+    let mut counting_down = false;
+    // Duty cycle phase last reported to the status bar
+    let mut duty_phase: Option<DutyPhase> = None;
+    let mut click_index = 0u64;
+    // Mouse button presses since clicking started, for clicks_this_run
+    let mut run_clicks = 0u64;
+    // Verified clicks that never took this run, for the run log
+    let mut run_verify_failures = 0u64;
+    let mut was_running = false;
+    let mut was_paused = false;
+    let mut run_start = clock.now();
+    // Time the current run spent paused, and when the current pause began
+    let mut paused_for = Duration::ZERO;
+    let mut paused_at = run_start;
+    let mut minute_start = run_start;
+    // Highest achieved rate of the current run, for the run log
+    let mut run_peak = 0.0f64;
+    // Opened on the first logged run
+    let mut run_log: Option<RunLog> = None;
+    // Key pressed down by the hold mode, released when clicking stops
+    let mut held_key: Option<Key> = None;
+    // Raised while something clicks and given back when idle; dropped with
+    // the engine, so a crashed engine doesn't leave it raised either
+    let mut timer_resolution: Option<TimerResolution> = None;
+
+    loop {
+        if shutdown.load(Ordering::SeqCst) {
+            if let Some(key) = held_key.take() {
+                backend.key_up(key);
+            }
+            backend.flush();
+            return;
+        }
+        let is_running = auto_clicker_running.load(Ordering::SeqCst);
+        engine_settings.refresh(&mut settings_version, &mut settings);
+        // Skipped while the TUI holds the statistics, and tried again on the
+        // next pass
+        if tally.is_due() {
+            if let Ok(mut stats) = stats_tracker.try_lock() {
+                flush_tally(&mut tally, &mut stats, &mut vars);
+            }
+        }
+
+        if is_running != was_running {
+            let (start_delay, notifications, log_run, profile_name, calibration) = (
+                settings.start_delay_secs,
+                settings.notifications,
+                settings.run_log,
+                Arc::clone(&settings.profile_name),
+                settings.calibration,
+            );
+            if is_running
+                && start_delay > 0
+                && !run_countdown(
+                    start_delay,
+                    &notifications,
+                    &auto_clicker_running,
+                    &countdown,
+                    &audio_manager,
+                    &clock,
+                )
+            {
+                continue;
+            }
+
+            if let Ok(audio) = audio_manager.lock() {
+                if is_running {
+                    audio.play_start_sound();
+                } else {
+                    audio.play_stop_sound();
+                }
+            }
+
+            if is_running {
+                notifications.send(
+                    NotifyEvent::Start,
+                    "BClicker",
+                    &t!("notify-clicking-started"),
+                );
+            } else {
+                notifications.send(
+                    NotifyEvent::Stop,
+                    "BClicker",
+                    &t!("notify-clicking-stopped"),
+                );
+            }
+
+            let mut hook_env = vec![
+                ("BCLICKER_PROFILE", profile_name.to_string()),
+                ("BCLICKER_CPS", format!("{:.2}", settings.target_cps())),
+                ("BCLICKER_TOTAL_CLICKS", vars.total_clicks.to_string()),
+            ];
+            if is_running {
+                settings.hooks.run(HookEvent::Start, &hook_env);
+            } else {
+                hook_env.push(("BCLICKER_RUN_CLICKS", run_clicks.to_string()));
+                hook_env.push((
+                    "BCLICKER_RUN_SECS",
+                    clock.since(run_start).as_secs().to_string(),
+                ));
+                settings.hooks.run(HookEvent::Stop, &hook_env);
+            }
+
+            let run = RunInfo {
+                profile: profile_name.to_string(),
+                clicks: if is_running { 0 } else { run_clicks },
+                total_clicks: vars.total_clicks,
+                duration: if is_running {
+                    Duration::ZERO
+                } else {
+                    clock.since(run_start)
+                },
+                cps: settings.target_cps(),
+            };
+            // Taken on a start too, so a reason left after its run ended
+            // can't stick to the next one
+            let stopped_by = stop_reason.lock().unwrap_or_else(|e| e.into_inner()).take();
+            let (event, message) = if is_running {
+                (WebhookEvent::Start, t!("notify-clicking-started"))
+            } else {
+                stopped_by.unwrap_or_else(|| (WebhookEvent::Stop, t!("notify-clicking-stopped")))
+            };
+            webhooks::post(&settings.webhooks, event, &message, &run);
+
+            if let Ok(mut tray) = tray_manager.lock() {
+                if let Some(tray) = tray.as_mut() {
+                    if is_running {
+                        tray.start_flashing();
+                    } else {
+                        tray.stop_flashing();
+                    }
+                }
+            }
+
+            if !is_running && log_run {
+                let duration = clock.since(run_start);
+                let run = RunRecord {
+                    started_at: (SystemTime::now() - duration)
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs(),
+                    duration_secs: duration.as_secs_f64(),
+                    clicks: run_clicks,
+                    peak_cps: run_peak,
+                    profile: profile_name.to_string(),
+                    verify_failures: run_verify_failures,
+                };
+                if run_log.is_none() {
+                    run_log = RunLog::open()
+                        .map_err(|e| eprintln!("[WARNING] Run log unavailable: {}", e))
+                        .ok();
+                }
+                if let Some(Err(e)) = run_log.as_ref().map(|log| log.record(&run)) {
+                    eprintln!("[WARNING] Could not record run: {}", e);
+                }
+            }
+
+            was_running = is_running;
+            was_paused = false;
+            was_outside = false;
+            shown_cap = None;
+            auto_clicker_paused.store(false, Ordering::SeqCst);
+            paused_for = Duration::ZERO;
+            if let Some(key) = held_key.take() {
+                backend.key_up(key);
+            }
+            backend.flush();
+            scheduler.set_calibration(calibration);
+            scheduler.reset();
+            second_scheduler.set_calibration(calibration);
+            second_scheduler.reset();
+            rate_meter.reset();
+            click_index = 0;
+            run_clicks = 0;
+            run_verify_failures = 0;
+            run_peak = 0.0;
+            duty_phase = None;
+            if is_running {
+                // Restarted after a crash and running again
+                *engine_fault.lock().unwrap_or_else(|e| e.into_inner()) = None;
+                run_start = clock.now();
+                minute_start = run_start;
+                interval_tracker.reset();
+                watchdog.reset();
+                if let Ok(mut stats) = stats_tracker.lock() {
+                    flush_tally(&mut tally, &mut stats, &mut vars);
+                    stats.start_minute();
+                }
+            } else {
+                *rate_status.lock().unwrap_or_else(|e| e.into_inner()) = RateStatus {
+                    timing: interval_tracker.summary(),
+                    injection_blocked: watchdog.tripped(),
+                    ..RateStatus::default()
+                };
+            }
+        }
+
+        // A pause freezes the run where it is: the click index, and with
+        // it the pattern and sequence position, stays put, pending
+        // deadlines move back by the pause and the duty cycle only
+        // counts time spent unpaused
+        let is_paused = is_running && auto_clicker_paused.load(Ordering::SeqCst);
+        if is_paused != was_paused {
+            was_paused = is_paused;
+            if is_paused {
+                paused_at = clock.now();
+                if let Some(key) = held_key.take() {
+                    backend.key_up(key);
+                }
+                backend.flush();
+            } else {
+                let pause = clock.since(paused_at);
+                paused_for += pause;
+                scheduler.postpone(pause);
+                second_scheduler.postpone(pause);
+                job_scheduler.postpone(pause);
+                rate_meter.reset();
+                interval_tracker.break_sequence();
+                let mut status = rate_status.lock().unwrap_or_else(|e| e.into_inner());
+                status.next_click = status.next_click.map(|at| at + pause);
+                status.duty = status.duty.map(|(phase, ends)| (phase, ends + pause));
+            }
+        }
+        if is_paused {
+            clock.sleep(Duration::from_millis(50));
+            continue;
+        }
+
+        // Nothing clicks into a blocked window, queued jobs included.
+        // Clicking is held off like a pause and starts fresh once focus
+        // moves on
+        let busy = is_running || jobs.front().is_some();
+        if busy != timer_resolution.is_some() {
+            timer_resolution = busy.then(TimerResolution::raise);
+        }
+        let blocked = busy
+            .then(|| focus_check.blocked(&settings.blocked_windows))
+            .flatten();
+        if blocked.is_some() != was_blocked {
+            was_blocked = blocked.is_some();
+            rate_status
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .blocked = was_blocked;
+            if let Some(window) = blocked {
+                if let Some(key) = held_key.take() {
+                    backend.key_up(key);
+                }
+                backend.flush();
+                let window = if window.process.is_empty() {
+                    &window.title
+                } else {
+                    &window.process
+                };
+                settings.notifications.send(
+                    NotifyEvent::Error,
+                    &t!("title-blocked"),
+                    &t!("notify-blocked", window = window.clone()),
+                );
+            } else {
+                scheduler.reset();
+                second_scheduler.reset();
+                job_scheduler.reset();
+                rate_meter.reset();
+                interval_tracker.break_sequence();
+            }
+        }
+        if was_blocked {
+            clock.sleep(Duration::from_millis(50));
+            continue;
+        }
+
+        // A CPS cap for the focused window stretches every interval to at
+        // least its own, queued jobs and the second channel included
+        let cap = busy
+            .then(|| focus_check.cps_cap(&settings.cps_caps))
+            .flatten();
+        let min_interval = cap.map_or(Duration::ZERO, |cps| Duration::from_secs_f64(1.0 / cps));
+        let throttled = cap.filter(|&cps| cps < settings.target_cps());
+        if throttled != shown_cap {
+            shown_cap = throttled;
+            rate_status
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .cps_cap = throttled;
+        }
+
+        // Queued jobs run ahead of the main clicking, which waits for
+        // them whether or not it's on
+        if let Some((id, job)) = jobs.front() {
+            let clicks = match job_run {
+                Some((run_id, clicks)) if run_id == id => clicks,
+                _ => {
+                    job_scheduler.reset();
+                    0
+                }
+            };
+            job_run = Some((id, clicks));
+            if job_scheduler.poll(job.interval().max(min_interval)) {
+                job.perform(&mut Glide::new(
+                    backend.as_mut(),
+                    settings.human_motion,
+                    &mut rng,
+                ));
+                backend.flush();
+                tally.record_action("Click Job", true);
+                vars.total_clicks += 1;
+                job_run = Some((id, clicks + 1));
+                if clicks + 1 >= job.count {
+                    jobs.finish(id);
+                }
+                rate_status.lock().unwrap_or_else(|e| e.into_inner()).job =
+                    Some((clicks + 1, job.count));
+            }
+            continue;
+        }
+        if job_run.take().is_some() {
+            // Back to the main clicking without catching up on the wait
+            scheduler.reset();
+            second_scheduler.reset();
+            rate_meter.reset();
+            interval_tracker.break_sequence();
+            rate_status.lock().unwrap_or_else(|e| e.into_inner()).job = None;
+        }
+
+        if is_running {
+            // Backends are switchable at runtime through the config
+            if settings.backend != backend_kind {
+                if let Some(key) = held_key.take() {
+                    backend.key_up(key);
+                }
+                backend_kind = settings.backend;
+                backend = make_backend(backend_kind);
+            }
+
+            // Input that visibly goes nowhere stops the run rather than
+            // carrying on as if it clicked
+            if settings.injection_watchdog {
+                watchdog.check_sent(backend.as_mut());
+            }
+            if watchdog.tripped() {
+                *stop_reason.lock().unwrap_or_else(|e| e.into_inner()) =
+                    Some((WebhookEvent::Error, t!("notify-injection-blocked")));
+                auto_clicker_running.store(false, Ordering::SeqCst);
+                eprintln!(
+                    "[ERROR] Input from the {} backend isn't arriving, stopping the run",
+                    backend.name()
+                );
+                settings.notifications.send(
+                    NotifyEvent::Error,
+                    &t!("title-injection-blocked"),
+                    &t!("notify-injection-blocked"),
+                );
+                continue;
+            }
+
+            // Outside the click region the run holds off like a pause and
+            // starts fresh once the cursor is back inside
+            let outside = region_check.outside(settings.click_region, backend.as_ref());
+            if outside != was_outside {
+                was_outside = outside;
+                rate_status
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .outside_region = outside;
+                if outside {
+                    if let Some(key) = held_key.take() {
+                        backend.key_up(key);
+                    }
+                    backend.flush();
+                } else {
+                    scheduler.reset();
+                    second_scheduler.reset();
+                    rate_meter.reset();
+                    interval_tracker.break_sequence();
+                }
+            }
+            if outside {
+                clock.sleep(Duration::from_millis(50));
+                continue;
+            }
+
+            // The profile's duty cycle pauses clicking for part of each
+            // cycle, counted from the start of the run
+            let duty = settings
+                .duty_cycle
+                .map(|duty| duty.phase(clock.since(run_start).saturating_sub(paused_for)));
+            let phase = duty.map(|(phase, _)| phase);
+            if phase != duty_phase {
+                duty_phase = phase;
+                // Each clicking phase starts fresh instead of catching up
+                // on the pause
+                scheduler.reset();
+                second_scheduler.reset();
+                rate_meter.reset();
+                interval_tracker.break_sequence();
+                rate_status.lock().unwrap_or_else(|e| e.into_inner()).duty =
+                    duty.map(|(phase, left)| (phase, clock.now() + left));
+            }
+
+            // The hold mode lets go of its key during duty cycle pauses
+            // and when the key changes
+            let hold = settings
+                .hold_key
+                .filter(|_| !matches!(duty, Some((DutyPhase::Paused, _))));
+            if hold != held_key {
+                if let Some(key) = held_key {
+                    backend.key_up(key);
+                }
+                if let Some(key) = hold {
+                    backend.key_down(key);
+                }
+                backend.flush();
+                held_key = hold;
+            }
+
+            if let Some((DutyPhase::Paused, left)) = duty {
+                clock.sleep(left.min(Duration::from_millis(50)));
+            } else {
+                // The second channel fires whenever its own deadline
+                // passes, and the main poll wakes up in time for it
+                if let Some(channel) = settings.second_channel {
+                    // In hold mode its poll does the waiting, as nothing
+                    // else is scheduled
+                    let due = settings.hold_key.is_some() || second_scheduler.is_due();
+                    if due && second_scheduler.poll(channel.interval().max(min_interval)) {
+                        let modifier = settings.modifier.filter(|_| settings.anti_afk.is_none());
+                        modifier::hold(modifier, backend.as_mut(), |backend| {
+                            backend.click(channel.mouse_button())
+                        });
+                        if !scheduler.is_due() {
+                            backend.flush();
+                        }
+                        tally.record_action(channel.action_label(), true);
+                        vars.total_clicks += 1;
+                        run_clicks += 1;
+                    }
+                }
+                if settings.hold_key.is_some() {
+                    // Nothing to schedule while a key is held
+                    if settings.second_channel.is_none() {
+                        clock.sleep(Duration::from_millis(50));
+                    }
+                } else {
+                    let wake_by = settings
+                        .second_channel
+                        .map(|_| second_scheduler.release_time());
+
+                    let interval = settings
+                        .next_interval(&mut rng, click_index)
+                        .max(min_interval);
+                    let deadline = scheduler.next_deadline();
+                    if scheduler.poll_until(interval, wake_by) {
+                        interval_tracker.record(deadline, clock.now());
+                        vars.run_time = clock.since(run_start).saturating_sub(paused_for);
+                        vars.clicks_this_run = run_clicks;
+                        let gate = settings.gate(&mut probe, click_index, &vars);
+                        if gate == Gate::Stop {
+                            *stop_reason.lock().unwrap_or_else(|e| e.into_inner()) =
+                                Some((WebhookEvent::AutoStop, t!("notify-stop-condition")));
+                            auto_clicker_running.store(false, Ordering::SeqCst);
+                            settings.notifications.send(
+                                NotifyEvent::Info,
+                                &t!("title-sequence"),
+                                &t!("notify-stop-condition"),
+                            );
+                            continue;
+                        }
+                        if gate == Gate::Run {
+                            let label = settings.action_label(click_index);
+                            let mut watched = Watched::new(
+                                backend.as_mut(),
+                                &mut watchdog,
+                                settings.injection_watchdog,
+                            );
+                            let mut glide =
+                                Glide::new(&mut watched, settings.human_motion, &mut rng);
+                            // The pixel under the target before the click,
+                            // to tell whether it changed
+                            let check = settings
+                                .verify
+                                .as_ref()
+                                .zip(settings.click_target(click_index))
+                                .map(|(verify, target)| (verify, target, probe.pixel(target)));
+                            let clicked = settings.fire(&mut glide, click_index, &vars);
+                            if let Some((verify, target, before)) = check {
+                                let checking_since = clock.now();
+                                let mut retries = 0;
+                                let passed = loop {
+                                    glide.flush();
+                                    clock.sleep(verify.delay());
+                                    if verify.passed(before, probe.pixel(target)) {
+                                        break true;
+                                    }
+                                    if retries == u64::from(verify.retries) {
+                                        break false;
+                                    }
+                                    retries += 1;
+                                    let again = settings.fire(&mut glide, click_index, &vars);
+                                    tally.record_action(label, again);
+                                    vars.total_clicks += again as u64;
+                                    run_clicks += again as u64;
+                                };
+                                // Time spent checking doesn't have to be caught up
+                                scheduler.postpone(clock.since(checking_since));
+                                tally.record_verify(retries, passed);
+                                if !passed {
+                                    run_verify_failures += 1;
+                                    eprintln!(
+                                        "[WARNING] Click at ({}, {}) not verified after {} retries",
+                                        target.x, target.y, retries
+                                    );
+                                    // Once per run, not for every click after
+                                    if run_verify_failures == 1 {
+                                        let message = t!(
+                                            "notify-verify-failed",
+                                            point = format!("({}, {})", target.x, target.y),
+                                            retries = retries
+                                        );
+                                        settings.notifications.send(
+                                            NotifyEvent::Error,
+                                            &t!("title-verify"),
+                                            &message,
+                                        );
+                                        let run = RunInfo {
+                                            profile: settings.profile_name.to_string(),
+                                            clicks: run_clicks,
+                                            total_clicks: vars.total_clicks,
+                                            duration: clock.since(run_start),
+                                            cps: settings.target_cps(),
+                                        };
+                                        webhooks::post(
+                                            &settings.webhooks,
+                                            WebhookEvent::Error,
+                                            &message,
+                                            &run,
+                                        );
+                                    }
+                                }
+                            }
+
+                            // Clicks that are already due join the same batch
+                            if !scheduler.is_due() {
+                                backend.flush();
+                            }
+
+                            tally.record_action(label, clicked);
+                            vars.total_clicks += clicked as u64;
+                            if let Some(name) = settings.counter(click_index) {
+                                tally.bump_counter(name);
+                                match vars.counters.get_mut(name) {
+                                    Some(count) => *count += 1,
+                                    None => {
+                                        vars.counters.insert(name.to_string(), 1);
+                                    }
+                                }
+                            }
+                            run_clicks += clicked as u64;
+                        }
+
+                        rate_meter.record_click();
+                        click_index += 1;
+
+                        let slow = interval >= Duration::from_secs(1);
+                        if slow || counting_down {
+                            counting_down = slow;
+                            rate_status
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner())
+                                .next_click = slow.then(|| scheduler.next_deadline());
+                        }
+                    }
+
+                    let target_cps = settings.target_cps().min(cap.unwrap_or(f64::MAX));
+                    if let Some(achieved) = rate_meter.sample(target_cps) {
+                        {
+                            let mut status = rate_status.lock().unwrap_or_else(|e| e.into_inner());
+                            *status = RateStatus {
+                                next_click: status.next_click,
+                                duty: status.duty,
+                                job: status.job,
+                                blocked: status.blocked,
+                                outside_region: status.outside_region,
+                                cps_cap: status.cps_cap,
+                                timing: interval_tracker.summary(),
+                                ..RateStatus::measured(achieved, target_cps)
+                            };
+                        }
+                        run_peak = run_peak.max(achieved);
+                        if let Ok(mut stats) = stats_tracker.lock() {
+                            stats.record_rate(
+                                achieved,
+                                clock.since(run_start).saturating_sub(paused_for).as_secs(),
+                            );
+                        }
+                    }
+                }
+            }
+            // Checked every pass: slow rates measure over windows longer
+            // than a minute
+            if clock.since(minute_start) >= Duration::from_secs(60) {
+                minute_start += Duration::from_secs(60);
+                if let Ok(mut stats) = stats_tracker.lock() {
+                    // The finished minute's clicks go in its own bucket
+                    flush_tally(&mut tally, &mut stats, &mut vars);
+                    stats.start_minute();
+                }
+            }
+        } else {
+            clock.sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+// FIXED: Fast event handling system without blocking
+fn setup_event_system() -> (mpsc::Sender<AppEvent>, mpsc::Receiver<AppEvent>) {
+    let (tx, rx) = mpsc::channel();
+    let tx_clone = tx.clone();
+
+    // Input handling thread - no more lag!
+    thread::spawn(move || {
+        loop {
+            if let Ok(CEvent::Key(key)) = event::read() {
+                if tx_clone.send(AppEvent::Input(key)).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    (tx, rx)
+}
+
+// The whole application; the bclicker binary only calls this. Kept as a
+// library so the benches can drive the scheduler and backends directly
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    run_with(std::env::args().skip(1).collect(), false)
+}
+
+// `bclickerw`: the daemon with its log in bclicker_daemon.log, since a
+// program without a console has nowhere else to print. Takes the daemon's
+// flags
+pub fn run_background() -> Result<(), Box<dyn std::error::Error>> {
+    let args = ["daemon".to_string()]
+        .into_iter()
+        .chain(std::env::args().skip(1))
+        .collect();
+    run_with(args, true)
+}
+
+fn run_with(args: Vec<String>, background: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if args.first().map(String::as_str) == Some("benchmark") {
+        println!("[BENCH] Running click engine against a null backend...");
+        let mut config = load_config();
+        config.sanitize();
+        println!("{}", benchmark::run_benchmark(&config));
+        return Ok(());
+    }
+
+    if let Some(command @ ("export" | "import")) = args.first().map(String::as_str) {
+        if let Err(e) = transfer::run_cli(command, &args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("calibrate") {
+        if let Err(e) = calibration::run_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("token") {
+        if let Err(e) = auth::run_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("click") {
+        if let Err(e) = oneshot::run_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("headless") {
+        if let Err(e) = headless::run_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("status") {
+        if let Err(e) = ipc::run_status_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("watch") {
+        if let Err(e) = ipc::run_watch_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("service") {
+        if let Err(e) = service::run_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("quit") {
+        if let Err(e) = ipc::run_quit_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+    if args.first().map(String::as_str) == Some("attach") {
+        if let Err(e) = ipc::run_attach_cli(&args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+    if let Some(verb @ ("start" | "stop" | "toggle")) = args.first().map(String::as_str) {
+        if let Err(e) = ipc::run_control_cli(verb, &args[1..]) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+    if let Some(command @ ("completions" | "man")) = args.first().map(String::as_str) {
+        let result = match command {
+            "completions" => cli::run_completions(&args[1..]),
+            _ => cli::run_man(),
+        };
+        if let Err(e) = result {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    match args.first().map(String::as_str) {
+        Some("-h" | "--help") => {
+            cli::command().print_long_help()?;
+            return Ok(());
+        }
+        Some("-V" | "--version") => {
+            print!("{}", cli::command().render_version());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // A daemon already running shows up here rather than a second instance
+    // starting; one running in its own terminal refuses, and then a second
+    // instance starts as it always has
+    if args.is_empty() && ipc::instance_running() {
+        match ipc::run_attach_cli(&[]) {
+            Err(ipc::IpcError::Refused(_)) => {}
+            Err(e) => {
+                eprintln!("[ERROR] {}", e);
+                std::process::exit(e.exit_code());
+            }
+            Ok(()) => return Ok(()),
+        }
+    }
+
+    // `bclicker daemon` is the whole application without a terminal of its
+    // own: engine, hotkeys, tray and servers, with the TUI shown to whichever
+    // client attaches. The same flags apply
+    let daemon = args.first().map(String::as_str) == Some("daemon");
+    let mut args: Vec<String> = args.into_iter().skip(usize::from(daemon)).collect();
+    // `--tray-only` is the daemon kept in the tray: no TUI or raw mode here,
+    // just the tray icon, hotkeys and notifications, and the TUI a `bclicker
+    // attach` away
+    let tray_only = !daemon && args.iter().any(|a| a == "--tray-only");
+    let daemon = daemon || tray_only;
+    args.retain(|a| a != "--tray-only");
+    // `--dir DIR` for a daemon started from elsewhere, e.g. at login: it
+    // works in DIR, and finds its config there
+    if let Some(at) = args.iter().position(|a| a == "--dir").filter(|_| daemon) {
+        let dir = args
+            .drain(at..(at + 2).min(args.len()))
+            .nth(1)
+            .unwrap_or_default();
+        if let Err(e) = std::env::set_current_dir(&dir) {
+            eprintln!("[ERROR] --dir {}: {}", dir, e);
+            std::process::exit(1);
+        }
+    }
+    let logged = if background {
+        daemon::log_to_file()
+    } else if tray_only {
+        daemon::leave_console()
+    } else {
+        Ok(())
+    };
+    if let Err(e) = logged {
+        eprintln!("[ERROR] {}", e);
+        std::process::exit(1);
+    }
+    if daemon && args.iter().any(|a| a == "--detach") {
+        if let Err(e) = daemon::detach(&args) {
+            eprintln!("[ERROR] {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if daemon && ipc::instance_running() {
+        eprintln!("[ERROR] {}", daemon::ALREADY_RUNNING);
+        std::process::exit(1);
+    }
+    // `daemon --systemd` runs as a Type=notify service with its log in the
+    // journal; `bclicker service install` writes the unit
+    let systemd = daemon && args.iter().any(|a| a == "--systemd");
+    let args: Vec<String> = args.into_iter().filter(|a| a != "--systemd").collect();
+    #[cfg(unix)]
+    let _journal = if systemd {
+        service::JournalLog::start()
+    } else {
+        None
+    };
+
+    // --dry-run is a session switch rather than a setting, so it isn't an
+    // override and never reaches the config file
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let args: Vec<String> = args.into_iter().filter(|a| a != "--dry-run").collect();
+    let requested = overrides::collect(&args);
+
+    dpi::enable_dpi_awareness();
+    // The splash runs before the config loads, so it follows the locale;
+    // App::new applies the configured language
+    i18n::set_language(Language::detect());
+    if !daemon {
+        loading_animation()?;
+    }
+
+    let mut app = App::new(&requested, dry_run);
+    if dry_run {
+        println!("[INFO] Dry run: the engine clicks through the null backend");
+    }
+    println!(
+        "[INIT] BClicker Professional initialized (Session #{})",
+        app.stats_tracker
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .total_sessions
+    );
+    if !app.config.backend.suited_to_session() {
+        println!(
+            "[WARNING] Wayland session detected: enigo clicks only reach XWayland windows, \
+             set backend = \"wayland\" on wlroots compositors or \"portal\" on GNOME and KDE"
+        );
+    }
+    if app.config.run_log && !cfg!(feature = "sqlite") {
+        println!("[WARNING] run_log needs a build with the sqlite feature; runs aren't recorded");
+    }
+    if app.config.grpc.is_some() && !cfg!(feature = "grpc") {
+        println!("[WARNING] [grpc] needs a build with the grpc feature; the gRPC server is off");
+    }
+
+    // FIXED: Fast event system setup. A daemon's keys come from its client
+    let (tx, rx) = if daemon {
+        mpsc::channel()
+    } else {
+        setup_event_system()
+    };
+    if daemon {
+        daemon::quit_on_signals(tx.clone());
+    }
+
+    let tray_manager = TrayManager::new(
+        Arc::clone(&app.show_tui),
+        Arc::clone(&app.auto_clicker_running),
+        daemon,
+        tx.clone(),
+    );
+    if tray_only && tray_manager.is_none() {
+        println!(
+            "[WARNING] No tray icon could be made; `bclicker attach` shows the TUI \
+             and `bclicker quit` closes BClicker"
+        );
+    }
+    let tray_manager_arc = Arc::new(Mutex::new(tray_manager));
+
+    app.show_notification(
+        NotifyEvent::Info,
+        "BClicker Professional",
+        &t!("notify-started"),
+    );
+
+    // Mouse and tap triggers come from the global input listener. Hotkeys are
+    // registered with the OS where it can be done, so a taken combo shows up
+    // as a conflict, and are otherwise matched in the listener's key stream
+    let (hotkey_tx, hotkey_rx) = mpsc::channel();
+    app.hotkey_state
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .wake = Some(hotkey_tx.clone());
+    let listener = KeyListener::new();
+    #[cfg(any(windows, target_os = "linux"))]
+    let registered = GlobalHotkeys::start(hotkey_tx.clone());
+    #[cfg(not(any(windows, target_os = "linux")))]
+    let registered: Result<NoHotkeys, String> = Err("not on this platform".to_string());
+    let _input_hook_handle = match input_hook::spawn(
+        Arc::clone(&app.hotkey_state),
+        Arc::clone(&app.auto_clicker_running),
+        listener.clone(),
+        hotkey_tx,
+    ) {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            if app.config.mouse_trigger.is_some() || app.config.tap_trigger.is_some() {
+                println!("[WARNING] Mouse and tap triggers unavailable: {}", e);
+            }
+            app.input_hook_error = Some(e);
+            None
+        }
+    };
+    let hotkey_backend: Box<dyn HotkeyBackend> = match (registered, &app.input_hook_error) {
+        (Ok(registered), _) => Box::new(registered),
+        (Err(_), None) => Box::new(listener),
+        (Err(e), Some(hook)) => {
+            let reason = format!("{}, and {}", e, hook);
+            if app.config.toggle_keybind.is_some() {
+                println!("[WARNING] Global hotkeys unavailable: {}", reason);
+            }
+            Box::new(NoHotkeys(reason))
+        }
+    };
+    let _hotkey_handle = setup_global_hotkey(
+        Arc::clone(&app.hotkey_state),
+        Arc::clone(&app.auto_clicker_running),
+        hotkey_backend,
+        hotkey_rx,
+        tx.clone(),
+    );
+
+    let audio_manager = Arc::new(Mutex::new(app.audio_manager.clone()));
+
+    let _focus_handle = focus::spawn(Arc::clone(&app.focused_window), tx.clone());
+
+    let _overlay_handle = overlay::spawn(OverlayFeed {
+        enabled: Arc::clone(&app.overlay_enabled),
+        running: Arc::clone(&app.auto_clicker_running),
+        countdown: Arc::clone(&app.countdown),
+        rate_status: Arc::clone(&app.rate_status),
+    });
+
+    // `bclicker status` and friends talk to this instance through it
+    let feed = IpcFeed {
+        running: Arc::clone(&app.auto_clicker_running),
+        paused: Arc::clone(&app.auto_clicker_paused),
+        countdown: Arc::clone(&app.countdown),
+        engine_settings: Arc::clone(&app.engine_settings),
+        stats: Arc::clone(&app.stats_tracker),
+        rate_status: Arc::clone(&app.rate_status),
+        started: Instant::now(),
+        ui: tx.clone(),
+    };
+    if let Some(mqtt) = app.config.mqtt.clone() {
+        mqtt::spawn(mqtt, feed.clone());
+    }
+    // The web, gRPC and deck APIs, and OSC from other machines, are never
+    // open without a token, so the first start with any of them makes one
+    let needs_token = app.config.web.is_some()
+        || app.config.grpc.is_some()
+        || app.config.deck.is_some()
+        || app.config.osc.as_ref().is_some_and(OscConfig::needs_token);
+    if needs_token && app.config.api_tokens.is_empty() {
+        let token = ApiToken::generate("default", Scope::Control);
+        match &app.config.web {
+            Some(web) => println!(
+                "[INFO] API token created; open {} on your phone. \
+                 `bclicker token` lists it again",
+                web.link(&token.token)
+            ),
+            None => println!(
+                "[INFO] API token created: {}. `bclicker token` lists it again",
+                token.token
+            ),
+        }
+        app.config.api_tokens.push(token);
+        app.save_config();
+    }
+    if let Some(Err(e)) = app
+        .config
+        .deck
+        .map(|deck| deck::spawn(deck, app.config.api_tokens.clone(), feed.clone()))
+    {
+        println!("[WARNING] Deck endpoint unavailable: {}", e);
+    }
+    if let Some(midi) = app.config.midi.clone() {
+        midi::spawn(midi, feed.clone());
+    }
+    if let Some(Err(e)) = app
+        .config
+        .osc
+        .clone()
+        .map(|osc| osc::spawn(osc, app.config.api_tokens.clone(), feed.clone()))
+    {
+        println!("[WARNING] OSC server unavailable: {}", e);
+    }
+    #[cfg(feature = "grpc")]
+    if let Some(Err(e)) = app
+        .config
+        .grpc
+        .clone()
+        .map(|config| grpc::spawn(config, app.config.api_tokens.clone(), feed.clone()))
+    {
+        println!("[WARNING] gRPC server unavailable: {}", e);
+    }
+    let _mdns = match app.config.web.clone() {
+        Some(web) => match web::spawn(web.clone(), app.config.api_tokens.clone(), feed.clone()) {
+            Ok(()) if web.advertise && !web.bind.is_loopback() => mdns::advertise(&web)
+                .map_err(|e| println!("[WARNING] mDNS announcement unavailable: {}", e))
+                .ok(),
+            Ok(()) => None,
+            Err(e) => {
+                println!("[WARNING] Web remote unavailable: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    // Nothing could attach to a daemon without it
+    let _ipc = match ipc::spawn(feed) {
+        Ok(server) => Some(server),
+        Err(e) if daemon => return Err(format!("control socket unavailable: {}", e).into()),
+        Err(e) => {
+            println!("[WARNING] Control socket unavailable: {}", e);
+            None
+        }
+    };
+
+    let _clicker_handle = start_clicker_thread(EngineShared {
+        auto_clicker_running: Arc::clone(&app.auto_clicker_running),
+        auto_clicker_paused: Arc::clone(&app.auto_clicker_paused),
+        countdown: Arc::clone(&app.countdown),
+        engine_settings: Arc::clone(&app.engine_settings),
+        stats_tracker: Arc::clone(&app.stats_tracker),
+        rate_status: Arc::clone(&app.rate_status),
+        audio_manager: Arc::clone(&audio_manager),
+        tray_manager: Arc::clone(&tray_manager_arc),
+        jobs: app.jobs.clone(),
+        engine_fault: Arc::clone(&app.engine_fault),
+        stop_reason: Arc::clone(&app.stop_reason),
+        make_backend: Arc::new(BackendKind::create),
+        clock: Clock::Real,
+        rng_seed: None,
+        shutdown: Arc::new(AtomicBool::new(false)),
+    });
+
+    println!("[SUCCESS] BClicker Professional started successfully");
+
+    let mut screen = if daemon {
+        if tray_only {
+            println!(
+                "[INFO] Running in the tray; its Show item or `bclicker attach` opens the TUI"
+            );
+        } else {
+            println!("[INFO] Running as a daemon; `bclicker attach` shows the TUI");
+        }
+        if systemd {
+            service::notify("READY=1");
+        }
+        Screen::Remote(None)
+    } else {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        let backend = CrosstermBackend::new(stdout);
+        Screen::Local(Terminal::new(backend)?)
+    };
+    let mut sessions = 0..;
+
+    // Sleeps until input arrives, another thread wakes it or the next
+    // update is due, so an idle or hidden TUI costs next to no CPU
+    let mut next_update = Instant::now();
+    loop {
+        if app.should_quit {
+            break;
+        }
+
+        let first = match rx.recv_timeout(next_update.saturating_duration_since(Instant::now())) {
+            Ok(event) => Some(event),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        for event in first.into_iter().chain(rx.try_iter()) {
+            match event {
+                AppEvent::Input(key_event) => {
+                    // Only process input when UI is shown
+                    if app.show_tui.load(Ordering::SeqCst) {
+                        app.handle_input(key_event);
+                    }
+                }
+                AppEvent::Wake => {}
+                AppEvent::Control(request) => {
+                    let _ = request.reply.send(app.handle_control(request.control));
+                }
+                AppEvent::Focus(window) => app.apply_profile_rules(window.as_ref()),
+                AppEvent::Attach(attach) => match &mut screen {
+                    // A newer client takes the screen from the one before
+                    Screen::Remote(remote) => {
+                        *remote =
+                            daemon::RemoteScreen::accept(attach, sessions.next().unwrap_or(0))
+                                .map_err(|e| println!("[WARNING] Client couldn't attach: {}", e))
+                                .ok();
+                        app.show_tui.store(true, Ordering::SeqCst);
+                        app.needs_redraw = true;
+                    }
+                    Screen::Local(_) => daemon::refuse(attach),
+                },
+                AppEvent::Remote(session, input) => {
+                    let Screen::Remote(remote) = &mut screen else {
+                        continue;
+                    };
+                    let Some(client) = remote.as_mut().filter(|r| r.session == session) else {
+                        continue;
+                    };
+                    match input {
+                        ipc::RemoteInput::Key(key_event) => {
+                            if app.show_tui.load(Ordering::SeqCst) {
+                                app.handle_input(key_event);
+                            }
+                            // The quit key lets the client go; the daemon
+                            // itself stops with `bclicker quit` or the tray
+                            if app.should_quit {
+                                app.should_quit = false;
+                                *remote = None;
+                            }
+                        }
+                        ipc::RemoteInput::Resize(cols, rows) => {
+                            if client.resize(cols, rows).is_err() {
+                                *remote = None;
+                            }
+                            app.needs_redraw = true;
+                        }
+                    }
+                }
+                AppEvent::Quit => {
+                    app.should_quit = true;
+                }
+            }
+            // Whatever the event changed is picked up straight away
+            next_update = Instant::now();
+        }
+
+        if Instant::now() >= next_update {
+            app.update();
+            next_update = Instant::now() + app.update_interval();
+        }
+
+        // Only draw when UI is shown AND needs redraw - no more lag!
+        if app.show_tui.load(Ordering::SeqCst) && app.needs_redraw {
+            app.refresh_main_cache();
+            match &mut screen {
+                Screen::Local(terminal) => draw_screen(terminal, &app)?,
+                // A client that went away just stops being drawn to
+                Screen::Remote(remote) => {
+                    if let Some(client) = remote {
+                        if draw_screen(&mut client.terminal, &app).is_err() {
+                            *remote = None;
+                        }
+                    }
+                }
+            }
+            app.needs_redraw = false;
+        }
+    }
+
+    if systemd {
+        service::notify("STOPPING=1");
+    }
+    app.release_held_key();
+    app.save_config();
+    app.save_statistics();
+    if let Screen::Local(mut terminal) = screen {
+        cleanup_terminal(&mut terminal)?;
+    }
+    println!("[EXIT] BClicker Professional closed. Configuration saved.");
+    Ok(())
+}
+
+// Where the TUI is drawn
+enum Screen {
+    // The terminal this process was started in
+    Local(Terminal<CrosstermBackend<Stdout>>),
+    // A daemon's attached client, when there is one
+    Remote(Option<daemon::RemoteScreen>),
+}
+
+fn draw_screen<B: Backend>(terminal: &mut Terminal<B>, app: &App) -> io::Result<()> {
+    terminal.draw(|f| {
+        if app.input_mode == InputMode::ShowingHelp {
+            draw_help_screen(f, app);
+        } else if app.input_mode == InputMode::EditingSequence {
+            draw_sequence_editor(f, app);
+        } else if app.input_mode == InputMode::EditingPresets {
+            draw_preset_editor(f, app);
+        } else if app.input_mode == InputMode::EditingSchedules {
+            draw_schedule_editor(f, app);
+        } else if app.input_mode == InputMode::SelectingAudioDevice {
+            draw_audio_device_picker(f, app);
+        } else if app.input_mode == InputMode::ShowingStats {
+            draw_stats_screen(f, app);
+        } else if app.input_mode == InputMode::ConfirmingImport {
+            draw_import_preview(f, app);
+        } else if app.input_mode == InputMode::CommandPalette {
+            draw_ui(f, app);
+            draw_command_palette(f, app);
+        } else if app.input_mode == InputMode::HotkeyConflict {
+            draw_ui(f, app);
+            draw_hotkey_conflict(f, app);
+        } else {
+            draw_ui(f, app);
+        }
+    })?;
+    Ok(())
+}
+
+// FIXED: Simplified and perfectly centered loading animation
+fn loading_animation() -> Result<(), Box<dyn std::error::Error>> {
+    let mut stdout = io::stdout();
+    execute!(stdout, terminal::Clear(ClearType::All), cursor::Hide)?;
+
+    let term_size = terminal::size()?;
+    let term_width = term_size.0 as usize;
+    let term_height = term_size.1 as usize;
+
+    let title = "BClicker Professional v2.0";
+    let warning = format!("⚠️  {}  ⚠️", t!("splash-warning"));
+
+    let spinner_chars = vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+    let center_y = term_height / 2;
+
+    for i in 0..40 {
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        // Centered title
+        let title_x = if term_width > title.len() {
+            (term_width - title.len()) / 2
+        } else {
+            0
+        };
+        execute!(
+            stdout,
+            cursor::MoveTo(title_x as u16, (center_y.saturating_sub(3)) as u16),
+            SetForegroundColor(Color::Cyan),
+            Print(title),
+            ResetColor
+        )?;
+
+        // Centered spinner
+        let spinner_idx = i % spinner_chars.len();
+        let spinner = spinner_chars[spinner_idx];
+        let loader_text = format!("{} {}", t!("splash-loading"), spinner);
+        let loader_x = if term_width > loader_text.len() {
+            (term_width - loader_text.len()) / 2
+        } else {
+            0
+        };
+        execute!(
+            stdout,
+            cursor::MoveTo(loader_x as u16, center_y as u16),
+            SetForegroundColor(Color::Green),
+            Print(loader_text),
+            ResetColor
+        )?;
+
+        // Centered warning
+        let warning_x = if term_width > warning.len() {
+            (term_width - warning.len()) / 2
+        } else {
+            0
+        };
+        execute!(
+            stdout,
+            cursor::MoveTo(warning_x as u16, (center_y + 2) as u16),
+            SetForegroundColor(Color::Red),
+            Print(&warning),
+            ResetColor
+        )?;
+
+        stdout.flush()?;
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    let complete_msg = t!("splash-complete");
+    let complete_x = if term_width > complete_msg.len() {
+        (term_width - complete_msg.len()) / 2
+    } else {
+        0
+    };
+    execute!(
+        stdout,
+        cursor::MoveTo(complete_x as u16, (center_y + 4) as u16),
+        SetForegroundColor(Color::Green),
+        Print(&complete_msg),
+        ResetColor
+    )?;
+
+    stdout.flush()?;
+    thread::sleep(Duration::from_millis(1500));
+
+    execute!(stdout, cursor::Show)?;
+    Ok(())
+}
+
+fn cleanup_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        cursor::Show
+    )?;
+    terminal.show_cursor()?;
+
+    Ok(())
+}
+
+// FIXED: Beautiful and responsive help screen
+fn draw_help_screen<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let area = f.size();
+
+    let keymap = &app.config.keymap;
+    let bind = |action: Action| format!("   {:<16} {}", keymap.label(action), action.description());
+
+    let mut help_text: Vec<String> = vec![
+        "".to_string(),
+        "╔══════════════════════════════════════════════════════════════╗".to_string(),
+        "║                  BClicker Professional v2.0                 ║".to_string(),
+        format!("║{:^62}║", t!("help-heading")),
+        "╚══════════════════════════════════════════════════════════════╝".to_string(),
+        "".to_string(),
+        t!("help-main-controls"),
+        format!(
+            "   {:<16} {}",
+            t!(
+                "help-nav-keys",
+                down = keymap.label(Action::Down),
+                up = keymap.label(Action::Up)
+            ),
+            t!("help-navigate")
+        ),
+        bind(Action::Button),
+        format!("   {:<16} {}", "Enter", t!("help-confirm")),
+        format!("   {:<16} {}", "Esc", t!("help-cancel")),
+        format!("   {:<16} {}", "Ctrl+P", t!("help-palette")),
+        "".to_string(),
+        t!("help-primary"),
+    ];
+    help_text.extend(
+        [
+            Action::CustomCps,
+            Action::Presets,
+            Action::Range,
+            Action::SlowMode,
+            Action::AntiAfk,
+            Action::DutyCycle,
+            Action::Pattern,
+            Action::SecondChannel,
+            Action::KeyRotation,
+            Action::HoldKey,
+            Action::QueueJob,
+            Action::Pause,
+            Action::Timing,
+            Action::StdDev,
+            Action::ScrollAmount,
+            Action::RepeatText,
+            Action::StartDelay,
+            Action::IdleTrigger,
+            Action::Overlay,
+            Action::Position,
+            Action::ReturnToOrigin,
+            Action::Region,
+            Action::Modifier,
+            Action::Backend,
+            Action::Hotkey,
+            Action::HotkeyMode,
+            Action::MouseTrigger,
+            Action::CpsUpHotkey,
+            Action::CpsDownHotkey,
+            Action::PauseHotkey,
+            Action::Hide,
+            Action::Quit,
+            Action::Help,
+        ]
+        .map(bind),
+    );
+    help_text.push(String::new());
+    help_text.push(t!("help-advanced"));
+    help_text.extend(
+        [
+            Action::Audio,
+            Action::AudioDevice,
+            Action::Theme,
+            Action::Language,
+            Action::ResetStats,
+            Action::Stats,
+            Action::Benchmark,
+            Action::TestFire,
+            Action::DryRun,
+            Action::Sequence,
+            Action::Scheduler,
+            Action::SaveSlot,
+            Action::ExportConfig,
+            Action::ImportConfig,
+        ]
+        .map(bind),
+    );
+    help_text.push(String::new());
+    // Section bodies are one message each, indented here
+    let sections = [
+        ("help-hotkey", t!("help-hotkey-body")),
+        ("help-tray", t!("help-tray-body")),
+        (
+            "help-audio",
+            t!(
+                "help-audio-body",
+                key = keymap.label(Action::Audio),
+                device_key = keymap.label(Action::AudioDevice)
+            ),
+        ),
+        ("help-config", t!("help-config-body")),
+        ("help-stats", t!("help-stats-body")),
+        ("help-notice", t!("help-notice-body")),
+    ];
+    for (heading, body) in sections {
+        help_text.push(t!(heading));
+        help_text.extend(body.lines().map(|line| format!("   {}", line)));
+        help_text.push(String::new());
+    }
+    help_text.push("────────────────────────────────────────────────────────────────".to_string());
+    help_text.push(t!("help-close", key = keymap.label(Action::Help)));
+
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    let start_line = app.help_scroll;
+    let end_line = (start_line + visible_lines).min(help_text.len());
+
+    let help_content: Vec<Spans> = help_text[start_line..end_line]
+        .iter()
+        .map(|line| {
+            if line.contains("BClicker Professional") {
+                Spans::from(Span::styled(
+                    line.as_str(),
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else if line.starts_with("🎯") || line.starts_with("⚡") || line.starts_with("🔧")
+            {
+                Spans::from(Span::styled(
+                    line.as_str(),
+                    Style::default()
+                        .fg(app.theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else if line.starts_with("   ") && line.len() > 3 {
+                let parts: Vec<&str> = line.splitn(2, ' ').collect();
+                if parts.len() >= 2 {
+                    Spans::from(vec![
+                        Span::styled(
+                            parts[0],
+                            Style::default()
+                                .fg(app.theme.success)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(&line[parts[0].len()..], Style::default().fg(app.theme.text)),
+                    ])
+                } else {
+                    Spans::from(Span::styled(
+                        line.as_str(),
+                        Style::default().fg(app.theme.text),
+                    ))
+                }
+            } else if line.starts_with("⚠️") {
+                Spans::from(Span::styled(
+                    line.as_str(),
+                    Style::default()
+                        .fg(app.theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Spans::from(Span::styled(
+                    line.as_str(),
+                    Style::default().fg(app.theme.text),
+                ))
+            }
+        })
+        .collect();
+
+    let help_widget = Paragraph::new(help_content)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(
+                    format!(" {} ", t!("help-title")),
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .border_style(Style::default().fg(app.theme.primary)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(help_widget, area);
+}
+
+fn draw_import_preview<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(3)].as_ref())
+        .split(f.size());
+
+    let Some(pending) = app.pending_import.as_ref() else {
+        return;
+    };
+    let mut lines: Vec<Spans> = pending
+        .warnings
+        .iter()
+        .map(|w| {
+            Spans::from(Span::styled(
+                format!("⚠️  {}", w),
+                Style::default().fg(app.theme.warning),
+            ))
+        })
+        .collect();
+    lines.extend(pending.changes.iter().map(|change| {
+        let color = match change.chars().next() {
+            Some('+') => app.theme.success,
+            Some('-') => app.theme.error,
+            _ => app.theme.text,
+        };
+        Spans::from(Span::styled(change.as_str(), Style::default().fg(color)))
+    }));
+
+    let preview = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                format!(" {} ", t!("import-title", count = pending.changes.len())),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .border_style(Style::default().fg(app.theme.primary)),
+    );
+    f.render_widget(preview, chunks[0]);
+
+    let instructions = Paragraph::new(t!("import-keys"))
+        .style(Style::default().fg(app.theme.warning))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions, chunks[1]);
+}
+
+fn draw_hotkey_conflict<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let HotkeyStatus::Failed(reason) = &app.hotkey_status_seen else {
+        return;
+    };
+    let combo = app
+        .config
+        .toggle_keybind
+        .as_ref()
+        .map(|k| k.to_string())
+        .unwrap_or_default();
+
+    let size = f.size();
+    let width = size.width.min(64);
+    let height = size.height.min(9);
+    let area = Rect::new(
+        (size.width - width) / 2,
+        (size.height - height) / 3,
+        width,
+        height,
+    );
+    f.render_widget(Clear, area);
+
+    let text = vec![
+        Spans::from(vec![
+            Span::styled(
+                combo,
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                " {}",
+                t!("conflict-reason", reason = reason.as_str())
+            )),
+        ]),
+        Spans::from(""),
+        Spans::from(t!("conflict-fallback")),
+        Spans::from(""),
+        Spans::from(Span::styled(
+            t!("conflict-keys"),
+            Style::default().fg(app.theme.warning),
+        )),
+    ];
+    let popup = Paragraph::new(text)
+        .style(Style::default().fg(app.theme.text))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(
+                    format!(" {} ", t!("conflict-title")),
+                    Style::default()
+                        .fg(app.theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .border_style(Style::default().fg(app.theme.error)),
+        );
+    f.render_widget(popup, area);
+}
+
+fn draw_command_palette<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let size = f.size();
+    let width = size.width.min(70);
+    let height = size.height.min(16);
+    let area = Rect::new(
+        (size.width - width) / 2,
+        (size.height - height) / 3,
+        width,
+        height,
+    );
+    f.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+        .split(area);
+
+    let query = Paragraph::new(format!("> {}_", app.palette_query))
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(
+                    format!(" {} ", t!("palette-title")),
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .border_style(Style::default().fg(app.theme.primary)),
+        );
+    f.render_widget(query, chunks[0]);
+
+    let keymap = &app.config.keymap;
+    let items: Vec<ListItem> = palette::filter(&app.palette_query)
+        .into_iter()
+        .map(|command| {
+            let key = match command {
+                Command::ToggleClicking => app
+                    .config
+                    .toggle_keybind
+                    .as_ref()
+                    .map(|k| k.to_string())
+                    .unwrap_or_default(),
+                Command::Run(action) => keymap.label(action),
+            };
+            ListItem::new(Spans::from(vec![
+                Span::raw(format!("{:<52}", command.title())),
+                Span::styled(key, Style::default().fg(app.theme.secondary)),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.palette_cursor));
+    }
+    let list = List::new(items)
+        .style(Style::default().fg(app.theme.text))
+        .highlight_style(
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ")
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", t!("palette-keys")))
+                .border_style(Style::default().fg(app.theme.primary)),
+        );
+    f.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+fn draw_stats_screen<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let stats = app
+        .stats_tracker
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let history_height = if app.run_history.is_some() { 6 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(6),              // Summary
+                Constraint::Length(4),              // Timing accuracy
+                Constraint::Length(8),              // Per-mode counts
+                Constraint::Length(history_height), // Run log
+                Constraint::Min(8),                 // Histogram
+                Constraint::Length(3),              // Instructions
+            ]
+            .as_ref(),
+        )
+        .split(f.size());
+
+    let session_duration = app.session_start.elapsed().as_secs();
+    let summary = vec![
+        Spans::from(t!(
+            "stats-screen-total",
+            clicks = stats.total_clicks,
+            sessions = stats.total_sessions
+        )),
+        Spans::from(t!(
+            "stats-screen-session",
+            clicks = stats.session_clicks,
+            mins = session_duration / 60,
+            secs = session_duration % 60
+        )),
+        Spans::from(t!(
+            "stats-screen-peak",
+            cps = format!("{:.1}", stats.peak_cps)
+        )),
+        Spans::from(t!(
+            "stats-screen-longest",
+            mins = stats.longest_run_secs / 60,
+            secs = stats.longest_run_secs % 60
+        )),
+    ];
+    let summary_widget = Paragraph::new(summary)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(
+                    format!(" {} ", t!("stats-title")),
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .border_style(Style::default().fg(app.theme.primary)),
+        );
+    f.render_widget(summary_widget, chunks[0]);
+
+    let accuracy_widget = Paragraph::new(timing_accuracy_lines(
+        app.get_rate_status().timing,
+        &app.theme,
+    ))
+    .style(Style::default().fg(app.theme.text))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", t!("timing-title")))
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    f.render_widget(accuracy_widget, chunks[1]);
+
+    let mut counts: Vec<ListItem> = if stats.button_counts.is_empty() {
+        vec![ListItem::new(t!("stats-screen-empty"))]
+    } else {
+        stats
+            .button_counts
+            .iter()
+            .map(|(mode, count)| ListItem::new(format!("{:<14} {}", mode, count)))
+            .collect()
+    };
+    // Script counters follow the modes
+    counts.extend(
+        stats
+            .counters
+            .iter()
+            .map(|(name, value)| ListItem::new(format!("#{:<13} {}", name, value))),
+    );
+    if stats.verify_failures > 0 || stats.verify_retries > 0 {
+        counts.push(ListItem::new(t!(
+            "stats-screen-verify",
+            failures = stats.verify_failures,
+            retries = stats.verify_retries
+        )));
+    }
+    let counts_widget = List::new(counts)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", t!("stats-screen-modes")))
+                .border_style(Style::default().fg(app.theme.secondary)),
+        );
+    f.render_widget(counts_widget, chunks[2]);
+
+    if let Some(history) = &app.run_history {
+        let lines = match history {
+            Ok(history) if history.runs == 0 => vec![Spans::from(t!("runs-empty"))],
+            Ok(history) => run_history_lines(history),
+            Err(e) => vec![Spans::from(Span::styled(
+                t!("runs-unavailable", error = e.as_str()),
+                Style::default().fg(app.theme.warning),
+            ))],
+        };
+        let history_widget = Paragraph::new(lines)
+            .style(Style::default().fg(app.theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", t!("runs-title")))
+                    .border_style(Style::default().fg(app.theme.secondary)),
+            );
+        f.render_widget(history_widget, chunks[3]);
+    }
+
+    // Most recent minutes that fit the width, one bar plus gap per minute
+    let fits = (chunks[4].width.saturating_sub(2) / 5) as usize;
+    let skip = stats.clicks_per_minute.len().saturating_sub(fits);
+    let labels: Vec<String> = (skip + 1..=stats.clicks_per_minute.len())
+        .map(|minute| minute.to_string())
+        .collect();
+    let data: Vec<(&str, u64)> = labels
+        .iter()
+        .map(String::as_str)
+        .zip(stats.clicks_per_minute[skip..].iter().copied())
+        .collect();
+    let histogram = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", t!("stats-screen-histogram")))
+                .border_style(Style::default().fg(app.theme.secondary)),
+        )
+        .data(&data)
+        .bar_width(4)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(app.theme.primary))
+        .value_style(Style::default().fg(TuiColor::Black).bg(app.theme.primary))
+        .label_style(Style::default().fg(app.theme.text));
+    f.render_widget(histogram, chunks[4]);
+
+    let keymap = &app.config.keymap;
+    let instructions = Paragraph::new(t!(
+        "stats-screen-keys",
+        back = keymap.label(Action::Stats),
+        reset = keymap.label(Action::ResetStats)
+    ))
+    .style(Style::default().fg(app.theme.secondary))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(instructions, chunks[5]);
+}
+
+// Interval error of the current or last run against the chosen rate
+fn timing_accuracy_lines(timing: Option<TimingAccuracy>, theme: &Theme) -> Vec<Spans<'static>> {
+    let Some(timing) = timing else {
+        return vec![Spans::from(t!("timing-empty"))];
+    };
+    let verdict = if timing.steady() {
+        Span::styled(t!("timing-steady"), Style::default().fg(theme.success))
+    } else {
+        Span::styled(
+            t!(
+                "timing-unsteady",
+                percent = format!("{:.0}", timing.p95_error_ms / timing.target_ms * 100.0)
+            ),
+            Style::default().fg(theme.warning),
+        )
+    };
+    vec![
+        Spans::from(t!(
+            "timing-errors",
+            samples = timing.samples,
+            target = format!("{:.2}", timing.target_ms),
+            mean = format!("{:.2}", timing.mean_error_ms),
+            p95 = format!("{:.2}", timing.p95_error_ms),
+            max = format!("{:.2}", timing.max_error_ms)
+        )),
+        Spans::from(verdict),
+    ]
+}
+
+fn run_history_lines(history: &RunHistory) -> Vec<Spans<'static>> {
+    let total_mins = (history.total_secs / 60.0) as u64;
+    let days = history
+        .daily
+        .iter()
+        .map(|(day, clicks)| format!("{} {}", day, clicks))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let profiles = history
+        .profiles
+        .iter()
+        .map(|(name, runs, clicks)| {
+            t!(
+                "runs-profile",
+                name = name.as_str(),
+                runs = *runs,
+                clicks = *clicks
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" · ");
+    vec![
+        Spans::from(t!(
+            "runs-summary",
+            runs = history.runs,
+            clicks = history.clicks,
+            hours = total_mins / 60,
+            mins = total_mins % 60
+        )),
+        Spans::from(t!(
+            "runs-best",
+            average = format!("{:.1}", history.best_average_cps),
+            peak = format!("{:.1}", history.best_peak_cps)
+        )),
+        Spans::from(t!("runs-days", days = days)),
+        Spans::from(t!("runs-profiles", profiles = profiles)),
+    ]
+}
+
+fn draw_sequence_editor<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let profile = app.config.active_profile();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(3), // Summary
+                Constraint::Min(5),    // Steps
+                Constraint::Length(4), // Instructions
+            ]
+            .as_ref(),
+        )
+        .split(f.size());
+
+    // Live preview of the cycle the engine will run
+    let cycle = sequence::cycle_time(&profile.sequence);
+    let summary = Paragraph::new(Spans::from(vec![
+        if profile.sequence_enabled {
+            Span::styled(
+                t!("sequence-enabled"),
+                Style::default()
+                    .fg(app.theme.success)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled(
+                t!("sequence-disabled"),
+                Style::default()
+                    .fg(app.theme.secondary)
+                    .add_modifier(Modifier::BOLD),
+            )
+        },
+        Span::raw(format!(
+            " {}",
+            t!(
+                "sequence-summary",
+                profile = profile.name.as_str(),
+                steps = profile.sequence.len(),
+                secs = format!("{:.3}", cycle.as_secs_f64())
+            )
+        )),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                format!(" {} ", t!("sequence-title")),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .border_style(Style::default().fg(app.theme.primary)),
+    );
+    f.render_widget(summary, chunks[0]);
+
+    let step_items: Vec<ListItem> = if profile.sequence.is_empty() {
+        vec![
+            ListItem::new(format!("  {}", t!("sequence-empty")))
+                .style(Style::default().fg(app.theme.secondary)),
+        ]
+    } else {
+        profile
+            .sequence
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let selected = i == app.sequence_cursor;
+                let style = if selected {
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(app.theme.text)
+                };
+                let prefix = if selected { "▶ " } else { "  " };
+                ListItem::new(format!("{}{:>2}. {}", prefix, i + 1, step.describe())).style(style)
+            })
+            .collect()
+    };
+    let steps = List::new(step_items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                format!(" {} ", t!("sequence-steps")),
+                Style::default().fg(app.theme.primary),
+            ))
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    f.render_widget(steps, chunks[1]);
+
+    let instructions = match app.sequence_field {
+        Some(field) => {
+            let label = match field {
+                SequenceField::Position => t!("sequence-field-position"),
+                SequenceField::Target => t!("sequence-field-target"),
+                SequenceField::Duration => t!("sequence-field-duration"),
+                SequenceField::Delay => t!("sequence-field-delay"),
+                SequenceField::Key => t!("sequence-field-key"),
+            };
+            vec![
+                Spans::from(Span::styled(
+                    format!("✏️  {}: {}_", label, app.sequence_input),
+                    Style::default().fg(app.theme.accent),
+                )),
+                Spans::from(t!("keys-save-cancel")),
+            ]
+        }
+        None => vec![
+            Spans::from(t!("sequence-keys")),
+            Spans::from(t!("sequence-keys-edit")),
+        ],
+    };
+    let instructions = Paragraph::new(instructions)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", t!("controls-title")))
+                .border_style(Style::default().fg(app.theme.secondary)),
+        );
+    f.render_widget(instructions, chunks[2]);
+}
+
+fn draw_preset_editor<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(4)].as_ref())
+        .split(f.size());
+
+    let in_use = app.config.current_choice();
+    let items: Vec<ListItem> = app
+        .config
+        .cps_presets
+        .iter()
+        .enumerate()
+        .map(|(i, cps)| {
+            let selected = i == app.preset_cursor;
+            let style = if selected {
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let prefix = if selected { "▶ " } else { "  " };
+            let marker = if in_use == CpsChoice::Preset(i) {
+                format!("  {}", t!("presets-in-use"))
+            } else {
+                String::new()
+            };
+            ListItem::new(format!("{}{:>2}. {} CPS{}", prefix, i + 1, cps, marker)).style(style)
+        })
+        .collect();
+    let presets = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                format!(" {} ", t!("presets-title")),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .border_style(Style::default().fg(app.theme.primary)),
+    );
+    f.render_widget(presets, chunks[0]);
+
+    let instructions = match app.preset_field {
+        Some(field) => {
+            let label = match field {
+                PresetField::Add => t!("presets-new"),
+                PresetField::Edit => t!("presets-edit"),
+            };
+            vec![
+                Spans::from(Span::styled(
+                    format!(
+                        "✏️  {} (1-{} CPS): {}_",
+                        label, MAX_SAFE_CPS, app.preset_input
+                    ),
+                    Style::default().fg(app.theme.accent),
+                )),
+                Spans::from(t!("keys-save-cancel")),
+            ]
+        }
+        None => vec![
+            Spans::from(t!("presets-keys")),
+            Spans::from(t!("presets-saved")),
+        ],
+    };
+    let instructions = Paragraph::new(instructions)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", t!("controls-title")))
+                .border_style(Style::default().fg(app.theme.secondary)),
+        );
+    f.render_widget(instructions, chunks[1]);
+}
+
+fn draw_schedule_editor<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(4)].as_ref())
+        .split(f.size());
+
+    let entries = app.config.schedule_entries();
+    let items: Vec<ListItem> = if entries.is_empty() {
+        vec![ListItem::new(t!("scheduler-empty")).style(Style::default().fg(app.theme.text))]
+    } else {
+        entries
+            .iter()
+            .enumerate()
+            .map(|(i, &(p, s))| {
+                let profile = &app.config.profiles[p];
+                let schedule = &profile.schedules[s];
+                let selected = i == app.schedule_cursor;
+                let style = if selected {
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else if schedule.enabled {
+                    Style::default().fg(app.theme.text)
+                } else {
+                    Style::default().fg(app.theme.secondary)
+                };
+                let prefix = if selected { "▶ " } else { "  " };
+                let next = if !schedule.enabled {
+                    t!("scheduler-off")
+                } else {
+                    match CronExpr::parse(&schedule.cron)
+                        .ok()
+                        .and_then(|c| c.next_run())
+                    {
+                        Some(time) => t!("scheduler-next", time = time.to_string()),
+                        None => t!("scheduler-never"),
+                    }
+                };
+                ListItem::new(format!(
+                    "{}{:<12} {:<20} {:>8}  {}",
+                    prefix,
+                    profile.name,
+                    schedule.cron,
+                    interval::describe(schedule.duration_secs),
+                    next
+                ))
+                .style(style)
+            })
+            .collect()
+    };
+    let schedules = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                format!(" {} ", t!("scheduler-title")),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .border_style(Style::default().fg(app.theme.primary)),
+    );
+    f.render_widget(schedules, chunks[0]);
+
+    let instructions = match &app.schedule_input {
+        Some(input) => vec![
+            Spans::from(Span::styled(
+                format!(
+                    "✏️  {}: {}_",
+                    t!(
+                        "scheduler-new",
+                        profile = app.config.active_profile().name.clone()
+                    ),
+                    input
+                ),
+                Style::default().fg(app.theme.accent),
+            )),
+            Spans::from(t!("keys-save-cancel")),
+        ],
+        None => vec![
+            Spans::from(t!("scheduler-keys")),
+            Spans::from(t!("scheduler-saved")),
+        ],
+    };
+    let instructions = Paragraph::new(instructions)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", t!("controls-title")))
+                .border_style(Style::default().fg(app.theme.secondary)),
+        );
+    f.render_widget(instructions, chunks[1]);
+}
+
+fn draw_audio_device_picker<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(5), Constraint::Length(4)].as_ref())
+        .split(f.size());
+
+    let saved = app.config.audio_device.as_deref();
+    let items: Vec<ListItem> = std::iter::once(None)
+        .chain(app.audio_devices.iter().map(|d| Some(d.as_str())))
+        .enumerate()
+        .map(|(i, device)| {
+            let selected = i == app.audio_device_cursor;
+            let style = if selected {
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let prefix = if selected { "▶ " } else { "  " };
+            let marker = if device == saved {
+                format!("  {}", t!("presets-in-use"))
+            } else {
+                String::new()
+            };
+            let name = device.map_or_else(|| t!("audio-default"), str::to_string);
+            ListItem::new(format!("{}{}{}", prefix, name, marker)).style(style)
+        })
+        .collect();
+    let devices = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                format!(" {} ", t!("audio-title")),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .border_style(Style::default().fg(app.theme.primary)),
+    );
+    f.render_widget(devices, chunks[0]);
+
+    let instructions = Paragraph::new(vec![
+        Spans::from(t!("audio-keys")),
+        Spans::from(t!("audio-saved")),
+    ])
+    .style(Style::default().fg(app.theme.text))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", t!("controls-title")))
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    f.render_widget(instructions, chunks[1]);
+}
+
+// FIXED: Optimized and responsive UI with better layout
+// Parts of the main screen that only change with the config, the engine
+// settings or the input mode. Rebuilt when one of those moves instead of on
+// every frame, where the live refresh would otherwise re-run the same Fluent
+// lookups four times a second
+#[derive(Default)]
+struct MainScreenCache {
+    // Engine settings version and input mode it was built for; None after a
+    // key press, since nearly any key can change the config
+    built_for: Option<(u64, InputMode)>,
+    app_title: String,
+    speed_title: String,
+    stats_title: String,
+    controls_title: String,
+    pro_features: String,
+    pro_features_list: String,
+    button_text: String,
+    rate_text: String,
+    second_channel: Option<String>,
+    duty: Option<String>,
+    cps_items: Vec<ListItem<'static>>,
+    input_title: String,
+    input_text: String,
+    input_style: Style,
+    instruction_text: String,
+    instruction_style: Style,
+}
+
+impl MainScreenCache {
+    fn build(app: &App) -> Self {
+        let engine_settings = app.get_engine_settings();
+
+        let current_choice = app.config.current_choice();
+        let cps_items: Vec<ListItem<'static>> = app
+            .config
+            .cps_choices()
+            .into_iter()
+            .map(|choice| {
+                let selected = choice == current_choice;
+                let style = if selected {
+                    Style::default()
+                        .fg(app.theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else if matches!(choice, CpsChoice::Preset(_)) {
+                    Style::default().fg(app.theme.text)
+                } else {
+                    Style::default().fg(app.theme.secondary)
+                };
+                let prefix = if selected { "▶ " } else { "  " };
+                let label = match choice {
+                    CpsChoice::Preset(i) => {
+                        t!("choice-preset", cps = app.config.cps_presets[i].to_string())
+                    }
+                    CpsChoice::Custom => t!(
+                        "choice-custom",
+                        cps = app.config.custom_cps_value.unwrap_or(20.0).to_string()
+                    ),
+                    CpsChoice::Slow => t!(
+                        "choice-slow",
+                        interval =
+                            interval::describe(app.config.slow_interval_secs.unwrap_or_default())
+                    ),
+                    CpsChoice::Pattern(p) => format!("{} (~{:.0} CPS)", p.label(), p.average_cps()),
+                    CpsChoice::AntiAfk => {
+                        t!("choice-anti-afk", window = app.config.anti_afk.window())
+                    }
+                };
+                ListItem::new(format!("{}{}", prefix, label)).style(style)
+            })
+            .collect();
+
+        let input_style = match app.input_mode {
+            InputMode::EditingCps
+            | InputMode::ConfirmingHighCps
+            | InputMode::EditingRange
+            | InputMode::EditingInterval
+            | InputMode::EditingAntiAfk
+            | InputMode::EditingDutyCycle
+            | InputMode::EditingPattern
+            | InputMode::EditingSecondChannel
+            | InputMode::EditingKeyRotation
+            | InputMode::EditingHoldKey
+            | InputMode::EditingJob
+            | InputMode::EditingStdDev
+            | InputMode::EditingScroll
+            | InputMode::EditingPosition
+            | InputMode::CapturingRegion
+            | InputMode::EditingText
+            | InputMode::EditingExportPath
+            | InputMode::EditingImportPath => Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+            _ => Style::default().fg(app.theme.secondary),
+        };
+
+        let input_title = match app.input_mode {
+            InputMode::EditingCps => t!("input-cps"),
+            InputMode::ConfirmingHighCps => t!("input-high-cps"),
+            InputMode::EditingRange => t!("input-range"),
+            InputMode::EditingInterval => t!("input-interval"),
+            InputMode::EditingAntiAfk => t!("input-anti-afk"),
+            InputMode::EditingDutyCycle => t!("input-duty"),
+            InputMode::EditingPattern => t!("input-pattern"),
+            InputMode::EditingSecondChannel => t!("input-second-channel"),
+            InputMode::EditingKeyRotation => t!("input-rotation"),
+            InputMode::EditingHoldKey => t!("input-hold-key"),
+            InputMode::EditingJob => t!("input-job"),
+            InputMode::EditingStdDev => t!("input-std-dev"),
+            InputMode::EditingScroll => t!("input-scroll"),
+            InputMode::EditingPosition => t!("input-position"),
+            InputMode::CapturingRegion => t!("input-region"),
+            InputMode::EditingText => t!("input-text"),
+            InputMode::EditingExportPath => t!("input-export"),
+            InputMode::EditingImportPath => t!("input-import"),
+            _ => t!(
+                "input-idle",
+                key = app.config.keymap.label(Action::CustomCps)
+            ),
+        };
+
+        let input_text = match app.input_mode {
+            InputMode::EditingCps => format!("{}_", &app.custom_cps_input),
+            InputMode::EditingRange => format!("{}_", &app.range_input),
+            InputMode::EditingInterval => format!("{}_", &app.interval_input),
+            InputMode::EditingAntiAfk => format!("{}_", &app.anti_afk_input),
+            InputMode::EditingDutyCycle => format!("{}_", &app.duty_input),
+            InputMode::EditingPattern => format!("{}_", &app.pattern_input),
+            InputMode::EditingSecondChannel => format!("{}_", &app.second_channel_input),
+            InputMode::EditingKeyRotation => format!("{}_", &app.rotation_input),
+            InputMode::EditingHoldKey => format!("{}_", &app.hold_key_input),
+            InputMode::EditingJob => format!("{}_", &app.job_input),
+            InputMode::EditingStdDev => format!("{}_", &app.std_dev_input),
+            InputMode::EditingScroll => format!("{}_", &app.scroll_input),
+            InputMode::EditingPosition => format!("{}_", &app.position_input),
+            InputMode::CapturingRegion => match app.region_corner {
+                Some(p) => t!("input-region-second", point = p.to_string()),
+                None => t!("input-region-first"),
+            },
+            InputMode::EditingExportPath | InputMode::EditingImportPath => {
+                format!("{}_", &app.transfer_input)
+            }
+            InputMode::EditingText => format!(
+                "{}_{}",
+                &app.text_input,
+                if app.text_input_enter {
+                    format!("  {}", t!("input-with-enter"))
+                } else {
+                    String::new()
+                }
+            ),
+            InputMode::ConfirmingHighCps => t!(
+                "input-confirm-high-cps",
+                cps = app.pending_high_cps.unwrap_or_default().to_string(),
+                limit = MAX_SAFE_CPS
+            ),
+            _ => "".to_string(),
+        };
+
+        let instruction_color = match app.input_mode {
+            InputMode::AwaitingKeybind => app.theme.warning,
+            InputMode::SettingKeybind | InputMode::AssigningSlot => app.theme.accent,
+            InputMode::EditingCps => app.theme.primary,
+            InputMode::ConfirmingHighCps => app.theme.warning,
+            InputMode::EditingRange
+            | InputMode::EditingInterval
+            | InputMode::EditingAntiAfk
+            | InputMode::EditingDutyCycle
+            | InputMode::EditingPattern
+            | InputMode::EditingSecondChannel
+            | InputMode::EditingKeyRotation
+            | InputMode::EditingHoldKey
+            | InputMode::EditingJob
+            | InputMode::EditingStdDev
+            | InputMode::EditingScroll
+            | InputMode::EditingPosition
+            | InputMode::CapturingRegion
+            | InputMode::EditingText
+            | InputMode::EditingExportPath
+            | InputMode::EditingImportPath => app.theme.primary,
+            _ => app.theme.secondary,
+        };
+
+        let mut normal_bar = app.config.keymap.instruction_bar();
+        for slot in &app.config.quick_slots {
+            normal_bar.push_str(&format!(" │ {}={}", slot.key, slot.label(&app.config)));
+        }
+        let instruction_text = match app.input_mode {
+            InputMode::AwaitingKeybind => t!("hint-preparing"),
+            InputMode::SettingKeybind => match app.keybind_target {
+                KeybindTarget::Toggle => t!("hint-capture-toggle"),
+                KeybindTarget::Faster => t!("hint-capture-faster"),
+                KeybindTarget::Slower => t!("hint-capture-slower"),
+                KeybindTarget::Pause => t!("hint-capture-pause"),
+            },
+            InputMode::EditingCps => t!("hint-cps"),
+            InputMode::ConfirmingHighCps => t!("hint-high-cps"),
+            InputMode::EditingRange => t!("hint-range"),
+            InputMode::EditingInterval => t!("hint-interval"),
+            InputMode::EditingAntiAfk => t!("hint-anti-afk"),
+            InputMode::EditingDutyCycle => t!("hint-duty"),
+            InputMode::EditingPattern => t!("hint-pattern"),
+            InputMode::EditingSecondChannel => t!("hint-second-channel"),
+            InputMode::EditingKeyRotation => t!("hint-rotation"),
+            InputMode::EditingHoldKey => t!("hint-hold-key"),
+            InputMode::EditingJob => t!("hint-job"),
+            InputMode::EditingStdDev => t!("hint-std-dev"),
+            InputMode::EditingScroll => t!("hint-scroll"),
+            InputMode::EditingPosition => t!("hint-position"),
+            InputMode::CapturingRegion => t!("hint-region"),
+            InputMode::EditingText => t!("hint-text"),
+            InputMode::EditingExportPath => t!("hint-export"),
+            InputMode::EditingImportPath => t!("hint-import"),
+            InputMode::AssigningSlot => t!("hint-slot"),
+            _ => normal_bar,
+        };
+
+        Self {
+            built_for: Some((app.engine_settings.version(), app.input_mode)),
+            app_title: format!(" {} ", t!("app-title")),
+            speed_title: format!(" {} ", t!("speed-title")),
+            stats_title: format!(" {} ", t!("stats-title")),
+            controls_title: format!(" {} ", t!("controls-info-title")),
+            pro_features: format!("{} ", t!("pro-features")),
+            pro_features_list: t!("pro-features-list"),
+            button_text: app.get_current_button_text(),
+            rate_text: engine_settings.rate_text(),
+            second_channel: engine_settings.second_channel.map(|channel| {
+                t!(
+                    "status-second-channel",
+                    button = channel.action_label(),
+                    cps = channel.cps.to_string()
+                )
+            }),
+            duty: engine_settings
+                .duty_cycle
+                .map(|duty| t!("status-duty", duty = duty.to_string())),
+            cps_items,
+            input_title: format!(" {} ", input_title),
+            input_text,
+            input_style,
+            instruction_text,
+            instruction_style: Style::default().fg(instruction_color),
+        }
+    }
+}
+
+fn draw_ui<B: Backend>(f: &mut tui::Frame<B>, app: &App) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(3), // Status
+                Constraint::Min(5),    // CPS Selection
+                Constraint::Length(3), // Custom Input
+                Constraint::Length(5), // Statistics
+                Constraint::Length(4), // Instructions
+            ]
+            .as_ref(),
+        )
+        .split(size);
+
+    // Status bar with clean hotkey display
+    let countdown = app.countdown.load(Ordering::SeqCst);
+    let running_status = if countdown > 0 {
+        Span::styled(
+            t!("status-starting", secs = countdown),
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if app.auto_clicker_running.load(Ordering::SeqCst)
+        && app.auto_clicker_paused.load(Ordering::SeqCst)
+    {
+        Span::styled(
+            t!("status-paused-run"),
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if app.auto_clicker_running.load(Ordering::SeqCst) {
+        Span::styled(
+            t!("status-active", mark = app.theme.active_mark),
+            Style::default()
+                .fg(app.theme.success)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(
+            t!("status-idle", mark = app.theme.idle_mark),
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let mut status_spans = vec![running_status];
+    if app.config.dry_run {
+        status_spans.push(Span::styled(
+            format!(" {}", t!("status-dry-run")),
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    status_spans.push(Span::raw(format!(" │ {} ", t!("status-hotkey"))));
+
+    if let Some(keybind) = &app.config.toggle_keybind {
+        status_spans.extend(create_hotkey_spans(keybind, &app.theme));
+        if let HotkeyStatus::Failed(_) = app.hotkey_status_seen {
+            status_spans.push(Span::styled(
+                format!(" {}", t!("status-unavailable")),
+                Style::default().fg(app.theme.error),
+            ));
+        }
+    }
+    let extra_triggers: Vec<String> = app
+        .config
+        .mouse_trigger
+        .map(|t| t.to_string())
+        .into_iter()
+        .chain(app.config.tap_trigger.as_ref().map(|t| t.to_string()))
+        .collect();
+    if app.config.toggle_keybind.is_none() && extra_triggers.is_empty() {
+        status_spans.push(Span::styled(
+            t!("status-not-set"),
+            Style::default().fg(app.theme.error),
+        ));
+    }
+    for (i, trigger) in extra_triggers.into_iter().enumerate() {
+        if i > 0 || app.config.toggle_keybind.is_some() {
+            status_spans.push(Span::raw(" + "));
+        }
+        status_spans.push(Span::styled(
+            trigger,
+            Style::default().fg(if app.input_hook_error.is_some() {
+                app.theme.error
+            } else {
+                app.theme.accent
+            }),
+        ));
+    }
+
+    let cache = &app.main_cache;
+    status_spans.extend(vec![
+        Span::raw(format!(" │ {} ", t!("status-button"))),
+        Span::styled(
+            cache.button_text.as_str(),
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+
+    let rate_status = app.get_rate_status();
+    let running = app.auto_clicker_running.load(Ordering::SeqCst);
+    let paused = matches!(rate_status.duty, Some((DutyPhase::Paused, _)));
+    if let (true, false, Some(next_click)) = (running, paused, rate_status.next_click) {
+        // At slow rates a countdown says more than the achieved CPS
+        let remaining = next_click.saturating_duration_since(Instant::now());
+        status_spans.push(Span::raw(format!(" │ {} │ ", cache.rate_text)));
+        status_spans.push(Span::styled(
+            t!("status-next-click", time = interval::clock(remaining)),
+            Style::default().fg(app.theme.success),
+        ));
+    } else if running && rate_status.achieved_cps > 0.0 {
+        status_spans.push(Span::raw(" │ "));
+        status_spans.push(Span::styled(
+            format!("{:.1}", rate_status.achieved_cps),
+            Style::default().fg(if rate_status.unachievable {
+                app.theme.warning
+            } else {
+                app.theme.success
+            }),
+        ));
+        status_spans.push(Span::raw(format!("/{}", cache.rate_text)));
+    } else {
+        status_spans.push(Span::raw(format!(" │ {}", cache.rate_text)));
+    }
+
+    if let Some((done, total)) = rate_status.job {
+        status_spans.push(Span::raw(format!(
+            " │ {}",
+            t!(
+                "status-job",
+                done = done,
+                total = total,
+                queued = app.jobs.len().saturating_sub(1)
+            )
+        )));
+    }
+
+    if let Some(channel) = &cache.second_channel {
+        status_spans.push(Span::raw(format!(" │ {}", channel)));
+    }
+
+    match (running, rate_status.duty, &cache.duty) {
+        (true, Some((phase, ends)), _) => {
+            let left = interval::clock(ends.saturating_duration_since(Instant::now()));
+            let (text, color) = match phase {
+                DutyPhase::Clicking => (t!("status-clicking", time = left), app.theme.success),
+                DutyPhase::Paused => (t!("status-paused", time = left), app.theme.warning),
+            };
+            status_spans.push(Span::raw(" │ "));
+            status_spans.push(Span::styled(text, Style::default().fg(color)));
+        }
+        (false, _, Some(duty)) => status_spans.push(Span::raw(format!(" │ {}", duty))),
+        _ => {}
+    }
+
+    if let (true, Some(stop_at)) = (running, app.scheduled_stop) {
+        let left = interval::clock(stop_at.saturating_duration_since(Instant::now()));
+        status_spans.push(Span::raw(format!(
+            " │ {}",
+            t!("status-scheduled", time = left)
+        )));
+    }
+
+    if running && rate_status.blocked {
+        status_spans.push(Span::styled(
+            format!(" │ {}", t!("status-blocked")),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if running && rate_status.outside_region {
+        status_spans.push(Span::styled(
+            format!(" │ {}", t!("status-outside-region")),
+            Style::default().fg(app.theme.warning),
+        ));
+    }
+
+    if let (true, Some(cap)) = (running, rate_status.cps_cap) {
+        status_spans.push(Span::styled(
+            format!(" │ {}", t!("status-capped", cps = cap.to_string())),
+            Style::default().fg(app.theme.warning),
+        ));
+    }
+
+    if !running && app.injection_blocked_shown {
+        status_spans.push(Span::styled(
+            format!(" │ {}", t!("status-injection-blocked")),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(fault) = &app.engine_fault_seen {
+        let text = if fault.restarted {
+            t!("status-engine-restarted", error = fault.error.clone())
+        } else {
+            t!("status-engine-stopped", error = fault.error.clone())
+        };
+        status_spans.push(Span::styled(
+            format!(" │ {}", text),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if app.rate_warning_shown {
+        status_spans.push(Span::styled(
+            format!(" {}", t!("status-unachievable")),
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let status = Paragraph::new(Spans::from(status_spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                cache.app_title.as_str(),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .border_style(Style::default().fg(app.theme.primary)),
+    );
+
+    f.render_widget(status, chunks[0]);
+
+    // CPS Selection with better visual indicators
+    let cps_list = List::new(cache.cps_items.clone()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(
+                cache.speed_title.as_str(),
+                Style::default().fg(app.theme.primary),
+            ))
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+
+    f.render_widget(cps_list, chunks[1]);
+
+    // Enhanced input field
+    let input_block = Paragraph::new(cache.input_text.as_str())
+        .style(cache.input_style)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(cache.input_title.as_str())
+                .border_style(Style::default().fg(app.theme.secondary)),
+        );
+
+    f.render_widget(input_block, chunks[2]);
+
+    // Compact statistics
+    let stats = app.stats_tracker.lock().unwrap_or_else(|e| e.into_inner());
+    let session_duration = app.session_start.elapsed().as_secs();
+    let session_cps = stats
+        .session_clicks
+        .checked_div(session_duration)
+        .unwrap_or(0);
+
+    let mut stats_content = vec![
+        Spans::from(t!(
+            "stats-session",
+            clicks = stats.session_clicks,
+            mins = session_duration / 60,
+            secs = session_duration % 60,
+            cps = session_cps
+        )),
+        Spans::from(t!(
+            "stats-total",
+            clicks = stats.total_clicks,
+            sessions = stats.total_sessions,
+            audio = if app.config.sound_enabled {
+                t!("stats-audio-on")
+            } else {
+                t!("stats-audio-off")
+            },
+            backend = app.config.backend.label()
+        )),
+    ];
+
+    let benchmark_line = if app.benchmark_in_progress {
+        t!("benchmark-running")
+    } else if let Some(report) = app
+        .benchmark_report
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        t!("benchmark-result", summary = report.summary())
+    } else {
+        t!(
+            "benchmark-hint",
+            key = app.config.keymap.label(Action::Benchmark)
+        )
+    };
+    stats_content.push(Spans::from(benchmark_line));
+
+    let stats_widget = Paragraph::new(stats_content)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(
+                    cache.stats_title.as_str(),
+                    Style::default().fg(app.theme.primary),
+                ))
+                .border_style(Style::default().fg(app.theme.secondary)),
+        );
+
+    f.render_widget(stats_widget, chunks[3]);
+
+    // Dynamic instructions based on mode
+    let instructions = Paragraph::new(vec![
+        Spans::from(Span::styled(
+            cache.instruction_text.as_str(),
+            cache.instruction_style,
+        )),
+        Spans::from(vec![
+            Span::styled(
+                cache.pro_features.as_str(),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(cache.pro_features_list.as_str()),
+        ]),
+    ])
+    .style(Style::default().fg(app.theme.text))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(cache.controls_title.as_str())
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+
+    f.render_widget(instructions, chunks[4]);
+}
@@ -0,0 +1,65 @@
+use crate::web::WebConfig;
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use std::net::IpAddr;
+
+// DNS-SD service type companion apps browse for
+const SERVICE_TYPE: &str = "_bclicker._tcp.local.";
+
+// This machine's name as a DNS label, for the instance and host names
+pub fn host_label() -> String {
+    let name = ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .chain(std::fs::read_to_string("/etc/hostname"))
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+        .unwrap_or_default();
+    let label: String = name
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let label = label.trim_matches('-');
+    if label.is_empty() {
+        "bclicker".to_string()
+    } else {
+        label.to_lowercase()
+    }
+}
+
+// Announces the web remote as `_bclicker._tcp` so companion apps find it
+// without anyone typing an address. The TXT record has the version, where
+// the page and API are, http or https, and that the API takes a bearer
+// token. Announced for as long as the daemon is kept
+pub fn advertise(config: &WebConfig) -> Result<ServiceDaemon, String> {
+    let host = host_label();
+    let version = env!("CARGO_PKG_VERSION");
+    let properties = [
+        ("version", version),
+        ("path", "/"),
+        ("api", "/api"),
+        ("auth", "bearer"),
+        ("scheme", config.scheme()),
+    ];
+    // Bound to every interface, the addresses are whatever the interfaces
+    // have, followed as they change
+    let auto = config.bind.is_unspecified();
+    let addresses: &[IpAddr] = if auto { &[] } else { &[config.bind] };
+    let mut service = ServiceInfo::new(
+        SERVICE_TYPE,
+        &format!("BClicker on {}", host),
+        &format!("{}.local.", host),
+        addresses,
+        config.port,
+        &properties[..],
+    )
+    .map_err(|e| e.to_string())?;
+    if auto {
+        service = service.enable_addr_auto();
+    }
+    let daemon = ServiceDaemon::new().map_err(|e| e.to_string())?;
+    daemon.register(service).map_err(|e| e.to_string())?;
+    Ok(daemon)
+}
@@ -0,0 +1,253 @@
+use crate::{
+    MAX_SAFE_CPS, MIN_CPS,
+    ipc::{Control, IpcFeed},
+    round_cps,
+};
+use midir::{MidiInput, MidiInputConnection};
+use serde::{Deserialize, Serialize};
+use std::{sync::mpsc, thread, time::Duration};
+
+// How often to look again for a controller that isn't plugged in yet
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// Controller values at or above this count as a pad or button held down
+const PRESSED: u8 = 64;
+
+// What a mapped control does. Buttons and pads act when pressed; a knob or
+// fader on `cps` sets the rate anywhere between min and max as it turns
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum MidiAction {
+    Toggle,
+    Start,
+    Stop,
+    Cps { min: f64, max: f64 },
+    Profile { profile: String },
+}
+
+// One row of the mapping table: a note (pads, keys) or a control change
+// (knobs, faders, buttons), on one channel or any
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MidiMapping {
+    pub note: Option<u8>,
+    pub cc: Option<u8>,
+    // 1-16; None listens on all of them
+    pub channel: Option<u8>,
+    #[serde(flatten)]
+    pub action: MidiAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct MidiConfig {
+    // Part of the input port's name, ignoring case; None takes the first
+    pub port: Option<String>,
+    pub mappings: Vec<MidiMapping>,
+}
+
+// A message from the controller that the mappings care about
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MidiEvent {
+    // Velocity 0 is a release, the way most pads send it
+    Note { channel: u8, note: u8, velocity: u8 },
+    Cc { channel: u8, cc: u8, value: u8 },
+}
+
+impl MidiEvent {
+    fn parse(message: &[u8]) -> Option<Self> {
+        let (&status, data) = message.split_first()?;
+        let channel = (status & 0x0F) + 1;
+        match (status & 0xF0, data) {
+            (0x90, &[note, velocity, ..]) => Some(MidiEvent::Note {
+                channel,
+                note,
+                velocity,
+            }),
+            (0x80, &[note, _, ..]) => Some(MidiEvent::Note {
+                channel,
+                note,
+                velocity: 0,
+            }),
+            (0xB0, &[cc, value, ..]) => Some(MidiEvent::Cc { channel, cc, value }),
+            _ => None,
+        }
+    }
+}
+
+impl MidiMapping {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.note.is_some() == self.cc.is_some() {
+            return Err("needs either a note or a cc".to_string());
+        }
+        if self.note.into_iter().chain(self.cc).any(|n| n > 127) {
+            return Err("note and cc numbers go up to 127".to_string());
+        }
+        if self.channel.is_some_and(|c| !(1..=16).contains(&c)) {
+            return Err("channel must be 1-16".to_string());
+        }
+        if let MidiAction::Cps { min, max } = self.action {
+            if !(MIN_CPS..=MAX_SAFE_CPS).contains(&min)
+                || !(MIN_CPS..=MAX_SAFE_CPS).contains(&max)
+                || min >= max
+            {
+                return Err(format!(
+                    "cps needs {} <= min < max <= {}",
+                    MIN_CPS, MAX_SAFE_CPS
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> String {
+        match (self.note, self.cc) {
+            (Some(note), _) => format!("note {}", note),
+            (_, Some(cc)) => format!("cc {}", cc),
+            _ => "nothing".to_string(),
+        }
+    }
+
+    // The 0-127 value of the event when it's this mapping's control
+    fn value(&self, event: MidiEvent) -> Option<u8> {
+        let (channel, value) = match event {
+            MidiEvent::Note {
+                channel,
+                note,
+                velocity,
+            } if self.note == Some(note) => (channel, velocity),
+            MidiEvent::Cc { channel, cc, value } if self.cc == Some(cc) => (channel, value),
+            _ => return None,
+        };
+        self.channel.is_none_or(|c| c == channel).then_some(value)
+    }
+}
+
+impl MidiConfig {
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut problems = Vec::new();
+        self.mappings.retain(|mapping| match mapping.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                problems.push(format!(
+                    "ignoring mapping for {}: {}",
+                    mapping.describe(),
+                    e
+                ));
+                false
+            }
+        });
+        problems
+    }
+}
+
+// Turns controller values into requests. Remembers which buttons are down,
+// so a button only acts when pressed, not again when released or while the
+// controller repeats it
+#[derive(Default)]
+struct Mapper {
+    held: Vec<bool>,
+}
+
+impl Mapper {
+    fn map(&mut self, mappings: &[MidiMapping], event: MidiEvent) -> Vec<Control> {
+        self.held.resize(mappings.len(), false);
+        let mut controls = Vec::new();
+        for (mapping, held) in mappings.iter().zip(&mut self.held) {
+            let Some(value) = mapping.value(event) else {
+                continue;
+            };
+            let control = match &mapping.action {
+                MidiAction::Cps { min, max } => {
+                    let cps = round_cps(min + (max - min) * value as f64 / 127.0);
+                    controls.push(Control::SetCps(cps.max(MIN_CPS)));
+                    continue;
+                }
+                MidiAction::Toggle => Control::Toggle,
+                MidiAction::Start => Control::Start,
+                MidiAction::Stop => Control::Stop,
+                MidiAction::Profile { profile } => Control::Profile(profile.clone()),
+            };
+            let pressed = if mapping.note.is_some() {
+                value > 0
+            } else {
+                value >= PRESSED
+            };
+            if pressed && !*held {
+                controls.push(control);
+            }
+            *held = pressed;
+        }
+        controls
+    }
+}
+
+fn connect(
+    config: &MidiConfig,
+    requests: mpsc::Sender<Control>,
+) -> Result<(String, MidiInputConnection<()>), String> {
+    let input = MidiInput::new("BClicker").map_err(|e| e.to_string())?;
+    let wanted = config.port.as_deref().map(str::to_lowercase);
+    let (port, name) = input
+        .ports()
+        .into_iter()
+        .filter_map(|port| {
+            let name = input.port_name(&port).ok()?;
+            Some((port, name))
+        })
+        .find(|(_, name)| {
+            wanted
+                .as_deref()
+                .is_none_or(|wanted| name.to_lowercase().contains(wanted))
+        })
+        .ok_or_else(|| match &config.port {
+            Some(port) => format!("no MIDI input named like '{}'", port),
+            None => "no MIDI input found".to_string(),
+        })?;
+    let mappings = config.mappings.clone();
+    let mut mapper = Mapper::default();
+    let connection = input
+        .connect(
+            &port,
+            "bclicker-in",
+            move |_, message, _| {
+                let Some(event) = MidiEvent::parse(message) else {
+                    return;
+                };
+                for control in mapper.map(&mappings, event) {
+                    let _ = requests.send(control);
+                }
+            },
+            (),
+        )
+        .map_err(|e| format!("{}: {}", name, e))?;
+    Ok((name, connection))
+}
+
+// Connects to the controller once it shows up and hands what it sends to
+// the TUI
+pub fn spawn(config: MidiConfig, feed: IpcFeed) {
+    let requests = feed.queue("MIDI");
+    thread::spawn(move || {
+        let mut warned = false;
+        let _connection = loop {
+            match connect(&config, requests.clone()) {
+                Ok((name, connection)) => {
+                    println!("[INFO] MIDI: listening to {}", name);
+                    break connection;
+                }
+                Err(e) => {
+                    if !warned {
+                        println!("[WARNING] MIDI: {}, will keep looking", e);
+                        warned = true;
+                    }
+                    thread::sleep(RETRY_DELAY);
+                }
+            }
+        };
+        // The connection delivers on a thread of its own for as long as
+        // it's kept
+        loop {
+            thread::park();
+        }
+    });
+}
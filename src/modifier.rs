@@ -0,0 +1,58 @@
+use crate::backend::ClickBackend;
+use enigo::Key;
+use serde::{Deserialize, Serialize};
+
+// Key held down around each click, e.g. Shift for shift-clicking items
+// between inventories
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClickModifier {
+    Shift,
+    Ctrl,
+    Alt,
+}
+
+impl ClickModifier {
+    // Off, then each modifier in turn
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(ClickModifier::Shift),
+            Some(ClickModifier::Shift) => Some(ClickModifier::Ctrl),
+            Some(ClickModifier::Ctrl) => Some(ClickModifier::Alt),
+            Some(ClickModifier::Alt) => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ClickModifier::Shift => "Shift",
+            ClickModifier::Ctrl => "Ctrl",
+            ClickModifier::Alt => "Alt",
+        }
+    }
+
+    fn key(self) -> Key {
+        match self {
+            ClickModifier::Shift => Key::Shift,
+            ClickModifier::Ctrl => Key::Control,
+            ClickModifier::Alt => Key::Alt,
+        }
+    }
+}
+
+// Runs action with the modifier pressed, releasing it afterwards whatever the
+// action did; backends flush queued clicks before key events, so the press
+// always lands before the click and the release after it
+pub fn hold<T>(
+    modifier: Option<ClickModifier>,
+    backend: &mut dyn ClickBackend,
+    action: impl FnOnce(&mut dyn ClickBackend) -> T,
+) -> T {
+    let Some(modifier) = modifier else {
+        return action(backend);
+    };
+    backend.key_down(modifier.key());
+    let result = action(backend);
+    backend.key_up(modifier.key());
+    result
+}
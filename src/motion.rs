@@ -0,0 +1,251 @@
+use crate::backend::{ClickBackend, Point};
+use enigo::{Key, MouseButton};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{thread, time::Duration};
+
+// Time between cursor updates along a path, about one per frame at 120 Hz
+const MOVE_STEP: Duration = Duration::from_millis(8);
+
+// Moves shorter than this, in pixels, jump straight there, so clicking on
+// the spot the cursor already sits on costs no time
+const MIN_DISTANCE: f64 = 3.0;
+
+const MIN_SPEED: f64 = 100.0;
+const MAX_SPEED: f64 = 20_000.0;
+
+// Shortest and longest a move may take, whatever the distance
+const MIN_DURATION: Duration = Duration::from_millis(60);
+const MAX_DURATION: Duration = Duration::from_secs(2);
+
+// Moves to fixed targets along a curved path that speeds up and slows down
+// like a hand would, instead of jumping there
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct HumanMotion {
+    // Average speed in pixels per second
+    pub speed: f64,
+    // Aim a few pixels past the target and correct back
+    pub overshoot: bool,
+}
+
+impl Default for HumanMotion {
+    fn default() -> Self {
+        Self {
+            speed: 1500.0,
+            overshoot: true,
+        }
+    }
+}
+
+// Minimum-jerk easing: starts and ends at rest, fastest in the middle
+fn ease(t: f64) -> f64 {
+    t * t * t * (10.0 - 15.0 * t + 6.0 * t * t)
+}
+
+fn lerp(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+fn bezier(p: [(f64, f64); 4], t: f64) -> (f64, f64) {
+    let (ab, bc, cd) = (
+        lerp(p[0], p[1], t),
+        lerp(p[1], p[2], t),
+        lerp(p[2], p[3], t),
+    );
+    lerp(lerp(ab, bc, t), lerp(bc, cd, t), t)
+}
+
+impl HumanMotion {
+    pub fn sanitize(&mut self) {
+        self.speed = self.speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    fn duration(&self, distance: f64) -> Duration {
+        Duration::from_secs_f64(distance / self.speed).clamp(MIN_DURATION, MAX_DURATION)
+    }
+
+    // Points to visit one MOVE_STEP apart, ending exactly on the target;
+    // empty when the target is too close to bother
+    pub fn path(&self, from: Point, to: Point, rng: &mut impl Rng) -> Vec<Point> {
+        let start = (from.x as f64, from.y as f64);
+        let end = (to.x as f64, to.y as f64);
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let distance = dx.hypot(dy);
+        if distance < MIN_DISTANCE {
+            return Vec::new();
+        }
+
+        // The control points sit off the straight line to one side, so the
+        // path bows like a wrist turning
+        let normal = (-dy / distance, dx / distance);
+        let bend = rng.gen_range(-0.2..0.2) * distance.min(600.0);
+        let control = |along: f64, amount: f64| {
+            let (x, y) = lerp(start, end, along);
+            (x + normal.0 * amount, y + normal.1 * amount)
+        };
+        let overshoot = if self.overshoot {
+            (distance * rng.gen_range(0.02..0.06)).min(15.0)
+        } else {
+            0.0
+        };
+        let aim = (
+            end.0 + dx / distance * overshoot,
+            end.1 + dy / distance * overshoot,
+        );
+        let curve = [
+            start,
+            control(rng.gen_range(0.2..0.4), bend * rng.gen_range(0.6..1.0)),
+            control(rng.gen_range(0.6..0.8), bend * rng.gen_range(0.3..0.7)),
+            aim,
+        ];
+
+        let steps = (self.duration(distance).as_millis() / MOVE_STEP.as_millis()).max(2) as usize;
+        // The correction back from an overshoot takes the last few steps
+        let correction = if overshoot >= 1.0 {
+            (steps / 6).max(2)
+        } else {
+            0
+        };
+        let main = steps - correction;
+        let mut points: Vec<Point> = (1..=main)
+            .map(|i| bezier(curve, ease(i as f64 / main as f64)))
+            .chain((1..=correction).map(|i| lerp(aim, end, ease(i as f64 / correction as f64))))
+            .map(|(x, y)| Point {
+                x: x.round() as i32,
+                y: y.round() as i32,
+            })
+            .collect();
+        points.dedup();
+        if let Some(last) = points.last_mut() {
+            *last = to;
+        }
+        points
+    }
+}
+
+// A backend whose moves to fixed points follow a human path. Moves with a
+// button held, such as the steps of a drag, and moves from a cursor that
+// can't be read go straight through
+pub struct Glide<'a, R> {
+    backend: &'a mut dyn ClickBackend,
+    motion: Option<HumanMotion>,
+    rng: &'a mut R,
+    buttons_down: u32,
+}
+
+impl<'a, R: Rng> Glide<'a, R> {
+    pub fn new(
+        backend: &'a mut dyn ClickBackend,
+        motion: Option<HumanMotion>,
+        rng: &'a mut R,
+    ) -> Self {
+        Self {
+            backend,
+            motion,
+            rng,
+            buttons_down: 0,
+        }
+    }
+}
+
+impl<R: Rng> ClickBackend for Glide<'_, R> {
+    fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        self.backend.click(button);
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        self.buttons_down += 1;
+        self.backend.button_down(button);
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        self.buttons_down = self.buttons_down.saturating_sub(1);
+        self.backend.button_up(button);
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        let from = self
+            .motion
+            .filter(|_| self.buttons_down == 0)
+            .and_then(|motion| Some((motion, self.backend.cursor_position()?)));
+        let path = match from {
+            Some((motion, from)) => motion.path(from, Point { x, y }, self.rng),
+            None => Vec::new(),
+        };
+        if path.is_empty() {
+            self.backend.move_to(x, y);
+            return;
+        }
+        self.backend.flush();
+        for p in path {
+            thread::sleep(MOVE_STEP);
+            self.backend.move_to(p.x, p.y);
+            self.backend.flush();
+        }
+    }
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.backend.move_relative(dx, dy);
+    }
+
+    fn scroll(&mut self, amount: i32) {
+        self.backend.scroll(amount);
+    }
+
+    fn key(&mut self, key: Key) {
+        self.backend.key(key);
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.backend.key_down(key);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.backend.key_up(key);
+    }
+
+    fn type_text(&mut self, text: &str) {
+        self.backend.type_text(text);
+    }
+
+    fn cursor_position(&self) -> Option<Point> {
+        self.backend.cursor_position()
+    }
+
+    fn flush(&mut self) {
+        self.backend.flush();
+    }
+
+    fn take_sent(&mut self) -> Option<bool> {
+        self.backend.take_sent()
+    }
+}
+
+// Side of the square the hover mode traces without points of its own
+const HOVER_SQUARE: i32 = 40;
+
+// One move of the hover mode: to the next of the points in turn, or the next
+// corner of a small square around where the cursor started, which it comes
+// back to every four moves
+pub fn hover(backend: &mut dyn ClickBackend, points: &[Point], index: u64) {
+    if !points.is_empty() {
+        let p = points[(index % points.len() as u64) as usize];
+        backend.move_to(p.x, p.y);
+        return;
+    }
+    let (dx, dy) = match index % 4 {
+        0 => (HOVER_SQUARE, 0),
+        1 => (0, HOVER_SQUARE),
+        2 => (-HOVER_SQUARE, 0),
+        _ => (0, -HOVER_SQUARE),
+    };
+    match backend.cursor_position() {
+        Some(p) => backend.move_to(p.x + dx, p.y + dy),
+        None => backend.move_relative(dx, dy),
+    }
+}
@@ -0,0 +1,57 @@
+use crate::ActivationMode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SideButton {
+    // Back (XBUTTON1, BTN_SIDE)
+    Mouse4,
+    // Forward (XBUTTON2, BTN_EXTRA)
+    Mouse5,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseTrigger {
+    pub button: SideButton,
+    #[serde(default)]
+    pub mode: ActivationMode,
+}
+
+impl MouseTrigger {
+    const CYCLE: [MouseTrigger; 4] = [
+        MouseTrigger {
+            button: SideButton::Mouse4,
+            mode: ActivationMode::Toggle,
+        },
+        MouseTrigger {
+            button: SideButton::Mouse4,
+            mode: ActivationMode::Hold,
+        },
+        MouseTrigger {
+            button: SideButton::Mouse5,
+            mode: ActivationMode::Toggle,
+        },
+        MouseTrigger {
+            button: SideButton::Mouse5,
+            mode: ActivationMode::Hold,
+        },
+    ];
+
+    // Off, then each button in toggle and hold mode
+    pub fn next(current: Option<MouseTrigger>) -> Option<MouseTrigger> {
+        match current.and_then(|t| Self::CYCLE.iter().position(|&c| c == t)) {
+            None => Some(Self::CYCLE[0]),
+            Some(i) => Self::CYCLE.get(i + 1).copied(),
+        }
+    }
+}
+
+impl std::fmt::Display for MouseTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let button = match self.button {
+            SideButton::Mouse4 => "Mouse4",
+            SideButton::Mouse5 => "Mouse5",
+        };
+        write!(f, "{} ({})", button, self.mode.label())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::ipc::{Control, IpcFeed};
+use rumqttc::{Client, Event, LastWill, MqttOptions, Packet, QoS};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::atomic::Ordering,
+    thread,
+    time::{Duration, Instant},
+};
+
+// How often the publisher looks for a change of state
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Wait before connecting again after the broker went away
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// Broker to report state and stats to and take commands from, e.g. the
+// Mosquitto add-on of Home Assistant. Under the prefix:
+//   availability  online/offline (retained, offline set as the last will)
+//   state         running, paused or stopped (retained)
+//   status        the `bclicker status --json` object (retained)
+//   command       start, stop, toggle, `cps <rate>` or `profile <name>`,
+//                 subscribed to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub client_id: String,
+    pub topic_prefix: String,
+    // Seconds between status messages while nothing changes
+    pub status_interval_secs: u64,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 1883,
+            username: None,
+            password: None,
+            client_id: "bclicker".to_string(),
+            topic_prefix: "bclicker".to_string(),
+            status_interval_secs: 10,
+        }
+    }
+}
+
+impl MqttConfig {
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.topic_prefix = self.topic_prefix.trim_matches('/').to_string();
+        if self.host.trim().is_empty() {
+            return Err("host is empty".to_string());
+        }
+        if self.topic_prefix.is_empty() || self.topic_prefix.contains(['#', '+']) {
+            return Err(format!(
+                "topic_prefix '{}' must be a topic without wildcards",
+                self.topic_prefix
+            ));
+        }
+        if self.client_id.trim().is_empty() {
+            self.client_id = Self::default().client_id;
+        }
+        self.status_interval_secs = self.status_interval_secs.clamp(1, 3600);
+        Ok(())
+    }
+
+    fn topic(&self, name: &str) -> String {
+        format!("{}/{}", self.topic_prefix, name)
+    }
+}
+
+fn state(feed: &IpcFeed) -> &'static str {
+    if !feed.running.load(Ordering::SeqCst) {
+        "stopped"
+    } else if feed.paused.load(Ordering::SeqCst) {
+        "paused"
+    } else {
+        "running"
+    }
+}
+
+// Carries out a command off the connection thread, since the TUI may take a
+// moment to answer
+fn handle_command(feed: &IpcFeed, payload: &[u8]) {
+    let command = String::from_utf8_lossy(payload).trim().to_string();
+    let Some(control) = Control::parse(&command) else {
+        println!("[WARNING] MQTT: unknown command '{}'", command);
+        return;
+    };
+    let feed = feed.clone();
+    thread::spawn(move || {
+        if let Err(e) = feed.control(control) {
+            println!("[WARNING] MQTT: '{}' refused: {}", command, e);
+        }
+    });
+}
+
+// Connects in the background and keeps reconnecting for the life of the
+// process; the broker being away never holds up clicking
+pub fn spawn(config: MqttConfig, feed: IpcFeed) {
+    let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    options.set_last_will(LastWill::new(
+        config.topic("availability"),
+        "offline",
+        QoS::AtLeastOnce,
+        true,
+    ));
+    if let Some(username) = &config.username {
+        options.set_credentials(username, config.password.as_deref().unwrap_or_default());
+    }
+    let (client, mut connection) = Client::new(options, 16);
+
+    let publisher = client.clone();
+    let publisher_feed = feed.clone();
+    let publisher_config = config.clone();
+    thread::spawn(move || {
+        let (feed, config) = (publisher_feed, publisher_config);
+        let interval = Duration::from_secs(config.status_interval_secs);
+        let mut published: Option<(&str, Instant)> = None;
+        loop {
+            let state = state(&feed);
+            let due = published.is_none_or(|(last, at)| last != state || at.elapsed() >= interval);
+            if due {
+                // Dropped rather than queued while the broker is away; the
+                // next one carries the same news
+                let status = serde_json::to_string(&feed.status()).unwrap_or_default();
+                let _ = publisher.try_publish(config.topic("state"), QoS::AtLeastOnce, true, state);
+                let _ =
+                    publisher.try_publish(config.topic("status"), QoS::AtLeastOnce, true, status);
+                published = Some((state, Instant::now()));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+
+    thread::spawn(move || {
+        let command_topic = config.topic("command");
+        let mut connected = false;
+        // One warning per outage, not one per retry
+        let mut warned = false;
+        for event in connection.iter() {
+            match event {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    if !connected {
+                        println!("[INFO] MQTT: connected to {}:{}", config.host, config.port);
+                    }
+                    connected = true;
+                    warned = false;
+                    // A fresh session forgets the subscription
+                    let _ = client.try_subscribe(&command_topic, QoS::AtLeastOnce);
+                    let _ = client.try_publish(
+                        config.topic("availability"),
+                        QoS::AtLeastOnce,
+                        true,
+                        "online",
+                    );
+                }
+                Ok(Event::Incoming(Packet::Publish(message))) if message.topic == command_topic => {
+                    handle_command(&feed, &message.payload);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if connected {
+                        println!("[WARNING] MQTT: connection lost: {}", e);
+                    } else if !warned {
+                        println!("[WARNING] MQTT: could not connect: {}", e);
+                    }
+                    connected = false;
+                    warned = true;
+                    thread::sleep(RETRY_DELAY);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,75 @@
+use notify_rust::Notification;
+use serde::{Deserialize, Serialize};
+
+// Kinds of events that can raise a desktop notification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotifyEvent {
+    Start,
+    Stop,
+    Countdown,
+    Hotkey,
+    Milestone,
+    Error,
+    // Confirmations of settings changed in the TUI
+    Info,
+}
+
+// Which events notify, and for how long; start and stop are off by default
+// since the sounds and tray already signal them
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct NotificationPrefs {
+    pub start: bool,
+    pub stop: bool,
+    pub countdown: bool,
+    pub hotkey: bool,
+    pub milestone: bool,
+    pub error: bool,
+    pub info: bool,
+    pub timeout_ms: u32,
+}
+
+impl Default for NotificationPrefs {
+    fn default() -> Self {
+        Self {
+            start: false,
+            stop: false,
+            countdown: true,
+            hotkey: true,
+            milestone: true,
+            error: true,
+            info: true,
+            timeout_ms: 3000,
+        }
+    }
+}
+
+impl NotificationPrefs {
+    pub fn allows(&self, event: NotifyEvent) -> bool {
+        match event {
+            NotifyEvent::Start => self.start,
+            NotifyEvent::Stop => self.stop,
+            NotifyEvent::Countdown => self.countdown,
+            NotifyEvent::Hotkey => self.hotkey,
+            NotifyEvent::Milestone => self.milestone,
+            NotifyEvent::Error => self.error,
+            NotifyEvent::Info => self.info,
+        }
+    }
+
+    pub fn send(&self, event: NotifyEvent, title: &str, message: &str) {
+        self.send_for(event, title, message, self.timeout_ms);
+    }
+
+    // For notifications that should stay up for a specific time, e.g. a countdown
+    pub fn send_for(&self, event: NotifyEvent, title: &str, message: &str, timeout_ms: u32) {
+        if !self.allows(event) {
+            return;
+        }
+        let _ = Notification::new()
+            .summary(title)
+            .body(message)
+            .timeout(timeout_ms as i32)
+            .show();
+    }
+}
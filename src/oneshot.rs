@@ -0,0 +1,42 @@
+use crate::{job_queue::ClickJob, motion::Glide, scheduler::ClickScheduler};
+use std::time::Instant;
+
+const USAGE: &str =
+    "usage: bclicker click [--count N] [--cps N] [--at X,Y] [--button left|right|middle]";
+
+// `bclicker click ...`: clicks through the configured backend and exits. The
+// config is only read, for the backend, coordinate space and calibration
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    let job = ClickJob::from_args(args, USAGE)?;
+    let mut config = crate::load_config();
+    config.sanitize();
+    let job = job.in_physical(config.coordinate_space);
+
+    let mut backend = config.backend.create();
+    let mut rng = rand::thread_rng();
+    let mut scheduler = ClickScheduler::calibrated(config.calibration);
+    let interval = job.interval();
+    let start = Instant::now();
+    let mut clicks = 0;
+
+    while clicks < job.count {
+        if !scheduler.poll(interval) {
+            continue;
+        }
+        job.perform(&mut Glide::new(
+            backend.as_mut(),
+            config.human_motion,
+            &mut rng,
+        ));
+        backend.flush();
+        clicks += 1;
+    }
+
+    println!(
+        "[INFO] {} clicks in {:.2}s via {}",
+        clicks,
+        start.elapsed().as_secs_f64(),
+        backend.name()
+    );
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use crate::{
+    MIN_CPS,
+    auth::{self, ApiToken, Scope},
+    ipc::{Control, IpcFeed},
+    round_cps,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{IpAddr, SocketAddr, UdpSocket},
+    sync::{Arc, Mutex, atomic::Ordering},
+    thread,
+    time::Duration,
+};
+
+// How often the state is looked at for feedback to send
+const FEEDBACK_INTERVAL: Duration = Duration::from_millis(200);
+
+// Bundles nest; anything deeper than this is junk
+const MAX_BUNDLE_DEPTH: usize = 4;
+
+// Devices sent feedback at once; the one heard from longest ago goes first
+const MAX_CLIENTS: usize = 8;
+
+// Address every message starts with
+const PREFIX: &str = "/bclicker/";
+
+// OSC over UDP for TouchOSC and similar tablet layouts. Ports match
+// TouchOSC's own: it sends to 8000 and listens on 9000. OSC has no auth of
+// its own, so out of the box only this machine is heard; a tablet needs a
+// LAN bind, its address in allow and an API token as the last argument
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct OscConfig {
+    pub bind: IpAddr,
+    pub port: u16,
+    // Where on the senders' devices to send the state, so buttons light up
+    // and faders follow; None sends nothing back
+    pub feedback_port: Option<u16>,
+    // Addresses taken messages from; empty takes them from this machine only
+    pub allow: Vec<IpAddr>,
+}
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            bind: IpAddr::from([127, 0, 0, 1]),
+            port: 8000,
+            feedback_port: Some(9000),
+            allow: Vec::new(),
+        }
+    }
+}
+
+impl OscConfig {
+    fn allows(&self, from: IpAddr) -> bool {
+        if self.allow.is_empty() {
+            from.is_loopback()
+        } else {
+            self.allow.contains(&from)
+        }
+    }
+
+    // Addresses can be spoofed over UDP, so once other machines are allowed
+    // every message carries a control token as its last argument
+    pub fn needs_token(&self) -> bool {
+        self.allow.iter().any(|ip| !ip.is_loopback())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum OscArg {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+}
+
+impl OscArg {
+    fn number(&self) -> Option<f64> {
+        match self {
+            OscArg::Int(i) => Some(*i as f64),
+            OscArg::Float(f) => Some(*f),
+            OscArg::Str(s) => s.trim().parse().ok(),
+            OscArg::Bool(b) => Some(*b as u8 as f64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct OscMessage {
+    address: String,
+    args: Vec<OscArg>,
+}
+
+// Reads OSC's 4-byte aligned fields off the front of a packet
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.data.len() {
+            return None;
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(taken)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let end = self.data.iter().position(|&b| b == 0)?;
+        let text = String::from_utf8_lossy(&self.data[..end]).into_owned();
+        self.take((end + 4) & !3)?;
+        Some(text)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+}
+
+fn parse_message(data: &[u8]) -> Option<OscMessage> {
+    let mut reader = Reader { data };
+    let address = reader.string()?;
+    // Very old senders leave the type tags out; their messages have no
+    // arguments worth reading
+    let tags = if reader.data.first() == Some(&b',') {
+        reader.string()?
+    } else {
+        String::new()
+    };
+    let mut args = Vec::new();
+    for tag in tags.chars().skip(1) {
+        args.push(match tag {
+            'i' => OscArg::Int(i32::from_be_bytes(reader.array()?) as i64),
+            'h' => OscArg::Int(i64::from_be_bytes(reader.array()?)),
+            'f' => OscArg::Float(f32::from_be_bytes(reader.array()?) as f64),
+            'd' => OscArg::Float(f64::from_be_bytes(reader.array()?)),
+            's' | 'S' => OscArg::Str(reader.string()?),
+            'T' => OscArg::Bool(true),
+            'F' => OscArg::Bool(false),
+            // Blobs, colors and the rest mean nothing here, and their sizes
+            // can't all be known, so nothing after them is read either
+            _ => break,
+        });
+    }
+    Some(OscMessage { address, args })
+}
+
+// The messages of a packet, opening bundles; the time tags are ignored and
+// everything acts on arrival
+fn parse(data: &[u8], depth: usize, messages: &mut Vec<OscMessage>) {
+    let Some(mut rest) = data.strip_prefix(b"#bundle\0") else {
+        messages.extend(parse_message(data));
+        return;
+    };
+    if depth >= MAX_BUNDLE_DEPTH || rest.len() < 8 {
+        return;
+    }
+    rest = &rest[8..];
+    while let Some((size, after)) = rest.split_first_chunk::<4>() {
+        let size = u32::from_be_bytes(*size) as usize;
+        if size > after.len() {
+            return;
+        }
+        parse(&after[..size], depth + 1, messages);
+        rest = &after[size..];
+    }
+}
+
+// What a message asks for. Buttons send 1 when pressed and 0 when let go,
+// and only the press acts; a message with no argument is a press too.
+// Layouts send plenty that isn't for BClicker, which is passed over
+fn control(message: &OscMessage) -> Result<Option<Control>, String> {
+    let Some(action) = message.address.strip_prefix(PREFIX) else {
+        return Ok(None);
+    };
+    let first = message.args.first();
+    let pressed = first.is_none_or(|arg| arg.number().is_none_or(|n| n != 0.0));
+    let control = match action {
+        "toggle" => Control::Toggle,
+        "start" => Control::Start,
+        "stop" => Control::Stop,
+        "cps" => {
+            // A fader pulled all the way down asks for the slowest rate
+            let cps = first
+                .and_then(OscArg::number)
+                .filter(|cps| cps.is_finite())
+                .ok_or_else(|| format!("{} needs a rate", message.address))?;
+            return Ok(Some(Control::SetCps(round_cps(cps).max(MIN_CPS))));
+        }
+        "profile" => match first {
+            Some(OscArg::Str(name)) => return Ok(Some(Control::Profile(name.clone()))),
+            _ => return Err(format!("{} needs a profile name", message.address)),
+        },
+        _ => return Ok(None),
+    };
+    Ok(pressed.then_some(control))
+}
+
+// Takes the token off the end of a message meant for BClicker and checks it
+fn authorize(message: &mut OscMessage, tokens: &[ApiToken]) -> Result<(), String> {
+    let token = match message.args.pop() {
+        Some(OscArg::Str(token)) => Some(token),
+        _ => None,
+    };
+    auth::check(tokens, token.as_deref(), Scope::Control)
+        .map_err(|e| format!("{}: {}", message.address, e))
+}
+
+fn pad(buf: &mut Vec<u8>) {
+    buf.push(0);
+    while !buf.len().is_multiple_of(4) {
+        buf.push(0);
+    }
+}
+
+fn encode(address: &str, arg: &OscArg) -> Vec<u8> {
+    let mut buf = address.as_bytes().to_vec();
+    pad(&mut buf);
+    let (tag, value) = match arg {
+        OscArg::Int(i) => ("i", (*i as i32).to_be_bytes().to_vec()),
+        OscArg::Float(f) => ("f", (*f as f32).to_be_bytes().to_vec()),
+        OscArg::Bool(b) => ("i", (*b as i32).to_be_bytes().to_vec()),
+        OscArg::Str(s) => {
+            let mut value = s.as_bytes().to_vec();
+            pad(&mut value);
+            ("s", value)
+        }
+    };
+    buf.extend_from_slice(format!(",{}", tag).as_bytes());
+    pad(&mut buf);
+    buf.extend(value);
+    buf
+}
+
+// What the layout shows: /bclicker/running, /bclicker/paused (1 or 0),
+// /bclicker/cps and /bclicker/profile
+fn feedback(feed: &IpcFeed) -> Vec<(String, OscArg)> {
+    let settings = feed.engine_settings.get();
+    let running = feed.running.load(Ordering::SeqCst);
+    vec![
+        (format!("{}running", PREFIX), OscArg::Bool(running)),
+        (
+            format!("{}paused", PREFIX),
+            OscArg::Bool(running && feed.paused.load(Ordering::SeqCst)),
+        ),
+        (format!("{}cps", PREFIX), OscArg::Float(settings.cps)),
+        (
+            format!("{}profile", PREFIX),
+            OscArg::Str(settings.profile_name.to_string()),
+        ),
+    ]
+}
+
+// Sends every part of the state that changed to every device heard from,
+// and all of it to a device heard from for the first time
+fn spawn_feedback(socket: UdpSocket, feed: IpcFeed, clients: Arc<Mutex<Vec<(SocketAddr, bool)>>>) {
+    thread::spawn(move || {
+        let mut shown: Vec<(String, OscArg)> = Vec::new();
+        loop {
+            let state = feedback(&feed);
+            let mut clients = clients.lock().unwrap_or_else(|e| e.into_inner());
+            for (client, fresh) in clients.iter_mut() {
+                for (i, (address, arg)) in state.iter().enumerate() {
+                    if *fresh || shown.get(i) != Some(&state[i]) {
+                        let _ = socket.send_to(&encode(address, arg), *client);
+                    }
+                }
+                *fresh = false;
+            }
+            drop(clients);
+            shown = state;
+            thread::sleep(FEEDBACK_INTERVAL);
+        }
+    });
+}
+
+// Listens until the process exits
+pub fn spawn(config: OscConfig, tokens: Vec<ApiToken>, feed: IpcFeed) -> Result<(), String> {
+    let socket = UdpSocket::bind((config.bind, config.port))
+        .map_err(|e| format!("{}:{}: {}", config.bind, config.port, e))?;
+    if !config.bind.is_loopback() && config.allow.is_empty() {
+        println!(
+            "[WARNING] OSC listens on {} but allow is empty, so only this machine is heard; \
+             list your devices' addresses in allow",
+            config.bind
+        );
+    }
+    let clients = Arc::new(Mutex::new(Vec::new()));
+    if config.feedback_port.is_some() {
+        let socket = socket.try_clone().map_err(|e| e.to_string())?;
+        spawn_feedback(socket, feed.clone(), Arc::clone(&clients));
+    }
+    let requests = feed.queue("OSC");
+    thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        loop {
+            let (len, from) = match socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(e) => {
+                    println!("[WARNING] OSC: {}", e);
+                    thread::sleep(Duration::from_secs(1));
+                    continue;
+                }
+            };
+            if !config.allows(from.ip()) {
+                continue;
+            }
+            let mut messages = Vec::new();
+            parse(&buf[..len], 0, &mut messages);
+            // Feedback only goes to devices that showed a token, when one
+            // is needed, so the state isn't sent to anyone who asks
+            let mut heard = !config.needs_token();
+            for message in &mut messages {
+                if !message.address.starts_with(PREFIX) {
+                    continue;
+                }
+                if config.needs_token() {
+                    if let Err(e) = authorize(message, &tokens) {
+                        println!("[WARNING] OSC from {}: {}", from.ip(), e);
+                        continue;
+                    }
+                    heard = true;
+                }
+                match control(message) {
+                    Ok(Some(control)) => {
+                        let _ = requests.send(control);
+                    }
+                    Ok(None) => {}
+                    Err(e) => println!("[WARNING] OSC from {}: {}", from.ip(), e),
+                }
+            }
+            if let Some(port) = config.feedback_port.filter(|_| heard) {
+                let client = SocketAddr::new(from.ip(), port);
+                let mut clients = clients.lock().unwrap_or_else(|e| e.into_inner());
+                if !clients.iter().any(|(known, _)| *known == client) {
+                    if clients.len() >= MAX_CLIENTS {
+                        clients.remove(0);
+                    }
+                    clients.push((client, true));
+                }
+            }
+        }
+    });
+    Ok(())
+}
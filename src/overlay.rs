@@ -0,0 +1,240 @@
+use crate::scheduler::RateStatus;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+    thread,
+};
+
+// State the indicator mirrors; shared with the engine and the TUI
+pub struct OverlayFeed {
+    pub enabled: Arc<AtomicBool>,
+    pub running: Arc<AtomicBool>,
+    pub countdown: Arc<AtomicU32>,
+    pub rate_status: Arc<Mutex<RateStatus>>,
+}
+
+impl OverlayFeed {
+    // Whether the clicker is active, plus the text shown next to the dot
+    fn status(&self) -> (bool, String) {
+        let countdown = self.countdown.load(Ordering::SeqCst);
+        if countdown > 0 {
+            return (false, format!("Starting in {}", countdown));
+        }
+        if !self.running.load(Ordering::SeqCst) {
+            return (false, "Idle".to_string());
+        }
+        let rate = *self.rate_status.lock().unwrap_or_else(|e| e.into_inner());
+        (true, format!("{:.1} CPS", rate.achieved_cps))
+    }
+}
+
+// The tray icon and TUI are hidden behind fullscreen games, so the indicator
+// lives in its own always-on-top surface; the thread idles while disabled
+pub fn spawn(feed: OverlayFeed) -> thread::JoinHandle<()> {
+    thread::spawn(move || native::run(feed))
+}
+
+// Click-through topmost layered window drawn with GDI
+#[cfg(windows)]
+mod native {
+    use super::OverlayFeed;
+    use std::sync::{OnceLock, atomic::Ordering};
+    use windows::{
+        Win32::{
+            Foundation::{COLORREF, HWND, LPARAM, LRESULT, WPARAM},
+            Graphics::Gdi::{
+                BeginPaint, CreateSolidBrush, DeleteObject, Ellipse, EndPaint, FillRect,
+                InvalidateRect, PAINTSTRUCT, SelectObject, SetBkMode, SetTextColor, TRANSPARENT,
+                TextOutW,
+            },
+            System::LibraryLoader::GetModuleHandleW,
+            UI::WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, HWND_TOPMOST,
+                LWA_ALPHA, MSG, RegisterClassW, SW_HIDE, SW_SHOWNOACTIVATE, SWP_NOACTIVATE,
+                SWP_NOMOVE, SWP_NOSIZE, SetLayeredWindowAttributes, SetTimer, SetWindowPos,
+                ShowWindow, TranslateMessage, WM_PAINT, WM_TIMER, WNDCLASSW, WS_EX_LAYERED,
+                WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP,
+            },
+        },
+        w,
+    };
+
+    // The window procedure has no user pointer plumbing, so the feed is global
+    static FEED: OnceLock<OverlayFeed> = OnceLock::new();
+
+    const WIDTH: i32 = 150;
+    const HEIGHT: i32 = 28;
+    const REFRESH_TIMER: usize = 1;
+    // COLORREF is 0x00BBGGRR
+    const BACKGROUND: COLORREF = COLORREF(0x0020_2020);
+    const ACTIVE_DOT: COLORREF = COLORREF(0x0050_C850);
+    const IDLE_DOT: COLORREF = COLORREF(0x003C_3CDC);
+    const TEXT: COLORREF = COLORREF(0x00FF_FFFF);
+
+    unsafe extern "system" fn wndproc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        unsafe {
+            match msg {
+                WM_TIMER => {
+                    let enabled = FEED
+                        .get()
+                        .is_some_and(|feed| feed.enabled.load(Ordering::SeqCst));
+                    if enabled {
+                        ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+                        // Games that grab topmost themselves would otherwise cover it
+                        SetWindowPos(
+                            hwnd,
+                            HWND_TOPMOST,
+                            0,
+                            0,
+                            0,
+                            0,
+                            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+                        );
+                        InvalidateRect(hwnd, None, true);
+                    } else {
+                        ShowWindow(hwnd, SW_HIDE);
+                    }
+                    LRESULT(0)
+                }
+                WM_PAINT => {
+                    paint(hwnd);
+                    LRESULT(0)
+                }
+                _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+            }
+        }
+    }
+
+    unsafe fn paint(hwnd: HWND) {
+        let (running, text) = match FEED.get() {
+            Some(feed) => feed.status(),
+            None => return,
+        };
+
+        unsafe {
+            let mut ps = PAINTSTRUCT::default();
+            let hdc = BeginPaint(hwnd, &mut ps);
+
+            let background = CreateSolidBrush(BACKGROUND);
+            FillRect(hdc, &ps.rcPaint, background);
+            DeleteObject(background);
+
+            let dot = CreateSolidBrush(if running { ACTIVE_DOT } else { IDLE_DOT });
+            let previous = SelectObject(hdc, dot);
+            Ellipse(hdc, 8, 8, 20, 20);
+            SelectObject(hdc, previous);
+            DeleteObject(dot);
+
+            SetBkMode(hdc, TRANSPARENT);
+            SetTextColor(hdc, TEXT);
+            let wide: Vec<u16> = text.encode_utf16().collect();
+            TextOutW(hdc, 28, 6, &wide);
+
+            EndPaint(hwnd, &ps);
+        }
+    }
+
+    pub fn run(feed: OverlayFeed) {
+        if FEED.set(feed).is_err() {
+            return;
+        }
+
+        unsafe {
+            let instance = GetModuleHandleW(None).unwrap_or_default();
+            let class = w!("BClickerOverlay");
+            let wc = WNDCLASSW {
+                lpfnWndProc: Some(wndproc),
+                hInstance: instance,
+                lpszClassName: class,
+                ..Default::default()
+            };
+            RegisterClassW(&wc);
+
+            // Transparent to input so it never swallows the clicks it reports on
+            let hwnd = CreateWindowExW(
+                WS_EX_TOPMOST
+                    | WS_EX_LAYERED
+                    | WS_EX_TRANSPARENT
+                    | WS_EX_TOOLWINDOW
+                    | WS_EX_NOACTIVATE,
+                class,
+                w!("BClicker"),
+                WS_POPUP,
+                10,
+                10,
+                WIDTH,
+                HEIGHT,
+                None,
+                None,
+                instance,
+                None,
+            );
+            SetLayeredWindowAttributes(hwnd, COLORREF(0), 210, LWA_ALPHA);
+            SetTimer(hwnd, REFRESH_TIMER, 250, None);
+
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    }
+}
+
+// Persistent desktop notification updated in place, the closest thing to an
+// OSD that works across X11 and Wayland desktops
+#[cfg(all(unix, not(target_os = "macos")))]
+mod native {
+    use super::OverlayFeed;
+    use notify_rust::{Notification, NotificationHandle, Timeout, Urgency};
+    use std::{sync::atomic::Ordering, thread, time::Duration};
+
+    pub fn run(feed: OverlayFeed) {
+        let mut handle: Option<NotificationHandle> = None;
+        let mut shown = String::new();
+
+        loop {
+            if feed.enabled.load(Ordering::SeqCst) {
+                let (running, text) = feed.status();
+                let text = format!("{} {}", if running { "🟢" } else { "🔴" }, text);
+                match handle.as_mut() {
+                    Some(h) if text != shown => {
+                        h.body(&text);
+                        h.update();
+                    }
+                    Some(_) => {}
+                    None => {
+                        handle = Notification::new()
+                            .summary("BClicker")
+                            .body(&text)
+                            .urgency(Urgency::Low)
+                            .timeout(Timeout::Never)
+                            .show()
+                            .ok();
+                    }
+                }
+                shown = text;
+            } else if let Some(h) = handle.take() {
+                h.close();
+                shown.clear();
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod native {
+    use super::OverlayFeed;
+
+    pub fn run(_feed: OverlayFeed) {
+        println!("[WARNING] Overlay indicator is not supported on macOS");
+    }
+}
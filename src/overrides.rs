@@ -0,0 +1,223 @@
+use crate::{BUTTON_MODES, Config, MAX_CPS, MIN_CPS, parse_cps};
+use toml::{Table, Value};
+
+const ENV_PREFIX: &str = "BCLICKER_";
+
+// A single requested override, e.g. BCLICKER_BACKEND=null or --cps 25
+pub struct Override {
+    pub source: String,
+    pub key: String,
+    pub raw: String,
+}
+
+// Environment overrides first, then CLI flags, so applying them in order
+// gives CLI > env > file > defaults
+pub fn collect(args: &[String]) -> Vec<Override> {
+    let mut overrides: Vec<Override> = std::env::vars()
+        .filter_map(|(name, raw)| {
+            let key = name.strip_prefix(ENV_PREFIX)?.to_ascii_lowercase();
+            Some(Override {
+                source: name,
+                key,
+                raw,
+            })
+        })
+        .collect();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let Some(value) = inline.or_else(|| args.next().cloned()) else {
+            println!("[WARNING] {} needs a value", flag);
+            continue;
+        };
+        match flag {
+            "--cps" | "--button" => overrides.push(Override {
+                source: flag.to_string(),
+                key: flag.trim_start_matches('-').to_string(),
+                raw: value,
+            }),
+            // --set key=value; the first '=' above split off "--set"
+            "--set" => match value.split_once('=') {
+                Some((key, raw)) => overrides.push(Override {
+                    source: format!("--set {}", key),
+                    key: key.to_string(),
+                    raw: raw.to_string(),
+                }),
+                None => println!("[WARNING] --set expects key=value, got '{}'", value),
+            },
+            _ => println!("[WARNING] Ignoring unknown argument '{}'", arg),
+        }
+    }
+    overrides
+}
+
+// Bare words become strings, so BCLICKER_BACKEND=null works without quotes
+fn parse_value(raw: &str) -> Value {
+    format!("v = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+fn button_index(raw: &str) -> Option<usize> {
+    let name = raw.to_ascii_lowercase().replace(['-', '_', ' '], "");
+    let index = match name.as_str() {
+        "left" => 0,
+        "right" => 1,
+        "scrollup" => 2,
+        "scrolldown" => 3,
+        "text" | "type" => 4,
+        "alternate" | "alt" => 5,
+        "hold" => 6,
+        "hover" => 7,
+        _ => name.parse().ok()?,
+    };
+    (index < BUTTON_MODES.len()).then_some(index)
+}
+
+type Entries = Vec<(&'static str, Option<Value>)>;
+
+// Top-level config entries a shorthand setting expands to; None removes the
+// entry. Returns None for keys that aren't shorthands
+fn expand_alias(key: &str, raw: &str) -> Option<Result<Entries, String>> {
+    let entries = match key {
+        "cps" => match parse_cps(raw).filter(|&c| c <= MAX_CPS) {
+            Some(cps) => Ok(vec![
+                ("custom_cps_value", Some(Value::Float(cps))),
+                ("using_custom_cps", Some(Value::Boolean(true))),
+                ("human_pattern", None),
+            ]),
+            None => Err(format!("expected a CPS from {} to {}", MIN_CPS, MAX_CPS)),
+        },
+        "button" => match button_index(raw) {
+            Some(index) => Ok(vec![(
+                "selected_button",
+                Some(Value::Integer(index as i64)),
+            )]),
+            None => Err(
+                "expected left, right, scroll-up, scroll-down, text, alternate or hold".to_string(),
+            ),
+        },
+        _ => return None,
+    };
+    Some(entries)
+}
+
+fn round_trips(config: &Config, changes: &[(String, Option<Value>)]) -> bool {
+    let Ok(Value::Table(table)) = Value::try_from(config) else {
+        return false;
+    };
+    changes
+        .iter()
+        .all(|(key, value)| value.is_none() || table.contains_key(key))
+}
+
+// Remembers what the overrides replaced so saving writes the file's own
+// values back; overrides never end up in the config file
+pub struct Overrides {
+    base: Table,
+    applied: Table,
+    keys: Vec<String>,
+}
+
+impl Overrides {
+    pub fn apply(config: &mut Config, overrides: &[Override]) -> Self {
+        let base = Value::try_from(&*config)
+            .ok()
+            .and_then(|v| v.as_table().cloned())
+            .unwrap_or_default();
+        let mut table = base.clone();
+        let mut keys = Vec::new();
+
+        for o in overrides {
+            let changes: Vec<(String, Option<Value>)> = match expand_alias(&o.key, &o.raw) {
+                Some(Ok(entries)) => entries
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect(),
+                Some(Err(e)) => {
+                    println!("[WARNING] Ignoring {}: {}", o.source, e);
+                    continue;
+                }
+                // Any other top-level setting by its config name
+                None if o.key != "statistics" => {
+                    vec![(o.key.clone(), Some(parse_value(&o.raw)))]
+                }
+                None => {
+                    println!(
+                        "[WARNING] Ignoring {}: statistics can't be overridden",
+                        o.source
+                    );
+                    continue;
+                }
+            };
+
+            let mut candidate = table.clone();
+            for (key, value) in &changes {
+                match value {
+                    Some(value) => candidate.insert(key.clone(), value.clone()),
+                    None => candidate.remove(key),
+                };
+            }
+
+            // Reject values of the wrong type rather than losing the whole file;
+            // unknown keys are dropped by deserializing, so check they survive
+            match Value::Table(candidate.clone()).try_into::<Config>() {
+                Ok(parsed) if !round_trips(&parsed, &changes) => {
+                    println!(
+                        "[WARNING] Ignoring {}: unknown setting '{}'",
+                        o.source, o.key
+                    );
+                }
+                Ok(_) => {
+                    table = candidate;
+                    keys.extend(changes.into_iter().map(|(key, _)| key));
+                    println!("[INFO] {} overrides the config file", o.source);
+                }
+                Err(e) => println!("[WARNING] Ignoring {}: {}", o.source, e.message()),
+            }
+        }
+
+        if let Ok(overridden) = Value::Table(table.clone()).try_into::<Config>() {
+            *config = overridden;
+        }
+        keys.sort();
+        keys.dedup();
+        Self {
+            base,
+            applied: table,
+            keys,
+        }
+    }
+
+    // The config as it should be saved: overridden settings the user hasn't
+    // changed since startup go back to their file values
+    pub fn for_saving(&self, config: &Config) -> Config {
+        if self.keys.is_empty() {
+            return config.clone();
+        }
+        let Some(mut table) = Value::try_from(config)
+            .ok()
+            .and_then(|v| v.as_table().cloned())
+        else {
+            return config.clone();
+        };
+        for key in &self.keys {
+            if table.get(key) != self.applied.get(key) {
+                continue;
+            }
+            match self.base.get(key) {
+                Some(value) => table.insert(key.clone(), value.clone()),
+                None => table.remove(key),
+            };
+        }
+        Value::Table(table)
+            .try_into()
+            .unwrap_or_else(|_| config.clone())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{i18n::t, keymap::Action};
+
+// Something the command palette can run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    ToggleClicking,
+    Run(Action),
+}
+
+impl Command {
+    // Every command, in the order shown for an empty query; list navigation
+    // is left out since the palette has its own
+    pub fn all() -> Vec<Command> {
+        std::iter::once(Command::ToggleClicking)
+            .chain(
+                Action::ALL
+                    .into_iter()
+                    .filter(|&a| a != Action::Up && a != Action::Down)
+                    .map(Command::Run),
+            )
+            .collect()
+    }
+
+    pub fn title(self) -> String {
+        match self {
+            Command::ToggleClicking => t!("action-toggle_clicking"),
+            Command::Run(action) => action.description(),
+        }
+    }
+}
+
+// Commands matching the query, best match first
+pub fn filter(query: &str) -> Vec<Command> {
+    let mut matches: Vec<(usize, Command)> = Command::all()
+        .into_iter()
+        .filter_map(|command| fuzzy_score(query, &command.title()).map(|score| (score, command)))
+        .collect();
+    // Stable, so equally good matches keep their listing order
+    matches.sort_by_key(|&(score, _)| score);
+    matches.into_iter().map(|(_, command)| command).collect()
+}
+
+// The query's characters must appear in order in the candidate; the score
+// counts skipped characters, with a penalty for matches starting late.
+// Lower is better
+fn fuzzy_score(query: &str, candidate: &str) -> Option<usize> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut pos = 0;
+    let mut first = None;
+    let mut gaps = 0;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = candidate[pos..].iter().position(|&c| c == q)?;
+        if first.is_some() {
+            gaps += offset;
+        } else {
+            first = Some(pos + offset);
+        }
+        pos += offset + 1;
+    }
+    Some(gaps + first.unwrap_or(0))
+}
@@ -0,0 +1,165 @@
+use crate::{MAX_CPS, MIN_CPS, backend::ClickBackend, condition::Condition, sequence};
+use std::time::Duration;
+
+// Upper bounds so a typo can't describe a cycle of billions of clicks
+const MAX_RUN_CLICKS: u64 = 1_000_000;
+const MAX_WAIT: Duration = Duration::from_secs(24 * 60 * 60);
+
+// A run of clicks at one rate, then whatever waits followed it
+#[derive(Clone, Debug, PartialEq)]
+struct Run {
+    count: u64,
+    cps: f64,
+    button: usize,
+    pause_after: Duration,
+}
+
+// A profile's pattern string such as "20x@30cps; wait 2s; 5x@10cps(right)",
+// compiled for the engine. Each click is one engine action; waits stretch the
+// interval after the click before them, so a leading wait ends the cycle.
+// "until clicks_this_run > 1000" stops clicking once the comparison holds
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClickPattern {
+    runs: Vec<Run>,
+    cycle_clicks: u64,
+    until: Option<Condition>,
+}
+
+// "500ms", "2s", "1.5m", "1h"
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim().to_ascii_lowercase();
+    let split = input.find(|c: char| c.is_ascii_alphabetic())?;
+    let (number, unit) = input.split_at(split);
+    let value: f64 = number.trim().parse().ok()?;
+    let secs = value
+        * match unit {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+    (secs.is_finite() && secs > 0.0)
+        .then(|| Duration::from_secs_f64(secs))
+        .filter(|&d| d <= MAX_WAIT)
+}
+
+// "20x@30cps" with an optional "(left)" or "(right)"
+fn parse_run(input: &str) -> Result<Run, String> {
+    let (run, button) = match input.split_once('(') {
+        Some((run, button)) => {
+            let button = button
+                .strip_suffix(')')
+                .ok_or_else(|| format!("missing ')' in '{}'", input))?;
+            let button = match button.trim().to_ascii_lowercase().as_str() {
+                "left" => 0,
+                "right" => 1,
+                other => return Err(format!("unknown button '{}'", other)),
+            };
+            (run, button)
+        }
+        None => (input, 0),
+    };
+    let (count, rate) = run
+        .split_once('@')
+        .ok_or_else(|| format!("expected COUNTx@RATEcps, got '{}'", input))?;
+    let count: u64 = count
+        .trim()
+        .strip_suffix(['x', 'X'])
+        .and_then(|n| n.trim().parse().ok())
+        .filter(|n| (1..=MAX_RUN_CLICKS).contains(n))
+        .ok_or_else(|| format!("bad click count in '{}'", input))?;
+    let cps = rate
+        .trim()
+        .to_ascii_lowercase()
+        .strip_suffix("cps")
+        .and_then(crate::parse_cps)
+        .filter(|cps| (MIN_CPS..=MAX_CPS).contains(cps))
+        .ok_or_else(|| format!("bad rate in '{}'", input))?;
+    Ok(Run {
+        count,
+        cps,
+        button,
+        pause_after: Duration::ZERO,
+    })
+}
+
+impl ClickPattern {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut runs: Vec<Run> = Vec::new();
+        // Waits before the first run belong to the end of the cycle
+        let mut leading_wait = Duration::ZERO;
+        let mut until = None;
+        for segment in input.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let lower = segment.to_ascii_lowercase();
+            if let Some(wait) = lower.strip_prefix("wait") {
+                let wait =
+                    parse_duration(wait).ok_or_else(|| format!("bad wait in '{}'", segment))?;
+                match runs.last_mut() {
+                    Some(run) => run.pause_after += wait,
+                    None => leading_wait += wait,
+                }
+            } else if lower.starts_with("until") {
+                // Counter names keep their case
+                until = Some(
+                    Condition::parse_comparison(&segment["until".len()..]).ok_or_else(|| {
+                        format!("expected until NAME > NUMBER, got '{}'", segment)
+                    })?,
+                );
+            } else {
+                runs.push(parse_run(segment)?);
+            }
+        }
+        let last = runs
+            .last_mut()
+            .ok_or("a pattern needs at least one click run")?;
+        last.pause_after += leading_wait;
+        Ok(Self {
+            cycle_clicks: runs.iter().map(|run| run.count).sum(),
+            runs,
+            until,
+        })
+    }
+
+    // The run of the click at this index and whether it ends the run
+    fn run_at(&self, click_index: u64) -> (&Run, bool) {
+        let mut i = click_index % self.cycle_clicks;
+        for run in &self.runs {
+            if i < run.count {
+                return (run, i + 1 == run.count);
+            }
+            i -= run.count;
+        }
+        unreachable!("click index within the cycle")
+    }
+
+    pub fn next_interval(&self, click_index: u64) -> Duration {
+        let (run, last) = self.run_at(click_index);
+        let interval = Duration::from_secs_f64(1.0 / run.cps);
+        if last {
+            interval + run.pause_after
+        } else {
+            interval
+        }
+    }
+
+    pub fn perform(&self, backend: &mut dyn ClickBackend, click_index: u64) {
+        let (run, _) = self.run_at(click_index);
+        backend.click(sequence::mouse_button(run.button));
+    }
+
+    pub fn until(&self) -> Option<&Condition> {
+        self.until.as_ref()
+    }
+
+    pub fn cycle_clicks(&self) -> u64 {
+        self.cycle_clicks
+    }
+
+    pub fn cycle_time(&self) -> Duration {
+        self.runs
+            .iter()
+            .map(|run| Duration::from_secs_f64(run.count as f64 / run.cps) + run.pause_after)
+            .sum()
+    }
+}
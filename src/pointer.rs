@@ -0,0 +1,66 @@
+use crate::sequence::Point;
+
+// enigo only exposes the cursor location on Windows and macOS, so on X11 ask
+// libxdo (which enigo already links) directly
+#[cfg(all(unix, not(target_os = "macos")))]
+mod xdo {
+    use std::os::raw::{c_char, c_int, c_void};
+
+    #[link(name = "xdo")]
+    unsafe extern "C" {
+        pub fn xdo_new(display: *const c_char) -> *mut c_void;
+        pub fn xdo_free(xdo: *mut c_void);
+        pub fn xdo_get_mouse_location(
+            xdo: *const c_void,
+            x: *mut c_int,
+            y: *mut c_int,
+            screen: *mut c_int,
+        ) -> c_int;
+    }
+}
+
+// Reads the current cursor position; holds the X connection open on Linux so
+// per-click lookups stay cheap
+pub struct Pointer {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    xdo: *mut std::os::raw::c_void,
+}
+
+impl Pointer {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn new() -> Self {
+        Self {
+            xdo: unsafe { xdo::xdo_new(std::ptr::null()) },
+        }
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn position(&self) -> Option<Point> {
+        if self.xdo.is_null() {
+            return None;
+        }
+        let (mut x, mut y, mut screen) = (0, 0, 0);
+        let status = unsafe { xdo::xdo_get_mouse_location(self.xdo, &mut x, &mut y, &mut screen) };
+        (status == 0).then_some(Point { x, y })
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    pub fn position(&self) -> Option<Point> {
+        let (x, y) = enigo::Enigo::mouse_location();
+        Some(Point { x, y })
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl Drop for Pointer {
+    fn drop(&mut self) {
+        if !self.xdo.is_null() {
+            unsafe { xdo::xdo_free(self.xdo) };
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Config, CpsChoice, MAX_CPS, MIN_CPS, humanize::HumanPattern, interval};
+use serde::{Deserialize, Serialize};
+
+// A loadout on a number key of the main screen: a profile plus the rate to
+// click it at
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QuickSlot {
+    // Number key, 1-9
+    pub key: u8,
+    // Index into profiles
+    pub profile: usize,
+    #[serde(default)]
+    pub cps: Option<f64>,
+    // Slow mode interval; takes precedence over cps
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+    // Takes precedence over both, like the pattern entries of the CPS list
+    #[serde(default)]
+    pub human_pattern: Option<HumanPattern>,
+    // Switches to anti-AFK with its configured window, ahead of everything else
+    #[serde(default)]
+    pub anti_afk: bool,
+}
+
+impl QuickSlot {
+    // The active profile and rate as they are now
+    pub fn capture(key: u8, config: &Config) -> Self {
+        let mut slot = Self {
+            key,
+            profile: config.active_profile,
+            cps: None,
+            interval_secs: None,
+            human_pattern: None,
+            anti_afk: false,
+        };
+        match config.current_choice() {
+            CpsChoice::Pattern(pattern) => slot.human_pattern = Some(pattern),
+            CpsChoice::Slow => slot.interval_secs = config.slow_interval_secs,
+            CpsChoice::Custom => slot.cps = config.custom_cps_value,
+            CpsChoice::Preset(i) => slot.cps = config.cps_presets.get(i).copied(),
+            CpsChoice::AntiAfk => slot.anti_afk = true,
+        }
+        slot
+    }
+
+    // A rate that is also a preset selects the preset, anything else becomes
+    // the custom CPS
+    pub fn apply(&self, config: &mut Config) {
+        config.active_profile = self.profile;
+        if self.anti_afk {
+            config.apply_choice(CpsChoice::AntiAfk);
+        } else if let Some(pattern) = self.human_pattern {
+            config.apply_choice(CpsChoice::Pattern(pattern));
+        } else if let Some(secs) = self.interval_secs {
+            config.slow_interval_secs = Some(secs);
+            config.apply_choice(CpsChoice::Slow);
+        } else if let Some(cps) = self.cps {
+            match config.cps_presets.iter().position(|&c| c == cps) {
+                Some(i) => config.apply_choice(CpsChoice::Preset(i)),
+                None => {
+                    config.custom_cps_value = Some(cps);
+                    config.apply_choice(CpsChoice::Custom);
+                }
+            }
+        }
+    }
+
+    // "Default 20cps", as shown on the instruction bar
+    pub fn label(&self, config: &Config) -> String {
+        let profile = &config.profiles[self.profile].name;
+        if self.anti_afk {
+            return format!("{} anti-AFK", profile);
+        }
+        match (self.human_pattern, self.interval_secs, self.cps) {
+            (Some(pattern), _, _) => format!("{} {}", profile, pattern.label()),
+            (None, Some(secs), _) => format!("{} every {}", profile, interval::describe(secs)),
+            (None, None, Some(cps)) => format!("{} {}cps", profile, cps),
+            (None, None, None) => profile.clone(),
+        }
+    }
+}
+
+// Drops slots on keys other than 1-9 or on profiles that no longer exist,
+// keeping the first of any that share a key
+pub fn sanitize(slots: &mut Vec<QuickSlot>, profile_count: usize) {
+    slots.retain(|slot| (1..=9).contains(&slot.key) && slot.profile < profile_count);
+    slots.sort_by_key(|slot| slot.key);
+    slots.dedup_by_key(|slot| slot.key);
+    for slot in slots.iter_mut() {
+        slot.cps = slot.cps.map(|cps| cps.clamp(MIN_CPS, MAX_CPS));
+        slot.interval_secs = slot
+            .interval_secs
+            .map(|secs| secs.clamp(interval::MIN_SECS, interval::MAX_SECS));
+    }
+}
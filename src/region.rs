@@ -0,0 +1,97 @@
+use crate::{backend::ClickBackend, sequence::Point};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// How often the engine reads the cursor while a region is set; short enough
+// that a click doesn't slip out past the edge
+const CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+// Screen rectangle clicking is kept to, edges included
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Region {
+    // The box spanned by two opposite corners, picked in either order
+    pub fn from_corners(a: Point, b: Point) -> Self {
+        Self {
+            left: a.x.min(b.x),
+            top: a.y.min(b.y),
+            right: a.x.max(b.x),
+            bottom: a.y.max(b.y),
+        }
+    }
+
+    // Puts swapped edges from a hand-edited config the right way round
+    pub fn normalized(self) -> Self {
+        Self::from_corners(
+            Point {
+                x: self.left,
+                y: self.top,
+            },
+            Point {
+                x: self.right,
+                y: self.bottom,
+            },
+        )
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.left..=self.right).contains(&p.x) && (self.top..=self.bottom).contains(&p.y)
+    }
+
+    // Both corners through a coordinate conversion
+    pub fn map(self, f: impl Fn(Point) -> Point) -> Self {
+        Self::from_corners(
+            f(Point {
+                x: self.left,
+                y: self.top,
+            }),
+            f(Point {
+                x: self.right,
+                y: self.bottom,
+            }),
+        )
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({},{})-({},{})",
+            self.left, self.top, self.right, self.bottom
+        )
+    }
+}
+
+// The engine's check of the cursor against the region. A cursor that can't
+// be read counts as inside, so the null backend and Wayland keep clicking
+#[derive(Default)]
+pub struct RegionCheck {
+    checked_at: Option<Instant>,
+    outside: bool,
+}
+
+impl RegionCheck {
+    pub fn outside(&mut self, region: Option<Region>, backend: &dyn ClickBackend) -> bool {
+        let Some(region) = region else {
+            self.outside = false;
+            return false;
+        };
+        if self
+            .checked_at
+            .is_none_or(|at| at.elapsed() >= CHECK_INTERVAL)
+        {
+            self.checked_at = Some(Instant::now());
+            self.outside = backend
+                .cursor_position()
+                .is_some_and(|p| !region.contains(p));
+        }
+        self.outside
+    }
+}
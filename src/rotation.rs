@@ -0,0 +1,83 @@
+use crate::{backend::ClickBackend, pattern::parse_duration, sequence};
+use enigo::Key;
+use std::time::Duration;
+
+// Enough for the longest ability bars
+const MAX_ROTATION_KEYS: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+struct RotationKey {
+    name: String,
+    key: Key,
+    // Wait after pressing this key before the next one
+    interval: Duration,
+}
+
+// A profile's keyboard rotation such as "1@1.5s, 2@800ms, 3@800ms, 4@2s",
+// pressed in turn by the Type Text mode instead of the repeat text. Each key
+// press is one engine action and sets the interval before the next
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyRotation {
+    keys: Vec<RotationKey>,
+}
+
+impl KeyRotation {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let keys = input
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|entry| {
+                let (name, interval) = entry.rsplit_once('@').ok_or_else(|| {
+                    format!("expected KEY@INTERVAL like 1@500ms, got '{}'", entry)
+                })?;
+                let name = name.trim();
+                let key =
+                    sequence::parse_key(name).ok_or_else(|| format!("unknown key '{}'", name))?;
+                let interval = parse_duration(interval)
+                    .ok_or_else(|| format!("bad interval in '{}'", entry))?;
+                Ok(RotationKey {
+                    name: name.to_string(),
+                    key,
+                    interval,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if keys.is_empty() {
+            return Err("a rotation needs at least one key".to_string());
+        }
+        if keys.len() > MAX_ROTATION_KEYS {
+            return Err(format!("at most {} keys per rotation", MAX_ROTATION_KEYS));
+        }
+        Ok(Self { keys })
+    }
+
+    fn key_at(&self, index: u64) -> &RotationKey {
+        &self.keys[(index % self.keys.len() as u64) as usize]
+    }
+
+    pub fn next_interval(&self, index: u64) -> Duration {
+        self.key_at(index).interval
+    }
+
+    pub fn perform(&self, backend: &mut dyn ClickBackend, index: u64) {
+        backend.key(self.key_at(index).key);
+    }
+
+    pub fn key_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn cycle_time(&self) -> Duration {
+        self.keys.iter().map(|key| key.interval).sum()
+    }
+
+    // "1→2→3→4" for the status bar
+    pub fn summary(&self) -> String {
+        self.keys
+            .iter()
+            .map(|key| key.name.as_str())
+            .collect::<Vec<_>>()
+            .join("→")
+    }
+}
@@ -0,0 +1,188 @@
+// Per-run history for long-term analysis, kept in SQLite when built with the
+// `sqlite` feature and run_log is on; stats.rs keeps the running totals
+#[cfg(feature = "sqlite")]
+use rusqlite::{Connection, params};
+
+pub const RUN_LOG_FILE: &str = "bclicker_runs.db";
+
+// One start-to-stop stretch of clicking
+pub struct RunRecord {
+    // Unix seconds
+    pub started_at: u64,
+    pub duration_secs: f64,
+    pub clicks: u64,
+    pub peak_cps: f64,
+    pub profile: String,
+    // Clicks at a fixed point that still looked wrong after their retries
+    pub verify_failures: u64,
+}
+
+impl RunRecord {
+    pub fn average_cps(&self) -> f64 {
+        if self.duration_secs > 0.0 {
+            self.clicks as f64 / self.duration_secs
+        } else {
+            0.0
+        }
+    }
+}
+
+// What the statistics view shows from the log
+#[derive(Clone, Debug, Default)]
+pub struct RunHistory {
+    pub runs: u64,
+    pub clicks: u64,
+    pub total_secs: f64,
+    pub best_average_cps: f64,
+    pub best_peak_cps: f64,
+    // Clicks on the most recent days with runs, oldest first, as ("MM-DD", clicks)
+    pub daily: Vec<(String, u64)>,
+    // (profile, runs, clicks), most clicks first
+    pub profiles: Vec<(String, u64, u64)>,
+}
+
+// Days and profiles listed in the history
+const HISTORY_ROWS: usize = 7;
+
+#[cfg(feature = "sqlite")]
+pub struct RunLog {
+    conn: Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl RunLog {
+    // Next to the config file, created on first use
+    pub fn open() -> Result<Self, String> {
+        let path = std::env::current_dir()
+            .unwrap_or_else(|_| std::path::PathBuf::from("."))
+            .join(RUN_LOG_FILE);
+        let conn = Connection::open(&path)
+            .map_err(|e| format!("could not open {}: {}", path.display(), e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS runs (
+                 id INTEGER PRIMARY KEY,
+                 started_at INTEGER NOT NULL,
+                 duration_secs REAL NOT NULL,
+                 clicks INTEGER NOT NULL,
+                 average_cps REAL NOT NULL,
+                 peak_cps REAL NOT NULL,
+                 profile TEXT NOT NULL,
+                 verify_failures INTEGER NOT NULL DEFAULT 0
+             );
+             CREATE INDEX IF NOT EXISTS runs_started_at ON runs (started_at);",
+        )
+        .map_err(|e| e.to_string())?;
+        // Logs from before click verification lack the column
+        if conn.prepare("SELECT verify_failures FROM runs").is_err() {
+            conn.execute_batch(
+                "ALTER TABLE runs ADD COLUMN verify_failures INTEGER NOT NULL DEFAULT 0",
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(Self { conn })
+    }
+
+    pub fn record(&self, run: &RunRecord) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT INTO runs (started_at, duration_secs, clicks, average_cps, peak_cps, profile,
+                                   verify_failures)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    run.started_at as i64,
+                    run.duration_secs,
+                    run.clicks as i64,
+                    run.average_cps(),
+                    run.peak_cps,
+                    run.profile,
+                    run.verify_failures as i64,
+                ],
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn history(&self) -> Result<RunHistory, String> {
+        let mut history = self
+            .conn
+            .query_row(
+                "SELECT COUNT(*), COALESCE(SUM(clicks), 0), COALESCE(SUM(duration_secs), 0),
+                        COALESCE(MAX(average_cps), 0), COALESCE(MAX(peak_cps), 0)
+                 FROM runs",
+                [],
+                |row| {
+                    Ok(RunHistory {
+                        runs: row.get::<_, i64>(0)? as u64,
+                        clicks: row.get::<_, i64>(1)? as u64,
+                        total_secs: row.get(2)?,
+                        best_average_cps: row.get(3)?,
+                        best_peak_cps: row.get(4)?,
+                        ..RunHistory::default()
+                    })
+                },
+            )
+            .map_err(|e| e.to_string())?;
+
+        let mut daily = self
+            .conn
+            .prepare(
+                "SELECT strftime('%m-%d', started_at, 'unixepoch', 'localtime') AS day,
+                        SUM(clicks)
+                 FROM runs
+                 GROUP BY date(started_at, 'unixepoch', 'localtime')
+                 ORDER BY date(started_at, 'unixepoch', 'localtime') DESC
+                 LIMIT ?1",
+            )
+            .map_err(|e| e.to_string())?;
+        history.daily = daily
+            .query_map([HISTORY_ROWS as i64], |row| {
+                Ok((row.get(0)?, row.get::<_, i64>(1)? as u64))
+            })
+            .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+            .map_err(|e| e.to_string())?;
+        history.daily.reverse();
+
+        let mut profiles = self
+            .conn
+            .prepare(
+                "SELECT profile, COUNT(*), SUM(clicks)
+                 FROM runs
+                 GROUP BY profile
+                 ORDER BY SUM(clicks) DESC
+                 LIMIT ?1",
+            )
+            .map_err(|e| e.to_string())?;
+        history.profiles = profiles
+            .query_map([HISTORY_ROWS as i64], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get::<_, i64>(1)? as u64,
+                    row.get::<_, i64>(2)? as u64,
+                ))
+            })
+            .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+            .map_err(|e| e.to_string())?;
+
+        Ok(history)
+    }
+}
+
+// Without the feature every call reports why, so the setting degrades to a
+// startup warning and a note on the statistics view
+#[cfg(not(feature = "sqlite"))]
+pub struct RunLog;
+
+#[cfg(not(feature = "sqlite"))]
+impl RunLog {
+    pub fn open() -> Result<Self, String> {
+        Err("built without the sqlite feature".to_string())
+    }
+
+    pub fn record(&self, _run: &RunRecord) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn history(&self) -> Result<RunHistory, String> {
+        Ok(RunHistory::default())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{calibration::Calibration, clock::Clock, duty_cycle::DutyPhase};
+use std::{
+    collections::VecDeque,
+    thread,
+    time::{Duration, Instant},
+};
+
+// How far behind schedule the engine may fall before it gives up catching up
+// and resyncs, so a long stall doesn't turn into a burst of rapid clicks
+const MAX_CATCH_UP: Duration = Duration::from_millis(100);
+
+// Longest wait poll makes in one go, so the engine still notices being stopped
+// during the multi-second intervals of sub-1 CPS rates
+const MAX_POLL_WAIT: Duration = Duration::from_millis(50);
+
+// Sleep granularity is 1-15 ms depending on the platform, so sleep through the
+// bulk of the wait and spin for the final stretch (the calibrated spin
+// threshold) to actually hit 500+ CPS. A virtual clock just moves on
+fn precise_sleep(clock: &Clock, duration: Duration, spin_threshold: Duration) {
+    if clock.is_virtual() {
+        clock.sleep(duration);
+        return;
+    }
+    let deadline = Instant::now() + duration;
+    if duration > spin_threshold {
+        thread::sleep(duration - spin_threshold);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+// Absolute-deadline scheduling: each click is due one interval after the
+// previous deadline rather than after the previous click, so a slow click is
+// made up on the next one instead of permanently lowering the rate
+pub struct ClickScheduler {
+    next_deadline: Instant,
+    spin_threshold: Duration,
+    // Clicks are released this much before their deadline to cover the
+    // backend's injection latency
+    lead: Duration,
+    clock: Clock,
+}
+
+impl Default for ClickScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClickScheduler {
+    pub fn new() -> Self {
+        Self::calibrated(Calibration::default())
+    }
+
+    pub fn calibrated(calibration: Calibration) -> Self {
+        Self::with_clock(calibration, Clock::Real)
+    }
+
+    pub fn with_clock(calibration: Calibration, clock: Clock) -> Self {
+        Self {
+            next_deadline: clock.now(),
+            spin_threshold: calibration.spin_threshold(),
+            lead: calibration.lead(),
+            clock,
+        }
+    }
+
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.spin_threshold = calibration.spin_threshold();
+        self.lead = calibration.lead();
+    }
+
+    pub fn reset(&mut self) {
+        self.next_deadline = self.clock.now();
+    }
+
+    // Pushes the next deadline later, e.g. by the length of a pause
+    pub fn postpone(&mut self, by: Duration) {
+        self.next_deadline += by;
+    }
+
+    pub fn next_deadline(&self) -> Instant {
+        self.next_deadline
+    }
+
+    // When the next click is released, its deadline minus the lead
+    pub fn release_time(&self) -> Instant {
+        self.next_deadline
+            .checked_sub(self.lead)
+            .unwrap_or(self.next_deadline)
+    }
+
+    // Whether the next click is already due, i.e. the engine is catching up
+    pub fn is_due(&self) -> bool {
+        self.clock.now() >= self.release_time()
+    }
+
+    // Returns true when a click is due, otherwise sleeps toward the deadline
+    pub fn poll(&mut self, interval: Duration) -> bool {
+        self.poll_until(interval, None)
+    }
+
+    // Like poll, but stops sleeping at wake_by so another channel's deadline
+    // isn't overslept
+    pub fn poll_until(&mut self, interval: Duration, wake_by: Option<Instant>) -> bool {
+        let now = self.clock.now();
+        let release = self.release_time();
+        if now >= release {
+            if now - release > MAX_CATCH_UP {
+                self.next_deadline = now + self.lead;
+            }
+            self.next_deadline += interval;
+            true
+        } else {
+            let wake = wake_by.map_or(release, |wake_by| wake_by.min(release));
+            let remaining = wake.saturating_duration_since(now);
+            if remaining > MAX_POLL_WAIT {
+                self.clock.sleep(MAX_POLL_WAIT);
+            } else {
+                precise_sleep(&self.clock, remaining, self.spin_threshold);
+            }
+            false
+        }
+    }
+}
+
+// Falling more than 10% short of the target flags the rate as unachievable
+const UNACHIEVABLE_RATIO: f64 = 0.9;
+
+// Engine-reported rate, refreshed once per measurement window
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RateStatus {
+    pub achieved_cps: f64,
+    pub unachievable: bool,
+    // When the next click is due, kept only for intervals of a second or more
+    // so the status bar can count down to it
+    pub next_click: Option<Instant>,
+    // Duty cycle phase and when it ends, while the profile has one
+    pub duty: Option<(DutyPhase, Instant)>,
+    // Interval error of the current or last run; kept after clicking stops
+    pub timing: Option<TimingAccuracy>,
+    // Clicks done and total of the queued job being run
+    pub job: Option<(u64, u64)>,
+    // A blocked window has focus, so nothing is clicking
+    pub blocked: bool,
+    // The cursor is outside the click region, so clicking waits for it
+    pub outside_region: bool,
+    // Rate the focused window is capped at, while it's below the target
+    pub cps_cap: Option<f64>,
+    // The last run was stopped because its input wasn't arriving; kept
+    // until clicking starts again
+    pub injection_blocked: bool,
+}
+
+impl RateStatus {
+    pub fn measured(achieved_cps: f64, target_cps: f64) -> Self {
+        Self {
+            achieved_cps,
+            unachievable: achieved_cps < target_cps * UNACHIEVABLE_RATIO,
+            next_click: None,
+            duty: None,
+            timing: None,
+            job: None,
+            blocked: false,
+            outside_region: false,
+            cps_cap: None,
+            injection_blocked: false,
+        }
+    }
+}
+
+// Achieved rate measured over windows of at least a second, long enough for
+// a few clicks at slow rates
+pub struct RateMeter {
+    window_start: Instant,
+    window_clicks: u64,
+    clock: Clock,
+}
+
+impl Default for RateMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateMeter {
+    pub fn new() -> Self {
+        Self::with_clock(Clock::Real)
+    }
+
+    pub fn with_clock(clock: Clock) -> Self {
+        Self {
+            window_start: clock.now(),
+            window_clicks: 0,
+            clock,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.window_start = self.clock.now();
+        self.window_clicks = 0;
+    }
+
+    pub fn record_click(&mut self) {
+        self.window_clicks += 1;
+    }
+
+    // Returns the achieved CPS each time a window completes
+    pub fn sample(&mut self, target_cps: f64) -> Option<f64> {
+        let window = self.clock.since(self.window_start);
+        if window.as_secs_f64() < (4.0 / target_cps).max(1.0) {
+            return None;
+        }
+        let achieved = self.window_clicks as f64 / window.as_secs_f64();
+        self.reset();
+        Some(achieved)
+    }
+}
+
+// Intervals kept for the accuracy report, about 100s of clicking at 50 CPS
+const MAX_INTERVAL_SAMPLES: usize = 5000;
+
+// How far actual click intervals strayed from their targets
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TimingAccuracy {
+    pub samples: usize,
+    pub target_ms: f64,
+    pub mean_error_ms: f64,
+    pub p95_error_ms: f64,
+    pub max_error_ms: f64,
+}
+
+impl TimingAccuracy {
+    // A p95 error above a quarter of the interval means clicks visibly bunch
+    // up and spread out instead of landing on the chosen rate
+    pub fn steady(&self) -> bool {
+        self.p95_error_ms <= self.target_ms * 0.25
+    }
+}
+
+// Compares each click's spacing from the previous one with the spacing of
+// their deadlines; runs and duty cycle phases start a fresh pair
+pub struct IntervalTracker {
+    last: Option<(Instant, Instant)>,
+    // (target, absolute error) in milliseconds, oldest first
+    samples: VecDeque<(f64, f64)>,
+}
+
+impl Default for IntervalTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntervalTracker {
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            samples: VecDeque::new(),
+        }
+    }
+
+    // Forgets the previous click so a pause isn't counted as an error
+    pub fn break_sequence(&mut self) {
+        self.last = None;
+    }
+
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.samples.clear();
+    }
+
+    pub fn record(&mut self, deadline: Instant, fired: Instant) {
+        if let Some((last_deadline, last_fired)) = self.last {
+            let target = deadline.saturating_duration_since(last_deadline);
+            let actual = fired.saturating_duration_since(last_fired);
+            let error = actual.abs_diff(target);
+            if self.samples.len() == MAX_INTERVAL_SAMPLES {
+                self.samples.pop_front();
+            }
+            self.samples
+                .push_back((target.as_secs_f64() * 1000.0, error.as_secs_f64() * 1000.0));
+        }
+        self.last = Some((deadline, fired));
+    }
+
+    pub fn summary(&self) -> Option<TimingAccuracy> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let count = self.samples.len();
+        let mut errors: Vec<f64> = self.samples.iter().map(|&(_, error)| error).collect();
+        errors.sort_by(f64::total_cmp);
+        let p95_index = ((count as f64 * 0.95).ceil() as usize).clamp(1, count) - 1;
+        Some(TimingAccuracy {
+            samples: count,
+            target_ms: self.samples.iter().map(|&(target, _)| target).sum::<f64>() / count as f64,
+            mean_error_ms: errors.iter().sum::<f64>() / count as f64,
+            p95_error_ms: errors[p95_index],
+            max_error_ms: errors[count - 1],
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{MAX_SAFE_CPS, MIN_CPS, sequence::mouse_button};
+use enigo::MouseButton;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// A second mouse button clicking alongside the main action at its own rate,
+// e.g. right at 5 CPS while the main engine left-clicks at 20. Written as
+// "right@5" in the TUI
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SecondChannel {
+    // 0 = left, 1 = right, like sequence steps
+    pub button: usize,
+    pub cps: f64,
+}
+
+impl SecondChannel {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (button, rate) = input
+            .split_once('@')
+            .ok_or_else(|| format!("expected BUTTON@CPS like right@5, got '{}'", input))?;
+        let button = match button.trim().to_ascii_lowercase().as_str() {
+            "left" => 0,
+            "right" => 1,
+            other => return Err(format!("unknown button '{}'", other)),
+        };
+        let rate = rate.trim().to_ascii_lowercase();
+        let cps = crate::parse_cps(rate.trim_end_matches("cps"))
+            .filter(|&cps| cps <= MAX_SAFE_CPS)
+            .ok_or_else(|| format!("rate must be between {} and {} CPS", MIN_CPS, MAX_SAFE_CPS))?;
+        Ok(Self { button, cps })
+    }
+
+    // Out-of-range values from a hand-edited file turn the channel off
+    pub fn is_valid(&self) -> bool {
+        self.button <= 1 && (MIN_CPS..=MAX_SAFE_CPS).contains(&self.cps)
+    }
+
+    pub fn mouse_button(&self) -> MouseButton {
+        mouse_button(self.button)
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.cps)
+    }
+
+    // Key of the per-mode statistics, matching the main button modes
+    pub fn action_label(&self) -> &'static str {
+        if self.button == 1 {
+            "Right Click"
+        } else {
+            "Left Click"
+        }
+    }
+}
+
+impl std::fmt::Display for SecondChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let button = if self.button == 1 { "right" } else { "left" };
+        write!(f, "{}@{}", button, self.cps)
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{
+    backend::ClickBackend,
+    condition::{Condition, Gate, Probe},
+    variables::Variables,
+};
+use enigo::{Key, MouseButton};
+use serde::{Deserialize, Serialize};
+use std::{thread, time::Duration};
+
+// Cursor updates during a drag are spaced this far apart
+const DRAG_STEP: Duration = Duration::from_millis(8);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StepKind {
+    #[default]
+    Click,
+    Key,
+    // Types the key field as text, with {name} replaced by variables
+    Type,
+    Move,
+    Drag,
+    Wait,
+}
+
+impl StepKind {
+    pub fn next(self) -> Self {
+        match self {
+            StepKind::Click => StepKind::Key,
+            StepKind::Key => StepKind::Type,
+            StepKind::Type => StepKind::Move,
+            StepKind::Move => StepKind::Drag,
+            StepKind::Drag => StepKind::Wait,
+            StepKind::Wait => StepKind::Click,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StepKind::Click => "Click",
+            StepKind::Key => "Key",
+            StepKind::Type => "Type",
+            StepKind::Move => "Move",
+            StepKind::Drag => "Drag",
+            StepKind::Wait => "Wait",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Point {
+    // Parses "x,y"; used by the editor's position field
+    pub fn parse(input: &str) -> Option<Self> {
+        let (x, y) = input.split_once(',')?;
+        Some(Self {
+            x: x.trim().parse().ok()?,
+            y: y.trim().parse().ok()?,
+        })
+    }
+}
+
+pub fn mouse_button(index: usize) -> MouseButton {
+    if index == 1 {
+        MouseButton::Right
+    } else {
+        MouseButton::Left
+    }
+}
+
+// Button down at one point, interpolated move, button up at another
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DragAction {
+    // Without a start point the drag begins at the cursor and `to` is an offset
+    pub from: Option<Point>,
+    pub to: Point,
+    pub duration_ms: u64,
+    pub button: usize,
+}
+
+impl std::fmt::Display for DragAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.from {
+            Some(from) => write!(f, "Drag {} → {}", from, self.to)?,
+            None => write!(f, "Drag cursor +{}", self.to)?,
+        }
+        write!(f, " over {} ms", self.duration_ms)
+    }
+}
+
+impl DragAction {
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    pub fn perform(&self, backend: &mut dyn ClickBackend) {
+        let button = mouse_button(self.button);
+        let steps = (self.duration().as_millis() / DRAG_STEP.as_millis()).max(1) as i32;
+        let pause = self.duration() / steps as u32;
+
+        if let Some(from) = self.from {
+            backend.move_to(from.x, from.y);
+        }
+        backend.button_down(button);
+        backend.flush();
+
+        // Relative drags track what has been moved so far so rounding errors
+        // don't accumulate across steps
+        let (mut moved_x, mut moved_y) = (0, 0);
+        for i in 1..=steps {
+            thread::sleep(pause);
+            match self.from {
+                Some(from) => backend.move_to(
+                    from.x + (self.to.x - from.x) * i / steps,
+                    from.y + (self.to.y - from.y) * i / steps,
+                ),
+                None => {
+                    let (x, y) = (self.to.x * i / steps, self.to.y * i / steps);
+                    backend.move_relative(x - moved_x, y - moved_y);
+                    (moved_x, moved_y) = (x, y);
+                }
+            }
+            backend.flush();
+        }
+
+        backend.button_up(button);
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct SequenceStep {
+    pub kind: StepKind,
+    // Clicks and moves without a position act at the current cursor location
+    pub position: Option<Point>,
+    pub button: usize,
+    // Key name, or the text of a type step
+    pub key: String,
+    // Drag destination (an offset when there is no position) and move time
+    pub target: Option<Point>,
+    pub duration_ms: u64,
+    // Delay after this step before the next one runs
+    pub delay_ms: u64,
+    // The step only runs when `when` holds and `unless` doesn't; a skipped
+    // step still takes its delay
+    pub when: Option<Condition>,
+    pub unless: Option<Condition>,
+    // Stops clicking instead of running the step once this holds
+    pub stop_when: Option<Condition>,
+    // Counter incremented each time the step runs, kept with the statistics
+    pub count: Option<String>,
+}
+
+impl Default for SequenceStep {
+    fn default() -> Self {
+        Self {
+            kind: StepKind::Click,
+            position: None,
+            button: 0,
+            key: String::new(),
+            target: None,
+            duration_ms: 200,
+            delay_ms: 100,
+            when: None,
+            unless: None,
+            stop_when: None,
+            count: None,
+        }
+    }
+}
+
+impl SequenceStep {
+    // Time slot of the step; drags occupy their move time plus the delay
+    pub fn delay(&self) -> Duration {
+        // Zero-delay steps would spin the engine; floor them at 1 ms
+        let delay = Duration::from_millis(self.delay_ms.max(1));
+        match self.kind {
+            StepKind::Drag => delay + self.drag().duration(),
+            _ => delay,
+        }
+    }
+
+    pub fn drag(&self) -> DragAction {
+        DragAction {
+            from: self.position,
+            to: self.target.unwrap_or(Point { x: 0, y: 0 }),
+            duration_ms: self.duration_ms,
+            button: self.button,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let at = self
+            .position
+            .map(|p| format!(" @ {}", p))
+            .unwrap_or_else(|| " @ cursor".to_string());
+        let action = match self.kind {
+            StepKind::Click => format!(
+                "Click {}{}",
+                if self.button == 1 { "Right" } else { "Left" },
+                at
+            ),
+            StepKind::Key => format!("Key {}", if self.key.is_empty() { "?" } else { &self.key }),
+            StepKind::Type => format!("Type \"{}\"", self.key),
+            StepKind::Move => format!("Move{}", at),
+            StepKind::Drag => self.drag().to_string(),
+            StepKind::Wait => "Wait".to_string(),
+        };
+        let mut text = format!("{} → {} ms", action, self.delay_ms);
+        for (word, condition) in [
+            ("when", &self.when),
+            ("unless", &self.unless),
+            ("stop when", &self.stop_when),
+        ] {
+            if let Some(condition) = condition {
+                text.push_str(&format!(" │ {} {}", word, condition));
+            }
+        }
+        if let Some(name) = &self.count {
+            text.push_str(&format!(" │ {} += 1", name));
+        }
+        text
+    }
+
+    pub fn conditions(&self) -> impl Iterator<Item = &Condition> {
+        [&self.when, &self.unless, &self.stop_when]
+            .into_iter()
+            .flatten()
+    }
+
+    // A met stop condition wins over the step's own conditions
+    pub fn gate(&self, probe: &mut Probe, vars: &Variables) -> Gate {
+        if let Some(stop) = &self.stop_when {
+            if probe.check(stop, vars) {
+                return Gate::Stop;
+            }
+        }
+        let allowed = self.when.as_ref().is_none_or(|c| probe.check(c, vars))
+            && !self.unless.as_ref().is_some_and(|c| probe.check(c, vars));
+        if allowed { Gate::Run } else { Gate::Skip }
+    }
+
+    // Runs the step and returns true when it pressed a mouse button
+    pub fn execute(&self, backend: &mut dyn ClickBackend, vars: &Variables) -> bool {
+        match self.kind {
+            StepKind::Click => {
+                if let Some(p) = self.position {
+                    backend.move_to(p.x, p.y);
+                }
+                backend.click(mouse_button(self.button));
+                true
+            }
+            StepKind::Drag => {
+                self.drag().perform(backend);
+                true
+            }
+            StepKind::Key => {
+                if let Some(key) = parse_key(&self.key) {
+                    backend.key(key);
+                }
+                false
+            }
+            StepKind::Type => {
+                backend.type_text(&vars.expand(&self.key));
+                false
+            }
+            StepKind::Move => {
+                if let Some(p) = self.position {
+                    backend.move_to(p.x, p.y);
+                }
+                false
+            }
+            StepKind::Wait => false,
+        }
+    }
+}
+
+// Named keys first, then any single character typed as-is
+pub fn parse_key(name: &str) -> Option<Key> {
+    let key = match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => Key::Return,
+        "tab" => Key::Tab,
+        "space" => Key::Space,
+        "esc" | "escape" => Key::Escape,
+        "backspace" => Key::Backspace,
+        "up" => Key::UpArrow,
+        "down" => Key::DownArrow,
+        "left" => Key::LeftArrow,
+        "right" => Key::RightArrow,
+        "shift" => Key::Shift,
+        "ctrl" | "control" => Key::Control,
+        "alt" => Key::Alt,
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Key::Layout(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(key)
+}
+
+pub fn cycle_time(steps: &[SequenceStep]) -> Duration {
+    steps.iter().map(SequenceStep::delay).sum()
+}
@@ -0,0 +1,330 @@
+use std::path::PathBuf;
+
+// `bclicker service [install | uninstall]`: a systemd user unit on Linux, a
+// start at login on Windows
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    match args {
+        [command] if command == "install" => install(),
+        [command] if command == "uninstall" => uninstall(),
+        _ => Err("usage: bclicker service [install | uninstall]".to_string()),
+    }
+}
+
+#[cfg(windows)]
+use login::{install, uninstall};
+#[cfg(target_os = "linux")]
+pub use systemd::notify;
+#[cfg(target_os = "linux")]
+use systemd::{install, uninstall};
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn install() -> Result<(), String> {
+    Err("services are only set up on Linux, with systemd, and on Windows".to_string())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn uninstall() -> Result<(), String> {
+    install()
+}
+
+// Only systemd listens for these
+#[cfg(not(target_os = "linux"))]
+pub fn notify(_state: &str) {}
+
+// The daemon runs where the config is, and finds it there
+fn working_dir() -> Result<PathBuf, String> {
+    std::env::current_dir().map_err(|e| format!("no working directory: {}", e))
+}
+
+#[cfg(target_os = "linux")]
+mod systemd {
+    use std::{
+        fs,
+        os::{
+            linux::net::SocketAddrExt,
+            unix::{ffi::OsStrExt, net::SocketAddr, net::UnixDatagram},
+        },
+        path::{Path, PathBuf},
+        process::Command,
+    };
+
+    const UNIT: &str = "bclicker.service";
+
+    fn unit_path() -> Result<PathBuf, String> {
+        let config = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                PathBuf::from(std::env::var_os("HOME").ok_or("HOME isn't set")?).join(".config")
+            }
+        };
+        Ok(config.join("systemd").join("user").join(UNIT))
+    }
+
+    // Paths in a unit are taken literally once quoted, apart from specifiers
+    fn quote(path: &Path) -> String {
+        let path = path
+            .display()
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%");
+        format!("\"{}\"", path)
+    }
+
+    // Clicking needs the display, which the user manager only has once the
+    // graphical session has started
+    fn unit(exe: &Path, dir: &Path) -> String {
+        format!(
+            "[Unit]\n\
+             Description=BClicker auto-clicker\n\
+             Documentation=https://github.com/linux-brat/BClicker\n\
+             PartOf=graphical-session.target\n\
+             After=graphical-session.target\n\
+             \n\
+             [Service]\n\
+             Type=notify\n\
+             ExecStart={} daemon --systemd\n\
+             WorkingDirectory={}\n\
+             Restart=on-failure\n\
+             RestartSec=5\n\
+             \n\
+             [Install]\n\
+             WantedBy=graphical-session.target\n",
+            quote(exe),
+            dir.display().to_string().replace('%', "%%")
+        )
+    }
+
+    fn systemctl(args: &[&str]) -> Result<(), String> {
+        let status = Command::new("systemctl")
+            .arg("--user")
+            .args(args)
+            .status()
+            .map_err(|e| format!("could not run systemctl: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "systemctl --user {} failed ({})",
+                args.join(" "),
+                status
+            ))
+        }
+    }
+
+    // Writes a user unit for this binary and this directory's config
+    pub fn install() -> Result<(), String> {
+        let path = unit_path()?;
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let dir = super::working_dir()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, unit(&exe, &dir))
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        println!("[INFO] Wrote {}", path.display());
+        systemctl(&["daemon-reload"])?;
+        println!(
+            "[INFO] Start it now and at every login with `systemctl --user enable --now bclicker`"
+        );
+        Ok(())
+    }
+
+    pub fn uninstall() -> Result<(), String> {
+        let path = unit_path()?;
+        if !path.exists() {
+            return Err(format!("no unit at {}", path.display()));
+        }
+        // Not enabled or not running is fine; the unit goes either way
+        let _ = systemctl(&["disable", "--now", UNIT]);
+        fs::remove_file(&path)
+            .map_err(|e| format!("could not remove {}: {}", path.display(), e))?;
+        systemctl(&["daemon-reload"])?;
+        println!("[INFO] Removed {}", path.display());
+        Ok(())
+    }
+
+    // Tells systemd how the daemon is doing, e.g. READY=1, when it was
+    // started as a Type=notify service; does nothing otherwise
+    pub fn notify(state: &str) {
+        let Some(socket_path) = std::env::var_os("NOTIFY_SOCKET") else {
+            return;
+        };
+        let sent = UnixDatagram::unbound().and_then(|socket| {
+            match socket_path.as_bytes().strip_prefix(b"@") {
+                Some(name) => {
+                    socket.send_to_addr(state.as_bytes(), &SocketAddr::from_abstract_name(name)?)
+                }
+                None => socket.send_to(state.as_bytes(), &socket_path),
+            }
+        });
+        if let Err(e) = sent {
+            println!("[WARNING] Could not notify systemd: {}", e);
+        }
+    }
+}
+
+// `bclickerw` under the current user's Run key: Windows starts it at every
+// login, and being a GUI program it opens no console window
+#[cfg(windows)]
+mod login {
+    use std::{path::PathBuf, process::Command};
+
+    const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+    const VALUE: &str = "BClicker";
+
+    fn reg(args: &[&str]) -> Result<(), String> {
+        let output = Command::new("reg")
+            .args(args)
+            .output()
+            .map_err(|e| format!("could not run reg: {}", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "reg {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    fn background_exe() -> Result<PathBuf, String> {
+        let exe = std::env::current_exe()
+            .map_err(|e| e.to_string())?
+            .with_file_name("bclickerw.exe");
+        if exe.exists() {
+            Ok(exe)
+        } else {
+            Err(format!(
+                "{} is missing; it's built alongside bclicker.exe",
+                exe.display()
+            ))
+        }
+    }
+
+    // Starts it straight away too, unless BClicker is already running
+    pub fn install() -> Result<(), String> {
+        let exe = background_exe()?;
+        // A trailing backslash would escape the closing quote
+        let dir = super::working_dir()?.display().to_string();
+        let dir = if dir.ends_with('\\') {
+            format!("{}.", dir)
+        } else {
+            dir
+        };
+        let command = format!("\"{}\" --dir \"{}\"", exe.display(), dir);
+        reg(&[
+            "add", RUN_KEY, "/v", VALUE, "/t", "REG_SZ", "/d", &command, "/f",
+        ])?;
+        println!(
+            "[INFO] BClicker now starts in the background at login, with the config in {}",
+            dir
+        );
+        if !crate::ipc::instance_running() {
+            Command::new(&exe)
+                .args(["--dir", &dir])
+                .spawn()
+                .map_err(|e| format!("could not start {}: {}", exe.display(), e))?;
+            println!("[INFO] Started; the tray icon's Show, or `bclicker attach`, opens the TUI");
+        }
+        Ok(())
+    }
+
+    pub fn uninstall() -> Result<(), String> {
+        reg(&["delete", RUN_KEY, "/v", VALUE, "/f"])?;
+        println!("[INFO] BClicker no longer starts at login; `bclicker quit` closes it");
+        Ok(())
+    }
+}
+
+// Under systemd, stdout and stderr go to the journal. While this is kept,
+// each line is sent on with the priority its tag stands for, `<3>` for
+// [ERROR] and `<4>` for [WARNING], so `journalctl -p warning` finds them;
+// the journal adds the time itself
+#[cfg(unix)]
+pub struct JournalLog {
+    journal: std::os::fd::RawFd,
+    relay: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(unix)]
+unsafe extern "C" {
+    fn pipe(fds: *mut i32) -> i32;
+    fn dup(fd: i32) -> i32;
+    fn dup2(from: i32, to: i32) -> i32;
+}
+
+#[cfg(unix)]
+fn priority(line: &[u8]) -> u8 {
+    if line.starts_with(b"[ERROR]") || line.starts_with(b"thread '") {
+        3
+    } else if line.starts_with(b"[WARNING]") || line.starts_with(b"Warning:") {
+        4
+    } else {
+        6
+    }
+}
+
+#[cfg(unix)]
+impl JournalLog {
+    pub fn start() -> Option<Self> {
+        use std::{
+            fs::File,
+            io::{BufRead, BufReader, Write},
+            os::fd::FromRawFd,
+        };
+        let mut fds = [0; 2];
+        if unsafe { pipe(fds.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        let journal = unsafe { dup(1) };
+        if journal < 0 {
+            return None;
+        }
+        unsafe {
+            dup2(fds[1], 1);
+            dup2(fds[1], 2);
+            drop(File::from_raw_fd(fds[1]));
+        }
+        let mut lines = BufReader::new(unsafe { File::from_raw_fd(fds[0]) });
+        let mut out = unsafe { File::from_raw_fd(dup(journal)) };
+        let relay = std::thread::spawn(move || {
+            let mut line = Vec::new();
+            while lines
+                .read_until(b'\n', &mut line)
+                .is_ok_and(|read| read > 0)
+            {
+                let prefix = format!("<{}>", priority(&line));
+                if out
+                    .write_all(prefix.as_bytes())
+                    .and_then(|_| out.write_all(&line))
+                    .is_err()
+                {
+                    break;
+                }
+                line.clear();
+            }
+        });
+        Some(Self {
+            journal,
+            relay: Some(relay),
+        })
+    }
+}
+
+// Points stdout and stderr straight at the journal again, which ends the
+// relay once it has sent everything written so far
+#[cfg(unix)]
+impl Drop for JournalLog {
+    fn drop(&mut self) {
+        unsafe {
+            dup2(self.journal, 1);
+            dup2(self.journal, 2);
+        }
+        if let Some(relay) = self.relay.take() {
+            let _ = relay.join();
+        }
+    }
+}
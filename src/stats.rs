@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+// Kept apart from the config so click counts don't rewrite the settings file
+// on every save, and dotfile diffs only show real setting changes
+pub const STATS_FILE: &str = "bclicker_stats.json";
+
+// How often the TUI writes changed statistics, besides on exit and reset
+pub const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+// Minutes of clicking kept for the histogram on the statistics view
+const MAX_MINUTE_BUCKETS: usize = 60;
+
+// How long the engine holds on to counted actions before adding them to the
+// shared statistics
+const TALLY_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Statistics {
+    pub total_clicks: u64,
+    pub session_clicks: u64,
+    pub total_sessions: u64,
+    pub last_session_start: u64,
+    pub session_duration: u64,
+    pub peak_cps: f64,
+    pub longest_run_secs: u64,
+    // Clicks made in each recent minute of clicking, oldest first; idle
+    // minutes are skipped
+    pub clicks_per_minute: Vec<u64>,
+    // Actions performed per mode, including scrolls and typing
+    pub button_counts: BTreeMap<String, u64>,
+    // Counters bumped by sequence steps, readable by conditions and patterns
+    pub counters: BTreeMap<String, i64>,
+    // Click verification: extra clicks made, and targets given up on
+    pub verify_retries: u64,
+    pub verify_failures: u64,
+}
+
+impl Statistics {
+    fn add_clicks(&mut self, clicks: u64) {
+        if clicks == 0 {
+            return;
+        }
+        self.total_clicks += clicks;
+        self.session_clicks += clicks;
+        match self.clicks_per_minute.last_mut() {
+            Some(minute) => *minute += clicks,
+            None => self.clicks_per_minute.push(clicks),
+        }
+    }
+
+    pub fn start_minute(&mut self) {
+        self.clicks_per_minute.push(0);
+        if self.clicks_per_minute.len() > MAX_MINUTE_BUCKETS {
+            self.clicks_per_minute.remove(0);
+        }
+    }
+
+    pub fn record_rate(&mut self, achieved_cps: f64, run_secs: u64) {
+        self.peak_cps = self.peak_cps.max(achieved_cps);
+        self.longest_run_secs = self.longest_run_secs.max(run_secs);
+    }
+}
+
+// Actions the engine counted but hasn't added to the Statistics yet, so the
+// click loop takes the statistics lock a few times a second rather than on
+// every click
+#[derive(Default)]
+pub struct ClickTally {
+    // Actions per mode label; only a handful of labels exist
+    actions: Vec<(&'static str, u64)>,
+    clicks: u64,
+    counters: Vec<(String, i64)>,
+    verify_retries: u64,
+    verify_failures: u64,
+    // When the oldest pending action was counted
+    pending_since: Option<Instant>,
+}
+
+impl ClickTally {
+    pub fn record_action(&mut self, label: &'static str, clicked: bool) {
+        match self.actions.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, count)) => *count += 1,
+            None => self.actions.push((label, 1)),
+        }
+        self.clicks += clicked as u64;
+        self.pending_since.get_or_insert_with(Instant::now);
+    }
+
+    pub fn bump_counter(&mut self, name: &str) {
+        match self.counters.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => self.counters.push((name.to_string(), 1)),
+        }
+        self.pending_since.get_or_insert_with(Instant::now);
+    }
+
+    pub fn record_verify(&mut self, retries: u64, passed: bool) {
+        self.verify_retries += retries;
+        self.verify_failures += !passed as u64;
+        self.pending_since.get_or_insert_with(Instant::now);
+    }
+
+    // Whether actions have been waiting long enough to be flushed
+    pub fn is_due(&self) -> bool {
+        self.pending_since
+            .is_some_and(|since| since.elapsed() >= TALLY_FLUSH_INTERVAL)
+    }
+
+    pub fn flush(&mut self, stats: &mut Statistics) {
+        for (label, count) in self.actions.drain(..) {
+            *stats.button_counts.entry(label.to_string()).or_default() += count;
+        }
+        stats.add_clicks(std::mem::take(&mut self.clicks));
+        for (name, count) in self.counters.drain(..) {
+            *stats.counters.entry(name).or_default() += count;
+        }
+        stats.verify_retries += std::mem::take(&mut self.verify_retries);
+        stats.verify_failures += std::mem::take(&mut self.verify_failures);
+        self.pending_since = None;
+    }
+}
+
+// Next to the config file, in the current directory
+fn stats_path() -> PathBuf {
+    std::env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(STATS_FILE)
+}
+
+pub fn load() -> Statistics {
+    let path = stats_path();
+    match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            println!(
+                "[WARNING] Invalid statistics file {} ({}), starting fresh",
+                path.display(),
+                e
+            );
+            Statistics::default()
+        }),
+        Err(_) => Statistics::default(),
+    }
+}
+
+pub fn save(stats: &Statistics) {
+    let path = stats_path();
+    match serde_json::to_string_pretty(stats) {
+        Ok(contents) => {
+            if let Err(e) = fs::write(&path, contents) {
+                eprintln!("Warning: Could not save statistics: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Warning: Could not serialize statistics: {}", e),
+    }
+}
+
+// Moves the [statistics] table of configs written before the stats file
+// existed; an existing stats file wins, so this only ever runs once
+pub fn migrate(legacy: Statistics) {
+    if stats_path().exists() {
+        return;
+    }
+    save(&legacy);
+    println!(
+        "[INFO] Moved statistics from the config file to {}",
+        STATS_FILE
+    );
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use tui::style::Color as TuiColor;
+
+// Palette picked in the config; cycled from the TUI
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Professional,
+    HighContrast,
+    // Blue/orange instead of green/red, safe for red-green color blindness
+    Deuteranopia,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 3] = [
+        ThemeName::Professional,
+        ThemeName::HighContrast,
+        ThemeName::Deuteranopia,
+    ];
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl std::fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ThemeName::Professional => "Professional",
+            ThemeName::HighContrast => "High contrast",
+            ThemeName::Deuteranopia => "Deuteranopia",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub primary: TuiColor,
+    pub secondary: TuiColor,
+    pub accent: TuiColor,
+    pub text: TuiColor,
+    pub success: TuiColor,
+    pub warning: TuiColor,
+    pub error: TuiColor,
+    // Running/stopped marks; the accessible themes use shapes so the state
+    // reads without telling colors apart
+    pub active_mark: &'static str,
+    pub idle_mark: &'static str,
+}
+
+impl Theme {
+    pub fn named(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Professional => Self::professional(),
+            ThemeName::HighContrast => Self::high_contrast(),
+            ThemeName::Deuteranopia => Self::deuteranopia(),
+        }
+    }
+
+    pub fn professional() -> Self {
+        Self {
+            primary: TuiColor::Rgb(70, 130, 180),
+            secondary: TuiColor::Rgb(105, 105, 105),
+            accent: TuiColor::Rgb(255, 165, 0),
+            text: TuiColor::Rgb(220, 220, 220),
+            success: TuiColor::Rgb(34, 139, 34),
+            warning: TuiColor::Rgb(255, 140, 0),
+            error: TuiColor::Rgb(220, 20, 60),
+            active_mark: "🟢",
+            idle_mark: "🔴",
+        }
+    }
+
+    // Named colors, so the terminal's own high-contrast scheme still applies
+    pub fn high_contrast() -> Self {
+        Self {
+            primary: TuiColor::White,
+            secondary: TuiColor::Gray,
+            accent: TuiColor::LightYellow,
+            text: TuiColor::White,
+            success: TuiColor::LightCyan,
+            warning: TuiColor::LightYellow,
+            error: TuiColor::LightMagenta,
+            active_mark: "▶",
+            idle_mark: "■",
+        }
+    }
+
+    // Okabe-Ito colors
+    pub fn deuteranopia() -> Self {
+        Self {
+            primary: TuiColor::Rgb(86, 180, 233),
+            secondary: TuiColor::Rgb(128, 128, 128),
+            accent: TuiColor::Rgb(240, 228, 66),
+            text: TuiColor::Rgb(220, 220, 220),
+            success: TuiColor::Rgb(0, 114, 178),
+            warning: TuiColor::Rgb(230, 159, 0),
+            error: TuiColor::Rgb(213, 94, 0),
+            active_mark: "▶",
+            idle_mark: "■",
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use rodio::Source;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 48_000;
+// Limits for hand-edited values: audible, and short enough not to overlap
+const MIN_FREQUENCY: f32 = 20.0;
+const MAX_FREQUENCY: f32 = 20_000.0;
+const MAX_DURATION_MS: u64 = 2000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Waveform {
+    Sine,
+    Square,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Tone {
+    pub frequency: f32,
+    pub duration_ms: u64,
+    #[serde(default = "default_waveform")]
+    pub waveform: Waveform,
+}
+
+fn default_waveform() -> Waveform {
+    Waveform::Sine
+}
+
+impl Tone {
+    const fn sine(frequency: f32, duration_ms: u64) -> Self {
+        Self {
+            frequency,
+            duration_ms,
+            waveform: Waveform::Sine,
+        }
+    }
+
+    fn sanitize(&mut self) {
+        if !self.frequency.is_finite() {
+            self.frequency = MIN_FREQUENCY;
+        }
+        self.frequency = self.frequency.clamp(MIN_FREQUENCY, MAX_FREQUENCY);
+        self.duration_ms = self.duration_ms.min(MAX_DURATION_MS);
+    }
+
+    pub fn source(&self) -> impl Source<Item = f32> + Send + 'static {
+        Oscillator {
+            frequency: self.frequency,
+            waveform: self.waveform,
+            phase: 0.0,
+        }
+        .take_duration(Duration::from_millis(self.duration_ms))
+    }
+}
+
+// The sound cues; a duration of 0 silences one cue and keeps the others
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct Tones {
+    pub start: Tone,
+    pub stop: Tone,
+    pub countdown: Tone,
+    pub milestone: Tone,
+}
+
+impl Default for Tones {
+    fn default() -> Self {
+        Self {
+            start: Tone::sine(880.0, 200),
+            stop: Tone::sine(440.0, 150),
+            countdown: Tone::sine(660.0, 80),
+            milestone: Tone::sine(1320.0, 250),
+        }
+    }
+}
+
+impl Tones {
+    pub fn sanitize(&mut self) {
+        for tone in [
+            &mut self.start,
+            &mut self.stop,
+            &mut self.countdown,
+            &mut self.milestone,
+        ] {
+            tone.sanitize();
+        }
+    }
+}
+
+// rodio only ships a sine wave, so both shapes are generated here
+struct Oscillator {
+    frequency: f32,
+    waveform: Waveform,
+    // Position within the current period, 0..1
+    phase: f32,
+}
+
+impl Iterator for Oscillator {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let phase = self.phase;
+        self.phase = (self.phase + self.frequency / SAMPLE_RATE as f32).fract();
+        Some(match self.waveform {
+            Waveform::Sine => (phase * std::f32::consts::TAU).sin(),
+            // Halved, as a full square is far louder than a sine of the
+            // same amplitude
+            Waveform::Square => {
+                if phase < 0.5 {
+                    0.5
+                } else {
+                    -0.5
+                }
+            }
+        })
+    }
+}
+
+impl Source for Oscillator {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
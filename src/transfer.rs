@@ -0,0 +1,325 @@
+use crate::{
+    Config, ConfigFormat, MAX_CPS, MIN_CPS, Profile, ahk,
+    sequence::{SequenceStep, StepKind},
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+use toml::Value;
+
+pub const DEFAULT_EXPORT_FILE: &str = "bclicker_export.toml";
+
+// A validated config read from an export file, and what applying it changes
+pub struct PendingImport {
+    pub config: Config,
+    pub changes: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+// Statistics live in their own file, so exports never carry them and imports
+// leave them alone. API tokens, the MQTT password and webhooks, whose URLs
+// usually hold a token, are secrets and stay on this machine too, and so do
+// shell hooks: a shared file mustn't get to run
+// commands here
+fn shareable(config: &Config) -> Result<Value, String> {
+    let mut value = Value::try_from(config).map_err(|e| e.to_string())?;
+    if let Some(table) = value.as_table_mut() {
+        table.remove("api_tokens");
+        table.remove("hooks");
+        table.remove("webhooks");
+        if let Some(mqtt) = table.get_mut("mqtt").and_then(Value::as_table_mut) {
+            mqtt.remove("password");
+        }
+    }
+    Ok(value)
+}
+
+// What shareable leaves out of a file comes from this machine's config
+fn keep_local(config: &mut Config, current: &Config) {
+    config.api_tokens = current.api_tokens.clone();
+    config.hooks = current.hooks.clone();
+    config.webhooks = current.webhooks.clone();
+    if let Some(mqtt) = &mut config.mqtt {
+        mqtt.password = current.mqtt.as_ref().and_then(|m| m.password.clone());
+    }
+}
+
+// The file's extension picks the format, like the main config file
+pub fn export(config: &Config, path: &Path) -> Result<(), String> {
+    let contents = ConfigFormat::from_path(path).serialize(&shareable(config)?)?;
+    fs::write(path, contents).map_err(|e| format!("could not write {}: {}", path.display(), e))
+}
+
+pub fn prepare_import(current: &Config, path: &Path) -> Result<PendingImport, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    let mut config: Config = ConfigFormat::from_path(path)
+        .parse(&contents)
+        .map_err(|e| format!("{} is not a valid config: {}", path.display(), e))?;
+
+    if config.cps_presets.is_empty()
+        || !config
+            .cps_presets
+            .iter()
+            .all(|c| (MIN_CPS..=MAX_CPS).contains(c))
+    {
+        return Err(format!(
+            "cps_presets must be non-empty with values in {}-{}",
+            MIN_CPS, MAX_CPS
+        ));
+    }
+    // Validated here rather than in sanitize so problems reach the preview
+    let warnings = config.keymap.validate();
+    keep_local(&mut config, current);
+    config.sanitize();
+
+    let changes = diff(
+        &flatten(&shareable(current)?),
+        &flatten(&shareable(&config)?),
+    );
+    Ok(PendingImport {
+        config,
+        changes,
+        warnings,
+    })
+}
+
+// One "path = value" entry per leaf, e.g. profiles[0].timing_model
+fn flatten(value: &Value) -> BTreeMap<String, String> {
+    fn walk(prefix: String, value: &Value, out: &mut BTreeMap<String, String>) {
+        match value {
+            Value::Table(table) => {
+                for (key, value) in table {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    walk(path, value, out);
+                }
+            }
+            Value::Array(items) if items.iter().any(|v| v.is_table()) => {
+                for (i, item) in items.iter().enumerate() {
+                    walk(format!("{}[{}]", prefix, i), item, out);
+                }
+            }
+            leaf => {
+                out.insert(prefix, leaf.to_string());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    walk(String::new(), value, &mut out);
+    out
+}
+
+fn diff(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (key, value) in old {
+        match new.get(key) {
+            Some(updated) if updated != value => {
+                changes.push(format!("~ {}: {} → {}", key, value, updated))
+            }
+            Some(_) => {}
+            None => changes.push(format!("- {} = {}", key, value)),
+        }
+    }
+    for (key, value) in new {
+        if !old.contains_key(key) {
+            changes.push(format!("+ {} = {}", key, value));
+        }
+    }
+    changes
+}
+
+// Asks on the terminal; anything but y declines
+fn confirm(question: &str) -> Result<bool, String> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush().map_err(|e| e.to_string())?;
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| e.to_string())?;
+    Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
+// An AutoHotkey script goes into a profile's sequence, named after the file
+// unless --profile says otherwise; an existing profile keeps its other
+// settings
+fn import_ahk(
+    mut config: Config,
+    path: &Path,
+    profile: Option<&str>,
+    assume_yes: bool,
+) -> Result<(), String> {
+    let script = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    let imported = ahk::import(&script).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let name = match profile {
+        Some(name) => name.to_string(),
+        None => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "AutoHotkey".to_string()),
+    };
+
+    for warning in &imported.warnings {
+        println!("[WARNING] {}", warning);
+    }
+    let existing = config.profiles.iter().position(|p| p.name == name);
+    match existing {
+        Some(i) => println!(
+            "[INFO] Replacing the {}-step sequence of profile {} with {} steps:",
+            config.profiles[i].sequence.len(),
+            name,
+            imported.steps.len()
+        ),
+        None => println!(
+            "[INFO] Adding profile {} with {} steps:",
+            name,
+            imported.steps.len()
+        ),
+    }
+    for (i, step) in imported.steps.iter().enumerate() {
+        println!("  {}. {}", i + 1, step.describe());
+    }
+    if !assume_yes && !confirm("Import this sequence?")? {
+        println!("[INFO] Import cancelled");
+        return Ok(());
+    }
+
+    let target = match existing {
+        Some(i) => &mut config.profiles[i],
+        None => {
+            config.profiles.push(Profile {
+                name: name.clone(),
+                ..Profile::default()
+            });
+            config.profiles.last_mut().expect("just pushed")
+        }
+    };
+    target.sequence = imported.steps;
+    target.sequence_enabled = true;
+    crate::save_config(&config);
+    println!(
+        "[INFO] Imported {} into profile {}; select it to run the sequence",
+        path.display(),
+        name
+    );
+    Ok(())
+}
+
+// A profile as an AutoHotkey script toggled by the same hotkey: its
+// sequence, or else plain clicking at the current rate
+fn export_ahk(config: &Config, path: &Path, profile: Option<&str>) -> Result<(), String> {
+    let profile = match profile {
+        Some(name) => config
+            .profiles
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("no profile named {}", name))?,
+        None => config.active_profile(),
+    };
+    let steps = if profile.sequence.is_empty() {
+        if config.selected_button > 1 {
+            return Err(format!(
+                "profile {} has no sequence, and only Left and Right clicking export without one",
+                profile.name
+            ));
+        }
+        let cps = crate::EngineSettings::from_config(config).cps;
+        vec![SequenceStep {
+            kind: StepKind::Click,
+            position: profile.click_position,
+            button: config.selected_button,
+            delay_ms: (1000.0 / cps).round() as u64,
+            ..SequenceStep::default()
+        }]
+    } else {
+        profile.sequence.clone()
+    };
+
+    let exported = ahk::export(
+        &profile.name,
+        &steps,
+        &ahk::hotkey(config.toggle_keybind.as_ref()),
+    );
+    for warning in &exported.warnings {
+        println!("[WARNING] {}", warning);
+    }
+    fs::write(path, exported.script)
+        .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+    println!(
+        "[INFO] Exported profile {} to {} for AutoHotkey v2",
+        profile.name,
+        path.display()
+    );
+    Ok(())
+}
+
+// `bclicker export [file]` and `bclicker import <file> [--yes]`
+pub fn run_cli(command: &str, args: &[String]) -> Result<(), String> {
+    let mut current = crate::load_config();
+    current.sanitize();
+
+    let profile = args
+        .iter()
+        .position(|a| a == "--profile")
+        .map(|i| args.get(i + 1).ok_or("--profile needs a name"))
+        .transpose()?;
+    let path = args
+        .iter()
+        .find(|a| !a.starts_with('-') && Some(*a) != profile);
+    let is_ahk = |path: &str| {
+        Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("ahk"))
+    };
+
+    if command == "export" {
+        let path = path.map_or(DEFAULT_EXPORT_FILE, String::as_str);
+        if is_ahk(path) {
+            return export_ahk(&current, Path::new(path), profile.map(String::as_str));
+        }
+        export(&current, Path::new(path))?;
+        println!("[INFO] Exported settings and profiles to {}", path);
+        return Ok(());
+    }
+
+    let assume_yes = args.iter().any(|a| a == "--yes" || a == "-y");
+    let path = path.ok_or("usage: bclicker import <file> [--yes] [--profile NAME]")?;
+    if is_ahk(path) {
+        return import_ahk(
+            current,
+            Path::new(path),
+            profile.map(String::as_str),
+            assume_yes,
+        );
+    }
+    let pending = prepare_import(&current, Path::new(path))?;
+
+    for warning in &pending.warnings {
+        println!("[WARNING] {}", warning);
+    }
+    if pending.changes.is_empty() {
+        println!("[INFO] {} matches the current settings", path);
+        return Ok(());
+    }
+    println!("[INFO] Importing {} changes:", pending.changes.len());
+    for change in &pending.changes {
+        println!("  {}", change);
+    }
+
+    if !assume_yes && !confirm("Apply these changes?")? {
+        println!("[INFO] Import cancelled");
+        return Ok(());
+    }
+
+    crate::save_config(&pending.config);
+    println!("[INFO] Settings imported from {}", path);
+    Ok(())
+}
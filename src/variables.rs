@@ -0,0 +1,49 @@
+use std::{collections::BTreeMap, time::Duration};
+
+// Values sequences and patterns can read: built-ins describing the current
+// run, plus the persistent counters kept with the statistics
+#[derive(Clone, Debug, Default)]
+pub struct Variables {
+    pub run_time: Duration,
+    pub clicks_this_run: u64,
+    pub total_clicks: u64,
+    pub counters: BTreeMap<String, i64>,
+}
+
+impl Variables {
+    // Counters that were never incremented read as 0
+    pub fn get(&self, name: &str) -> i64 {
+        match name {
+            "clicks_this_run" => self.clicks_this_run as i64,
+            "total_clicks" => self.total_clicks as i64,
+            "run_secs" => self.run_time.as_secs() as i64,
+            _ => self.counters.get(name).copied().unwrap_or(0),
+        }
+    }
+
+    // Replaces each {name} with the variable's value, e.g. "Loop {loops}"
+    pub fn expand(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            match after.find('}') {
+                Some(close) if is_name(&after[..close]) => {
+                    out.push_str(&self.get(&after[..close]).to_string());
+                    rest = &after[close + 1..];
+                }
+                _ => {
+                    out.push('{');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+pub fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,62 @@
+use crate::condition::{parse_color, same_color};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const MAX_RETRIES: u32 = 10;
+const MAX_DELAY_MS: u64 = 5000;
+
+// Checks that a click at a fixed point did something, by the pixel under
+// it, and clicks again if it didn't
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ClickVerify {
+    // "#RRGGBB" the pixel should show afterwards; without one it only has
+    // to change
+    pub color: Option<String>,
+    // Per channel, both for the expected color and for what counts as a change
+    pub tolerance: u8,
+    // Extra clicks before giving up on a target
+    pub retries: u32,
+    // Time the target gets to react before its pixel is read
+    pub delay_ms: u64,
+}
+
+impl Default for ClickVerify {
+    fn default() -> Self {
+        Self {
+            color: None,
+            tolerance: 8,
+            retries: 2,
+            delay_ms: 100,
+        }
+    }
+}
+
+impl ClickVerify {
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.retries = self.retries.min(MAX_RETRIES);
+        self.delay_ms = self.delay_ms.min(MAX_DELAY_MS);
+        match &self.color {
+            Some(color) if parse_color(color).is_none() => {
+                Err(format!("'{}' is not a #RRGGBB color", color))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    // Whether the pixel after the click looks right. One that can't be read,
+    // e.g. under Wayland, passes, as nothing can be told either way
+    pub fn passed(&self, before: Option<[u8; 3]>, after: Option<[u8; 3]>) -> bool {
+        let Some(after) = after else {
+            return true;
+        };
+        match self.color.as_deref().and_then(parse_color) {
+            Some(want) => same_color(want, after, self.tolerance),
+            None => before.is_none_or(|before| !same_color(before, after, self.tolerance)),
+        }
+    }
+}
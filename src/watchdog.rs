@@ -0,0 +1,156 @@
+use crate::backend::{ClickBackend, Point};
+use enigo::{Key, MouseButton};
+use std::time::{Duration, Instant};
+
+// How often a move is followed by reading the cursor back; reading flushes
+// the batch, so not on every click
+const CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+// Checks in a row that have to fail before the engine gives up, so the user
+// nudging the mouse once doesn't stop a run
+const MISSES_TO_TRIP: u32 = 5;
+
+// How far off, in pixels, a move may land, for rounding at DPI boundaries
+const SLACK: i32 = 2;
+
+// Notices when injected input goes nowhere, e.g. swallowed by anti-cheat or
+// refused by the OS for a window running as administrator: the cursor stays
+// put after moves, or the OS says it dropped events. A cursor that can't be
+// read tells nothing either way
+#[derive(Default)]
+pub struct InjectionWatchdog {
+    checked_at: Option<Instant>,
+    // Cursor checks that failed in a row
+    misses: u32,
+    // Flushes in a row the OS refused
+    refused: u32,
+    tripped: bool,
+}
+
+impl InjectionWatchdog {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    // Whether enough checks failed in a row that clicks can't be reaching
+    // anything
+    pub fn tripped(&self) -> bool {
+        self.tripped
+    }
+
+    fn due(&self) -> bool {
+        self.checked_at
+            .is_none_or(|at| at.elapsed() >= CHECK_INTERVAL)
+    }
+
+    fn check_cursor(&mut self, backend: &mut dyn ClickBackend, target: Point) {
+        if !self.due() {
+            return;
+        }
+        backend.flush();
+        let Some(at) = backend.cursor_position() else {
+            return;
+        };
+        self.checked_at = Some(Instant::now());
+        if (at.x - target.x).abs() <= SLACK && (at.y - target.y).abs() <= SLACK {
+            self.misses = 0;
+        } else {
+            self.misses += 1;
+            self.tripped |= self.misses >= MISSES_TO_TRIP;
+        }
+    }
+
+    // Takes the backend's word on what it sent since the last call
+    pub fn check_sent(&mut self, backend: &mut dyn ClickBackend) {
+        match backend.take_sent() {
+            Some(true) => self.refused = 0,
+            Some(false) => {
+                self.refused += 1;
+                self.tripped |= self.refused >= MISSES_TO_TRIP;
+            }
+            None => {}
+        }
+    }
+}
+
+// A backend that reports what it sees of its own input to the watchdog
+pub struct Watched<'a> {
+    backend: &'a mut dyn ClickBackend,
+    watchdog: &'a mut InjectionWatchdog,
+    enabled: bool,
+}
+
+impl<'a> Watched<'a> {
+    pub fn new(
+        backend: &'a mut dyn ClickBackend,
+        watchdog: &'a mut InjectionWatchdog,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            backend,
+            watchdog,
+            enabled,
+        }
+    }
+}
+
+impl ClickBackend for Watched<'_> {
+    fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn click(&mut self, button: MouseButton) {
+        self.backend.click(button);
+    }
+
+    fn button_down(&mut self, button: MouseButton) {
+        self.backend.button_down(button);
+    }
+
+    fn button_up(&mut self, button: MouseButton) {
+        self.backend.button_up(button);
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.backend.move_to(x, y);
+        if self.enabled {
+            self.watchdog.check_cursor(self.backend, Point { x, y });
+        }
+    }
+
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.backend.move_relative(dx, dy);
+    }
+
+    fn scroll(&mut self, amount: i32) {
+        self.backend.scroll(amount);
+    }
+
+    fn key(&mut self, key: Key) {
+        self.backend.key(key);
+    }
+
+    fn key_down(&mut self, key: Key) {
+        self.backend.key_down(key);
+    }
+
+    fn key_up(&mut self, key: Key) {
+        self.backend.key_up(key);
+    }
+
+    fn type_text(&mut self, text: &str) {
+        self.backend.type_text(text);
+    }
+
+    fn cursor_position(&self) -> Option<Point> {
+        self.backend.cursor_position()
+    }
+
+    fn flush(&mut self) {
+        self.backend.flush();
+    }
+
+    fn take_sent(&mut self) -> Option<bool> {
+        self.backend.take_sent()
+    }
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+// Slow endpoints give up after this rather than piling up threads
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    Start,
+    // Stopped by hand
+    Stop,
+    // Ended by a stop condition, the schedule or the idle monitor
+    AutoStop,
+    Error,
+}
+
+impl WebhookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            WebhookEvent::Start => "start",
+            WebhookEvent::Stop => "stop",
+            WebhookEvent::AutoStop => "auto_stop",
+            WebhookEvent::Error => "error",
+        }
+    }
+
+    fn default_template(self) -> &'static str {
+        match self {
+            WebhookEvent::Start => "BClicker: {message} ({profile}, {cps} CPS)",
+            WebhookEvent::Stop | WebhookEvent::AutoStop => {
+                "BClicker: {message} ({profile}, {clicks} clicks in {duration})"
+            }
+            WebhookEvent::Error => "BClicker: {message} ({profile})",
+        }
+    }
+}
+
+// Body the endpoint expects: a chat message for Discord and Slack, every
+// field for anything else. Auto picks by the URL
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    #[default]
+    Auto,
+    Discord,
+    Slack,
+    Json,
+}
+
+fn all_events() -> Vec<WebhookEvent> {
+    vec![
+        WebhookEvent::Start,
+        WebhookEvent::Stop,
+        WebhookEvent::AutoStop,
+        WebhookEvent::Error,
+    ]
+}
+
+// An endpoint told about runs, e.g. a Discord channel reporting when a long
+// unattended run ends. The template may use {event}, {message}, {profile},
+// {clicks}, {total_clicks}, {duration} and {cps}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    #[serde(default = "all_events")]
+    pub events: Vec<WebhookEvent>,
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+// Why the engine's current run is about to stop, left by whoever stops it
+// so the stop is reported as more than a stop by hand
+pub type StopReason = Arc<Mutex<Option<(WebhookEvent, String)>>>;
+
+// What the run looked like when the event happened
+#[derive(Clone, Debug, Default)]
+pub struct RunInfo {
+    pub profile: String,
+    pub clicks: u64,
+    pub total_clicks: u64,
+    pub duration: Duration,
+    pub cps: f64,
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {:02}s", m, s),
+        (h, m, s) => format!("{}h {:02}m {:02}s", h, m, s),
+    }
+}
+
+impl Webhook {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.url.starts_with("https://") && !self.url.starts_with("http://") {
+            return Err("url must start with http:// or https://".to_string());
+        }
+        Ok(())
+    }
+
+    fn format(&self) -> WebhookFormat {
+        match self.format {
+            WebhookFormat::Auto if self.url.contains("discord.com/api/webhooks") => {
+                WebhookFormat::Discord
+            }
+            WebhookFormat::Auto if self.url.contains("hooks.slack.com") => WebhookFormat::Slack,
+            WebhookFormat::Auto => WebhookFormat::Json,
+            format => format,
+        }
+    }
+
+    fn body(&self, event: WebhookEvent, message: &str, run: &RunInfo) -> String {
+        let text = self
+            .template
+            .as_deref()
+            .unwrap_or(event.default_template())
+            .replace("{event}", event.name())
+            .replace("{message}", message)
+            .replace("{profile}", &run.profile)
+            .replace("{clicks}", &run.clicks.to_string())
+            .replace("{total_clicks}", &run.total_clicks.to_string())
+            .replace("{duration}", &format_duration(run.duration))
+            .replace("{cps}", &format!("{:.2}", run.cps));
+        let body = match self.format() {
+            WebhookFormat::Discord => json!({ "content": text }),
+            WebhookFormat::Slack => json!({ "text": text }),
+            _ => json!({
+                "event": event.name(),
+                "message": message,
+                "text": text,
+                "profile": run.profile,
+                "clicks": run.clicks,
+                "total_clicks": run.total_clicks,
+                "duration_secs": run.duration.as_secs(),
+                "cps": run.cps,
+            }),
+        };
+        body.to_string()
+    }
+}
+
+// POSTs the event to every webhook that wants it, each on a thread of its
+// own so a slow endpoint can't hold up clicking
+pub fn post(webhooks: &[Webhook], event: WebhookEvent, message: &str, run: &RunInfo) {
+    for webhook in webhooks.iter().filter(|w| w.events.contains(&event)) {
+        let url = webhook.url.clone();
+        let body = webhook.body(event, message, run);
+        thread::spawn(move || {
+            let result = ureq::post(&url)
+                .timeout(TIMEOUT)
+                .set("Content-Type", "application/json")
+                .send_string(&body);
+            if let Err(e) = result {
+                eprintln!("[WARNING] Webhook {} failed: {}", event.name(), e);
+            }
+        });
+    }
+}
@@ -0,0 +1,140 @@
+// The recording backend and helpers shared by the engine test suites. Each
+// suite uses a different subset
+#![allow(dead_code)]
+
+use bclicker::{
+    backend::{BackendFactory, ClickBackend, Point},
+    clock::Clock,
+    headless::HeadlessEngine,
+};
+use enigo::{Key, MouseButton};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+// Keeps desktop popups out of test runs
+pub const QUIET: &str =
+    "[notifications]\nstart = false\nstop = false\ninfo = false\nerror = false\n";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Click(MouseButton),
+    Down(MouseButton),
+    Up(MouseButton),
+    MoveTo(i32, i32),
+    MoveBy(i32, i32),
+    Scroll(i32),
+    Key(Key),
+    KeyDown(Key),
+    KeyUp(Key),
+    Type(String),
+}
+
+pub type Log = Arc<Mutex<Vec<(Instant, Event)>>>;
+
+// Writes down every call with the time on the engine's clock
+struct RecordingBackend {
+    log: Log,
+    clock: Clock,
+}
+
+impl RecordingBackend {
+    fn record(&self, event: Event) {
+        self.log.lock().unwrap().push((self.clock.now(), event));
+    }
+}
+
+impl ClickBackend for RecordingBackend {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+    fn click(&mut self, button: MouseButton) {
+        self.record(Event::Click(button));
+    }
+    fn button_down(&mut self, button: MouseButton) {
+        self.record(Event::Down(button));
+    }
+    fn button_up(&mut self, button: MouseButton) {
+        self.record(Event::Up(button));
+    }
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.record(Event::MoveTo(x, y));
+    }
+    fn move_relative(&mut self, dx: i32, dy: i32) {
+        self.record(Event::MoveBy(dx, dy));
+    }
+    fn scroll(&mut self, amount: i32) {
+        self.record(Event::Scroll(amount));
+    }
+    fn key(&mut self, key: Key) {
+        self.record(Event::Key(key));
+    }
+    fn key_down(&mut self, key: Key) {
+        self.record(Event::KeyDown(key));
+    }
+    fn key_up(&mut self, key: Key) {
+        self.record(Event::KeyUp(key));
+    }
+    fn type_text(&mut self, text: &str) {
+        self.record(Event::Type(text.to_string()));
+    }
+    fn cursor_position(&self) -> Option<Point> {
+        None
+    }
+}
+
+// A backend factory whose backends all write to one fresh log
+pub fn recorder(clock: &Clock) -> (BackendFactory, Log) {
+    let log: Log = Arc::default();
+    let (shared, clock) = (Arc::clone(&log), clock.clone());
+    let make_backend: BackendFactory = Arc::new(move |_| {
+        Box::new(RecordingBackend {
+            log: Arc::clone(&shared),
+            clock: clock.clone(),
+        })
+    });
+    (make_backend, log)
+}
+
+// Starts clicking and waits for a stop condition to end the run
+pub fn run_until_stopped(engine: &HeadlessEngine, timeout: Duration) {
+    engine.set_running(true);
+    let start = Instant::now();
+    while engine.is_running() {
+        assert!(start.elapsed() < timeout, "engine didn't stop by itself");
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+// Gives the engine a moment to add its tally to the statistics
+pub fn settle() {
+    thread::sleep(Duration::from_millis(300));
+}
+
+pub fn events(log: &Log) -> Vec<Event> {
+    log.lock()
+        .unwrap()
+        .iter()
+        .map(|(_, event)| event.clone())
+        .collect()
+}
+
+pub fn clicks(log: &Log) -> Vec<(Instant, MouseButton)> {
+    log.lock()
+        .unwrap()
+        .iter()
+        .filter_map(|(at, event)| match event {
+            Event::Click(button) => Some((*at, *button)),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn gaps_ms(times: &[Instant]) -> Vec<f64> {
+    times
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).as_secs_f64() * 1000.0)
+        .collect()
+}
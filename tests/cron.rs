@@ -0,0 +1,130 @@
+// Parses cron expressions and checks which wall clock minutes they match:
+// ranges, steps, lists, "*/n" and how day of month and day of week combine
+use bclicker::cron::{CronExpr, LocalTime};
+
+// A minute in 2024; the weekday is given, 0 being Sunday
+fn at(month: u32, day: u32, weekday: u32, hour: u32, minute: u32) -> LocalTime {
+    LocalTime {
+        year: 2024,
+        month,
+        day,
+        hour,
+        minute,
+        weekday,
+    }
+}
+
+fn expr(input: &str) -> CronExpr {
+    CronExpr::parse(input).unwrap_or_else(|e| panic!("{input}: {e}"))
+}
+
+// The minutes of one hour the expression matches
+fn minutes(input: &str) -> Vec<u32> {
+    let expr = expr(input);
+    (0..60)
+        .filter(|&minute| expr.matches(&at(1, 1, 1, 0, minute)))
+        .collect()
+}
+
+// The hours of one day the expression matches, on the hour
+fn hours(input: &str) -> Vec<u32> {
+    let expr = expr(input);
+    (0..24)
+        .filter(|&hour| expr.matches(&at(1, 1, 1, hour, 0)))
+        .collect()
+}
+
+#[test]
+fn ranges_cover_both_ends() {
+    assert_eq!(minutes("10-14 * * * *"), [10, 11, 12, 13, 14]);
+    assert_eq!(hours("0 22-23 * * *"), [22, 23]);
+    assert_eq!(minutes("7-7 * * * *"), [7]);
+}
+
+#[test]
+fn steps_count_from_the_start_of_the_range() {
+    assert_eq!(minutes("*/15 * * * *"), [0, 15, 30, 45]);
+    assert_eq!(hours("0 */5 * * *"), [0, 5, 10, 15, 20]);
+    assert_eq!(minutes("10-30/10 * * * *"), [10, 20, 30]);
+    // A single value with a step runs to the end of the field
+    assert_eq!(minutes("50/3 * * * *"), [50, 53, 56, 59]);
+    assert_eq!(minutes("*/1 * * * *"), (0..60).collect::<Vec<_>>());
+}
+
+#[test]
+fn lists_combine_values_ranges_and_steps() {
+    assert_eq!(minutes("1,3,5 * * * *"), [1, 3, 5]);
+    assert_eq!(minutes("0,20-22,*/25 * * * *"), [0, 20, 21, 22, 25, 50]);
+    assert_eq!(hours("0 9,17 * * *"), [9, 17]);
+}
+
+#[test]
+fn month_and_weekday_narrow_the_day() {
+    let expr = expr("0 9 * 3-5 1-5");
+    // Monday 4 March
+    assert!(expr.matches(&at(3, 4, 1, 9, 0)));
+    // Saturday 9 March
+    assert!(!expr.matches(&at(3, 9, 6, 9, 0)));
+    // Monday 5 February
+    assert!(!expr.matches(&at(2, 5, 1, 9, 0)));
+    assert!(!expr.matches(&at(3, 4, 1, 10, 0)));
+}
+
+#[test]
+fn sunday_is_both_0_and_7() {
+    for input in ["0 0 * * 0", "0 0 * * 7"] {
+        let expr = expr(input);
+        assert!(expr.matches(&at(1, 7, 0, 0, 0)), "{input}");
+        assert!(!expr.matches(&at(1, 8, 1, 0, 0)), "{input}");
+    }
+}
+
+#[test]
+fn restricted_day_and_weekday_match_either() {
+    // The 1st of the month, and every Friday
+    let expr = expr("0 12 1 * 5");
+    // Monday 1 January: the day matches
+    assert!(expr.matches(&at(1, 1, 1, 12, 0)));
+    // Friday 5 January: the weekday matches
+    assert!(expr.matches(&at(1, 5, 5, 12, 0)));
+    // Tuesday 2 January: neither does
+    assert!(!expr.matches(&at(1, 2, 2, 12, 0)));
+}
+
+#[test]
+fn a_star_on_either_day_field_requires_both() {
+    // Every Friday, whatever the date
+    let fridays = expr("0 12 * * 5");
+    assert!(fridays.matches(&at(1, 5, 5, 12, 0)));
+    assert!(!fridays.matches(&at(1, 1, 1, 12, 0)));
+    // The 1st, whatever the weekday
+    let firsts = expr("0 12 1 * *");
+    assert!(firsts.matches(&at(1, 1, 1, 12, 0)));
+    assert!(!firsts.matches(&at(1, 5, 5, 12, 0)));
+}
+
+#[test]
+fn bad_expressions_are_refused() {
+    for input in [
+        "* * * *",
+        "* * * * * *",
+        "60 * * * *",
+        "* 24 * * *",
+        "* * 0 * *",
+        "* * * 13 *",
+        "* * * * 8",
+        "30-10 * * * *",
+        "*/0 * * * *",
+        "a * * * *",
+        "1,,2 * * * *",
+    ] {
+        assert!(CronExpr::parse(input).is_err(), "{input} was accepted");
+    }
+}
+
+#[test]
+fn next_run_is_found_unless_the_date_never_comes() {
+    assert!(expr("* * * * *").next_run().is_some());
+    assert!(expr("0 0 31 * *").next_run().is_some());
+    assert!(expr("0 0 30 2 *").next_run().is_none());
+}
